@@ -1,24 +1,43 @@
+#![cfg_attr(not(feature = "std"), no_std)]
 #![deny(missing_docs)]
 #![deny(missing_debug_implementations)]
 #![cfg_attr(test, deny(warnings))]
-#![doc(html_root_url = "https://docs.rs/headers-core/0.3.0")]
+#![doc(html_root_url = "https://docs.rs/headers-core/0.3.1")]
 
 //! # headers-core
 //!
 //! This is the core crate of the typed HTTP headers system, providing only
 //! the relevant traits. All actual header implementations are in other crates.
+//!
+//! # Features
+//!
+//! - `std`, on by default, provides the `Header` and `NamedHeader` traits,
+//!   which are built on the `http` crate and so need `std`.
+//! - `alloc` provides the parsers in [`parse`] that need to allocate.
+//!
+//! Without `std`, this crate is `no_std`, and [`parse`] has the parsing
+//! for the simplest headers, for embedded HTTP servers. Anything that
+//! works with dates stays behind `std`, in the `headers` crate.
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate core;
+#[cfg(feature = "std")]
 extern crate http;
 
+#[cfg(feature = "std")]
 pub use http::header::{self, HeaderName, HeaderValue};
 
-use std::error;
-use std::fmt::{self, Display, Formatter};
+use core::fmt::{self, Display, Formatter};
+
+pub mod parse;
 
 /// A trait for any object that will represent a header field and value.
 ///
 /// This trait represents the construction and identification of headers,
 /// and contains trait-object unsafe methods.
+#[cfg(feature = "std")]
 pub trait Header {
     /// The name of this header.
     fn name() -> &'static HeaderName;
@@ -29,14 +48,100 @@ pub trait Header {
         Self: Sized,
         I: Iterator<Item = &'i HeaderValue>;
 
+    /// Decode this type from owned `HeaderValue`s.
+    ///
+    /// This is for callers that already own their values, such as a
+    /// hand-rolled HTTP parser, so they don't need to build a `HeaderMap`
+    /// first. It shares the parsing of `decode`, which it calls with
+    /// references to the values. A single value is passed through directly,
+    /// while multiple values are buffered first.
+    fn decode_owned<I>(values: I) -> Result<Self, Error>
+    where
+        Self: Sized,
+        I: IntoIterator<Item = HeaderValue>,
+    {
+        let mut values = values.into_iter();
+        let first = match values.next() {
+            Some(first) => first,
+            None => return Self::decode(&mut std::iter::empty()),
+        };
+
+        match values.next() {
+            None => Self::decode(&mut std::iter::once(&first)),
+            Some(second) => {
+                let mut all = vec![first, second];
+                all.extend(values);
+                Self::decode(&mut all.iter())
+            }
+        }
+    }
+
+    /// Decode this type from an iterator of `HeaderValue`s, with `options`.
+    ///
+    /// This is for callers that need to choose how closely the values must
+    /// follow the RFCs. The default implementation ignores `options` and
+    /// calls `decode`, so only headers with obsolete or lenient forms need
+    /// to implement it. Those must keep `decode` the same as calling this
+    /// with `DecodeOptions::default()`.
+    fn decode_with<'i, I>(values: &mut I, options: &DecodeOptions) -> Result<Self, Error>
+    where
+        Self: Sized,
+        I: Iterator<Item = &'i HeaderValue>,
+    {
+        let _ = options;
+        Self::decode(values)
+    }
+
     /// Encode this type to a `HeaderMap`.
     ///
     /// This function should be infallible. Any errors converting to a
     /// `HeaderValue` should have been caught when parsing or constructing
     /// this value.
+    ///
+    /// Implementations only ever extend `values`. They never remove or
+    /// replace what is already there, so callers can reuse a buffer across
+    /// many headers.
     fn encode<E: Extend<HeaderValue>>(&self, values: &mut E);
 }
 
+/// A `Header` whose name is available as a constant.
+///
+/// `Header::name` can only be called at runtime, while `NAME` can be used in
+/// const contexts, such as a compile-time routing table keyed by
+/// `HeaderName`. Every header shipped by the `headers` crate implements this.
+#[cfg(feature = "std")]
+pub trait NamedHeader: Header {
+    /// The name of this header.
+    ///
+    /// This is always equal to `Header::name()`.
+    const NAME: HeaderName;
+}
+
+/// Options for `Header::decode_with`.
+///
+/// The default is lenient, which is how `Header::decode` behaves. More
+/// options may be added, so this can only be built with `default()` or
+/// `strict()`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct DecodeOptions {
+    /// Reject forms that the RFCs obsolete, or that recipients may accept
+    /// but senders must not generate.
+    ///
+    /// For example, a strict date must be an IMF-fixdate, and not one of
+    /// the obsolete RFC 850 or asctime formats. Gateways concerned with
+    /// request smuggling would decode strictly, while proxies that want to
+    /// accept what browsers do would not.
+    pub strict: bool,
+}
+
+impl DecodeOptions {
+    /// Options that reject obsolete and lenient forms.
+    pub fn strict() -> DecodeOptions {
+        DecodeOptions { strict: true }
+    }
+}
+
 /// Errors trying to decode a header.
 #[derive(Debug)]
 pub struct Error {
@@ -46,6 +151,7 @@ pub struct Error {
 #[derive(Debug)]
 enum Kind {
     Invalid,
+    InvalidAt { offset: usize },
 }
 
 impl Error {
@@ -55,14 +161,36 @@ impl Error {
             kind: Kind::Invalid,
         }
     }
+
+    /// Create an 'invalid' Error, for a syntax error `offset` bytes into
+    /// the header value.
+    pub fn invalid_at(offset: usize) -> Error {
+        Error {
+            kind: Kind::InvalidAt { offset },
+        }
+    }
+
+    /// The byte offset into the header value where decoding failed, if it
+    /// is known.
+    ///
+    /// Only headers with a structured syntax report this. When a header has
+    /// several values, the offset is into the value that failed.
+    pub fn offset(&self) -> Option<usize> {
+        match self.kind {
+            Kind::Invalid => None,
+            Kind::InvalidAt { offset } => Some(offset),
+        }
+    }
 }
 
 impl Display for Error {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         match &self.kind {
             Kind::Invalid => f.write_str("invalid HTTP header"),
+            Kind::InvalidAt { offset } => write!(f, "invalid HTTP header at byte {}", offset),
         }
     }
 }
 
-impl error::Error for Error {}
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
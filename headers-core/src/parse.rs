@@ -0,0 +1,192 @@
+//! Parsing for the simplest headers, without `std`.
+//!
+//! These work on the bytes of one field value, and don't allocate unless
+//! noted. The `headers` crate decodes with them, so a server that can't
+//! use `std` parses exactly the way `headers` does.
+//!
+//! Headers covered:
+//!
+//! - `Content-Length`, with [`content_length`] and [`content_length_list`].
+//! - The media type of `Content-Type`, with [`media_type`].
+//! - Token lists, such as `Connection` and `Transfer-Encoding`, with
+//!   [`list`] and [`is_token`].
+//! - Quoted-strings, with `unquote`, which needs the `alloc` feature.
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+/// Whether `b` is a `tchar`, as defined in
+/// [RFC7230](https://tools.ietf.org/html/rfc7230#section-3.2.6).
+///
+/// ```text
+/// tchar = "!" / "#" / "$" / "%" / "&" / "'" / "*"
+///       / "+" / "-" / "." / "^" / "_" / "`" / "|" / "~"
+///       / DIGIT / ALPHA
+/// ```
+pub fn is_tchar(b: u8) -> bool {
+    match b {
+        b'!' | b'#' | b'$' | b'%' | b'&' | b'\'' | b'*' | b'+' | b'-' | b'.' | b'^' | b'_'
+        | b'`' | b'|' | b'~' => true,
+        _ => b.is_ascii_alphanumeric(),
+    }
+}
+
+/// Whether `s` is a `token`, one or more `tchar`s.
+pub fn is_token(s: &[u8]) -> bool {
+    !s.is_empty() && s.iter().all(|&b| is_tchar(b))
+}
+
+/// Trim optional whitespace, spaces and tabs, from both ends.
+pub fn trim_ows(bytes: &[u8]) -> &[u8] {
+    let is_ows = |b: &u8| *b == b' ' || *b == b'\t';
+    let start = bytes.iter().position(|b| !is_ows(b)).unwrap_or(bytes.len());
+    let end = bytes
+        .iter()
+        .rposition(|b| !is_ows(b))
+        .map_or(start, |i| i + 1);
+    &bytes[start..end]
+}
+
+/// Iterate the elements of a comma-separated list, trimmed, skipping
+/// empty elements.
+///
+/// This doesn't know about quoted-strings, so it is only for lists of
+/// tokens.
+pub fn list(value: &[u8]) -> impl Iterator<Item = &[u8]> {
+    value
+        .split(|&b| b == b',')
+        .map(trim_ows)
+        .filter(|element| !element.is_empty())
+}
+
+/// Parse a `Content-Length` value, `1*DIGIT`.
+///
+/// This rejects anything that another implementation might read
+/// differently, such as a sign or leading zeros.
+///
+/// It is on the path of every request with a body, so it checks and
+/// accumulates the digits in a single pass.
+pub fn content_length(bytes: &[u8]) -> Option<u64> {
+    if bytes.is_empty() || (bytes.len() > 1 && bytes[0] == b'0') {
+        return None;
+    }
+
+    bytes.iter().try_fold(0u64, |acc, &b| {
+        let digit = b.wrapping_sub(b'0');
+        if digit < 10 {
+            acc.checked_mul(10)?.checked_add(u64::from(digit))
+        } else {
+            None
+        }
+    })
+}
+
+/// Parse every line of a `Content-Length` field.
+///
+/// Several lines, or a line folded into a list such as `42, 42`, are
+/// alright as long as every length is the same. Returns `None` if they
+/// differ, any fails to parse, or there are none.
+pub fn content_length_list<'a, I>(values: I) -> Option<u64>
+where
+    I: IntoIterator<Item = &'a [u8]>,
+{
+    let mut len = None;
+    for value in values {
+        for part in value.split(|&b| b == b',') {
+            let parsed = content_length(trim_ows(part))?;
+            match len {
+                Some(prev) if prev != parsed => return None,
+                _ => len = Some(parsed),
+            }
+        }
+    }
+    len
+}
+
+/// Split the media type of a `Content-Type` value into its type and
+/// subtype, such as `text` and `html` for `text/html; charset=utf-8`.
+///
+/// Parameters are ignored. Both parts are returned as sent, so compare
+/// them ignoring ASCII case.
+pub fn media_type(value: &[u8]) -> Option<(&[u8], &[u8])> {
+    let essence = value.split(|&b| b == b';').next().map(trim_ows)?;
+    let slash = essence.iter().position(|&b| b == b'/')?;
+    let (type_, subtype) = (&essence[..slash], &essence[slash + 1..]);
+    if is_token(type_) && is_token(subtype) {
+        Some((type_, subtype))
+    } else {
+        None
+    }
+}
+
+/// Remove the quotes and escapes from a quoted-string, or return a token
+/// as is.
+///
+/// Returns `None` for an unterminated quoted-string, or one that ends
+/// with a lone `\`.
+#[cfg(feature = "alloc")]
+pub fn unquote(value: &str) -> Option<String> {
+    if !value.starts_with('"') {
+        return Some(String::from(value));
+    }
+    let inner = value
+        .get(1..value.len() - 1)
+        .filter(|_| value.ends_with('"'))?;
+    let mut out = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        out.push(if c == '\\' { chars.next()? } else { c });
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokens() {
+        assert!(is_token(b"chunked"));
+        assert!(!is_token(b""));
+        assert!(!is_token(b"a b"));
+
+        let mut elements = list(b" gzip,, chunked ,");
+        assert_eq!(elements.next(), Some(&b"gzip"[..]));
+        assert_eq!(elements.next(), Some(&b"chunked"[..]));
+        assert_eq!(elements.next(), None);
+    }
+
+    #[test]
+    fn content_lengths() {
+        assert_eq!(content_length(b"3495"), Some(3495));
+        assert_eq!(content_length(b"0"), Some(0));
+        assert_eq!(content_length(b"007"), None);
+        assert_eq!(content_length(b"+5"), None);
+        assert_eq!(content_length(b"18446744073709551616"), None);
+
+        assert_eq!(content_length_list([&b"5"[..], b"5, 5"]), Some(5));
+        assert_eq!(content_length_list([&b"5"[..], b"6"]), None);
+        assert_eq!(content_length_list([&b"5,"[..]]), None);
+        assert_eq!(content_length_list(None), None);
+    }
+
+    #[test]
+    fn media_types() {
+        assert_eq!(
+            media_type(b"Text/HTML ; charset=utf-8"),
+            Some((&b"Text"[..], &b"HTML"[..]))
+        );
+        assert_eq!(media_type(b"text"), None);
+        assert_eq!(media_type(b"text/"), None);
+        assert_eq!(media_type(b"te xt/html"), None);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn unquoting() {
+        assert_eq!(unquote("token").unwrap(), "token");
+        assert_eq!(unquote("\"a \\\"b\\\"\"").unwrap(), "a \"b\"");
+        assert_eq!(unquote("\"open"), None);
+        assert_eq!(unquote("\"ends\\\""), None);
+    }
+}
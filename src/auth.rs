@@ -0,0 +1,92 @@
+//! Client authentication flows.
+//!
+//! A server asks for authentication with the challenges in a
+//! `WWW-Authenticate` header, and the client answers one of them with an
+//! `Authorization` header. This picks which challenge to answer.
+
+use www_authenticate::Challenge;
+use WwwAuthenticate;
+
+/// Pick the challenge to answer, out of those a server sent.
+///
+/// `preferred` lists the schemes the client can satisfy, most preferred
+/// first. The challenge returned is for the first of those the server
+/// offered, regardless of the order of the challenges. Schemes are compared
+/// ignoring ASCII case. Returns `None` if the client can satisfy none of
+/// them.
+///
+/// The chosen challenge's `scheme()` picks the `Credentials` to build an
+/// `Authorization` with, such as `Authorization::bearer` for `Bearer`, or
+/// `Authorization::basic` for `Basic`. Its `realm()` and other parameters
+/// tell the client which credentials the server wants.
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// use headers::auth::pick_scheme;
+/// use headers::authorization::{Authorization, Basic, Bearer};
+/// use headers::{Header, HeaderMapExt, WwwAuthenticate};
+///
+/// # fn main() {
+/// let value = "Basic realm=\"simple\", Bearer realm=\"api\"".parse().unwrap();
+/// let www_auth = WwwAuthenticate::decode(&mut std::iter::once(&value)).unwrap();
+///
+/// let mut request = headers::HeaderMap::new();
+/// let challenge = pick_scheme(&www_auth, &["Bearer", "Basic"]).unwrap();
+/// match challenge.scheme() {
+///     s if s.eq_ignore_ascii_case("bearer") => {
+///         request.typed_insert(Authorization::bearer("token-for-api").unwrap());
+///     }
+///     _ => {
+///         request.typed_insert(Authorization::basic("aladdin", "opensesame"));
+///     }
+/// }
+///
+/// assert_eq!(challenge.realm(), Some("api"));
+/// assert!(request.typed_get::<Authorization<Bearer>>().is_some());
+/// assert!(request.typed_get::<Authorization<Basic>>().is_none());
+/// # }
+/// ```
+pub fn pick_scheme<'a>(
+    challenges: &'a WwwAuthenticate,
+    preferred: &[&str],
+) -> Option<&'a Challenge> {
+    preferred.iter().find_map(|scheme| {
+        challenges
+            .iter()
+            .find(|challenge| challenge.scheme().eq_ignore_ascii_case(scheme))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::pick_scheme;
+    use {Header, HeaderValue, WwwAuthenticate};
+
+    fn www_auth(values: &[&'static str]) -> WwwAuthenticate {
+        let values = values
+            .iter()
+            .map(|s| HeaderValue::from_static(s))
+            .collect::<Vec<_>>();
+        WwwAuthenticate::decode(&mut values.iter()).unwrap()
+    }
+
+    #[test]
+    fn prefers_bearer_over_basic() {
+        let www_auth = www_auth(&["Basic realm=\"simple\"", "Bearer realm=\"api\""]);
+        let challenge = pick_scheme(&www_auth, &["Bearer", "Basic"]).unwrap();
+        assert_eq!(challenge.scheme(), "Bearer");
+        assert_eq!(challenge.realm(), Some("api"));
+
+        let challenge = pick_scheme(&www_auth, &["basic", "bearer"]).unwrap();
+        assert_eq!(challenge.scheme(), "Basic");
+    }
+
+    #[test]
+    fn none_satisfiable() {
+        let www_auth = www_auth(&["Negotiate", "Digest realm=\"x\", nonce=\"y\""]);
+        assert_eq!(pick_scheme(&www_auth, &["Bearer", "Basic"]), None);
+        assert_eq!(pick_scheme(&www_auth, &[]), None);
+    }
+}
@@ -0,0 +1,165 @@
+//! Client retry decisions.
+//!
+//! Whether a failed request is worth retrying depends on the response
+//! status, and how long to wait on the `Retry-After` header, if the server
+//! sent one. This follows the conventions most HTTP clients share, for
+//! clients that don't need their own policy.
+
+use std::time::{Duration, SystemTime};
+
+use http::StatusCode;
+
+use RetryAfter;
+
+/// Whether, and when, to retry a request.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Decision {
+    /// Whether the request may be retried.
+    pub retryable: bool,
+    /// How long the server asked the client to wait first, if it did.
+    ///
+    /// When this is `None` for a retryable response, the client should
+    /// pick its own delay, such as with exponential backoff.
+    pub wait: Option<Duration>,
+}
+
+/// Decide whether to retry a request that got a `status` response.
+///
+/// These statuses are retryable, since they report a temporary problem:
+///
+/// - 408 Request Timeout
+/// - 429 Too Many Requests
+/// - 500 Internal Server Error
+/// - 502 Bad Gateway
+/// - 503 Service Unavailable
+/// - 504 Gateway Timeout
+///
+/// Any other status, including every other 4xx, is not, since sending the
+/// same request again would get the same answer. For a retryable status,
+/// `wait` is the time from `now` until the `retry_after`, or zero if that
+/// is already past.
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// # extern crate http;
+/// use std::time::{Duration, SystemTime};
+/// use headers::backoff::decide;
+/// use headers::RetryAfter;
+/// use http::StatusCode;
+///
+/// let retry_after = RetryAfter::delay(Duration::from_secs(120));
+/// let decision = decide(
+///     StatusCode::SERVICE_UNAVAILABLE,
+///     Some(&retry_after),
+///     SystemTime::now(),
+/// );
+/// assert!(decision.retryable);
+/// assert_eq!(decision.wait, Some(Duration::from_secs(120)));
+/// ```
+pub fn decide(status: StatusCode, retry_after: Option<&RetryAfter>, now: SystemTime) -> Decision {
+    if !is_retryable(status) {
+        return Decision {
+            retryable: false,
+            wait: None,
+        };
+    }
+
+    let wait = retry_after.map(|retry_after| retry_after.wait(now));
+    Decision {
+        retryable: true,
+        wait,
+    }
+}
+
+fn is_retryable(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::REQUEST_TIMEOUT
+            | StatusCode::TOO_MANY_REQUESTS
+            | StatusCode::INTERNAL_SERVER_ERROR
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+
+    use http::StatusCode;
+
+    use super::{decide, Decision};
+    use RetryAfter;
+
+    #[test]
+    fn too_many_requests() {
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+
+        let retry_after = RetryAfter::delay(Duration::from_secs(30));
+        assert_eq!(
+            decide(StatusCode::TOO_MANY_REQUESTS, Some(&retry_after), now),
+            Decision {
+                retryable: true,
+                wait: Some(Duration::from_secs(30)),
+            }
+        );
+
+        let retry_after = RetryAfter::date(now + Duration::from_secs(60));
+        assert_eq!(
+            decide(StatusCode::TOO_MANY_REQUESTS, Some(&retry_after), now).wait,
+            Some(Duration::from_secs(60))
+        );
+
+        let retry_after = RetryAfter::date(now - Duration::from_secs(60));
+        assert_eq!(
+            decide(StatusCode::TOO_MANY_REQUESTS, Some(&retry_after), now).wait,
+            Some(Duration::from_secs(0))
+        );
+
+        assert_eq!(
+            decide(StatusCode::TOO_MANY_REQUESTS, None, now),
+            Decision {
+                retryable: true,
+                wait: None,
+            }
+        );
+    }
+
+    #[test]
+    fn huge_delay() {
+        // Adding this to `now` would overflow a `SystemTime`.
+        let retry_after = RetryAfter::delay(Duration::from_secs(u64::MAX));
+        assert_eq!(
+            decide(
+                StatusCode::SERVICE_UNAVAILABLE,
+                Some(&retry_after),
+                SystemTime::now()
+            )
+            .wait,
+            Some(Duration::from_secs(u64::MAX))
+        );
+    }
+
+    #[test]
+    fn not_retryable() {
+        let now = SystemTime::UNIX_EPOCH;
+        let retry_after = RetryAfter::delay(Duration::from_secs(30));
+        for &status in &[
+            StatusCode::OK,
+            StatusCode::BAD_REQUEST,
+            StatusCode::NOT_FOUND,
+            StatusCode::NOT_IMPLEMENTED,
+        ] {
+            assert_eq!(
+                decide(status, Some(&retry_after), now),
+                Decision {
+                    retryable: false,
+                    wait: None,
+                }
+            );
+        }
+    }
+}
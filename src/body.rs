@@ -0,0 +1,138 @@
+//! Whether a message has a body.
+
+use http::header::{CONTENT_LENGTH, TRANSFER_ENCODING};
+use http::{HeaderMap, Method, StatusCode};
+
+use {ContentLength, HeaderMapExt};
+
+/// Check if a message may have a body, following
+/// [RFC7230](https://tools.ietf.org/html/rfc7230#section-3.3.3).
+///
+/// For a response, pass the `method` of the request it answers and its
+/// `status`. A response to `HEAD`, a `1xx`, `204 No Content` or
+/// `304 Not Modified` response, and a `2xx` response to `CONNECT` never
+/// have a body, whatever their headers say. For a request, pass `None` as
+/// the status: it only has a body if it has a `Transfer-Encoding` or a
+/// non-zero `Content-Length`.
+///
+/// Otherwise, a `Transfer-Encoding` means there is a body, and a
+/// `Content-Length` says whether there is one. A response with neither has
+/// a body that runs until the connection closes.
+///
+/// This doesn't check the headers agree; use
+/// [`framing::detect_conflict`](::framing::detect_conflict) first. A
+/// `Content-Length` that doesn't decode counts as a body, so it is never
+/// silently skipped.
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// # extern crate http;
+/// use headers::body::may_have_body;
+/// use headers::{ContentLength, HeaderMapExt};
+/// use http::{Method, StatusCode};
+///
+/// let mut headers = http::HeaderMap::new();
+/// headers.typed_insert(ContentLength(512));
+///
+/// assert!(may_have_body(&Method::GET, Some(StatusCode::OK), &headers));
+/// assert!(!may_have_body(&Method::HEAD, Some(StatusCode::OK), &headers));
+/// assert!(!may_have_body(&Method::GET, Some(StatusCode::NOT_MODIFIED), &headers));
+/// ```
+pub fn may_have_body(method: &Method, status: Option<StatusCode>, headers: &HeaderMap) -> bool {
+    if let Some(status) = status {
+        if *method == Method::HEAD
+            || status.is_informational()
+            || status == StatusCode::NO_CONTENT
+            || status == StatusCode::NOT_MODIFIED
+            || (*method == Method::CONNECT && status.is_success())
+        {
+            return false;
+        }
+    }
+
+    if headers.contains_key(TRANSFER_ENCODING) {
+        return true;
+    }
+
+    if headers.contains_key(CONTENT_LENGTH) {
+        return match headers.typed_get::<ContentLength>() {
+            Some(len) => !len.is_zero(),
+            None => true,
+        };
+    }
+
+    // Without framing headers, a response is read until the connection
+    // closes, while a request has no body.
+    status.is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use HeaderValue;
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for &(name, value) in pairs {
+            map.append(name, HeaderValue::from_static(value));
+        }
+        map
+    }
+
+    #[test]
+    fn may_have_body_responses() {
+        let ok = Some(StatusCode::OK);
+        let chunked = headers(&[("transfer-encoding", "chunked")]);
+        let empty = headers(&[("content-length", "0")]);
+
+        assert!(may_have_body(&Method::GET, ok, &chunked));
+        assert!(!may_have_body(&Method::GET, ok, &empty));
+        assert!(may_have_body(&Method::GET, ok, &headers(&[])));
+        assert!(may_have_body(
+            &Method::GET,
+            ok,
+            &headers(&[("content-length", "nope")])
+        ));
+
+        assert!(!may_have_body(&Method::HEAD, ok, &chunked));
+        assert!(!may_have_body(
+            &Method::HEAD,
+            ok,
+            &headers(&[("content-length", "5")])
+        ));
+        for &status in &[
+            StatusCode::CONTINUE,
+            StatusCode::NO_CONTENT,
+            StatusCode::NOT_MODIFIED,
+        ] {
+            assert!(!may_have_body(&Method::GET, Some(status), &chunked));
+        }
+        assert!(!may_have_body(&Method::CONNECT, ok, &headers(&[])));
+        assert!(may_have_body(
+            &Method::CONNECT,
+            Some(StatusCode::BAD_GATEWAY),
+            &headers(&[])
+        ));
+    }
+
+    #[test]
+    fn may_have_body_requests() {
+        let chunked = headers(&[("transfer-encoding", "gzip, chunked")]);
+        assert!(may_have_body(&Method::POST, None, &chunked));
+        assert!(may_have_body(
+            &Method::POST,
+            None,
+            &headers(&[("content-length", "5")])
+        ));
+        assert!(!may_have_body(
+            &Method::POST,
+            None,
+            &headers(&[("content-length", "0")])
+        ));
+        assert!(!may_have_body(&Method::GET, None, &headers(&[])));
+        // A request to `HEAD` can still have a body of its own.
+        assert!(may_have_body(&Method::HEAD, None, &chunked));
+    }
+}
@@ -0,0 +1,137 @@
+//! Helpers for HTTP caches.
+
+use bytes::BytesMut;
+use http::HeaderMap;
+
+use util;
+use {HeaderName, HeaderValue, Vary};
+
+/// Build the secondary cache key for a request, based on a response's `Vary`.
+///
+/// Each header named by `vary` is looked up in the `request` headers, and
+/// paired with its value. Multiple field lines of the same header are joined
+/// with commas, and a header missing from the request is paired with `None`,
+/// since its absence is just as significant. The pairs are sorted by name, so
+/// the key does not depend on the order of the `Vary` list.
+///
+/// Returns `None` if the response is not cacheable by key, because the `Vary`
+/// contains `*`, or names something that is not a valid header name.
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// # extern crate http;
+/// use headers::{HeaderMapExt, Vary};
+/// use http::header::{HeaderMap, HeaderValue, ACCEPT_ENCODING};
+///
+/// let mut response = HeaderMap::new();
+/// response.insert("vary", HeaderValue::from_static("accept-encoding"));
+/// let vary = response.typed_get::<Vary>().unwrap();
+///
+/// let mut request = HeaderMap::new();
+/// request.insert(ACCEPT_ENCODING, HeaderValue::from_static("gzip"));
+///
+/// let key = headers::cache::vary_key(&vary, &request).unwrap();
+/// assert_eq!(key, vec![(ACCEPT_ENCODING, Some(HeaderValue::from_static("gzip")))]);
+///
+/// assert_eq!(headers::cache::vary_key(&Vary::any(), &request), None);
+/// ```
+pub fn vary_key(
+    vary: &Vary,
+    request: &HeaderMap,
+) -> Option<Vec<(HeaderName, Option<HeaderValue>)>> {
+    if !vary.is_cacheable() {
+        return None;
+    }
+
+    let mut names = vary
+        .iter_strs()
+        .filter(|name| !name.is_empty())
+        .map(|name| name.parse::<HeaderName>().ok())
+        .collect::<Option<Vec<_>>>()?;
+    names.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+    names.dedup();
+
+    let key = names
+        .into_iter()
+        .map(|name| {
+            let value = joined_value(request, &name);
+            (name, value)
+        })
+        .collect();
+    Some(key)
+}
+
+fn joined_value(request: &HeaderMap, name: &HeaderName) -> Option<HeaderValue> {
+    let mut values = request.get_all(name).iter();
+    let first = values.next()?;
+
+    let mut buf = BytesMut::from(util::trim_ows(first.as_bytes()));
+    for value in values {
+        buf.extend_from_slice(b", ");
+        buf.extend_from_slice(util::trim_ows(value.as_bytes()));
+    }
+
+    let value = HeaderValue::from_maybe_shared(buf.freeze())
+        .expect("comma separated HeaderValues are valid");
+    Some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::header::{ACCEPT, ACCEPT_ENCODING};
+    use HeaderMapExt;
+
+    fn vary(s: &'static str) -> Vary {
+        let mut map = HeaderMap::new();
+        map.insert("vary", HeaderValue::from_static(s));
+        map.typed_get().unwrap()
+    }
+
+    #[test]
+    fn sorted_key() {
+        let mut request = HeaderMap::new();
+        request.insert(ACCEPT_ENCODING, HeaderValue::from_static("gzip"));
+        request.append(ACCEPT, HeaderValue::from_static("text/html"));
+        request.append(ACCEPT, HeaderValue::from_static(" application/json"));
+
+        let key = vary_key(&vary("Accept-Encoding, Accept"), &request).unwrap();
+        assert_eq!(
+            key,
+            vec![
+                (
+                    ACCEPT,
+                    Some(HeaderValue::from_static("text/html, application/json"))
+                ),
+                (ACCEPT_ENCODING, Some(HeaderValue::from_static("gzip"))),
+            ]
+        );
+
+        // Order of the Vary list doesn't matter.
+        assert_eq!(
+            vary_key(&vary("accept, accept-encoding, Accept"), &request),
+            Some(key)
+        );
+    }
+
+    #[test]
+    fn missing_request_header() {
+        let key = vary_key(&vary("Accept-Encoding, Accept"), &HeaderMap::new()).unwrap();
+        assert_eq!(key, vec![(ACCEPT, None), (ACCEPT_ENCODING, None)]);
+    }
+
+    #[test]
+    fn uncacheable() {
+        let request = HeaderMap::new();
+        assert_eq!(vary_key(&Vary::any(), &request), None);
+        assert_eq!(vary_key(&vary("accept, *"), &request), None);
+        assert_eq!(vary_key(&vary("not valid"), &request), None);
+
+        let mut response = HeaderMap::new();
+        response.insert("vary", HeaderValue::from_bytes(b"accept, x-\xe9").unwrap());
+        let non_text = response.typed_get::<Vary>().unwrap();
+        assert_eq!(vary_key(&non_text, &request), None);
+    }
+}
@@ -0,0 +1,460 @@
+//! Accept header and types.
+
+use std::fmt;
+
+use mime::{self, Mime};
+
+use util::{self, is_token, FlatCsv, TryFromValues};
+use {HeaderValue, QualityValue};
+
+/// `Accept` header, defined in
+/// [RFC7231](http://tools.ietf.org/html/rfc7231#section-5.3.2)
+///
+/// The `Accept` header field can be used by user agents to specify
+/// response media types that are acceptable. Accept header fields can
+/// be used to indicate that the request is specifically limited to a
+/// small set of desired types, as in the case of a request for an
+/// in-line image.
+///
+/// # ABNF
+///
+/// ```text
+/// Accept = #( media-range [ accept-params ] )
+///
+/// media-range    = ( "*/*"
+///                  / ( type "/" "*" )
+///                  / ( type "/" subtype )
+///                  ) *( OWS ";" OWS parameter )
+/// accept-params  = weight *( accept-ext )
+/// accept-ext = OWS ";" OWS token [ "=" ( token / quoted-string ) ]
+/// ```
+///
+/// # Example values
+///
+/// * `audio/*; q=0.2, audio/basic`
+/// * `text/plain; q=0.5, text/html, text/x-dvi; q=0.8, text/x-c`
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// # extern crate http;
+/// # extern crate mime;
+/// use headers::{Accept, HeaderMapExt};
+///
+/// let mut map = http::HeaderMap::new();
+/// map.insert("accept", "text/html, application/json;q=0.9".parse().unwrap());
+/// let accept = map.typed_get::<Accept>().unwrap();
+///
+/// let available = [mime::APPLICATION_JSON, mime::TEXT_HTML];
+/// assert_eq!(accept.negotiate(&available), Some(&mime::TEXT_HTML));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Accept(Vec<MediaRange>);
+
+/// A media range and its quality, in an `Accept` header.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MediaRange {
+    mime: Mime,
+    quality: QualityValue,
+}
+
+impl ::Header for Accept {
+    fn name() -> &'static ::HeaderName {
+        &::http::header::ACCEPT
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        Accept::try_from_values(values)
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        struct Fmt<'a>(&'a [MediaRange]);
+
+        impl<'a> fmt::Display for Fmt<'a> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                util::csv::fmt_comma_delimited(f, self.0.iter())
+            }
+        }
+
+        values.extend(::std::iter::once(util::fmt(Fmt(&self.0))));
+    }
+}
+
+impl ::NamedHeader for Accept {
+    const NAME: ::HeaderName = ::http::header::ACCEPT;
+}
+
+impl Accept {
+    /// Iterate the media ranges and their qualities, in the order they were
+    /// sent.
+    ///
+    /// Use `sorted_media_ranges` for the order of preference.
+    pub fn media_ranges(&self) -> impl Iterator<Item = (&Mime, QualityValue)> {
+        self.0.iter().map(|range| (&range.mime, range.quality))
+    }
+
+    /// Iterate the media ranges from most to least preferred.
+    ///
+    /// Ranges with the same quality keep the order they were sent in.
+    pub fn sorted_media_ranges(&self) -> impl Iterator<Item = &MediaRange> {
+        let mut ranges = self.0.iter().collect::<Vec<_>>();
+        ranges.sort_by_key(|range| ::std::cmp::Reverse(range.quality));
+        ranges.into_iter()
+    }
+
+    /// Pick the best of the `available` media types for this `Accept`.
+    ///
+    /// Each available type gets the quality of the most specific range that
+    /// matches it, and the type with the highest quality wins. Ties go to
+    /// whichever type comes first in `available`, so list them in the
+    /// server's order of preference. Types with a quality of 0, or matched
+    /// by no range at all, are never picked.
+    ///
+    /// A range with parameters, such as `application/json; version=2`, only
+    /// matches types that have the same parameters, while a range without
+    /// parameters matches its type with any parameters.
+    pub fn negotiate<'a, I>(&self, available: I) -> Option<&'a Mime>
+    where
+        I: IntoIterator<Item = &'a Mime>,
+    {
+        // The precedence, per RFC 9110 section 12.5.1:
+        //
+        // 1. Each available type is weighed by the one range that matches it
+        //    most specifically: `type/subtype;params`, then `type/subtype`
+        //    with fewer params, then `type/*`, then `*/*`. Less specific
+        //    ranges are ignored, even if their quality is higher.
+        // 2. A type weighed 0, or matched by nothing, is not acceptable.
+        // 3. The highest weight wins. On a tie the earlier available type is
+        //    kept, as only a strictly higher weight replaces it.
+        //
+        // So browsers, which send `text/html` with an implicit q=1 and
+        // `*/*;q=0.8`, get HTML over JSON.
+        let mut best: Option<(&'a Mime, QualityValue)> = None;
+        for mime in available {
+            let quality = match self.quality_of(mime) {
+                Some(quality) if quality > QualityValue::ZERO => quality,
+                _ => continue,
+            };
+            match best {
+                Some((_, best_quality)) if best_quality >= quality => (),
+                _ => best = Some((mime, quality)),
+            }
+        }
+        best.map(|(mime, _)| mime)
+    }
+
+    /// The quality given to `mime` by its most specific matching range.
+    fn quality_of(&self, mime: &Mime) -> Option<QualityValue> {
+        self.0
+            .iter()
+            .filter(|range| range.matches(mime))
+            .max_by_key(|range| range.specificity())
+            .map(|range| range.quality)
+    }
+}
+
+impl MediaRange {
+    /// The media range, without its quality or any extensions.
+    pub fn mime(&self) -> &Mime {
+        &self.mime
+    }
+
+    /// The quality of this range.
+    pub fn quality(&self) -> QualityValue {
+        self.quality
+    }
+
+    fn matches(&self, mime: &Mime) -> bool {
+        if self.mime.type_() == mime::STAR {
+            return true;
+        }
+        if self.mime.type_() != mime.type_() {
+            return false;
+        }
+        if self.mime.subtype() == mime::STAR {
+            return true;
+        }
+        if self.mime.subtype() != mime.subtype() || self.mime.suffix() != mime.suffix() {
+            return false;
+        }
+
+        self.mime.params().all(|(name, value)| {
+            mime.get_param(name).map_or(false, |other| {
+                if name == mime::CHARSET {
+                    other.as_str().eq_ignore_ascii_case(value.as_str())
+                } else {
+                    other == value
+                }
+            })
+        })
+    }
+
+    /// Ranks how specific this range is, for picking among several that
+    /// match the same type.
+    fn specificity(&self) -> (u8, usize) {
+        if self.mime.type_() == mime::STAR {
+            (0, 0)
+        } else if self.mime.subtype() == mime::STAR {
+            (1, 0)
+        } else {
+            (2, self.mime.params().count())
+        }
+    }
+
+    fn parse(s: &str) -> Option<MediaRange> {
+        let full = s.parse::<Mime>().ok()?;
+        if full.type_() == mime::STAR && full.subtype() != mime::STAR {
+            return None;
+        }
+
+        // Parameters after the weight are accept-ext, not part of the range.
+        let mut range = String::from(full.essence_str());
+        let mut quality = QualityValue::ONE;
+        for (name, value) in full.params() {
+            if name.as_str().eq_ignore_ascii_case("q") {
+                quality = value.as_str().parse().ok()?;
+                break;
+            }
+            range.push(';');
+            range.push_str(name.as_str());
+            range.push('=');
+            if is_token(value.as_str()) {
+                range.push_str(value.as_str());
+            } else {
+                range.push('"');
+                for c in value.as_str().chars() {
+                    if c == '"' || c == '\\' {
+                        range.push('\\');
+                    }
+                    range.push(c);
+                }
+                range.push('"');
+            }
+        }
+
+        let mime = range.parse().ok()?;
+        Some(MediaRange { mime, quality })
+    }
+}
+
+impl TryFromValues for Accept {
+    fn try_from_values<'i, I>(values: &mut I) -> Result<Self, ::Error>
+    where
+        I: Iterator<Item = &'i HeaderValue>,
+    {
+        let csv = values.collect::<FlatCsv>();
+        let ranges = csv
+            .iter()
+            .filter(|s| !s.is_empty())
+            .map(MediaRange::parse)
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(::Error::invalid)?;
+
+        if ranges.is_empty() {
+            Err(::Error::invalid())
+        } else {
+            Ok(Accept(ranges))
+        }
+    }
+}
+
+impl fmt::Display for MediaRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.mime, f)?;
+        if self.quality != QualityValue::ONE {
+            write!(f, ";q={}", self.quality)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    fn mimes(list: &[&str]) -> Vec<Mime> {
+        list.iter().map(|s| s.parse().unwrap()).collect()
+    }
+
+    fn negotiate(accept: &str, available: &[&str]) -> Option<String> {
+        let accept = test_decode::<Accept>(&[accept]).unwrap();
+        let available = mimes(available);
+        accept.negotiate(&available).map(|m| m.to_string())
+    }
+
+    #[test]
+    fn decode() {
+        let accept =
+            test_decode::<Accept>(&["text/plain; q=0.5, text/html", "*/*;q=0.01"]).unwrap();
+        let ranges = accept.sorted_media_ranges().collect::<Vec<_>>();
+        assert_eq!(ranges.len(), 3);
+        assert_eq!(ranges[0].mime(), &mime::TEXT_HTML);
+        assert_eq!(ranges[0].quality(), QualityValue::ONE);
+        assert_eq!(ranges[1].mime(), &mime::TEXT_PLAIN);
+        assert_eq!(ranges[1].quality().as_f32(), 0.5);
+        assert_eq!(ranges[2].mime(), &mime::STAR_STAR);
+        assert_eq!(ranges[2].quality().to_string(), "0.01");
+    }
+
+    #[test]
+    fn media_ranges_in_sent_order() {
+        let accept = test_decode::<Accept>(&["*/*;q=0.1, text/plain;q=0.5", "text/html"]).unwrap();
+        let ranges = accept
+            .media_ranges()
+            .map(|(mime, quality)| (mime.to_string(), quality.to_string()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ranges,
+            [
+                ("*/*".to_owned(), "0.1".to_owned()),
+                ("text/plain".to_owned(), "0.5".to_owned()),
+                ("text/html".to_owned(), "1".to_owned()),
+            ]
+        );
+
+        let sorted = accept
+            .sorted_media_ranges()
+            .map(|range| range.mime().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(sorted, ["text/html", "text/plain", "*/*"]);
+    }
+
+    #[test]
+    fn encode() {
+        let accept =
+            test_decode::<Accept>(&["text/html;level=1;q=0.25;ext=1, application/json;q=1.000"])
+                .unwrap();
+        let headers = test_encode(accept);
+        assert_eq!(
+            headers["accept"],
+            "text/html;level=1;q=0.25, application/json"
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(test_decode::<Accept>(&[""]), None);
+        assert_eq!(test_decode::<Accept>(&["text/html;q=1.5"]), None);
+        assert_eq!(test_decode::<Accept>(&["text/html;q=0.1234"]), None);
+        assert_eq!(test_decode::<Accept>(&["*/html"]), None);
+        assert_eq!(test_decode::<Accept>(&["html"]), None);
+    }
+
+    #[test]
+    fn negotiate_quality_and_wildcards() {
+        let available = &["application/json", "text/html"];
+        assert_eq!(
+            negotiate("text/html, application/json;q=0.9", available),
+            Some("text/html".into())
+        );
+        assert_eq!(
+            negotiate("text/*;q=0.5, */*;q=0.8", available),
+            Some("application/json".into())
+        );
+        // Ties go to the server's preference.
+        assert_eq!(negotiate("*/*", available), Some("application/json".into()));
+        // The most specific range wins, even with a lower quality.
+        assert_eq!(
+            negotiate("*/*, application/json;q=0", available),
+            Some("text/html".into())
+        );
+        assert_eq!(negotiate("image/*", available), None);
+    }
+
+    // The `Accept` browsers send when navigating to a page.
+    const CHROME: &str = "text/html,application/xhtml+xml,application/xml;q=0.9,image/avif,\
+                          image/webp,image/apng,*/*;q=0.8,application/signed-exchange;v=b3;q=0.7";
+    const FIREFOX: &str =
+        "text/html,application/xhtml+xml,application/xml;q=0.9,image/avif,image/webp,*/*;q=0.8";
+    const FIREFOX_128: &str = "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8";
+    const SAFARI: &str = "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8";
+    // The `Accept` Chrome sends for an `<img>`.
+    const CHROME_IMAGE: &str = "image/avif,image/webp,image/apng,image/svg+xml,image/*,*/*;q=0.8";
+
+    #[test]
+    fn negotiate_browsers() {
+        for &accept in &[CHROME, FIREFOX, FIREFOX_128, SAFARI] {
+            assert_eq!(
+                negotiate(accept, &["application/json", "text/html"]),
+                Some("text/html".into()),
+                "{}",
+                accept
+            );
+            assert_eq!(
+                negotiate(accept, &["text/html", "application/json"]),
+                Some("text/html".into()),
+                "{}",
+                accept
+            );
+            assert_eq!(
+                negotiate(accept, &["application/xml", "text/html; charset=utf-8"]),
+                Some("text/html; charset=utf-8".into()),
+                "{}",
+                accept
+            );
+            // Without HTML, the explicitly listed XML beats the wildcard.
+            assert_eq!(
+                negotiate(accept, &["application/json", "application/xml"]),
+                Some("application/xml".into()),
+                "{}",
+                accept
+            );
+            // Anything else is still acceptable, through `*/*`.
+            assert_eq!(
+                negotiate(accept, &["application/json"]),
+                Some("application/json".into()),
+                "{}",
+                accept
+            );
+        }
+
+        assert_eq!(
+            negotiate(CHROME_IMAGE, &["application/json", "image/png"]),
+            Some("image/png".into())
+        );
+        assert_eq!(
+            negotiate(CHROME_IMAGE, &["image/png", "image/webp"]),
+            Some("image/png".into())
+        );
+        assert_eq!(
+            negotiate(CHROME_IMAGE, &["text/html"]),
+            Some("text/html".into())
+        );
+    }
+
+    #[test]
+    fn negotiate_parameters() {
+        let available = &["application/json; version=1", "application/json; version=2"];
+        assert_eq!(
+            negotiate("application/json; version=2", available),
+            Some("application/json; version=2".into())
+        );
+        assert_eq!(negotiate("application/json; version=3", available), None);
+        // A parameterless range matches any version.
+        assert_eq!(
+            negotiate("application/json", available),
+            Some("application/json; version=1".into())
+        );
+        assert_eq!(
+            negotiate(
+                "application/json;q=0.5, application/json; version=2, */*;q=0.1",
+                available
+            ),
+            Some("application/json; version=2".into())
+        );
+        assert_eq!(
+            negotiate("application/json; version=2;q=0, application/*", available),
+            Some("application/json; version=1".into())
+        );
+        // A range's parameters don't match a type without them.
+        assert_eq!(
+            negotiate(
+                "application/json; version=2, text/*;q=0.2",
+                &["application/json", "text/plain"]
+            ),
+            Some("text/plain".into())
+        );
+    }
+}
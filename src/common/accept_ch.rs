@@ -0,0 +1,149 @@
+use std::iter::FromIterator;
+
+use util::{self, csv, FlatCsv};
+use {HeaderName, HeaderValue};
+
+const ACCEPT_CH: HeaderName = HeaderName::from_static("accept-ch");
+
+/// `Accept-CH` header, defined in
+/// [RFC8942](https://www.rfc-editor.org/rfc/rfc8942.html#section-3.1)
+///
+/// The `Accept-CH` response header field indicates which client hints the
+/// server would like the user agent to send on subsequent requests.
+///
+/// # ABNF
+///
+/// ```text
+/// Accept-CH = sf-list   ; of client hint header names, as tokens
+/// ```
+///
+/// # Example values
+///
+/// * `Sec-CH-UA-Platform, Device-Memory`
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// # extern crate http;
+/// use headers::AcceptCH;
+/// use http::header::HeaderName;
+///
+/// let accept_ch = vec![
+///     HeaderName::from_static("sec-ch-ua-platform"),
+///     HeaderName::from_static("device-memory"),
+/// ]
+/// .into_iter()
+/// .collect::<AcceptCH>();
+///
+/// assert!(accept_ch.contains("Device-Memory"));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct AcceptCH(Vec<HeaderName>);
+
+impl AcceptCH {
+    /// Check if this includes the given client hint header name.
+    ///
+    /// The comparison is case-insensitive.
+    pub fn contains(&self, name: &str) -> bool {
+        self.0
+            .iter()
+            .any(|hint| hint.as_str().eq_ignore_ascii_case(name))
+    }
+
+    /// Returns an iterator over the client hint `HeaderName`s contained within.
+    pub fn iter(&self) -> impl Iterator<Item = &HeaderName> {
+        self.0.iter()
+    }
+}
+
+impl ::Header for AcceptCH {
+    fn name() -> &'static HeaderName {
+        static NAME: HeaderName = ACCEPT_CH;
+        &NAME
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        let hints: Vec<Hint> = csv::from_comma_delimited(values)?;
+        Ok(hints.into_iter().map(|Hint(name)| name).collect())
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        let flat = self
+            .0
+            .iter()
+            .cloned()
+            .map(HeaderValue::from)
+            .collect::<FlatCsv>();
+        values.extend(::std::iter::once(HeaderValue::from(&flat)));
+    }
+}
+
+impl ::NamedHeader for AcceptCH {
+    const NAME: ::HeaderName = ACCEPT_CH;
+}
+
+impl FromIterator<HeaderName> for AcceptCH {
+    /// Collect header names into an `Accept-CH`, skipping any repeated name.
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = HeaderName>,
+    {
+        AcceptCH(util::dedup(iter))
+    }
+}
+
+// Adapter to be used with `csv::from_comma_delimited`.
+struct Hint(HeaderName);
+
+impl ::std::str::FromStr for Hint {
+    type Err = ::Error;
+
+    fn from_str(s: &str) -> Result<Hint, ::Error> {
+        if !util::is_token(s) {
+            return Err(::Error::invalid());
+        }
+        s.parse().map(Hint).map_err(|_| ::Error::invalid())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn decode() {
+        let accept_ch =
+            test_decode::<AcceptCH>(&["Sec-CH-UA-Platform, Device-Memory", "Viewport-Width"])
+                .unwrap();
+
+        let hints = accept_ch.iter().collect::<Vec<_>>();
+        assert_eq!(
+            hints,
+            ["sec-ch-ua-platform", "device-memory", "viewport-width"]
+        );
+        assert!(accept_ch.contains("sec-ch-ua-platform"));
+        assert!(accept_ch.contains("DEVICE-MEMORY"));
+        assert!(!accept_ch.contains("sec-ch-ua"));
+    }
+
+    #[test]
+    fn decode_dedupes() {
+        let accept_ch = test_decode::<AcceptCH>(&["Device-Memory, device-memory"]).unwrap();
+        assert_eq!(accept_ch.iter().count(), 1);
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<AcceptCH>(&["Device Memory"]), None);
+        assert_eq!(test_decode::<AcceptCH>(&["\"Device-Memory\""]), None);
+    }
+
+    #[test]
+    fn encode() {
+        let accept_ch = test_decode::<AcceptCH>(&["Sec-CH-UA, Device-Memory"]).unwrap();
+        let headers = test_encode(accept_ch);
+        assert_eq!(headers["accept-ch"], "sec-ch-ua, device-memory");
+    }
+}
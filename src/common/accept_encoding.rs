@@ -1,7 +1,7 @@
 use std::convert::TryFrom;
 
 use {ContentCoding, HeaderValue};
-use util::{QualityValue, TryFromValues};
+use util::{QualityList, TryFromValues};
 
 /// `Accept-Encoding` header, defined in
 /// [RFC7231](https://tools.ietf.org/html/rfc7231#section-5.3.4)
@@ -24,7 +24,7 @@ use util::{QualityValue, TryFromValues};
 /// * `br;q=1.0, gzip;q=0.8`
 ///
 #[derive(Clone, Debug)]
-pub struct AcceptEncoding(pub QualityValue);
+pub struct AcceptEncoding(pub QualityList);
 
 derive_header! {
     AcceptEncoding(_),
@@ -54,10 +54,10 @@ impl AcceptEncoding {
     {
         let values: Vec<HeaderValue> = pairs
             .map(|pair| {
-                QualityValue::try_from(pair).map(|qual: QualityValue| HeaderValue::from(qual))
+                QualityList::try_from(pair).map(|qual: QualityList| HeaderValue::from(qual))
             })
             .collect::<Result<Vec<HeaderValue>, ::Error>>()?;
-        let value = QualityValue::try_from_values(&mut values.iter())?;
+        let value = QualityList::try_from_values(&mut values.iter())?;
         Ok(AcceptEncoding(value))
     }
 
@@ -78,7 +78,7 @@ impl AcceptEncoding {
     /// assert_eq!(accept_enc.prefered_encoding(), Some(ContentCoding::GZIP));
     /// ```
     pub fn prefered_encoding(&self) -> Option<ContentCoding> {
-        self.0.iter().peekable().peek().map(|s| ContentCoding::from_str(*s))
+        self.0.iter().peekable().peek().map(|s| ContentCoding::from_str(s))
     }
 
     /// Returns a quality sorted iterator of the `ContentCoding`
@@ -98,7 +98,7 @@ impl AcceptEncoding {
     /// assert_eq!(encodings.next(), None);
     /// ```
     pub fn sorted_encodings<'a>(&'a self) -> impl Iterator<Item = ContentCoding> + 'a {
-        self.0.iter().map(|s| ContentCoding::from_str(s))
+        self.0.iter().map(ContentCoding::from_str)
     }
 
     /// Returns a quality sorted iterator of values
@@ -120,6 +120,38 @@ impl AcceptEncoding {
     pub fn sorted_values(&self) -> impl Iterator<Item = &str> {
         self.0.iter()
     }
+
+    /// Check if a response without any content coding is acceptable.
+    ///
+    /// Following [RFC7231](https://tools.ietf.org/html/rfc7231#section-5.3.4),
+    /// the `identity` coding is always acceptable, unless the header lists
+    /// `identity;q=0`, or lists `*;q=0` without listing `identity` with a
+    /// non-zero weight. When this is `false` and the server can't send any
+    /// of the listed codings, it may respond `406 Not Acceptable`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use headers::{AcceptEncoding, HeaderValue};
+    ///
+    /// let val = HeaderValue::from_static("gzip;q=1.0, identity;q=0");
+    /// assert!(!AcceptEncoding(val.into()).allows_identity());
+    ///
+    /// let val = HeaderValue::from_static("gzip;q=1.0, *;q=0.5");
+    /// assert!(AcceptEncoding(val.into()).allows_identity());
+    /// ```
+    pub fn allows_identity(&self) -> bool {
+        let mut any = None;
+        for (coding, quality) in self.0.iter_with_quality() {
+            if coding.eq_ignore_ascii_case("identity") {
+                return quality > 0;
+            }
+            if coding == "*" && any.is_none() {
+                any = Some(quality);
+            }
+        }
+        any.map_or(true, |quality| quality > 0)
+    }
 }
 
 #[cfg(test)]
@@ -141,6 +173,24 @@ mod tests {
         assert_eq!(encodings.next(), None);
     }
 
+    #[test]
+    fn allows_identity() {
+        let allows =
+            |s: &'static str| AcceptEncoding(HeaderValue::from_static(s).into()).allows_identity();
+
+        assert!(allows("gzip"));
+        assert!(allows(""));
+        assert!(allows("*"));
+        assert!(allows("gzip, *;q=0.1"));
+        assert!(allows("*;q=0, identity"));
+        assert!(allows("identity;q=0.5, *;q=0"));
+
+        assert!(!allows("gzip;q=1.0, identity;q=0"));
+        assert!(!allows("gzip, IDENTITY; q=0.000"));
+        assert!(!allows("*;q=0, gzip"));
+        assert!(!allows("identity;q=0, *"));
+    }
+
     #[test]
     fn from_pairs() {
         let pairs = vec![("gzip", 1.0), ("br", 0.9)];
@@ -0,0 +1,164 @@
+//! Accept-Post header and types.
+
+use std::fmt;
+use std::iter::FromIterator;
+
+use mime::{self, Mime};
+
+use util::{ListHeader, TryFromValues};
+use {HeaderName, HeaderValue};
+
+const ACCEPT_POST: HeaderName = HeaderName::from_static("accept-post");
+
+/// `Accept-Post` header, defined in
+/// [Linked Data Platform 1.0](https://www.w3.org/TR/ldp/#header-accept-post)
+///
+/// The `Accept-Post` response header field lists the media types that the
+/// server accepts in the body of a POST request to the resource. It is
+/// usually sent in response to an `OPTIONS` request.
+///
+/// # ABNF
+///
+/// ```text
+/// Accept-Post = #media-range
+/// ```
+///
+/// # Example values
+///
+/// * `text/turtle, application/ld+json`
+/// * `image/*`
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// # extern crate mime;
+/// use headers::AcceptPost;
+///
+/// let accept_post = AcceptPost::new(vec![mime::APPLICATION_JSON, mime::TEXT_PLAIN]);
+/// assert!(accept_post.contains(&mime::APPLICATION_JSON));
+/// assert!(!accept_post.contains(&mime::IMAGE_PNG));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct AcceptPost(ListHeader<Mime>);
+
+impl AcceptPost {
+    /// Create an `Accept-Post` from a list of media types.
+    pub fn new(mimes: Vec<Mime>) -> AcceptPost {
+        AcceptPost(ListHeader::from(mimes))
+    }
+
+    /// Iterate the media types, in the order they were sent.
+    pub fn iter(&self) -> impl Iterator<Item = &Mime> {
+        self.0.iter()
+    }
+
+    /// Check if a POST body of type `mime` is accepted.
+    ///
+    /// Wildcards such as `image/*` match any subtype, and parameters are
+    /// ignored, so `text/turtle` accepts `text/turtle; charset=utf-8`.
+    pub fn contains(&self, mime: &Mime) -> bool {
+        self.0.iter().any(|range| {
+            if range.type_() == mime::STAR {
+                return true;
+            }
+            range.type_() == mime.type_()
+                && (range.subtype() == mime::STAR
+                    || (range.subtype() == mime.subtype() && range.suffix() == mime.suffix()))
+        })
+    }
+}
+
+impl FromIterator<Mime> for AcceptPost {
+    /// Collect media types into an `Accept-Post`, skipping any repeated type.
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = Mime>,
+    {
+        AcceptPost(iter.into_iter().collect())
+    }
+}
+
+impl ::Header for AcceptPost {
+    fn name() -> &'static HeaderName {
+        static NAME: HeaderName = ACCEPT_POST;
+        &NAME
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        let mimes = ListHeader::try_from_values(values)?;
+        if mimes.iter().next().is_none() {
+            Err(::Error::invalid())
+        } else {
+            Ok(AcceptPost(mimes))
+        }
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once((&self.0).into()));
+    }
+}
+
+impl ::NamedHeader for AcceptPost {
+    const NAME: ::HeaderName = ACCEPT_POST;
+}
+
+impl fmt::Display for AcceptPost {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let accept_post = test_decode::<AcceptPost>(&["application/ld+json, text/turtle"]).unwrap();
+        let mimes = accept_post
+            .iter()
+            .map(|m| m.essence_str())
+            .collect::<Vec<_>>();
+        assert_eq!(mimes, ["application/ld+json", "text/turtle"]);
+
+        let headers = test_encode(accept_post);
+        assert_eq!(headers["accept-post"], "application/ld+json, text/turtle");
+    }
+
+    #[test]
+    fn contains() {
+        let accept_post = test_decode::<AcceptPost>(&["text/turtle", "image/*"]).unwrap();
+        assert!(accept_post.contains(&"text/turtle; charset=utf-8".parse().unwrap()));
+        assert!(accept_post.contains(&mime::IMAGE_PNG));
+        assert!(!accept_post.contains(&mime::TEXT_PLAIN));
+        assert!(!accept_post.contains(&"application/ld+json".parse().unwrap()));
+    }
+
+    #[test]
+    fn from_iter() {
+        let accept_post = AcceptPost::from_iter([
+            mime::APPLICATION_JSON,
+            mime::TEXT_PLAIN,
+            mime::APPLICATION_JSON,
+        ]);
+        let headers = test_encode(accept_post);
+        assert_eq!(headers["accept-post"], "application/json, text/plain");
+
+        let accept_post = vec![mime::IMAGE_PNG, mime::TEXT_PLAIN, mime::IMAGE_JPEG]
+            .into_iter()
+            .filter(|mime| mime.type_() == mime::IMAGE)
+            .collect::<AcceptPost>();
+        assert_eq!(
+            accept_post.iter().cloned().collect::<Vec<_>>(),
+            [mime::IMAGE_PNG, mime::IMAGE_JPEG]
+        );
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<AcceptPost>(&[""]), None);
+        assert_eq!(test_decode::<AcceptPost>(&["text/turtle, nope"]), None);
+    }
+}
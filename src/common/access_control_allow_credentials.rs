@@ -56,6 +56,10 @@ impl Header for AccessControlAllowCredentials {
     }
 }
 
+impl ::NamedHeader for AccessControlAllowCredentials {
+    const NAME: HeaderName = ::http::header::ACCESS_CONTROL_ALLOW_CREDENTIALS;
+}
+
 #[cfg(test)]
 mod tests {
     use super::super::test_decode;
@@ -1,6 +1,6 @@
 use std::iter::FromIterator;
 
-use util::FlatCsv;
+use util::{self, FlatCsv};
 use {HeaderName, HeaderValue};
 
 /// `Access-Control-Allow-Headers` header, part of
@@ -46,16 +46,20 @@ impl AccessControlAllowHeaders {
             .iter()
             .map(|s| s.parse().ok())
             .take_while(|val| val.is_some())
-            .filter_map(|val| val)
+            .flatten()
     }
 }
 
 impl FromIterator<HeaderName> for AccessControlAllowHeaders {
+    /// Collect header names, skipping any repeated name.
     fn from_iter<I>(iter: I) -> Self
     where
         I: IntoIterator<Item = HeaderName>,
     {
-        let flat = iter.into_iter().map(HeaderValue::from).collect();
+        let flat = util::dedup(iter)
+            .into_iter()
+            .map(HeaderValue::from)
+            .collect();
         AccessControlAllowHeaders(flat)
     }
 }
@@ -87,6 +91,35 @@ mod tests {
             headers["access-control-allow-headers"],
             "cache-control, if-range"
         );
+
+        let allow = AccessControlAllowHeaders::from_iter([
+            ::http::header::CONTENT_TYPE,
+            HeaderName::from_static("x-request-id"),
+            "Content-Type".parse().unwrap(),
+        ]);
+        let headers = test_encode(allow);
+        assert_eq!(
+            headers["access-control-allow-headers"],
+            "content-type, x-request-id"
+        );
+    }
+
+    #[test]
+    fn decode_owned() {
+        use {Header, HeaderValue};
+
+        let values = vec![
+            HeaderValue::from_static("foo, bar"),
+            HeaderValue::from_static("baz"),
+        ];
+        let allow_headers = AccessControlAllowHeaders::decode_owned(values).unwrap();
+
+        let as_vec = allow_headers.iter().collect::<Vec<_>>();
+        assert_eq!(as_vec, ["foo", "bar", "baz"]);
+
+        let single =
+            AccessControlAllowHeaders::decode_owned(Some(HeaderValue::from_static("foo"))).unwrap();
+        assert_eq!(single.iter().collect::<Vec<_>>(), ["foo"]);
     }
 
     #[test]
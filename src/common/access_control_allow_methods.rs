@@ -2,7 +2,7 @@ use std::iter::FromIterator;
 
 use http::Method;
 
-use util::FlatCsv;
+use util::{self, FlatCsv};
 
 /// `Access-Control-Allow-Methods` header, part of
 /// [CORS](http://www.w3.org/TR/cors/#access-control-allow-methods-response-header)
@@ -19,6 +19,7 @@ use util::FlatCsv;
 ///
 /// # Example values
 /// * `PUT, DELETE, XMODIFY`
+/// * `*`
 ///
 /// # Examples
 ///
@@ -31,6 +32,8 @@ use util::FlatCsv;
 /// let allow_methods = vec![Method::GET, Method::PUT]
 ///     .into_iter()
 ///     .collect::<AccessControlAllowMethods>();
+/// assert!(allow_methods.contains(&Method::PUT));
+/// assert!(!allow_methods.contains(&Method::DELETE));
 /// ```
 #[derive(Clone, Debug, PartialEq)]
 pub struct AccessControlAllowMethods(FlatCsv);
@@ -41,18 +44,46 @@ derive_header! {
 }
 
 impl AccessControlAllowMethods {
+    /// `*`, allowing every method for requests without credentials.
+    pub fn any() -> AccessControlAllowMethods {
+        AccessControlAllowMethods(::HeaderValue::from_static("*").into())
+    }
+
+    /// Check if this includes the `*` wildcard.
+    ///
+    /// The wildcard only allows every method if the request was sent
+    /// without credentials, so it is kept apart from the listed methods,
+    /// which are allowed either way.
+    pub fn is_wildcard(&self) -> bool {
+        self.0.iter().any(|s| s == "*")
+    }
+
+    /// Check if `method` is explicitly listed.
+    ///
+    /// Methods are case-sensitive. This doesn't account for the wildcard;
+    /// see `is_wildcard`.
+    pub fn contains(&self, method: &Method) -> bool {
+        self.iter().any(|listed| listed == *method)
+    }
+
     /// Returns an iterator over `Method`s contained within.
+    ///
+    /// The `*` wildcard isn't a method, so it is skipped.
     pub fn iter<'a>(&'a self) -> impl Iterator<Item = Method> + 'a {
-        self.0.iter().filter_map(|s| s.parse().ok())
+        self.0
+            .iter()
+            .filter(|&s| s != "*")
+            .filter_map(|s| s.parse().ok())
     }
 }
 
 impl FromIterator<Method> for AccessControlAllowMethods {
+    /// Collect methods, skipping any repeated method.
     fn from_iter<I>(iter: I) -> Self
     where
         I: IntoIterator<Item = Method>,
     {
-        let methods = iter
+        let methods = util::dedup(iter)
             .into_iter()
             .map(|method| {
                 method
@@ -87,5 +118,40 @@ mod tests {
 
         let headers = test_encode(allow);
         assert_eq!(headers["access-control-allow-methods"], "GET, PUT");
+
+        let allow =
+            AccessControlAllowMethods::from_iter(vec![Method::GET, Method::POST, Method::GET]);
+        assert_eq!(
+            allow.iter().collect::<Vec<_>>(),
+            [Method::GET, Method::POST]
+        );
+        let headers = test_encode(allow);
+        assert_eq!(headers["access-control-allow-methods"], "GET, POST");
+    }
+
+    #[test]
+    fn contains() {
+        let allowed = test_decode::<AccessControlAllowMethods>(&["GET, XMODIFY", "PUT"]).unwrap();
+        assert!(allowed.contains(&Method::GET));
+        assert!(allowed.contains(&Method::PUT));
+        assert!(allowed.contains(&Method::from_bytes(b"XMODIFY").unwrap()));
+        assert!(!allowed.contains(&Method::from_bytes(b"get").unwrap()));
+        assert!(!allowed.contains(&Method::DELETE));
+        assert!(!allowed.is_wildcard());
+    }
+
+    #[test]
+    fn wildcard() {
+        let allowed = test_decode::<AccessControlAllowMethods>(&["*, DELETE"]).unwrap();
+        assert!(allowed.is_wildcard());
+        assert!(allowed.contains(&Method::DELETE));
+        assert!(!allowed.contains(&Method::GET));
+        assert_eq!(allowed.iter().collect::<Vec<_>>(), [Method::DELETE]);
+
+        let any = AccessControlAllowMethods::any();
+        assert!(any.is_wildcard());
+        assert_eq!(any.iter().count(), 0);
+        let headers = test_encode(any);
+        assert_eq!(headers["access-control-allow-methods"], "*");
     }
 }
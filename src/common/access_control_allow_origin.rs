@@ -1,7 +1,7 @@
 use std::convert::TryFrom;
 
 use super::origin::Origin;
-use util::{IterExt, TryFromValues};
+use util::TryFromValues;
 use HeaderValue;
 
 /// The `Access-Control-Allow-Origin` response header,
@@ -62,6 +62,11 @@ impl AccessControlAllowOrigin {
             _ => None,
         }
     }
+
+    /// Whether this allows any origin, `*`.
+    pub fn is_wildcard(&self) -> bool {
+        self.0 == OriginOrAny::Any
+    }
 }
 
 impl TryFrom<&str> for AccessControlAllowOrigin {
@@ -89,23 +94,37 @@ impl TryFromValues for OriginOrAny {
     where
         I: Iterator<Item = &'i HeaderValue>,
     {
-        values
-            .just_one()
-            .and_then(|value| {
-                if value == "*" {
-                    return Some(OriginOrAny::Any);
-                }
-
-                Origin::try_from_value(value).map(OriginOrAny::Origin)
-            })
-            .ok_or_else(::Error::invalid)
+        // Only one origin may be allowed. Proxies that merge duplicate
+        // fields can produce several, but if they differ, picking any one
+        // of them could allow an origin the server didn't mean to, so
+        // that's an error, not just the first one winning.
+        let first = values
+            .next()
+            .and_then(OriginOrAny::from_value)
+            .ok_or_else(::Error::invalid)?;
+        for value in values {
+            if OriginOrAny::from_value(value).as_ref() != Some(&first) {
+                return Err(::Error::invalid());
+            }
+        }
+        Ok(first)
+    }
+}
+
+impl OriginOrAny {
+    fn from_value(value: &HeaderValue) -> Option<OriginOrAny> {
+        if value == "*" {
+            return Some(OriginOrAny::Any);
+        }
+
+        Origin::try_from_value(value).map(OriginOrAny::Origin)
     }
 }
 
 impl<'a> From<&'a OriginOrAny> for HeaderValue {
     fn from(origin: &'a OriginOrAny) -> HeaderValue {
         match origin {
-            OriginOrAny::Origin(ref origin) => origin.into_value(),
+            OriginOrAny::Origin(ref origin) => origin.to_value(),
             OriginOrAny::Any => HeaderValue::from_static("*"),
         }
     }
@@ -154,10 +173,36 @@ mod tests {
         let allow_origin = test_decode::<AccessControlAllowOrigin>(&["*"]).unwrap();
         assert_eq!(allow_origin, AccessControlAllowOrigin::ANY);
 
+        assert!(allow_origin.is_wildcard());
+
         let headers = test_encode(allow_origin);
         assert_eq!(headers["access-control-allow-origin"], "*");
     }
 
+    #[test]
+    fn multiple_values() {
+        let a = "https://a.example";
+        let b = "https://b.example";
+
+        // Duplicates of the same origin are harmless.
+        let allow_origin = test_decode::<AccessControlAllowOrigin>(&[a, a]).unwrap();
+        assert_eq!(allow_origin, AccessControlAllowOrigin::try_from(a).unwrap());
+        assert!(!allow_origin.is_wildcard());
+
+        assert_eq!(test_decode::<AccessControlAllowOrigin>(&[a, b]), None);
+        assert_eq!(test_decode::<AccessControlAllowOrigin>(&[a, "*"]), None);
+        assert_eq!(test_decode::<AccessControlAllowOrigin>(&["null", a]), None);
+        assert_eq!(
+            test_decode::<AccessControlAllowOrigin>(&["https://a.example, https://b.example"]),
+            None
+        );
+
+        let mut values = vec![HeaderValue::from_static(a), HeaderValue::from_static(b)];
+        assert!(<AccessControlAllowOrigin as ::Header>::decode(&mut values.iter()).is_err());
+        values.pop();
+        assert!(<AccessControlAllowOrigin as ::Header>::decode(&mut values.iter()).is_ok());
+    }
+
     #[test]
     fn null() {
         let allow_origin = test_decode::<AccessControlAllowOrigin>(&["null"]).unwrap();
@@ -1,6 +1,6 @@
 use std::iter::FromIterator;
 
-use util::FlatCsv;
+use util::{self, FlatCsv};
 use {HeaderName, HeaderValue};
 
 /// `Access-Control-Expose-Headers` header, part of
@@ -17,6 +17,7 @@ use {HeaderName, HeaderValue};
 ///
 /// # Example values
 /// * `ETag, Content-Length`
+/// * `*`
 ///
 /// # Examples
 ///
@@ -30,6 +31,8 @@ use {HeaderName, HeaderValue};
 /// let expose = vec![CONTENT_LENGTH, ETAG]
 ///     .into_iter()
 ///     .collect::<AccessControlExposeHeaders>();
+/// assert!(expose.contains(&ETAG));
+/// assert!(!expose.is_wildcard());
 /// # }
 /// ```
 #[derive(Clone, Debug)]
@@ -41,18 +44,48 @@ derive_header! {
 }
 
 impl AccessControlExposeHeaders {
+    /// `*`, exposing every header to requests without credentials.
+    pub fn any() -> AccessControlExposeHeaders {
+        AccessControlExposeHeaders(HeaderValue::from_static("*").into())
+    }
+
+    /// Check if this includes the `*` wildcard.
+    ///
+    /// The wildcard only exposes every header if the request was sent
+    /// without credentials, so it is kept apart from the listed names,
+    /// which are exposed either way.
+    pub fn is_wildcard(&self) -> bool {
+        self.0.iter().any(|s| s == "*")
+    }
+
+    /// Check if `name` is explicitly listed.
+    ///
+    /// This doesn't account for the wildcard; see `is_wildcard`.
+    pub fn contains(&self, name: &HeaderName) -> bool {
+        self.iter().any(|listed| listed == *name)
+    }
+
     /// Returns an iterator over `HeaderName`s contained within.
+    ///
+    /// The `*` wildcard isn't a name, so it is skipped.
     pub fn iter<'a>(&'a self) -> impl Iterator<Item = HeaderName> + 'a {
-        self.0.iter().filter_map(|s| s.parse().ok())
+        self.0
+            .iter()
+            .filter(|&s| s != "*")
+            .filter_map(|s| s.parse().ok())
     }
 }
 
 impl FromIterator<HeaderName> for AccessControlExposeHeaders {
+    /// Collect header names, skipping any repeated name.
     fn from_iter<I>(iter: I) -> Self
     where
         I: IntoIterator<Item = HeaderName>,
     {
-        let flat = iter.into_iter().map(HeaderValue::from).collect();
+        let flat = util::dedup(iter)
+            .into_iter()
+            .map(HeaderValue::from)
+            .collect();
         AccessControlExposeHeaders(flat)
     }
 }
@@ -85,4 +118,38 @@ mod tests {
             "cache-control, if-range"
         );
     }
+
+    #[test]
+    fn contains() {
+        let expose = AccessControlExposeHeaders::from_iter(vec![
+            ::http::header::ETAG,
+            HeaderName::from_static("x-request-id"),
+            "ETag".parse().unwrap(),
+        ]);
+        assert!(expose.contains(&::http::header::ETAG));
+        assert!(expose.contains(&HeaderName::from_static("x-request-id")));
+        assert!(!expose.contains(&::http::header::CONTENT_LENGTH));
+        assert!(!expose.is_wildcard());
+
+        let headers = test_encode(expose);
+        assert_eq!(
+            headers["access-control-expose-headers"],
+            "etag, x-request-id"
+        );
+    }
+
+    #[test]
+    fn wildcard() {
+        let expose = test_decode::<AccessControlExposeHeaders>(&["*, Authorization"]).unwrap();
+        assert!(expose.is_wildcard());
+        assert!(expose.contains(&::http::header::AUTHORIZATION));
+        assert!(!expose.contains(&::http::header::ETAG));
+        assert_eq!(expose.iter().collect::<Vec<_>>(), ["authorization"]);
+
+        let any = AccessControlExposeHeaders::any();
+        assert!(any.is_wildcard());
+        assert_eq!(any.iter().count(), 0);
+        let headers = test_encode(any);
+        assert_eq!(headers["access-control-expose-headers"], "*");
+    }
 }
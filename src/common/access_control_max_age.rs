@@ -26,6 +26,7 @@ use util::Seconds;
 /// use headers::AccessControlMaxAge;
 ///
 /// let max_age = AccessControlMaxAge::from(Duration::from_secs(531));
+/// assert_eq!(max_age.as_secs(), 531);
 /// ```
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct AccessControlMaxAge(Seconds);
@@ -35,6 +36,13 @@ derive_header! {
     name: ACCESS_CONTROL_MAX_AGE
 }
 
+impl AccessControlMaxAge {
+    /// Returns the number of seconds a preflight result may be cached.
+    pub fn as_secs(&self) -> u64 {
+        self.0.as_u64()
+    }
+}
+
 impl From<Duration> for AccessControlMaxAge {
     fn from(dur: Duration) -> AccessControlMaxAge {
         AccessControlMaxAge(dur.into())
@@ -46,3 +54,31 @@ impl From<AccessControlMaxAge> for Duration {
         acma.0.into()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::super::{test_decode, test_encode};
+    use super::AccessControlMaxAge;
+
+    #[test]
+    fn round_trip() {
+        let max_age = test_decode::<AccessControlMaxAge>(&["600"]).unwrap();
+        assert_eq!(max_age, AccessControlMaxAge::from(Duration::from_secs(600)));
+        assert_eq!(max_age.as_secs(), 600);
+        assert_eq!(Duration::from(max_age.clone()), Duration::from_secs(600));
+
+        let headers = test_encode(max_age);
+        assert_eq!(headers["access-control-max-age"], "600");
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<AccessControlMaxAge>(&["-1"]), None);
+        assert_eq!(test_decode::<AccessControlMaxAge>(&["+600"]), None);
+        assert_eq!(test_decode::<AccessControlMaxAge>(&["60s"]), None);
+        assert_eq!(test_decode::<AccessControlMaxAge>(&["1.5"]), None);
+        assert_eq!(test_decode::<AccessControlMaxAge>(&[""]), None);
+    }
+}
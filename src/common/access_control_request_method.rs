@@ -60,6 +60,10 @@ impl Header for AccessControlRequestMethod {
     }
 }
 
+impl ::NamedHeader for AccessControlRequestMethod {
+    const NAME: HeaderName = ::http::header::ACCESS_CONTROL_REQUEST_METHOD;
+}
+
 impl From<Method> for AccessControlRequestMethod {
     fn from(method: Method) -> AccessControlRequestMethod {
         AccessControlRequestMethod(method)
@@ -2,7 +2,7 @@ use std::iter::FromIterator;
 
 use http::Method;
 
-use util::FlatCsv;
+use util::ListHeader;
 
 /// `Allow` header, defined in [RFC7231](http://tools.ietf.org/html/rfc7231#section-7.4.1)
 ///
@@ -35,7 +35,7 @@ use util::FlatCsv;
 ///     .collect::<Allow>();
 /// ```
 #[derive(Clone, Debug, PartialEq)]
-pub struct Allow(FlatCsv);
+pub struct Allow(ListHeader<String>);
 
 derive_header! {
     Allow(_),
@@ -44,25 +44,69 @@ derive_header! {
 
 impl Allow {
     /// Returns an iterator over `Method`s contained within.
+    ///
+    /// Elements that aren't valid methods are skipped.
     pub fn iter<'a>(&'a self) -> impl Iterator<Item = Method> + 'a {
         self.0.iter().filter_map(|s| s.parse().ok())
     }
 }
 
 impl FromIterator<Method> for Allow {
+    /// Collect methods into an `Allow`, skipping any repeated method.
     fn from_iter<I>(iter: I) -> Self
     where
         I: IntoIterator<Item = Method>,
     {
-        let flat = iter
+        Allow(
+            iter.into_iter()
+                .map(|method| method.as_str().to_owned())
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn from_iter() {
+        let allow = Allow::from_iter([Method::GET, Method::POST, Method::GET]);
+        assert_eq!(
+            allow.iter().collect::<Vec<_>>(),
+            [Method::GET, Method::POST]
+        );
+
+        let headers = test_encode(allow);
+        assert_eq!(headers["allow"], "GET, POST");
+
+        let allow = vec![Method::GET, Method::PUT, Method::DELETE, Method::HEAD]
             .into_iter()
-            .map(|method| {
-                method
-                    .as_str()
-                    .parse::<::HeaderValue>()
-                    .expect("Method is a valid HeaderValue")
-            })
-            .collect();
-        Allow(flat)
+            .filter(|method| method.is_safe())
+            .collect::<Allow>();
+        assert_eq!(
+            allow.iter().collect::<Vec<_>>(),
+            [Method::GET, Method::HEAD]
+        );
+    }
+
+    #[test]
+    fn decode() {
+        let allow = test_decode::<Allow>(&["GET, HEAD", "fOObAr,"]).unwrap();
+        assert_eq!(
+            allow.iter().collect::<Vec<_>>(),
+            [
+                Method::GET,
+                Method::HEAD,
+                Method::from_bytes(b"fOObAr").unwrap()
+            ]
+        );
+
+        let allow = test_decode::<Allow>(&[""]).unwrap();
+        assert_eq!(allow.iter().count(), 0);
+
+        let allow = test_decode::<Allow>(&["GET, (HEAD)"]).unwrap();
+        assert_eq!(allow.iter().collect::<Vec<_>>(), [Method::GET]);
     }
 }
@@ -0,0 +1,104 @@
+use std::fmt;
+
+use http::uri::Authority;
+
+use {Header, HeaderName, HeaderValue, Host};
+
+const ALT_USED: HeaderName = HeaderName::from_static("alt-used");
+
+/// `Alt-Used` header, defined in
+/// [RFC7838](https://tools.ietf.org/html/rfc7838#section-5)
+///
+/// The `Alt-Used` header field is used in requests to identify the
+/// alternative service in use, just as the `Host` header field
+/// identifies the host and port of the origin.
+///
+/// # ABNF
+///
+/// ```text
+/// Alt-Used = uri-host [ ":" port ]
+/// ```
+///
+/// # Example values
+///
+/// * `alternate.example.net`
+/// * `[2001:db8::1]:8443`
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// # extern crate http;
+/// use headers::AltUsed;
+/// use http::uri::Authority;
+///
+/// let alt_used = AltUsed::from(Authority::from_static("alternate.example.net"));
+/// assert_eq!(alt_used.hostname(), "alternate.example.net");
+/// assert_eq!(alt_used.port(), None);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd)]
+pub struct AltUsed(Host);
+
+impl AltUsed {
+    /// Get the hostname, such as alternate.example.net.
+    pub fn hostname(&self) -> &str {
+        self.0.hostname()
+    }
+
+    /// Get the optional port number.
+    pub fn port(&self) -> Option<u16> {
+        self.0.port()
+    }
+}
+
+impl Header for AltUsed {
+    fn name() -> &'static HeaderName {
+        static NAME: HeaderName = ALT_USED;
+        &NAME
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        Host::decode(values).map(AltUsed)
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        self.0.encode(values)
+    }
+}
+
+impl ::NamedHeader for AltUsed {
+    const NAME: HeaderName = ALT_USED;
+}
+
+impl From<Authority> for AltUsed {
+    fn from(auth: Authority) -> AltUsed {
+        AltUsed(Host::from(auth))
+    }
+}
+
+impl fmt::Display for AltUsed {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::AltUsed;
+
+    #[test]
+    fn ipv6_round_trip() {
+        let alt_used = test_decode::<AltUsed>(&["[2001:db8::1]:8443"]).unwrap();
+        assert_eq!(alt_used.hostname(), "[2001:db8::1]");
+        assert_eq!(alt_used.port(), Some(8443));
+
+        let headers = test_encode(alt_used);
+        assert_eq!(headers["alt-used"], "[2001:db8::1]:8443");
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(test_decode::<AltUsed>(&["not a host"]), None);
+    }
+}
@@ -3,8 +3,8 @@ use std::iter::FromIterator;
 use std::str::FromStr;
 use std::time::Duration;
 
-use util::{self, csv, Seconds};
-use HeaderValue;
+use util::{self, csv, FlatCsv, Seconds};
+use {HeaderName, HeaderValue};
 
 /// `Cache-Control` header, defined in [RFC7234](https://tools.ietf.org/html/rfc7234#section-5.2)
 /// with extensions in [RFC8246](https://www.rfc-editor.org/rfc/rfc8246)
@@ -26,6 +26,7 @@ use HeaderValue;
 /// * `no-cache`
 /// * `private, community="UCI"`
 /// * `max-age=30`
+/// * `no-cache="Set-Cookie"`
 ///
 /// # Example
 ///
@@ -35,9 +36,11 @@ use HeaderValue;
 ///
 /// let cc = CacheControl::new();
 /// ```
-#[derive(PartialEq, Clone, Debug)]
+#[derive(PartialEq, Clone)]
 pub struct CacheControl {
     flags: Flags,
+    no_cache_fields: Option<Vec<HeaderName>>,
+    private_fields: Option<Vec<HeaderName>>,
     max_age: Option<Seconds>,
     max_stale: Option<Seconds>,
     min_fresh: Option<Seconds>,
@@ -60,6 +63,9 @@ impl Flags {
     const PROXY_REVALIDATE: Self = Self { bits: 0b010000000 };
     const IMMUTABLE: Self = Self { bits: 0b100000000 };
     const MUST_UNDERSTAND: Self = Self { bits: 0b1000000000 };
+    const MAX_STALE_ANY: Self = Self {
+        bits: 0b10000000000,
+    };
 
     fn empty() -> Self {
         Self { bits: 0 }
@@ -72,6 +78,39 @@ impl Flags {
     fn insert(&mut self, flag: Self) {
         self.bits |= flag.bits;
     }
+
+    fn remove(&mut self, flag: Self) {
+        self.bits &= !flag.bits;
+    }
+}
+
+impl fmt::Debug for CacheControl {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CacheControl")
+            .field("no_cache", &self.no_cache())
+            .field("no_store", &self.no_store())
+            .field("no_transform", &self.no_transform())
+            .field("only_if_cached", &self.only_if_cached())
+            .field(
+                "must_revalidate",
+                &self.flags.contains(Flags::MUST_REVALIDATE),
+            )
+            .field("must_understand", &self.must_understand())
+            .field(
+                "proxy_revalidate",
+                &self.flags.contains(Flags::PROXY_REVALIDATE),
+            )
+            .field("public", &self.public())
+            .field("private", &self.private())
+            .field("no_cache_fields", &self.no_cache_fields)
+            .field("private_fields", &self.private_fields)
+            .field("immutable", &self.immutable())
+            .field("max_age", &self.max_age)
+            .field("max_stale", &self.max_stale())
+            .field("min_fresh", &self.min_fresh)
+            .field("s_max_age", &self.s_max_age)
+            .finish()
+    }
 }
 
 impl CacheControl {
@@ -79,6 +118,8 @@ impl CacheControl {
     pub fn new() -> Self {
         CacheControl {
             flags: Flags::empty(),
+            no_cache_fields: None,
+            private_fields: None,
             max_age: None,
             max_stale: None,
             min_fresh: None,
@@ -89,10 +130,20 @@ impl CacheControl {
     // getters
 
     /// Check if the `no-cache` directive is set.
+    ///
+    /// This is true for the qualified `no-cache="field"` form too, which a
+    /// cache may treat the same as a bare `no-cache`.
     pub fn no_cache(&self) -> bool {
         self.flags.contains(Flags::NO_CACHE)
     }
 
+    /// Get the field names of a qualified `no-cache="field, ..."` directive.
+    ///
+    /// Returns `None` if `no-cache` isn't set, or is set without fields.
+    pub fn no_cache_fields(&self) -> Option<&[HeaderName]> {
+        self.no_cache_fields.as_deref()
+    }
+
     /// Check if the `no-store` directive is set.
     pub fn no_store(&self) -> bool {
         self.flags.contains(Flags::NO_STORE)
@@ -109,15 +160,29 @@ impl CacheControl {
     }
 
     /// Check if the `public` directive is set.
+    ///
+    /// This is only true if `public` was sent. Without it, or `private`,
+    /// whether a shared cache may store the response depends on the other
+    /// directives and the request.
     pub fn public(&self) -> bool {
         self.flags.contains(Flags::PUBLIC)
     }
 
     /// Check if the `private` directive is set.
+    ///
+    /// This is true for the qualified `private="field"` form too, which a
+    /// cache may treat the same as a bare `private`.
     pub fn private(&self) -> bool {
         self.flags.contains(Flags::PRIVATE)
     }
 
+    /// Get the field names of a qualified `private="field, ..."` directive.
+    ///
+    /// Returns `None` if `private` isn't set, or is set without fields.
+    pub fn private_fields(&self) -> Option<&[HeaderName]> {
+        self.private_fields.as_deref()
+    }
+
     /// Check if the `immutable` directive is set.
     pub fn immutable(&self) -> bool {
         self.flags.contains(Flags::IMMUTABLE)
@@ -133,8 +198,15 @@ impl CacheControl {
     }
 
     /// Get the value of the `max-stale` directive if set.
-    pub fn max_stale(&self) -> Option<Duration> {
-        self.max_stale.map(Into::into)
+    ///
+    /// A bare `max-stale`, accepting a response however stale it is, is
+    /// `Some(None)`, while `max-stale=N` is `Some(Some(N))`.
+    pub fn max_stale(&self) -> Option<Option<Duration>> {
+        match self.max_stale {
+            Some(secs) => Some(Some(secs.into())),
+            None if self.flags.contains(Flags::MAX_STALE_ANY) => Some(None),
+            None => None,
+        }
     }
 
     /// Get the value of the `min-fresh` directive if set.
@@ -152,6 +224,16 @@ impl CacheControl {
     /// Set the `no-cache` directive.
     pub fn with_no_cache(mut self) -> Self {
         self.flags.insert(Flags::NO_CACHE);
+        self.no_cache_fields = None;
+        self
+    }
+
+    /// Set the `no-cache` directive, qualified with field names.
+    ///
+    /// With no fields, this is the same as `with_no_cache`.
+    pub fn with_no_cache_fields(mut self, fields: Vec<HeaderName>) -> Self {
+        self.flags.insert(Flags::NO_CACHE);
+        self.no_cache_fields = Some(fields).filter(|fields| !fields.is_empty());
         self
     }
 
@@ -173,14 +255,29 @@ impl CacheControl {
         self
     }
 
-    /// Set the `private` directive.
+    /// Set the `private` directive, clearing any `public`.
     pub fn with_private(mut self) -> Self {
+        self.flags.remove(Flags::PUBLIC);
+        self.flags.insert(Flags::PRIVATE);
+        self.private_fields = None;
+        self
+    }
+
+    /// Set the `private` directive, qualified with field names.
+    ///
+    /// With no fields, this is the same as `with_private`. This clears any
+    /// `public`.
+    pub fn with_private_fields(mut self, fields: Vec<HeaderName>) -> Self {
+        self.flags.remove(Flags::PUBLIC);
         self.flags.insert(Flags::PRIVATE);
+        self.private_fields = Some(fields).filter(|fields| !fields.is_empty());
         self
     }
 
-    /// Set the `public` directive.
+    /// Set the `public` directive, clearing any `private`.
     pub fn with_public(mut self) -> Self {
+        self.flags.remove(Flags::PRIVATE);
+        self.private_fields = None;
         self.flags.insert(Flags::PUBLIC);
         self
     }
@@ -208,6 +305,14 @@ impl CacheControl {
         self
     }
 
+    /// Set the `max-stale` directive without a value, accepting a response
+    /// however stale it is.
+    pub fn with_max_stale_any(mut self) -> Self {
+        self.max_stale = None;
+        self.flags.insert(Flags::MAX_STALE_ANY);
+        self
+    }
+
     /// Set the `min-fresh` directive.
     pub fn with_min_fresh(mut self, duration: Duration) -> Self {
         self.min_fresh = Some(duration.into());
@@ -221,13 +326,26 @@ impl CacheControl {
     }
 }
 
+impl Default for CacheControl {
+    fn default() -> Self {
+        CacheControl::new()
+    }
+}
+
 impl ::Header for CacheControl {
     fn name() -> &'static ::HeaderName {
         &::http::header::CACHE_CONTROL
     }
 
     fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
-        csv::from_comma_delimited(values).map(|FromIter(cc)| cc)
+        // Quote-aware, since a field list such as `no-cache="a, b"` has
+        // commas of its own.
+        let csv = values.collect::<FlatCsv>();
+        csv.iter()
+            .filter(|s| !s.is_empty())
+            .map(|s| s.parse().map_err(|_| ::Error::invalid()))
+            .collect::<Result<_, _>>()
+            .map(|FromIter(cc)| cc)
     }
 
     fn encode<E: Extend<::HeaderValue>>(&self, values: &mut E) {
@@ -235,6 +353,10 @@ impl ::Header for CacheControl {
     }
 }
 
+impl ::NamedHeader for CacheControl {
+    const NAME: ::HeaderName = ::http::header::CACHE_CONTROL;
+}
+
 // Adapter to be used in Header::decode
 struct FromIter(CacheControl);
 
@@ -255,6 +377,16 @@ impl FromIterator<KnownDirective> for FromIter {
             match directive {
                 Directive::NoCache => {
                     cc.flags.insert(Flags::NO_CACHE);
+                    cc.no_cache_fields = None;
+                }
+                Directive::NoCacheFields(fields) => {
+                    // A bare `no-cache` anywhere wins over the qualified form.
+                    if !cc.no_cache() || cc.no_cache_fields.is_some() {
+                        cc.flags.insert(Flags::NO_CACHE);
+                        cc.no_cache_fields
+                            .get_or_insert_with(Vec::new)
+                            .extend(fields);
+                    }
                 }
                 Directive::NoStore => {
                     cc.flags.insert(Flags::NO_STORE);
@@ -276,6 +408,16 @@ impl FromIterator<KnownDirective> for FromIter {
                 }
                 Directive::Private => {
                     cc.flags.insert(Flags::PRIVATE);
+                    cc.private_fields = None;
+                }
+                Directive::PrivateFields(fields) => {
+                    // A bare `private` anywhere wins over the qualified form.
+                    if !cc.private() || cc.private_fields.is_some() {
+                        cc.flags.insert(Flags::PRIVATE);
+                        cc.private_fields
+                            .get_or_insert_with(Vec::new)
+                            .extend(fields);
+                    }
                 }
                 Directive::Immutable => {
                     cc.flags.insert(Flags::IMMUTABLE);
@@ -284,16 +426,19 @@ impl FromIterator<KnownDirective> for FromIter {
                     cc.flags.insert(Flags::PROXY_REVALIDATE);
                 }
                 Directive::MaxAge(secs) => {
-                    cc.max_age = Some(Duration::from_secs(secs.into()).into());
+                    cc.max_age = Some(Duration::from_secs(secs).into());
                 }
                 Directive::MaxStale(secs) => {
-                    cc.max_stale = Some(Duration::from_secs(secs.into()).into());
+                    cc.max_stale = Some(Duration::from_secs(secs).into());
+                }
+                Directive::MaxStaleAny => {
+                    cc.flags.insert(Flags::MAX_STALE_ANY);
                 }
                 Directive::MinFresh(secs) => {
-                    cc.min_fresh = Some(Duration::from_secs(secs.into()).into());
+                    cc.min_fresh = Some(Duration::from_secs(secs).into());
                 }
                 Directive::SMaxAge(secs) => {
-                    cc.s_max_age = Some(Duration::from_secs(secs.into()).into());
+                    cc.s_max_age = Some(Duration::from_secs(secs).into());
                 }
             }
         }
@@ -314,14 +459,23 @@ impl<'a> fmt::Display for Fmt<'a> {
             }
         };
 
+        let no_cache = match self.0.no_cache_fields {
+            Some(ref fields) => Some(Directive::NoCacheFields(fields.clone())),
+            None => if_flag(Flags::NO_CACHE, Directive::NoCache),
+        };
+        let private = match self.0.private_fields {
+            Some(ref fields) => Some(Directive::PrivateFields(fields.clone())),
+            None => if_flag(Flags::PRIVATE, Directive::Private),
+        };
+
         let slice = &[
-            if_flag(Flags::NO_CACHE, Directive::NoCache),
+            no_cache,
             if_flag(Flags::NO_STORE, Directive::NoStore),
             if_flag(Flags::NO_TRANSFORM, Directive::NoTransform),
             if_flag(Flags::ONLY_IF_CACHED, Directive::OnlyIfCached),
             if_flag(Flags::MUST_REVALIDATE, Directive::MustRevalidate),
             if_flag(Flags::PUBLIC, Directive::Public),
-            if_flag(Flags::PRIVATE, Directive::Private),
+            private,
             if_flag(Flags::IMMUTABLE, Directive::Immutable),
             if_flag(Flags::MUST_UNDERSTAND, Directive::MustUnderstand),
             if_flag(Flags::PROXY_REVALIDATE, Directive::ProxyRevalidate),
@@ -329,10 +483,10 @@ impl<'a> fmt::Display for Fmt<'a> {
                 .max_age
                 .as_ref()
                 .map(|s| Directive::MaxAge(s.as_u64())),
-            self.0
-                .max_stale
-                .as_ref()
-                .map(|s| Directive::MaxStale(s.as_u64())),
+            self.0.max_stale().map(|max_stale| match max_stale {
+                Some(dur) => Directive::MaxStale(dur.as_secs()),
+                None => Directive::MaxStaleAny,
+            }),
             self.0
                 .min_fresh
                 .as_ref()
@@ -343,21 +497,22 @@ impl<'a> fmt::Display for Fmt<'a> {
                 .map(|s| Directive::SMaxAge(s.as_u64())),
         ];
 
-        let iter = slice.iter().filter_map(|o| *o);
+        let iter = slice.iter().flatten();
 
         csv::fmt_comma_delimited(f, iter)
     }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone)]
 enum KnownDirective {
     Known(Directive),
     Unknown,
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone)]
 enum Directive {
     NoCache,
+    NoCacheFields(Vec<HeaderName>),
     NoStore,
     NoTransform,
     OnlyIfCached,
@@ -365,6 +520,7 @@ enum Directive {
     // request directives
     MaxAge(u64),
     MaxStale(u64),
+    MaxStaleAny,
     MinFresh(u64),
 
     // response directives
@@ -372,6 +528,7 @@ enum Directive {
     MustUnderstand,
     Public,
     Private,
+    PrivateFields(Vec<HeaderName>),
     Immutable,
     ProxyRevalidate,
     SMaxAge(u64),
@@ -382,18 +539,25 @@ impl fmt::Display for Directive {
         fmt::Display::fmt(
             match *self {
                 Directive::NoCache => "no-cache",
+                Directive::NoCacheFields(ref fields) => {
+                    return write!(f, "no-cache=\"{}\"", FieldList(fields))
+                }
                 Directive::NoStore => "no-store",
                 Directive::NoTransform => "no-transform",
                 Directive::OnlyIfCached => "only-if-cached",
 
                 Directive::MaxAge(secs) => return write!(f, "max-age={}", secs),
                 Directive::MaxStale(secs) => return write!(f, "max-stale={}", secs),
+                Directive::MaxStaleAny => "max-stale",
                 Directive::MinFresh(secs) => return write!(f, "min-fresh={}", secs),
 
                 Directive::MustRevalidate => "must-revalidate",
                 Directive::MustUnderstand => "must-understand",
                 Directive::Public => "public",
                 Directive::Private => "private",
+                Directive::PrivateFields(ref fields) => {
+                    return write!(f, "private=\"{}\"", FieldList(fields))
+                }
                 Directive::Immutable => "immutable",
                 Directive::ProxyRevalidate => "proxy-revalidate",
                 Directive::SMaxAge(secs) => return write!(f, "s-maxage={}", secs),
@@ -403,6 +567,27 @@ impl fmt::Display for Directive {
     }
 }
 
+struct FieldList<'a>(&'a [HeaderName]);
+
+impl<'a> fmt::Display for FieldList<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        csv::fmt_comma_delimited(f, self.0.iter())
+    }
+}
+
+/// Parse the field names of a qualified `no-cache` or `private`.
+///
+/// An empty list is the same as the bare directive.
+fn parse_fields(s: &str) -> Result<Option<Vec<HeaderName>>, ()> {
+    let fields = s
+        .split(',')
+        .map(str::trim)
+        .filter(|field| !field.is_empty())
+        .map(|field| field.parse::<HeaderName>().map_err(|_| ()))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Some(fields).filter(|fields| !fields.is_empty()))
+}
+
 impl FromStr for KnownDirective {
     type Err = ();
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -411,6 +596,7 @@ impl FromStr for KnownDirective {
             "no-store" => Directive::NoStore,
             "no-transform" => Directive::NoTransform,
             "only-if-cached" => Directive::OnlyIfCached,
+            "max-stale" => Directive::MaxStaleAny,
             "must-revalidate" => Directive::MustRevalidate,
             "public" => Directive::Public,
             "private" => Directive::Private,
@@ -420,7 +606,15 @@ impl FromStr for KnownDirective {
             "" => return Err(()),
             _ => match s.find('=') {
                 Some(idx) if idx + 1 < s.len() => {
-                    match (&s[..idx], (&s[idx + 1..]).trim_matches('"')) {
+                    match (&s[..idx], s[idx + 1..].trim_matches('"')) {
+                        ("no-cache", fields) => match parse_fields(fields)? {
+                            Some(fields) => Directive::NoCacheFields(fields),
+                            None => Directive::NoCache,
+                        },
+                        ("private", fields) => match parse_fields(fields)? {
+                            Some(fields) => Directive::PrivateFields(fields),
+                            None => Directive::Private,
+                        },
                         ("max-age", secs) => secs.parse().map(Directive::MaxAge).map_err(|_| ())?,
                         ("max-stale", secs) => {
                             secs.parse().map(Directive::MaxStale).map_err(|_| ())?
@@ -445,6 +639,16 @@ mod tests {
     use super::super::{test_decode, test_encode};
     use super::*;
 
+    #[test]
+    fn test_debug() {
+        let cc = test_decode::<CacheControl>(&["max-age=600, public"]).unwrap();
+        let debug = format!("{:?}", cc);
+        assert!(debug.starts_with("CacheControl { no_cache: false, no_store: false,"));
+        assert!(debug.contains("public: true"));
+        assert!(debug.contains("max_age: Some(600s)"));
+        assert!(debug.contains("s_max_age: None"));
+    }
+
     #[test]
     fn test_parse_multiple_headers() {
         assert_eq!(
@@ -501,6 +705,135 @@ mod tests {
         assert!(cc.must_understand());
     }
 
+    #[test]
+    fn test_request_directives() {
+        let cc = test_decode::<CacheControl>(&["max-stale=60, only-if-cached"]).unwrap();
+        assert_eq!(cc.max_stale(), Some(Some(Duration::from_secs(60))));
+        assert!(cc.only_if_cached());
+        assert_eq!(cc.min_fresh(), None);
+        assert!(!cc.no_cache());
+        assert_eq!(
+            cc,
+            CacheControl::new()
+                .with_max_stale(Duration::from_secs(60))
+                .with_only_if_cached()
+        );
+
+        let headers = test_encode(cc);
+        assert_eq!(headers["cache-control"], "only-if-cached, max-stale=60");
+
+        let cc = test_decode::<CacheControl>(&["max-stale, min-fresh=5, no-cache"]).unwrap();
+        assert_eq!(cc.max_stale(), Some(None));
+        assert_eq!(cc.min_fresh(), Some(Duration::from_secs(5)));
+        assert!(cc.no_cache());
+
+        let headers = test_encode(cc);
+        assert_eq!(headers["cache-control"], "no-cache, max-stale, min-fresh=5");
+
+        let headers = test_encode(
+            CacheControl::new()
+                .with_max_stale(Duration::from_secs(1))
+                .with_max_stale_any(),
+        );
+        assert_eq!(headers["cache-control"], "max-stale");
+        assert_eq!(CacheControl::new().max_stale(), None);
+    }
+
+    #[test]
+    fn test_qualified_fields() {
+        let cc = test_decode::<CacheControl>(&["private=\"Authorization\", max-age=0"]).unwrap();
+        assert!(cc.private());
+        assert_eq!(
+            cc.private_fields(),
+            Some(&[::http::header::AUTHORIZATION][..])
+        );
+        assert!(!cc.no_cache());
+        assert_eq!(cc.no_cache_fields(), None);
+        assert_eq!(cc.max_age(), Some(Duration::from_secs(0)));
+
+        let headers = test_encode(cc);
+        assert_eq!(
+            headers["cache-control"],
+            "private=\"authorization\", max-age=0"
+        );
+
+        let cc =
+            test_decode::<CacheControl>(&["no-cache=\"Set-Cookie, X-Token\", public"]).unwrap();
+        assert!(cc.no_cache());
+        assert_eq!(
+            cc.no_cache_fields().unwrap(),
+            [
+                ::http::header::SET_COOKIE,
+                HeaderName::from_static("x-token")
+            ]
+        );
+        assert_eq!(
+            cc,
+            CacheControl::new()
+                .with_no_cache_fields(vec![
+                    ::http::header::SET_COOKIE,
+                    HeaderName::from_static("x-token"),
+                ])
+                .with_public()
+        );
+
+        let headers = test_encode(cc);
+        assert_eq!(
+            headers["cache-control"],
+            "no-cache=\"set-cookie, x-token\", public"
+        );
+    }
+
+    #[test]
+    fn test_qualified_and_bare() {
+        let cc = test_decode::<CacheControl>(&["no-cache=\"set-cookie\"", "no-cache"]).unwrap();
+        assert!(cc.no_cache());
+        assert_eq!(cc.no_cache_fields(), None);
+
+        let cc = test_decode::<CacheControl>(&["private, private=\"set-cookie\""]).unwrap();
+        assert!(cc.private());
+        assert_eq!(cc.private_fields(), None);
+
+        let cc = test_decode::<CacheControl>(&["private=\"\""]).unwrap();
+        assert_eq!(cc, CacheControl::new().with_private());
+
+        let cc = CacheControl::new()
+            .with_private_fields(vec![::http::header::SET_COOKIE])
+            .with_private();
+        assert_eq!(cc.private_fields(), None);
+
+        assert_eq!(
+            test_decode::<CacheControl>(&["no-cache=\"not a field\""]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_public_and_private() {
+        let cc = test_decode::<CacheControl>(&["public, max-age=60"]).unwrap();
+        assert!(cc.public());
+        assert!(!cc.private());
+        let headers = test_encode(cc);
+        assert_eq!(headers["cache-control"], "public, max-age=60");
+
+        let cc = test_decode::<CacheControl>(&["max-age=60"]).unwrap();
+        assert!(!cc.public());
+        assert!(!cc.private());
+
+        let cc = CacheControl::new()
+            .with_private_fields(vec![::http::header::SET_COOKIE])
+            .with_public();
+        assert!(cc.public());
+        assert!(!cc.private());
+        assert_eq!(cc.private_fields(), None);
+
+        let cc = CacheControl::new().with_public().with_private();
+        assert!(!cc.public());
+        assert!(cc.private());
+        let headers = test_encode(cc);
+        assert_eq!(headers["cache-control"], "private");
+    }
+
     #[test]
     fn test_parse_bad_syntax() {
         assert_eq!(test_decode::<CacheControl>(&["max-age=lolz"]), None);
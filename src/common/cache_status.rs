@@ -0,0 +1,343 @@
+//! Cache-Status header and types.
+
+use util::sf::{self, BareItem, Item, Member, Parameters};
+use util::{self, TryFromValues};
+use {HeaderName, HeaderValue};
+
+const CACHE_STATUS: HeaderName = HeaderName::from_static("cache-status");
+
+/// `Cache-Status` header, defined in
+/// [RFC9211](https://www.rfc-editor.org/rfc/rfc9211.html)
+///
+/// The `Cache-Status` response header field indicates how caches have
+/// handled that response and its corresponding request. Each cache in the
+/// chain appends an entry, so the first entry is the cache closest to the
+/// origin server, and the last is the one closest to the user agent.
+///
+/// # ABNF
+///
+/// ```text
+/// Cache-Status   = sf-list
+///
+/// ; each member is a cache identifier with parameters
+/// hit            = sf-boolean
+/// fwd            = sf-token
+/// fwd-status     = sf-integer
+/// ttl            = sf-integer
+/// stored         = sf-boolean
+/// collapsed      = sf-boolean
+/// key            = sf-string
+/// detail         = sf-token / sf-string
+/// ```
+///
+/// # Example values
+///
+/// * `ExampleCache; hit`
+/// * `OriginCache; hit; ttl=1100, "CDN Company Here"; hit; ttl=545`
+/// * `ExampleCache; fwd=uri-miss; stored`
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// # extern crate http;
+/// use headers::{CacheStatus, HeaderMapExt};
+///
+/// let mut map = http::HeaderMap::new();
+/// map.insert("cache-status", "ReverseProxy; fwd=uri-miss; stored, Browser; hit; ttl=30".parse().unwrap());
+///
+/// let status = map.typed_get::<CacheStatus>().unwrap();
+/// let entries = status.iter().collect::<Vec<_>>();
+/// assert_eq!(entries[0].forwarded_reason(), Some("uri-miss"));
+/// assert!(entries[1].is_hit());
+/// assert_eq!(entries[1].ttl(), Some(30));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct CacheStatus(Vec<Entry>);
+
+/// How a single cache handled a request, in a `Cache-Status` header.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Entry {
+    cache: BareItem,
+    hit: bool,
+    fwd: Option<String>,
+    fwd_status: Option<u16>,
+    ttl: Option<i64>,
+    stored: bool,
+    collapsed: bool,
+    key: Option<String>,
+    detail: Option<BareItem>,
+    extensions: Parameters,
+}
+
+impl ::Header for CacheStatus {
+    fn name() -> &'static HeaderName {
+        static NAME: HeaderName = CACHE_STATUS;
+        &NAME
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        CacheStatus::try_from_values(values)
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        let list = sf::List(self.0.iter().map(Entry::to_member).collect());
+        values.extend(::std::iter::once(util::fmt(list)));
+    }
+}
+
+impl ::NamedHeader for CacheStatus {
+    const NAME: ::HeaderName = CACHE_STATUS;
+}
+
+impl TryFromValues for CacheStatus {
+    fn try_from_values<'i, I>(values: &mut I) -> Result<Self, ::Error>
+    where
+        I: Iterator<Item = &'i HeaderValue>,
+    {
+        let list = sf::parse_list(values)?;
+        if list.0.is_empty() {
+            return Err(::Error::invalid());
+        }
+        list.0
+            .into_iter()
+            .map(Entry::from_member)
+            .collect::<Option<Vec<_>>>()
+            .map(CacheStatus)
+            .ok_or_else(::Error::invalid)
+    }
+}
+
+impl CacheStatus {
+    /// Iterate the entries of this `Cache-Status`, starting with the cache
+    /// closest to the origin server.
+    pub fn iter(&self) -> impl Iterator<Item = &Entry> {
+        self.0.iter()
+    }
+}
+
+impl Entry {
+    /// The identifier of the cache that added this entry.
+    pub fn cache(&self) -> &str {
+        match self.cache {
+            BareItem::Token(ref s) | BareItem::String(ref s) => s,
+            _ => unreachable!("Entry::cache is always a token or string"),
+        }
+    }
+
+    /// Whether the request was satisfied by the cache, without forwarding it.
+    pub fn is_hit(&self) -> bool {
+        self.hit
+    }
+
+    /// Why the request was forwarded towards the origin, such as `uri-miss`
+    /// or `stale`.
+    pub fn forwarded_reason(&self) -> Option<&str> {
+        self.fwd.as_deref()
+    }
+
+    /// The status code the next hop returned for a forwarded request.
+    pub fn forwarded_status(&self) -> Option<u16> {
+        self.fwd_status
+    }
+
+    /// The response's remaining freshness lifetime in seconds, as calculated
+    /// by the cache.
+    ///
+    /// This is negative if the response is stale.
+    pub fn ttl(&self) -> Option<i64> {
+        self.ttl
+    }
+
+    /// Whether the cache stored the response.
+    pub fn is_stored(&self) -> bool {
+        self.stored
+    }
+
+    /// Whether the request was collapsed with others.
+    pub fn is_collapsed(&self) -> bool {
+        self.collapsed
+    }
+
+    /// The cache key used for the response, in an implementation-specific
+    /// format.
+    pub fn key(&self) -> Option<&str> {
+        self.key.as_deref()
+    }
+
+    /// Additional implementation-specific details.
+    pub fn detail(&self) -> Option<&str> {
+        match self.detail {
+            Some(BareItem::Token(ref s)) | Some(BareItem::String(ref s)) => Some(s),
+            _ => None,
+        }
+    }
+
+    fn from_member(member: Member) -> Option<Entry> {
+        let item = match member {
+            Member::Item(item) => item,
+            Member::InnerList(..) => return None,
+        };
+
+        match item.bare {
+            BareItem::Token(_) | BareItem::String(_) => (),
+            _ => return None,
+        }
+
+        let mut entry = Entry {
+            cache: item.bare,
+            hit: false,
+            fwd: None,
+            fwd_status: None,
+            ttl: None,
+            stored: false,
+            collapsed: false,
+            key: None,
+            detail: None,
+            extensions: Parameters::default(),
+        };
+
+        // Parameters with an unexpected type are ignored, as the spec asks.
+        for (name, value) in item.params.iter() {
+            match name {
+                "hit" => entry.hit = value.as_bool().unwrap_or(false),
+                "fwd" => entry.fwd = value.as_token().map(str::to_owned),
+                "fwd-status" => {
+                    entry.fwd_status = value.as_integer().and_then(|n| {
+                        if (100..=999).contains(&n) {
+                            Some(n as u16)
+                        } else {
+                            None
+                        }
+                    })
+                }
+                "ttl" => entry.ttl = value.as_integer(),
+                "stored" => entry.stored = value.as_bool().unwrap_or(false),
+                "collapsed" => entry.collapsed = value.as_bool().unwrap_or(false),
+                "key" => entry.key = value.as_str().map(str::to_owned),
+                "detail" => match *value {
+                    BareItem::Token(_) | BareItem::String(_) => entry.detail = Some(value.clone()),
+                    _ => (),
+                },
+                _ => entry.extensions.insert(name.to_owned(), value.clone()),
+            }
+        }
+
+        Some(entry)
+    }
+
+    fn to_member(&self) -> Member {
+        // Known parameters are always emitted in the order the spec lists
+        // them, followed by any extensions in the order they were received.
+        let mut params = Parameters::default();
+        if self.hit {
+            params.insert("hit".to_owned(), BareItem::Boolean(true));
+        }
+        if let Some(ref fwd) = self.fwd {
+            params.insert("fwd".to_owned(), BareItem::Token(fwd.clone()));
+        }
+        if let Some(status) = self.fwd_status {
+            params.insert("fwd-status".to_owned(), BareItem::Integer(status.into()));
+        }
+        if let Some(ttl) = self.ttl {
+            params.insert("ttl".to_owned(), BareItem::Integer(ttl));
+        }
+        if self.stored {
+            params.insert("stored".to_owned(), BareItem::Boolean(true));
+        }
+        if self.collapsed {
+            params.insert("collapsed".to_owned(), BareItem::Boolean(true));
+        }
+        if let Some(ref key) = self.key {
+            params.insert("key".to_owned(), BareItem::String(key.clone()));
+        }
+        if let Some(ref detail) = self.detail {
+            params.insert("detail".to_owned(), detail.clone());
+        }
+        for (name, value) in self.extensions.iter() {
+            params.insert(name.to_owned(), value.clone());
+        }
+
+        Member::Item(Item {
+            bare: self.cache.clone(),
+            params,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn hit() {
+        let status = test_decode::<CacheStatus>(&["ExampleCache; hit"]).unwrap();
+        let entry = status.iter().next().unwrap();
+        assert_eq!(entry.cache(), "ExampleCache");
+        assert!(entry.is_hit());
+        assert_eq!(entry.forwarded_reason(), None);
+        assert_eq!(entry.ttl(), None);
+    }
+
+    #[test]
+    fn multiple_caches() {
+        let status = test_decode::<CacheStatus>(&[
+            "OriginCache; hit; ttl=1100",
+            "\"CDN Company Here\"; fwd=stale; fwd-status=304; ttl=-5; collapsed",
+        ])
+        .unwrap();
+
+        let entries = status.iter().collect::<Vec<_>>();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].cache(), "OriginCache");
+        assert_eq!(entries[0].ttl(), Some(1100));
+        assert_eq!(entries[1].cache(), "CDN Company Here");
+        assert!(!entries[1].is_hit());
+        assert_eq!(entries[1].forwarded_reason(), Some("stale"));
+        assert_eq!(entries[1].forwarded_status(), Some(304));
+        assert_eq!(entries[1].ttl(), Some(-5));
+        assert!(entries[1].is_collapsed());
+    }
+
+    #[test]
+    fn key_and_detail() {
+        let status =
+            test_decode::<CacheStatus>(&["ExampleCache; stored; key=\"/a?b\"; detail=\"x y\""])
+                .unwrap();
+        let entry = status.iter().next().unwrap();
+        assert!(entry.is_stored());
+        assert_eq!(entry.key(), Some("/a?b"));
+        assert_eq!(entry.detail(), Some("x y"));
+    }
+
+    #[test]
+    fn encode_deterministic_order() {
+        let status = test_decode::<CacheStatus>(&[
+            "ExampleCache; ext=1; ttl=30; key=\"k\"; hit; fwd=miss; stored",
+        ])
+        .unwrap();
+
+        let headers = test_encode(status);
+        assert_eq!(
+            headers["cache-status"],
+            "ExampleCache;hit;fwd=miss;ttl=30;stored;key=\"k\";ext=1"
+        );
+    }
+
+    #[test]
+    fn ignores_wrongly_typed_params() {
+        let status = test_decode::<CacheStatus>(&["ExampleCache; hit=1; ttl=\"30\""]).unwrap();
+        let entry = status.iter().next().unwrap();
+        assert!(!entry.is_hit());
+        assert_eq!(entry.ttl(), None);
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(test_decode::<CacheStatus>(&[""]), None);
+        assert_eq!(test_decode::<CacheStatus>(&["1; hit"]), None);
+        assert_eq!(test_decode::<CacheStatus>(&["(a b)"]), None);
+        assert_eq!(test_decode::<CacheStatus>(&["ExampleCache; hit,"]), None);
+    }
+}
@@ -1,7 +1,7 @@
 use std::iter::FromIterator;
 
 use self::sealed::AsConnectionOption;
-use util::FlatCsv;
+use util::{self, FlatCsv};
 use {HeaderName, HeaderValue};
 
 /// `Connection` header, defined in
@@ -61,6 +61,43 @@ impl Connection {
         Connection(HeaderValue::from_static("upgrade").into())
     }
 
+    /// Add the `Upgrade` option, as a response switching protocols needs.
+    ///
+    /// This is written as `Upgrade`, unless the header already has the
+    /// option in some casing, which is then kept as is.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate headers;
+    /// use headers::Connection;
+    ///
+    /// let conn = Connection::keep_alive().with_upgrade();
+    /// assert!(conn.contains("upgrade"));
+    /// assert!(conn.is_keep_alive());
+    /// ```
+    pub fn with_upgrade(self) -> Connection {
+        self.with_option("Upgrade")
+    }
+
+    /// Add the `close` option, unless the header already has it.
+    pub fn with_close(self) -> Connection {
+        self.with_option("close")
+    }
+
+    /// Add the `keep-alive` option, unless the header already has it.
+    pub fn with_keep_alive(self) -> Connection {
+        self.with_option("keep-alive")
+    }
+
+    fn with_option(self, option: &'static str) -> Connection {
+        if self.contains(option) {
+            return self;
+        }
+        let values = vec![self.0.value, HeaderValue::from_static(option)];
+        Connection(values.into_iter().collect())
+    }
+
     /// Check if this header contains a given "connection option".
     ///
     /// This can be used with various argument types:
@@ -86,45 +123,60 @@ impl Connection {
     /// assert!(conn.contains("Keep-Alive"));
     /// ```
     pub fn contains(&self, name: impl AsConnectionOption) -> bool {
-        let s = name.as_connection_option();
-        self.0
-            .iter()
-            .find(|&opt| opt.eq_ignore_ascii_case(s))
-            .is_some()
+        contains_option(&self.0, name.as_connection_option())
     }
+
+    /// Check if this header contains the `close` option.
+    pub fn is_close(&self) -> bool {
+        self.contains("close")
+    }
+
+    /// Check if this header contains the `keep-alive` option.
+    pub fn is_keep_alive(&self) -> bool {
+        self.contains("keep-alive")
+    }
+}
+
+/// Check if a list of connection options contains `name`, ignoring case.
+pub(super) fn contains_option(options: &FlatCsv, name: &str) -> bool {
+    options.iter().any(|opt| opt.eq_ignore_ascii_case(name))
 }
 
 impl FromIterator<HeaderName> for Connection {
+    /// Collect options into a `Connection`, skipping any repeated option.
     fn from_iter<I>(iter: I) -> Self
     where
         I: IntoIterator<Item = HeaderName>,
     {
-        let flat = iter.into_iter().map(HeaderValue::from).collect();
+        let flat = util::dedup(iter)
+            .into_iter()
+            .map(HeaderValue::from)
+            .collect();
         Connection(flat)
     }
 }
 
-mod sealed {
+pub(super) mod sealed {
     pub trait AsConnectionOption: Sealed {
         fn as_connection_option(&self) -> &str;
     }
     pub trait Sealed {}
 
-    impl<'a> AsConnectionOption for &'a str {
+    impl AsConnectionOption for &str {
         fn as_connection_option(&self) -> &str {
-            *self
+            self
         }
     }
 
-    impl<'a> Sealed for &'a str {}
+    impl Sealed for &str {}
 
-    impl<'a> AsConnectionOption for &'a ::HeaderName {
+    impl AsConnectionOption for &::HeaderName {
         fn as_connection_option(&self) -> &str {
             self.as_ref()
         }
     }
 
-    impl<'a> Sealed for &'a ::HeaderName {}
+    impl Sealed for &::HeaderName {}
 
     impl AsConnectionOption for ::HeaderName {
         fn as_connection_option(&self) -> &str {
@@ -134,3 +186,43 @@ mod sealed {
 
     impl Sealed for ::HeaderName {}
 }
+
+#[cfg(test)]
+mod tests {
+    use std::iter::FromIterator;
+
+    use http::header::{TE, UPGRADE};
+
+    use super::super::{test_decode, test_encode};
+    use super::Connection;
+
+    #[test]
+    fn keep_alive_and_upgrade() {
+        let conn = Connection::keep_alive().with_upgrade();
+        let headers = test_encode(conn.clone());
+        assert_eq!(headers["connection"], "keep-alive, Upgrade");
+
+        let headers = test_encode(conn.with_upgrade().with_keep_alive());
+        assert_eq!(headers["connection"], "keep-alive, Upgrade");
+
+        let headers = test_encode(Connection::close().with_keep_alive().with_close());
+        assert_eq!(headers["connection"], "close, keep-alive");
+    }
+
+    #[test]
+    fn preserves_case() {
+        let conn = test_decode::<Connection>(&["KEEP-ALIVE, upgrade"]).unwrap();
+        let headers = test_encode(conn.with_keep_alive().with_upgrade());
+        assert_eq!(headers["connection"], "KEEP-ALIVE, upgrade");
+
+        let headers = test_encode(Connection::upgrade().with_upgrade());
+        assert_eq!(headers["connection"], "upgrade");
+    }
+
+    #[test]
+    fn from_iter() {
+        let conn = Connection::from_iter(vec![UPGRADE, TE, UPGRADE]);
+        let headers = test_encode(conn);
+        assert_eq!(headers["connection"], "upgrade, te");
+    }
+}
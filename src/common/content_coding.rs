@@ -51,6 +51,7 @@ macro_rules! define_content_coding {
             /// assert_eq!(valid, ContentCoding::GZIP);
             /// ```
             #[inline]
+            #[allow(clippy::should_implement_trait)]
             pub fn from_str(s: &str) -> Self {
                 ContentCoding::try_from_str(s).unwrap_or_else(|_| ContentCoding::IDENTITY)
             }
@@ -72,6 +73,7 @@ macro_rules! define_content_coding {
             /// assert_eq!(valid.unwrap(), ContentCoding::GZIP);
             /// ```
             #[inline]
+            #[allow(clippy::result_unit_err)]
             pub fn try_from_str(s: &str) -> Result<Self, ()> {
                 match s {
                     $(
@@ -83,6 +85,7 @@ macro_rules! define_content_coding {
             }
         }
 
+        #[allow(clippy::to_string_trait_impl)]
         impl std::string::ToString for ContentCoding {
             #[inline]
             fn to_string(&self) -> String {
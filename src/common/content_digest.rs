@@ -0,0 +1,222 @@
+use util::sf::{self, BareItem, Item, Member};
+use util::{self, TryFromValues};
+use {HeaderName, HeaderValue};
+
+use super::DigestAlgorithm;
+
+const CONTENT_DIGEST: HeaderName = HeaderName::from_static("content-digest");
+
+/// `Content-Digest` header, defined in
+/// [RFC9530](https://www.rfc-editor.org/rfc/rfc9530.html#section-2)
+///
+/// The `Content-Digest` header field carries hashes of the content of a
+/// message, after any `Content-Encoding` has been applied, so that the
+/// recipient can check it arrived intact.
+///
+/// This replaces the `Digest` header of RFC 3230, which used a different
+/// grammar and hashed the selected representation instead. Hashes made by
+/// an algorithm this crate doesn't know, or that RFC 9530 deprecates, are
+/// ignored.
+///
+/// # ABNF
+///
+/// ```text
+/// Content-Digest   = sf-dictionary
+/// ```
+///
+/// # Example values
+///
+/// * `sha-256=:X48E9qOokqqrvdts8nOJRJN3OWDUoyWxBf7kbu9DBPE=:`
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// use headers::{ContentDigest, DigestAlgorithm};
+///
+/// let digest = ContentDigest::new(DigestAlgorithm::Sha256, &[0; 32]).unwrap();
+/// assert_eq!(digest.get(DigestAlgorithm::Sha256), Some(&[0; 32][..]));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ContentDigest(Vec<(DigestAlgorithm, Vec<u8>)>);
+
+error_type!(InvalidContentDigest);
+
+impl ContentDigest {
+    /// Create a `Content-Digest` with a single hash.
+    ///
+    /// Fails if the algorithm has no registered name, or the hash has the
+    /// wrong length for the algorithm.
+    pub fn new(alg: DigestAlgorithm, hash: &[u8]) -> Result<ContentDigest, InvalidContentDigest> {
+        ContentDigest(Vec::new()).with_hash(alg, hash)
+    }
+
+    /// Create a `Content-Digest` with the hash of `body`.
+    ///
+    /// Fails if the algorithm has no registered name.
+    #[cfg(feature = "digest")]
+    pub fn from_body(
+        alg: DigestAlgorithm,
+        body: &[u8],
+    ) -> Result<ContentDigest, InvalidContentDigest> {
+        ContentDigest::new(alg, &alg.hash(body))
+    }
+
+    /// Add another hash, replacing any existing hash of the same algorithm.
+    ///
+    /// Fails if the algorithm has no registered name, or the hash has the
+    /// wrong length for the algorithm.
+    pub fn with_hash(
+        mut self,
+        alg: DigestAlgorithm,
+        hash: &[u8],
+    ) -> Result<Self, InvalidContentDigest> {
+        if alg.http_name().is_none() || hash.len() != alg.output_len() {
+            return Err(InvalidContentDigest { _inner: () });
+        }
+        match self.0.iter_mut().find(|entry| entry.0 == alg) {
+            Some(entry) => entry.1 = hash.to_vec(),
+            None => self.0.push((alg, hash.to_vec())),
+        }
+        Ok(self)
+    }
+
+    /// Iterate the hashes, with the algorithm that made each.
+    pub fn iter(&self) -> impl Iterator<Item = (DigestAlgorithm, &[u8])> {
+        self.0.iter().map(|&(alg, ref hash)| (alg, &hash[..]))
+    }
+
+    /// Get the hash made by `alg`, if there is one.
+    pub fn get(&self, alg: DigestAlgorithm) -> Option<&[u8]> {
+        self.iter().find(|&(a, _)| a == alg).map(|(_, hash)| hash)
+    }
+
+    /// The strongest algorithm in the header.
+    pub fn strongest(&self) -> DigestAlgorithm {
+        self.0
+            .iter()
+            .map(|&(alg, _)| alg)
+            .max()
+            .expect("ContentDigest is never empty")
+    }
+
+    /// Check if `body` matches the hash made by the strongest algorithm.
+    ///
+    /// `body` is the content as sent, after any `Content-Encoding`. Hashes
+    /// made by weaker algorithms are not checked.
+    #[cfg(feature = "digest")]
+    pub fn verify(&self, body: &[u8]) -> bool {
+        let strongest = self.strongest();
+        self.get(strongest) == Some(&strongest.hash(body)[..])
+    }
+}
+
+impl ::Header for ContentDigest {
+    fn name() -> &'static HeaderName {
+        static NAME: HeaderName = CONTENT_DIGEST;
+        &NAME
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        ContentDigest::try_from_values(values)
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        let mut dict = sf::Dictionary::default();
+        for &(alg, ref hash) in &self.0 {
+            let name = alg.http_name().expect("checked in with_hash");
+            let item = Item::new(BareItem::ByteSeq(hash.clone()));
+            dict.insert(name.to_owned(), Member::Item(item));
+        }
+        values.extend(::std::iter::once(util::fmt(dict)));
+    }
+}
+
+impl ::NamedHeader for ContentDigest {
+    const NAME: ::HeaderName = CONTENT_DIGEST;
+}
+
+impl TryFromValues for ContentDigest {
+    fn try_from_values<'i, I>(values: &mut I) -> Result<Self, ::Error>
+    where
+        I: Iterator<Item = &'i HeaderValue>,
+    {
+        let dict = sf::parse_dictionary(values)?;
+        let mut hashes = Vec::new();
+        for (key, member) in dict.iter() {
+            let alg = match DigestAlgorithm::from_http_name(key) {
+                Some(alg) => alg,
+                None => continue,
+            };
+            match member.as_item().map(|item| &item.bare) {
+                Some(BareItem::ByteSeq(hash)) if hash.len() == alg.output_len() => {
+                    hashes.push((alg, hash.clone()));
+                }
+                _ => return Err(::Error::invalid()),
+            }
+        }
+
+        if hashes.is_empty() {
+            Err(::Error::invalid())
+        } else {
+            Ok(ContentDigest(hashes))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    // The examples from RFC 9530, hashes of `{"hello": "world"}`.
+    const SHA256: &str = "sha-256=:X48E9qOokqqrvdts8nOJRJN3OWDUoyWxBf7kbu9DBPE=:";
+    const SHA512: &str = "sha-512=:WZDPaVn/7XgHaAy8pmojAkGWoRx2UFChF41A2svX+TaPm+AbwAgBWnrIiYllu7BNNyealdVLvRwEmTHWXvJwew==:";
+
+    #[test]
+    fn round_trip() {
+        let s = format!("{}, md5=:AAAA:, {}", SHA256, SHA512);
+        let digest = test_decode::<ContentDigest>(&[&s]).unwrap();
+        let algs = digest.iter().map(|(alg, _)| alg).collect::<Vec<_>>();
+        assert_eq!(algs, [DigestAlgorithm::Sha256, DigestAlgorithm::Sha512]);
+        assert_eq!(digest.strongest(), DigestAlgorithm::Sha512);
+
+        let headers = test_encode(digest);
+        assert_eq!(
+            headers["content-digest"],
+            format!("{}, {}", SHA256, SHA512).as_str()
+        );
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<ContentDigest>(&[""]), None);
+        assert_eq!(test_decode::<ContentDigest>(&["md5=:AAAA:"]), None);
+        assert_eq!(test_decode::<ContentDigest>(&["sha-256=:AAAA:"]), None);
+        assert_eq!(test_decode::<ContentDigest>(&["sha-256=abc"]), None);
+        // The legacy `Digest` grammar.
+        assert_eq!(
+            test_decode::<ContentDigest>(&["SHA-256=X48E9qOokqqrvdts8nOJRJN3OWDUoyWxBf7kbu9DBPE="]),
+            None
+        );
+    }
+
+    #[test]
+    fn new_checks_algorithm() {
+        assert!(ContentDigest::new(DigestAlgorithm::Sha512, &[0; 64]).is_ok());
+        assert!(ContentDigest::new(DigestAlgorithm::Sha512, &[0; 32]).is_err());
+        assert!(ContentDigest::new(DigestAlgorithm::Sha384, &[0; 48]).is_err());
+    }
+
+    #[cfg(feature = "digest")]
+    #[test]
+    fn verify() {
+        let body = b"{\"hello\": \"world\"}";
+        let digest = test_decode::<ContentDigest>(&[SHA256, SHA512]).unwrap();
+        assert!(digest.verify(body));
+        assert!(!digest.verify(b"{}"));
+
+        let digest = ContentDigest::from_body(DigestAlgorithm::Sha256, body).unwrap();
+        assert_eq!(digest, test_decode::<ContentDigest>(&[SHA256]).unwrap());
+    }
+}
@@ -1,3 +1,8 @@
+use std::borrow::Cow;
+use std::{fmt, str};
+
+use util::{FlatCsv, SemiColon};
+
 // # References
 //
 // "The Content-Disposition Header Field" https://www.ietf.org/rfc/rfc2183.txt
@@ -14,7 +19,7 @@
 /// to use when saving the response payload locally.
 ///
 /// # ABNF
-
+///
 /// ```text
 /// content-disposition = "Content-Disposition" ":"
 ///                       disposition-type *( ";" disposition-parm )
@@ -43,26 +48,52 @@
 ///
 /// let cd = ContentDisposition::inline();
 /// ```
-#[derive(Clone, Debug)]
+#[derive(Clone)]
 pub struct ContentDisposition(::HeaderValue);
 
+error_type!(InvalidContentDisposition);
+
 impl ContentDisposition {
     /// Construct a `Content-Disposition: inline` header.
     pub fn inline() -> ContentDisposition {
         ContentDisposition(::HeaderValue::from_static("inline"))
     }
 
-    /*
-    pub fn attachment(filename: &str) -> ContentDisposition {
-        let full = Bytes::from(format!("attachment; filename={}", filename));
-        match ::HeaderValue::from_maybe_shared(full) {
-            Ok(val) => ContentDisposition(val),
-            Err(_) => {
-                unimplemented!("filename that isn't ASCII");
+    /// Construct a `Content-Disposition: attachment` header, suggesting
+    /// `filename` to save the content as.
+    ///
+    /// The filename is always quoted. Fails if it is empty, or has anything
+    /// but printable ASCII, so a filename from user input can't end the
+    /// header early with a CR or LF.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate headers;
+    /// use headers::ContentDisposition;
+    ///
+    /// let cd = ContentDisposition::attachment("report \"final\".pdf").unwrap();
+    /// assert_eq!(cd.filename_lossy().as_deref(), Some("report \"final\".pdf"));
+    ///
+    /// assert!(ContentDisposition::attachment("a.txt\r\nSet-Cookie: evil").is_err());
+    /// ```
+    pub fn attachment(filename: &str) -> Result<ContentDisposition, InvalidContentDisposition> {
+        if filename.is_empty() || !filename.bytes().all(|b| (b' '..=b'~').contains(&b)) {
+            return Err(InvalidContentDisposition { _inner: () });
+        }
+
+        let mut value = String::from("attachment; filename=\"");
+        for c in filename.chars() {
+            if c == '"' || c == '\\' {
+                value.push('\\');
             }
+            value.push(c);
         }
+        value.push('"');
+        ::HeaderValue::from_str(&value)
+            .map(ContentDisposition)
+            .map_err(|_| InvalidContentDisposition { _inner: () })
     }
-    */
 
     /// Check if the disposition-type is `inline`.
     pub fn is_inline(&self) -> bool {
@@ -79,6 +110,94 @@ impl ContentDisposition {
         self.get_type() == "form-data"
     }
 
+    /// Get the `filename` parameter, reading any obs-text as ISO-8859-1.
+    ///
+    /// Browsers accept any bytes in the filename, rather than only visible
+    /// ASCII, so this does the same. A quoted filename is unescaped. The
+    /// `filename*` parameter is not read.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate headers;
+    /// # extern crate http;
+    /// use headers::{ContentDisposition, HeaderMapExt};
+    ///
+    /// let mut map = http::HeaderMap::new();
+    /// map.insert("content-disposition", "attachment; filename=\"a.txt\"".parse().unwrap());
+    ///
+    /// let cd = map.typed_get::<ContentDisposition>().unwrap();
+    /// assert_eq!(cd.filename_lossy().as_deref(), Some("a.txt"));
+    /// ```
+    pub fn filename_lossy(&self) -> Option<String> {
+        self.param(b"filename").map(|value| ::util::latin1(&value))
+    }
+
+    /// Get the `name` parameter, which a `form-data` part has to say which
+    /// form field it is.
+    ///
+    /// A quoted name is unescaped, which is the only case that allocates.
+    /// Returns `None` if there is no `name`, or it isn't UTF-8.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate headers;
+    /// # extern crate http;
+    /// use headers::{ContentDisposition, HeaderMapExt};
+    ///
+    /// let mut map = http::HeaderMap::new();
+    /// map.insert(
+    ///     "content-disposition",
+    ///     "form-data; name=\"avatar\"; filename=\"pic.png\"".parse().unwrap(),
+    /// );
+    ///
+    /// let cd = map.typed_get::<ContentDisposition>().unwrap();
+    /// assert_eq!(cd.field_name().as_deref(), Some("avatar"));
+    /// assert_eq!(cd.filename_lossy().as_deref(), Some("pic.png"));
+    /// ```
+    pub fn field_name(&self) -> Option<Cow<'_, str>> {
+        match self.param(b"name")? {
+            Cow::Borrowed(bytes) => str::from_utf8(bytes).ok().map(Cow::Borrowed),
+            Cow::Owned(bytes) => String::from_utf8(bytes).ok().map(Cow::Owned),
+        }
+    }
+
+    /// Find the value of the parameter `name`, unescaped if it was quoted.
+    fn param(&self, name: &[u8]) -> Option<Cow<'_, [u8]>> {
+        let mut rest = self.0.as_bytes();
+        loop {
+            let semi = rest.iter().position(|&b| b == b';')?;
+            rest = &rest[semi + 1..];
+
+            let eq = rest.iter().position(|&b| b == b'=' || b == b';')?;
+            let param = ::util::trim_ows(&rest[..eq]);
+            if rest[eq] == b';' {
+                rest = &rest[eq..];
+                continue;
+            }
+            rest = &rest[eq + 1..];
+            while rest.first() == Some(&b' ') || rest.first() == Some(&b'\t') {
+                rest = &rest[1..];
+            }
+
+            let value = if rest.first() == Some(&b'"') {
+                let (value, len) = unquote(&rest[1..])?;
+                rest = &rest[len + 1..];
+                value
+            } else {
+                let end = rest.iter().position(|&b| b == b';').unwrap_or(rest.len());
+                let value = Cow::Borrowed(::util::trim_ows(&rest[..end]));
+                rest = &rest[end..];
+                value
+            };
+
+            if param.eq_ignore_ascii_case(name) {
+                return Some(value);
+            }
+        }
+    }
+
     fn get_type(&self) -> &str {
         self.0
             .to_str()
@@ -89,6 +208,49 @@ impl ContentDisposition {
     }
 }
 
+/// Parse a quoted-string after its opening quote, returning the unescaped
+/// bytes and how many bytes were read, including the closing quote.
+///
+/// The bytes are only copied if there is an escape to remove.
+fn unquote(bytes: &[u8]) -> Option<(Cow<'_, [u8]>, usize)> {
+    let end = bytes.iter().position(|&b| b == b'"' || b == b'\\')?;
+    if bytes[end] == b'"' {
+        return Some((Cow::Borrowed(&bytes[..end]), end + 1));
+    }
+
+    let mut out = bytes[..end].to_vec();
+    let mut iter = bytes.iter().enumerate().skip(end);
+    while let Some((i, &b)) = iter.next() {
+        match b {
+            b'"' => return Some((Cow::Owned(out), i + 1)),
+            b'\\' => out.push(*iter.next()?.1),
+            _ => out.push(b),
+        }
+    }
+    None
+}
+
+impl fmt::Debug for ContentDisposition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let csv = FlatCsv::<SemiColon>::from(self.0.clone());
+        let mut parts = csv.iter();
+        let disposition = parts.next().unwrap_or("");
+        let params = parts
+            .filter_map(|param| {
+                let mut kv = param.splitn(2, '=');
+                let name = kv.next()?.trim();
+                let value = kv.next()?.trim().trim_matches('"');
+                Some((name, value))
+            })
+            .collect::<Vec<_>>();
+
+        f.debug_struct("ContentDisposition")
+            .field("disposition", &disposition)
+            .field("params", &params)
+            .finish()
+    }
+}
+
 impl ::Header for ContentDisposition {
     fn name() -> &'static ::HeaderName {
         &::http::header::CONTENT_DISPOSITION
@@ -107,6 +269,97 @@ impl ::Header for ContentDisposition {
         values.extend(::std::iter::once(self.0.clone()));
     }
 }
+
+impl ::NamedHeader for ContentDisposition {
+    const NAME: ::HeaderName = ::http::header::CONTENT_DISPOSITION;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::ContentDisposition;
+
+    #[test]
+    fn attachment() {
+        let cd = ContentDisposition::attachment("a \\ \"b\".txt").unwrap();
+        assert!(cd.is_attachment());
+        assert_eq!(cd.filename_lossy().unwrap(), "a \\ \"b\".txt");
+
+        let headers = test_encode(cd);
+        assert_eq!(
+            headers["content-disposition"],
+            "attachment; filename=\"a \\\\ \\\"b\\\".txt\""
+        );
+    }
+
+    #[test]
+    fn attachment_rejects_injection() {
+        assert!(ContentDisposition::attachment("a.txt\r\nSet-Cookie: evil").is_err());
+        assert!(ContentDisposition::attachment("a.txt\nSet-Cookie: evil").is_err());
+        assert!(ContentDisposition::attachment("a.txt\rSet-Cookie: evil").is_err());
+        assert!(ContentDisposition::attachment("a\tb.txt").is_err());
+        assert!(ContentDisposition::attachment("caf\u{e9}.txt").is_err());
+        assert!(ContentDisposition::attachment("").is_err());
+    }
+
+    #[test]
+    fn debug() {
+        let cd = test_decode::<ContentDisposition>(&["attachment; filename=\"a; b.txt\""]).unwrap();
+        assert_eq!(
+            format!("{:?}", cd),
+            r#"ContentDisposition { disposition: "attachment", params: [("filename", "a; b.txt")] }"#
+        );
+    }
+
+    #[test]
+    fn field_name() {
+        let cd = test_decode::<ContentDisposition>(&[
+            "form-data; name=\"avatar\"; filename=\"pic.png\"",
+        ])
+        .unwrap();
+        assert!(cd.is_form_data());
+        assert_eq!(cd.field_name().as_deref(), Some("avatar"));
+        assert_eq!(cd.filename_lossy().as_deref(), Some("pic.png"));
+
+        let cd = test_decode::<ContentDisposition>(&[
+            "form-data; filename=\"a\"; name=\"say \\\"hi\\\"\"",
+        ])
+        .unwrap();
+        assert_eq!(cd.field_name().as_deref(), Some("say \"hi\""));
+
+        let cd = test_decode::<ContentDisposition>(&["form-data; name=field"]).unwrap();
+        assert_eq!(cd.field_name().as_deref(), Some("field"));
+
+        let cd = test_decode::<ContentDisposition>(&["form-data; filename=\"name=x\""]).unwrap();
+        assert_eq!(cd.field_name(), None);
+    }
+
+    #[test]
+    fn filename_lossy() {
+        let decode = |bytes: &[u8]| {
+            let value = ::HeaderValue::from_bytes(bytes).unwrap();
+            <ContentDisposition as ::Header>::decode(&mut ::std::iter::once(&value)).unwrap()
+        };
+
+        let cd = decode(b"attachment; filename=\"caf\xe9.txt\"");
+        assert_eq!(cd.filename_lossy().unwrap(), "caf\u{e9}.txt");
+
+        let cd = decode(b"attachment; size=1; filename=caf\xe9.txt");
+        assert_eq!(cd.filename_lossy().unwrap(), "caf\u{e9}.txt");
+
+        let cd = decode(b"attachment; filename=\"a \\\"b\\\"; c.txt\"");
+        assert_eq!(cd.filename_lossy().unwrap(), "a \"b\"; c.txt");
+
+        let cd = decode(b"attachment; name=\"filename=x\"; filename*=UTF-8''a.txt");
+        assert_eq!(cd.filename_lossy(), None);
+        assert_eq!(ContentDisposition::inline().filename_lossy(), None);
+        assert_eq!(
+            decode(b"attachment; filename=\"open").filename_lossy(),
+            None
+        );
+    }
+}
+
 /*
 use language_tags::LanguageTag;
 use std::fmt;
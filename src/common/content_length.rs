@@ -1,4 +1,6 @@
-use {Header, HeaderValue};
+use headers_core::parse;
+use util::{IterExt, TryFromValues};
+use {DecodeOptions, Header, HeaderValue};
 
 /// `Content-Length` header, defined in
 /// [RFC7230](http://tools.ietf.org/html/rfc7230#section-3.3.2)
@@ -40,33 +42,62 @@ use {Header, HeaderValue};
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct ContentLength(pub u64);
 
+impl ContentLength {
+    /// Create a `Content-Length` of the given number of octets.
+    pub fn new(len: u64) -> ContentLength {
+        ContentLength(len)
+    }
+
+    /// Get the length as a `u64`.
+    pub fn as_u64(&self) -> u64 {
+        self.0
+    }
+
+    /// Check if the length is zero, so there is no body.
+    pub fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Add `other` octets to this length, returning `None` on overflow.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate headers;
+    /// use headers::ContentLength;
+    ///
+    /// let len = ContentLength::new(1_000);
+    /// assert_eq!(len.checked_add(24), Some(ContentLength::new(1_024)));
+    /// assert_eq!(len.checked_add(u64::MAX), None);
+    /// ```
+    pub fn checked_add(&self, other: u64) -> Option<ContentLength> {
+        self.0.checked_add(other).map(ContentLength)
+    }
+}
+
 impl Header for ContentLength {
     fn name() -> &'static ::http::header::HeaderName {
         &::http::header::CONTENT_LENGTH
     }
 
     fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
-        // If multiple Content-Length headers were sent, everything can still
-        // be alright if they all contain the same value, and all parse
-        // correctly. If not, then it's an error.
-        let mut len = None;
-        for value in values {
-            let parsed = value
-                .to_str()
-                .map_err(|_| ::Error::invalid())?
-                .parse::<u64>()
-                .map_err(|_| ::Error::invalid())?;
-
-            if let Some(prev) = len {
-                if prev != parsed {
-                    return Err(::Error::invalid());
-                }
-            } else {
-                len = Some(parsed);
-            }
-        }
+        ContentLength::try_from_values(values)
+    }
 
-        len.map(ContentLength).ok_or_else(::Error::invalid)
+    /// A strict `Content-Length` must be a single value, rather than a list
+    /// of the same length repeated.
+    fn decode_with<'i, I>(values: &mut I, options: &DecodeOptions) -> Result<Self, ::Error>
+    where
+        I: Iterator<Item = &'i HeaderValue>,
+    {
+        if !options.strict {
+            return ContentLength::try_from_values(values);
+        }
+        values
+            .just_one()
+            .and_then(|value| parse::content_length(value.as_bytes()))
+            .map(ContentLength)
+            .ok_or_else(::Error::invalid)
     }
 
     fn encode<E: Extend<::HeaderValue>>(&self, values: &mut E) {
@@ -74,6 +105,33 @@ impl Header for ContentLength {
     }
 }
 
+impl ::NamedHeader for ContentLength {
+    const NAME: ::HeaderName = ::http::header::CONTENT_LENGTH;
+}
+
+impl TryFromValues for ContentLength {
+    fn try_from_values<'i, I>(values: &mut I) -> Result<Self, ::Error>
+    where
+        I: Iterator<Item = &'i HeaderValue>,
+    {
+        // If multiple Content-Length headers were sent, everything can still
+        // be alright if they all contain the same value, and all parse
+        // correctly. If not, then it's an error.
+        //
+        // The same goes for a single header that was folded into a list,
+        // such as `Content-Length: 42, 42`.
+        parse::content_length_list(values.map(HeaderValue::as_bytes))
+            .map(ContentLength)
+            .ok_or_else(::Error::invalid)
+    }
+}
+
+impl PartialEq<u64> for ContentLength {
+    fn eq(&self, other: &u64) -> bool {
+        self.0 == *other
+    }
+}
+
 /*
 __hyper__tm!(ContentLength, tests {
     // Testcase from RFC
@@ -93,3 +151,88 @@ __hyper__tm!(ContentLength, tests {
     test_header!(test_duplicates_vary, vec![b"5", b"6", b"5"], None);
 });
 */
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::ContentLength;
+    use {DecodeOptions, Header, HeaderValue};
+
+    #[test]
+    fn decode() {
+        assert_eq!(
+            test_decode::<ContentLength>(&["3495"]),
+            Some(ContentLength(3495))
+        );
+        assert_eq!(test_decode::<ContentLength>(&["0"]), Some(ContentLength(0)));
+    }
+
+    #[test]
+    fn encode() {
+        let headers = test_encode(ContentLength::new(3495));
+        assert_eq!(headers["content-length"], "3495");
+    }
+
+    #[test]
+    fn compare_u64() {
+        let len = ContentLength::new(5);
+        assert_eq!(len, 5);
+        assert_ne!(len, 6);
+        assert_eq!(len.as_u64(), 5);
+    }
+
+    #[test]
+    fn decode_strict() {
+        let decode = |values: &[&'static str], strict| {
+            let values = values
+                .iter()
+                .map(|&s| HeaderValue::from_static(s))
+                .collect::<Vec<_>>();
+            let options = if strict {
+                DecodeOptions::strict()
+            } else {
+                DecodeOptions::default()
+            };
+            ContentLength::decode_with(&mut values.iter(), &options).ok()
+        };
+
+        assert_eq!(decode(&["5"], true), Some(ContentLength(5)));
+        assert_eq!(decode(&["5", "5"], false), Some(ContentLength(5)));
+        assert_eq!(decode(&["5, 5"], false), Some(ContentLength(5)));
+        assert_eq!(decode(&["5", "5"], true), None);
+        assert_eq!(decode(&["5, 5"], true), None);
+        assert_eq!(decode(&["05"], false), None);
+    }
+
+    #[test]
+    fn decode_duplicates() {
+        assert_eq!(
+            test_decode::<ContentLength>(&["5", "5"]),
+            Some(ContentLength(5))
+        );
+        assert_eq!(
+            test_decode::<ContentLength>(&["5, 5"]),
+            Some(ContentLength(5))
+        );
+    }
+
+    #[test]
+    fn decode_conflicting_duplicates() {
+        assert_eq!(test_decode::<ContentLength>(&["5", "6"]), None);
+        assert_eq!(test_decode::<ContentLength>(&["5", "6", "5"]), None);
+        assert_eq!(test_decode::<ContentLength>(&["5, 6"]), None);
+        assert_eq!(test_decode::<ContentLength>(&["5,"]), None);
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<ContentLength>(&["34v95"]), None);
+        assert_eq!(test_decode::<ContentLength>(&["007"]), None);
+        assert_eq!(test_decode::<ContentLength>(&["+5"]), None);
+        assert_eq!(test_decode::<ContentLength>(&["-5"]), None);
+        assert_eq!(
+            test_decode::<ContentLength>(&["18446744073709551616"]),
+            None
+        );
+    }
+}
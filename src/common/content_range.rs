@@ -1,7 +1,8 @@
 use std::fmt;
 use std::ops::{Bound, RangeBounds};
 
-use {util, HeaderValue};
+use util::{self, is_token};
+use HeaderValue;
 
 /// Content-Range, described in [RFC7233](https://tools.ietf.org/html/rfc7233#section-4.2)
 ///
@@ -24,6 +25,9 @@ use {util, HeaderValue};
 /// other-range-resp    = *CHAR
 /// ```
 ///
+/// Ranges in other units are only supported if they use the same
+/// `first-last/complete-length` form as bytes, such as `seconds 0-9/60`.
+///
 /// # Example
 ///
 /// ```
@@ -32,10 +36,13 @@ use {util, HeaderValue};
 ///
 /// // 100 bytes (included byte 199), with a full length of 3,400
 /// let cr = ContentRange::bytes(100..200, 3400).unwrap();
+/// assert_eq!(cr.unit(), "bytes");
 /// ```
-//NOTE: only supporting bytes-content-range, YAGNI the extension
 #[derive(Clone, Debug, PartialEq)]
 pub struct ContentRange {
+    /// The range unit, `None` for bytes
+    unit: Option<String>,
+
     /// First and last bytes of the range, omitted if request could not be
     /// satisfied
     range: Option<(u64, u64)>,
@@ -70,6 +77,7 @@ impl ContentRange {
         };
 
         Ok(ContentRange {
+            unit: None,
             range: Some((start, end)),
             complete_length,
         })
@@ -80,20 +88,42 @@ impl ContentRange {
     /// The passed argument is the complete length of the entity.
     pub fn unsatisfied_bytes(complete_length: u64) -> Self {
         ContentRange {
+            unit: None,
             range: None,
             complete_length: Some(complete_length),
         }
     }
 
+    /// Get the range unit, such as `bytes`.
+    pub fn unit(&self) -> &str {
+        self.unit.as_deref().unwrap_or("bytes")
+    }
+
     /// Get the byte range if satisified.
     ///
-    /// Note that these byte ranges are inclusive on both ends.
+    /// Note that these byte ranges are inclusive on both ends. This is
+    /// `None` if the unit isn't `bytes`; see `range` for other units.
     pub fn bytes_range(&self) -> Option<(u64, u64)> {
-        self.range
+        self.unit.as_ref().map_or(self.range, |_| None)
     }
 
     /// Get the bytes complete length if available.
+    ///
+    /// This is `None` if the unit isn't `bytes`; see `complete_length` for
+    /// other units.
     pub fn bytes_len(&self) -> Option<u64> {
+        self.unit.as_ref().map_or(self.complete_length, |_| None)
+    }
+
+    /// Get the range if satisfied, in whatever the `unit` is.
+    ///
+    /// Note that these ranges are inclusive on both ends.
+    pub fn range(&self) -> Option<(u64, u64)> {
+        self.range
+    }
+
+    /// Get the complete length if available, in whatever the `unit` is.
+    pub fn complete_length(&self) -> Option<u64> {
         self.complete_length
     }
 }
@@ -109,10 +139,13 @@ impl ::Header for ContentRange {
             .and_then(|v| v.to_str().ok())
             .and_then(|s| split_in_two(s, ' '))
             .and_then(|(unit, spec)| {
-                if unit != "bytes" {
-                    // For now, this only supports bytes-content-range. nani?
+                let unit = if unit.eq_ignore_ascii_case("bytes") {
+                    None
+                } else if is_token(unit) {
+                    Some(unit.to_owned())
+                } else {
                     return None;
-                }
+                };
 
                 let (range, complete_length) = split_in_two(spec, '/')?;
 
@@ -135,6 +168,7 @@ impl ::Header for ContentRange {
                 };
 
                 Some(ContentRange {
+                    unit,
                     range,
                     complete_length,
                 })
@@ -147,7 +181,7 @@ impl ::Header for ContentRange {
 
         impl<'a> fmt::Display for Adapter<'a> {
             fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-                f.write_str("bytes ")?;
+                write!(f, "{} ", self.0.unit())?;
 
                 if let Some((first_byte, last_byte)) = self.0.range {
                     write!(f, "{}-{}", first_byte, last_byte)?;
@@ -169,6 +203,10 @@ impl ::Header for ContentRange {
     }
 }
 
+impl ::NamedHeader for ContentRange {
+    const NAME: ::HeaderName = ::http::header::CONTENT_RANGE;
+}
+
 fn split_in_two(s: &str, separator: char) -> Option<(&str, &str)> {
     let mut iter = s.splitn(2, separator);
     match (iter.next(), iter.next()) {
@@ -177,6 +215,50 @@ fn split_in_two(s: &str, separator: char) -> Option<(&str, &str)> {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::ContentRange;
+
+    #[test]
+    fn bytes() {
+        let cr = test_decode::<ContentRange>(&["bytes 0-499/500"]).unwrap();
+        assert_eq!(cr.unit(), "bytes");
+        assert_eq!(cr.bytes_range(), Some((0, 499)));
+        assert_eq!(cr.bytes_len(), Some(500));
+        assert_eq!(cr, ContentRange::bytes(0..500, 500).unwrap());
+
+        let cr = test_decode::<ContentRange>(&["bytes */500"]).unwrap();
+        assert_eq!(cr, ContentRange::unsatisfied_bytes(500));
+
+        let cr = test_decode::<ContentRange>(&["Bytes 0-499/500"]).unwrap();
+        assert_eq!(cr, ContentRange::bytes(0..500, 500).unwrap());
+    }
+
+    #[test]
+    fn other_unit() {
+        let cr = test_decode::<ContentRange>(&["frames 10-19/*"]).unwrap();
+        assert_eq!(cr.unit(), "frames");
+        assert_eq!(cr.bytes_range(), None);
+        assert_eq!(cr.bytes_len(), None);
+        assert_eq!(cr.range(), Some((10, 19)));
+        assert_eq!(cr.complete_length(), None);
+
+        let headers = test_encode(cr);
+        assert_eq!(headers["content-range"], "frames 10-19/*");
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(test_decode::<ContentRange>(&["bytes 0-499"]), None);
+        assert_eq!(test_decode::<ContentRange>(&["bytes 499-0/500"]), None);
+        assert_eq!(test_decode::<ContentRange>(&["frames 1-2-3/500"]), None);
+        assert_eq!(test_decode::<ContentRange>(&["frames whatever"]), None);
+        assert_eq!(test_decode::<ContentRange>(&["fr@mes 1-2/3"]), None);
+        assert_eq!(test_decode::<ContentRange>(&[""]), None);
+    }
+}
+
 /*
 test_header!(test_bytes,
     vec![b"bytes 0-499/500"],
@@ -0,0 +1,280 @@
+//! Content-Security-Policy header and types.
+
+use std::fmt;
+
+use util;
+use HeaderValue;
+
+/// `Content-Security-Policy` header, defined in
+/// [CSP3](https://www.w3.org/TR/CSP3/#csp-header)
+///
+/// The `Content-Security-Policy` response header field restricts the
+/// content a document may load or run, such as which origins scripts may
+/// come from. `Content-Security-Policy-Report-Only` uses the same policy,
+/// but only reports violations.
+///
+/// A field may carry several policies, separated by commas or on separate
+/// lines, which are all enforced. They can't be merged into one policy, so
+/// decoding such a field fails.
+///
+/// # ABNF
+///
+/// ```text
+/// serialized-policy    = serialized-directive *( OWS ";" [ OWS serialized-directive ] )
+/// serialized-directive = directive-name [ RWS directive-value ]
+/// directive-name       = 1*( ALPHA / DIGIT / "-" )
+/// directive-value      = *( %x09 / %x20-%x2B / %x2D-%x3A / %x3C-%x7E )
+/// ```
+///
+/// # Example values
+///
+/// * `default-src 'self'; img-src *`
+/// * `script-src 'nonce-2726c7f26c' https://cdn.example; upgrade-insecure-requests`
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// use headers::ContentSecurityPolicy;
+///
+/// let csp = ContentSecurityPolicy::new()
+///     .with_directive("default-src", &["'self'"])
+///     .unwrap()
+///     .with_directive("img-src", &["*", "data:"])
+///     .unwrap();
+/// let img_src = csp.directive("img-src").unwrap();
+/// assert_eq!(img_src.values().collect::<Vec<_>>(), ["*", "data:"]);
+/// assert!(csp.directive("script-src").is_none());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ContentSecurityPolicy {
+    directives: Vec<Directive>,
+}
+
+/// A directive in a Content Security Policy, such as `img-src *`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Directive {
+    name: String,
+    values: Vec<String>,
+}
+
+error_type!(InvalidContentSecurityPolicy);
+
+impl ContentSecurityPolicy {
+    /// Create an empty policy.
+    pub fn new() -> ContentSecurityPolicy {
+        ContentSecurityPolicy::default()
+    }
+
+    /// Add a directive and its values.
+    ///
+    /// Fails if `name` isn't a valid directive name, or the policy already
+    /// has it, or a value is empty or contains whitespace, `;` or `,`.
+    pub fn with_directive(
+        mut self,
+        name: &str,
+        values: &[&str],
+    ) -> Result<Self, InvalidContentSecurityPolicy> {
+        if !is_directive_name(name)
+            || self.directive(name).is_some()
+            || !values.iter().all(|value| is_value(value))
+        {
+            return Err(InvalidContentSecurityPolicy { _inner: () });
+        }
+        self.directives.push(Directive {
+            name: name.to_owned(),
+            values: values.iter().map(|&value| value.to_owned()).collect(),
+        });
+        Ok(self)
+    }
+
+    /// Get a directive, if the policy has it.
+    ///
+    /// Directive names are compared ignoring ASCII case.
+    pub fn directive(&self, name: &str) -> Option<&Directive> {
+        self.directives
+            .iter()
+            .find(|directive| directive.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Iterate the directives, in the order they were sent.
+    pub fn directives(&self) -> impl Iterator<Item = &Directive> {
+        self.directives.iter()
+    }
+
+    pub(super) fn decode_policy<'i, I>(values: &mut I) -> Result<Self, ::Error>
+    where
+        I: Iterator<Item = &'i HeaderValue>,
+    {
+        let value = values.next().ok_or_else(::Error::invalid)?;
+        if values.next().is_some() {
+            return Err(::Error::invalid());
+        }
+        let s = value.to_str().map_err(|_| ::Error::invalid())?;
+        if s.contains(',') {
+            return Err(::Error::invalid());
+        }
+
+        let mut policy = ContentSecurityPolicy::new();
+        for directive in s.split(';').filter(|d| !d.trim().is_empty()) {
+            let directive = parse_directive(directive).ok_or_else(::Error::invalid)?;
+            // Repeated directives are ignored, so the first one wins.
+            if policy.directive(&directive.name).is_none() {
+                policy.directives.push(directive);
+            }
+        }
+
+        if policy.directives.is_empty() {
+            Err(::Error::invalid())
+        } else {
+            Ok(policy)
+        }
+    }
+}
+
+impl Directive {
+    /// The directive name, such as `script-src`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Iterate the values, such as source expressions like `'self'`, in the
+    /// order they were sent.
+    pub fn values(&self) -> impl Iterator<Item = &str> {
+        self.values.iter().map(String::as_str)
+    }
+}
+
+fn is_directive_name(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-')
+}
+
+fn is_value(s: &str) -> bool {
+    !s.is_empty()
+        && s.bytes()
+            .all(|b| (b'\x21'..=b'\x7e').contains(&b) && b != b';' && b != b',')
+}
+
+fn parse_directive(directive: &str) -> Option<Directive> {
+    let mut values = directive
+        .split(|c| c == ' ' || c == '\t')
+        .filter(|v| !v.is_empty());
+    let name = values.next()?;
+    if !is_directive_name(name) {
+        return None;
+    }
+
+    let values = values.map(str::to_owned).collect::<Vec<_>>();
+    if !values.iter().all(|value| is_value(value)) {
+        return None;
+    }
+    Some(Directive {
+        name: name.to_owned(),
+        values,
+    })
+}
+
+impl ::Header for ContentSecurityPolicy {
+    fn name() -> &'static ::HeaderName {
+        &::http::header::CONTENT_SECURITY_POLICY
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        ContentSecurityPolicy::decode_policy(values)
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once(util::fmt(self)));
+    }
+}
+
+impl ::NamedHeader for ContentSecurityPolicy {
+    const NAME: ::HeaderName = ::http::header::CONTENT_SECURITY_POLICY;
+}
+
+impl fmt::Display for ContentSecurityPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, directive) in self.directives.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            fmt::Display::fmt(directive, f)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Directive {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.name)?;
+        for value in &self.values {
+            write!(f, " {}", value)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn directives() {
+        let csp = test_decode::<ContentSecurityPolicy>(&[
+            "default-src 'self';  img-src\t* data: ;; upgrade-insecure-requests; IMG-SRC 'none'",
+        ])
+        .unwrap();
+        let names = csp.directives().map(Directive::name).collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            ["default-src", "img-src", "upgrade-insecure-requests"]
+        );
+
+        let img_src = csp.directive("Img-Src").unwrap();
+        assert_eq!(img_src.values().collect::<Vec<_>>(), ["*", "data:"]);
+        assert_eq!(
+            csp.directive("upgrade-insecure-requests")
+                .unwrap()
+                .values()
+                .count(),
+            0
+        );
+
+        let headers = test_encode(csp);
+        assert_eq!(
+            headers["content-security-policy"],
+            "default-src 'self'; img-src * data:; upgrade-insecure-requests"
+        );
+    }
+
+    #[test]
+    fn builder() {
+        assert!(ContentSecurityPolicy::new()
+            .with_directive("script src", &[])
+            .is_err());
+        assert!(ContentSecurityPolicy::new()
+            .with_directive("script-src", &["'self' https://a.example"])
+            .is_err());
+        assert!(ContentSecurityPolicy::new()
+            .with_directive("script-src", &[])
+            .unwrap()
+            .with_directive("Script-Src", &["*"])
+            .is_err());
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<ContentSecurityPolicy>(&[""]), None);
+        assert_eq!(test_decode::<ContentSecurityPolicy>(&[" ; "]), None);
+        assert_eq!(test_decode::<ContentSecurityPolicy>(&["img_src *"]), None);
+        assert_eq!(
+            test_decode::<ContentSecurityPolicy>(&["img-src *, script-src 'none'"]),
+            None
+        );
+        assert_eq!(
+            test_decode::<ContentSecurityPolicy>(&["img-src *", "script-src 'none'"]),
+            None
+        );
+    }
+}
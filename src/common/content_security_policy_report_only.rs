@@ -0,0 +1,123 @@
+use super::content_security_policy::{Directive, InvalidContentSecurityPolicy};
+use super::ContentSecurityPolicy;
+use util;
+use HeaderValue;
+
+/// `Content-Security-Policy-Report-Only` header, defined in
+/// [CSP3](https://www.w3.org/TR/CSP3/#cspro-header)
+///
+/// The `Content-Security-Policy-Report-Only` response header field has the
+/// same policy as `Content-Security-Policy`, but violations are only
+/// reported, not blocked. This lets a policy be tried out before it is
+/// enforced.
+///
+/// # Example values
+///
+/// * `default-src 'self'; report-to csp-endpoint`
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// use headers::{ContentSecurityPolicy, ContentSecurityPolicyReportOnly};
+///
+/// let report_only = ContentSecurityPolicyReportOnly::new()
+///     .with_directive("script-src", &["'self'"])
+///     .unwrap();
+/// assert!(report_only.directive("script-src").is_some());
+///
+/// // Once it is known to work, it can be enforced.
+/// let csp = ContentSecurityPolicy::from(report_only);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ContentSecurityPolicyReportOnly(ContentSecurityPolicy);
+
+impl ContentSecurityPolicyReportOnly {
+    /// Create an empty policy.
+    pub fn new() -> ContentSecurityPolicyReportOnly {
+        ContentSecurityPolicyReportOnly::default()
+    }
+
+    /// Add a directive and its values.
+    ///
+    /// This fails in the same cases as
+    /// [`ContentSecurityPolicy::with_directive`].
+    pub fn with_directive(
+        self,
+        name: &str,
+        values: &[&str],
+    ) -> Result<Self, InvalidContentSecurityPolicy> {
+        self.0
+            .with_directive(name, values)
+            .map(ContentSecurityPolicyReportOnly)
+    }
+
+    /// Get a directive, if the policy has it.
+    ///
+    /// Directive names are compared ignoring ASCII case.
+    pub fn directive(&self, name: &str) -> Option<&Directive> {
+        self.0.directive(name)
+    }
+
+    /// Iterate the directives, in the order they were sent.
+    pub fn directives(&self) -> impl Iterator<Item = &Directive> {
+        self.0.directives()
+    }
+}
+
+impl From<ContentSecurityPolicy> for ContentSecurityPolicyReportOnly {
+    fn from(policy: ContentSecurityPolicy) -> Self {
+        ContentSecurityPolicyReportOnly(policy)
+    }
+}
+
+impl From<ContentSecurityPolicyReportOnly> for ContentSecurityPolicy {
+    fn from(report_only: ContentSecurityPolicyReportOnly) -> Self {
+        report_only.0
+    }
+}
+
+impl ::Header for ContentSecurityPolicyReportOnly {
+    fn name() -> &'static ::HeaderName {
+        &::http::header::CONTENT_SECURITY_POLICY_REPORT_ONLY
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        ContentSecurityPolicy::decode_policy(values).map(ContentSecurityPolicyReportOnly)
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once(util::fmt(&self.0)));
+    }
+}
+
+impl ::NamedHeader for ContentSecurityPolicyReportOnly {
+    const NAME: ::HeaderName = ::http::header::CONTENT_SECURITY_POLICY_REPORT_ONLY;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let report_only = test_decode::<ContentSecurityPolicyReportOnly>(&[
+            "default-src 'self'; report-to csp-endpoint",
+        ])
+        .unwrap();
+        let report_to = report_only.directive("report-to").unwrap();
+        assert_eq!(report_to.values().collect::<Vec<_>>(), ["csp-endpoint"]);
+
+        let headers = test_encode(report_only.clone());
+        assert_eq!(
+            headers["content-security-policy-report-only"],
+            "default-src 'self'; report-to csp-endpoint"
+        );
+        assert!(!headers.contains_key("content-security-policy"));
+
+        let headers = test_encode(ContentSecurityPolicy::from(report_only));
+        assert!(headers.contains_key("content-security-policy"));
+        assert!(!headers.contains_key("content-security-policy-report-only"));
+    }
+}
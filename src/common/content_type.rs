@@ -92,8 +92,210 @@ impl ContentType {
     pub fn octet_stream() -> ContentType {
         ContentType(mime::APPLICATION_OCTET_STREAM)
     }
+
+    /// Create a `Content-Type: multipart/form-data` header with `boundary`.
+    ///
+    /// The boundary is quoted if it has characters that need it, such as a
+    /// space.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `boundary` isn't a valid multipart boundary: 1 to 70 of
+    /// letters, digits and `'()+_,-./:=? `, not ending in a space.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate headers;
+    /// use headers::ContentType;
+    ///
+    /// let ct = ContentType::multipart_form_data_with_boundary("abc123");
+    /// assert_eq!(ct.to_string(), "multipart/form-data; boundary=abc123");
+    /// assert_eq!(ct.boundary(), Some("abc123"));
+    /// ```
+    pub fn multipart_form_data_with_boundary(boundary: &str) -> ContentType {
+        assert!(
+            is_boundary(boundary),
+            "invalid multipart boundary: {:?}",
+            boundary
+        );
+        let mime = if ::util::is_token(boundary) {
+            format!("multipart/form-data; boundary={}", boundary)
+        } else {
+            format!("multipart/form-data; boundary=\"{}\"", boundary)
+        };
+        ContentType(mime.parse().expect("boundary was validated"))
+    }
+
+    /// Create a `Content-Type: multipart/form-data` header with a random
+    /// boundary.
+    ///
+    /// The boundary is 32 random letters and digits, which never need
+    /// quoting, and are very unlikely to appear in the parts of the body.
+    #[cfg(feature = "multipart")]
+    pub fn multipart_boundary_random() -> ContentType {
+        let boundary = (0..32)
+            .map(|_| ::fastrand::alphanumeric())
+            .collect::<String>();
+        ContentType::multipart_form_data_with_boundary(&boundary)
+    }
+
+    /// Get the `boundary` parameter of a multipart media type, if any.
+    pub fn boundary(&self) -> Option<&str> {
+        if self.0.type_() != mime::MULTIPART {
+            return None;
+        }
+        self.0.get_param(mime::BOUNDARY).map(|name| name.as_str())
+    }
+
+    /// Guess the `Content-Type` of a file from its extension, such as `svg`.
+    ///
+    /// The extension is matched without its leading `.`, ignoring case.
+    /// This only knows a small set of common web types, and isn't a full
+    /// MIME database; anything else is `None`, and is usually served as
+    /// `application/octet-stream`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate headers;
+    /// use headers::ContentType;
+    ///
+    /// let ct = ContentType::from_extension("svg").unwrap();
+    /// assert_eq!(ct.to_string(), "image/svg+xml");
+    /// ```
+    pub fn from_extension(ext: &str) -> Option<ContentType> {
+        EXTENSIONS
+            .iter()
+            .find(|&&(e, _)| e.eq_ignore_ascii_case(ext))
+            .map(|&(_, mime)| ContentType(mime.parse().expect("extension table has valid mimes")))
+    }
+
+    /// Get a canonical form of this media type, to compare or use in a
+    /// cache key.
+    ///
+    /// The type, subtype, parameter names, and `charset` value are
+    /// lowercased, and parameters are written without extra spaces or
+    /// needless quotes. A `charset=utf-8` is dropped from JSON types and
+    /// `text/javascript`, where UTF-8 is the default. Other types, such as
+    /// `text/html`, keep it, since HTTP defines no default for them.
+    ///
+    /// This returns a new `ContentType`, leaving this one as it was sent.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate headers;
+    /// use headers::ContentType;
+    ///
+    /// let a = "Text/HTML; Charset=UTF-8".parse::<ContentType>().unwrap();
+    /// let b = "text/html;charset=\"utf-8\"".parse::<ContentType>().unwrap();
+    /// assert_eq!(a.normalized().to_string(), "text/html; charset=utf-8");
+    /// assert_eq!(a.normalized().to_string(), b.normalized().to_string());
+    /// ```
+    pub fn normalized(&self) -> ContentType {
+        let utf8_default = self.is_json()
+            || (self.0.type_() == mime::TEXT && self.0.subtype() == mime::JAVASCRIPT);
+
+        let mut out = self.0.essence_str().to_ascii_lowercase();
+        for (name, value) in self.0.params() {
+            let name = name.as_str().to_ascii_lowercase();
+            // `Mime` keeps the escapes of a quoted-string, so remove them
+            // before quoting again.
+            let mut unescaped = String::with_capacity(value.as_str().len());
+            let mut chars = value.as_str().chars();
+            while let Some(c) = chars.next() {
+                unescaped.extend(if c == '\\' { chars.next() } else { Some(c) });
+            }
+            let value = if name == "charset" {
+                if utf8_default && value.as_str().eq_ignore_ascii_case("utf-8") {
+                    continue;
+                }
+                unescaped.to_ascii_lowercase()
+            } else {
+                unescaped
+            };
+
+            out.push_str("; ");
+            out.push_str(&name);
+            out.push('=');
+            if ::util::is_token(&value) {
+                out.push_str(&value);
+            } else {
+                out.push('"');
+                out.push_str(&value.replace('\\', "\\\\").replace('"', "\\\""));
+                out.push('"');
+            }
+        }
+        ContentType(out.parse().expect("normalized from a valid Mime"))
+    }
+
+    /// Borrow the media type as a `Mime`.
+    pub fn mime(&self) -> &Mime {
+        &self.0
+    }
+
+    /// Get the structured syntax suffix of this media type, if any.
+    ///
+    /// For example, this is `json` for `application/problem+json`.
+    pub fn suffix(&self) -> Option<&str> {
+        self.0.suffix().map(|name| name.as_str())
+    }
+
+    /// Check if this is a JSON media type, such as `application/json` or
+    /// any type with a `+json` suffix.
+    pub fn is_json(&self) -> bool {
+        self.0.subtype() == mime::JSON || self.0.suffix() == Some(mime::JSON)
+    }
+
+    /// Check if this is an XML media type, such as `text/xml` or any type
+    /// with a `+xml` suffix.
+    pub fn is_xml(&self) -> bool {
+        self.0.subtype() == mime::XML || self.0.suffix() == Some(mime::XML)
+    }
+}
+
+/// Check for a valid multipart boundary, from
+/// [RFC2046](https://tools.ietf.org/html/rfc2046#section-5.1.1).
+fn is_boundary(s: &str) -> bool {
+    (1..=70).contains(&s.len())
+        && !s.ends_with(' ')
+        && s.bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b"'()+_,-./:=? ".contains(&b))
 }
 
+const EXTENSIONS: &[(&str, &str)] = &[
+    ("avif", "image/avif"),
+    ("css", "text/css"),
+    ("csv", "text/csv"),
+    ("gif", "image/gif"),
+    ("gz", "application/gzip"),
+    ("htm", "text/html"),
+    ("html", "text/html"),
+    ("ico", "image/x-icon"),
+    ("jpeg", "image/jpeg"),
+    ("jpg", "image/jpeg"),
+    ("js", "text/javascript"),
+    ("json", "application/json"),
+    ("md", "text/markdown"),
+    ("mjs", "text/javascript"),
+    ("mp3", "audio/mpeg"),
+    ("mp4", "video/mp4"),
+    ("otf", "font/otf"),
+    ("pdf", "application/pdf"),
+    ("png", "image/png"),
+    ("svg", "image/svg+xml"),
+    ("ttf", "font/ttf"),
+    ("txt", "text/plain"),
+    ("wasm", "application/wasm"),
+    ("webm", "video/webm"),
+    ("webp", "image/webp"),
+    ("woff", "font/woff"),
+    ("woff2", "font/woff2"),
+    ("xml", "text/xml"),
+    ("zip", "application/zip"),
+];
+
 impl ::Header for ContentType {
     fn name() -> &'static ::HeaderName {
         &::http::header::CONTENT_TYPE
@@ -117,12 +319,22 @@ impl ::Header for ContentType {
     }
 }
 
+impl ::NamedHeader for ContentType {
+    const NAME: ::HeaderName = ::http::header::CONTENT_TYPE;
+}
+
 impl From<mime::Mime> for ContentType {
     fn from(m: mime::Mime) -> ContentType {
         ContentType(m)
     }
 }
 
+impl<'a> From<&'a mime::Mime> for ContentType {
+    fn from(m: &'a mime::Mime) -> ContentType {
+        ContentType(m.clone())
+    }
+}
+
 impl From<ContentType> for mime::Mime {
     fn from(ct: ContentType) -> mime::Mime {
         ct.0
@@ -147,7 +359,7 @@ impl std::str::FromStr for ContentType {
 
 #[cfg(test)]
 mod tests {
-    use super::super::test_decode;
+    use super::super::{test_decode, test_encode};
     use super::ContentType;
 
     #[test]
@@ -158,6 +370,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn suffix() {
+        let ct = test_decode::<ContentType>(&["application/ld+json"]).unwrap();
+        assert_eq!(ct.suffix(), Some("json"));
+        assert!(ct.is_json());
+        assert!(!ct.is_xml());
+
+        let ct = test_decode::<ContentType>(&["application/atom+xml; charset=utf-8"]).unwrap();
+        assert_eq!(ct.suffix(), Some("xml"));
+        assert!(ct.is_xml());
+
+        assert_eq!(ContentType::json().suffix(), None);
+        assert!(ContentType::json().is_json());
+        assert!(ContentType::xml().is_xml());
+        assert!(!ContentType::text().is_json());
+    }
+
     #[test]
     fn from_str() {
         assert_eq!(
@@ -167,6 +396,158 @@ mod tests {
         assert!("invalid-mimetype".parse::<ContentType>().is_err());
     }
 
+    #[test]
+    fn from_mime() {
+        let mime = "application/problem+json".parse::<::mime::Mime>().unwrap();
+
+        let borrowed = ContentType::from(&mime);
+        assert_eq!(borrowed.mime(), &mime);
+
+        let owned = ContentType::from(mime);
+        assert_eq!(owned, borrowed);
+        assert!(owned.is_json());
+        assert_eq!(
+            ::mime::Mime::from(owned).essence_str(),
+            "application/problem+json"
+        );
+
+        assert_eq!(ContentType::json().mime(), &::mime::APPLICATION_JSON);
+    }
+
+    #[test]
+    fn from_extension() {
+        assert_eq!(
+            ContentType::from_extension("svg").unwrap().to_string(),
+            "image/svg+xml"
+        );
+        assert_eq!(ContentType::from_extension("PNG"), Some(ContentType::png()));
+        assert_eq!(
+            ContentType::from_extension("html"),
+            Some(ContentType::html())
+        );
+        assert_eq!(ContentType::from_extension("unknown"), None);
+        assert_eq!(ContentType::from_extension(".svg"), None);
+        assert_eq!(ContentType::from_extension(""), None);
+    }
+
+    #[test]
+    fn normalized() {
+        let normalized = |s: &str| {
+            test_decode::<ContentType>(&[s])
+                .unwrap()
+                .normalized()
+                .to_string()
+        };
+
+        assert_eq!(
+            normalized("Text/HTML; Charset=UTF-8"),
+            "text/html; charset=utf-8"
+        );
+        assert_eq!(
+            normalized("text/html;charset=\"utf-8\""),
+            "text/html; charset=utf-8"
+        );
+        assert_eq!(
+            normalized("multipart/form-data; Boundary=\"AbC\""),
+            "multipart/form-data; boundary=AbC"
+        );
+        assert_eq!(
+            normalized("text/plain; title=\"a b\"; charset=ISO-8859-1"),
+            "text/plain; title=\"a b\"; charset=iso-8859-1"
+        );
+
+        // UTF-8 is already the default for these.
+        assert_eq!(
+            normalized("Application/JSON; charset=UTF-8"),
+            "application/json"
+        );
+        assert_eq!(
+            normalized("application/ld+json;charset=utf-8"),
+            "application/ld+json"
+        );
+        assert_eq!(
+            normalized("text/javascript; charset=utf-8"),
+            "text/javascript"
+        );
+
+        let original = test_decode::<ContentType>(&["text/html;charset=\"utf-8\""]).unwrap();
+        assert_eq!(
+            original.normalized().to_string(),
+            "text/html; charset=utf-8"
+        );
+        assert_eq!(original.to_string(), "text/html;charset=\"utf-8\"");
+    }
+
+    #[test]
+    fn normalized_is_idempotent() {
+        for s in &[
+            "text/plain; title=\"a \\\\ b\"",
+            "Text/HTML; Charset=UTF-8",
+            "multipart/form-data; boundary=\"a b\"",
+        ] {
+            let once = test_decode::<ContentType>(&[s]).unwrap().normalized();
+            assert_eq!(once.normalized().to_string(), once.to_string(), "{}", s);
+        }
+
+        let ct = test_decode::<ContentType>(&["text/plain; title=\"a \\\\ b\""]).unwrap();
+        assert_eq!(
+            ct.normalized().to_string(),
+            "text/plain; title=\"a \\\\ b\""
+        );
+    }
+
+    #[test]
+    fn boundary() {
+        let ct = test_decode::<ContentType>(&["multipart/form-data; boundary=\"a b\""]).unwrap();
+        assert_eq!(ct.boundary(), Some("a b"));
+        assert_eq!(ContentType::json().boundary(), None);
+        assert_eq!(
+            test_decode::<ContentType>(&["text/plain; boundary=x"])
+                .unwrap()
+                .boundary(),
+            None
+        );
+
+        let ct = ContentType::multipart_form_data_with_boundary("----WebKitFormBoundary7MA4YWxk");
+        let headers = test_encode(ct);
+        assert_eq!(
+            headers["content-type"],
+            "multipart/form-data; boundary=----WebKitFormBoundary7MA4YWxk"
+        );
+
+        let ct = ContentType::multipart_form_data_with_boundary("simple boundary");
+        assert_eq!(
+            ct.to_string(),
+            "multipart/form-data; boundary=\"simple boundary\""
+        );
+        assert_eq!(ct.boundary(), Some("simple boundary"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn boundary_invalid() {
+        ContentType::multipart_form_data_with_boundary("trailing ");
+    }
+
+    #[cfg(feature = "multipart")]
+    #[test]
+    fn boundary_random() {
+        let ct = ContentType::multipart_boundary_random();
+        let boundary = ct.boundary().unwrap().to_owned();
+        assert_eq!(boundary.len(), 32);
+        assert!(::util::is_token(&boundary));
+
+        let headers = test_encode(ct);
+        let decoded =
+            test_decode::<ContentType>(&[headers["content-type"].to_str().unwrap()]).unwrap();
+        assert_eq!(decoded.boundary(), Some(&boundary[..]));
+
+        assert_ne!(
+            ContentType::multipart_boundary_random().boundary(),
+            Some(&boundary[..])
+        );
+    }
+
     bench_header!(bench_plain, ContentType, "text/plain");
     bench_header!(bench_json, ContentType, "application/json");
     bench_header!(
@@ -49,6 +49,7 @@ impl Cookie {
     }
 
     /// Get the number of key-value pairs this `Cookie` contains.
+    #[allow(clippy::len_without_is_empty)]
     pub fn len(&self) -> usize {
         self.iter().count()
     }
@@ -24,6 +24,7 @@ use util::HttpDate;
 /// use std::time::SystemTime;
 ///
 /// let date = Date::from(SystemTime::now());
+/// let now = Date::now();
 /// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Date(HttpDate);
@@ -33,6 +34,13 @@ derive_header! {
     name: DATE
 }
 
+impl Date {
+    /// Create a `Date` for the current time, read from the system clock.
+    pub fn now() -> Date {
+        Date::from(SystemTime::now())
+    }
+}
+
 impl From<SystemTime> for Date {
     fn from(time: SystemTime) -> Date {
         Date(time.into())
@@ -44,3 +52,41 @@ impl From<Date> for SystemTime {
         date.0.into()
     }
 }
+
+date_conversions!(Date);
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::Date;
+    use std::time::{Duration, SystemTime};
+
+    #[test]
+    fn now() {
+        let before = SystemTime::now() - Duration::from_secs(1);
+        let date = Date::now();
+        assert!(SystemTime::from(date) >= before);
+
+        let headers = test_encode(date);
+        let value = headers["date"].to_str().unwrap();
+        assert_eq!(test_decode::<Date>(&[value]), Some(date));
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_round_trip() {
+        use chrono::{DateTime, TimeZone, Utc};
+
+        let time = Utc.with_ymd_and_hms(2015, 10, 21, 7, 28, 0).unwrap();
+        let date = Date::from(time);
+        assert_eq!(DateTime::<Utc>::from(date), time);
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn time_round_trip() {
+        let time = ::time::OffsetDateTime::from_unix_timestamp(1_445_412_480).unwrap();
+        let date = Date::from(time);
+        assert_eq!(::time::OffsetDateTime::from(date), time);
+    }
+}
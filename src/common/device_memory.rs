@@ -0,0 +1,135 @@
+use util::{self, sf, IterExt};
+use {HeaderName, HeaderValue};
+
+const DEVICE_MEMORY: HeaderName = HeaderName::from_static("device-memory");
+
+const MIN_GIB: f64 = 0.25;
+const MAX_GIB: f64 = 8.0;
+
+/// `Device-Memory` header, defined in
+/// [Device Memory](https://www.w3.org/TR/device-memory/#sec-device-memory-client-hint-header)
+///
+/// The `Device-Memory` client hint indicates the approximate amount of
+/// memory the device has, in GiB. To limit fingerprinting, the value is
+/// rounded to the nearest power of two, and bounded to between `0.25` and
+/// `8`.
+///
+/// # ABNF
+///
+/// ```text
+/// Device-Memory = sf-decimal
+/// ```
+///
+/// # Example values
+///
+/// * `0.5`
+/// * `4`
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// use headers::DeviceMemory;
+///
+/// let mem = DeviceMemory::new(3.0);
+/// assert_eq!(mem.gib(), 4.0);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DeviceMemory(f64);
+
+impl DeviceMemory {
+    /// Create a `Device-Memory` header, rounding `gib` to one of the allowed
+    /// values.
+    pub fn new(gib: f64) -> DeviceMemory {
+        DeviceMemory(round(gib))
+    }
+
+    /// Get the approximate device memory, in GiB.
+    pub fn gib(&self) -> f64 {
+        self.0
+    }
+}
+
+fn round(gib: f64) -> f64 {
+    if gib.is_nan() || gib <= MIN_GIB {
+        return MIN_GIB;
+    }
+    if gib >= MAX_GIB {
+        return MAX_GIB;
+    }
+    2f64.powf(gib.log2().round())
+}
+
+impl ::Header for DeviceMemory {
+    fn name() -> &'static HeaderName {
+        static NAME: HeaderName = DEVICE_MEMORY;
+        &NAME
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        values
+            .just_one()
+            .and_then(|val| val.to_str().ok())
+            .and_then(|s| sf::parse_item(s).ok())
+            .and_then(|item| item.bare.as_decimal())
+            .filter(|gib| *gib > 0.0)
+            .map(DeviceMemory::new)
+            .ok_or_else(::Error::invalid)
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once(util::fmt(self.0)));
+    }
+}
+
+impl ::NamedHeader for DeviceMemory {
+    const NAME: ::HeaderName = DEVICE_MEMORY;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::DeviceMemory;
+
+    #[test]
+    fn decode() {
+        assert_eq!(
+            test_decode::<DeviceMemory>(&["0.5"]),
+            Some(DeviceMemory(0.5))
+        );
+        assert_eq!(test_decode::<DeviceMemory>(&["4"]), Some(DeviceMemory(4.0)));
+    }
+
+    #[test]
+    fn decode_rounds_to_allowed_values() {
+        assert_eq!(
+            test_decode::<DeviceMemory>(&["3.0"]),
+            Some(DeviceMemory(4.0))
+        );
+        assert_eq!(
+            test_decode::<DeviceMemory>(&["0.1"]),
+            Some(DeviceMemory(0.25))
+        );
+        assert_eq!(
+            test_decode::<DeviceMemory>(&["64"]),
+            Some(DeviceMemory(8.0))
+        );
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<DeviceMemory>(&["0"]), None);
+        assert_eq!(test_decode::<DeviceMemory>(&["-1"]), None);
+        assert_eq!(test_decode::<DeviceMemory>(&["lots"]), None);
+        assert_eq!(test_decode::<DeviceMemory>(&["1", "2"]), None);
+    }
+
+    #[test]
+    fn encode() {
+        let headers = test_encode(DeviceMemory::new(0.25));
+        assert_eq!(headers["device-memory"], "0.25");
+
+        let headers = test_encode(DeviceMemory::new(2.0));
+        assert_eq!(headers["device-memory"], "2");
+    }
+}
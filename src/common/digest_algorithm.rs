@@ -0,0 +1,77 @@
+/// A hash algorithm, as used by integrity metadata and digest fields.
+///
+/// Algorithms are ordered from weakest to strongest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DigestAlgorithm {
+    /// SHA-256
+    Sha256,
+    /// SHA-384
+    Sha384,
+    /// SHA-512
+    Sha512,
+}
+
+impl DigestAlgorithm {
+    /// The length of a hash made by this algorithm, in bytes.
+    pub fn output_len(self) -> usize {
+        match self {
+            DigestAlgorithm::Sha256 => 32,
+            DigestAlgorithm::Sha384 => 48,
+            DigestAlgorithm::Sha512 => 64,
+        }
+    }
+
+    /// The name used for this algorithm in
+    /// [Subresource Integrity](https://www.w3.org/TR/SRI/) metadata.
+    pub(crate) fn sri_name(self) -> &'static str {
+        match self {
+            DigestAlgorithm::Sha256 => "sha256",
+            DigestAlgorithm::Sha384 => "sha384",
+            DigestAlgorithm::Sha512 => "sha512",
+        }
+    }
+
+    pub(crate) fn from_sri_name(s: &str) -> Option<DigestAlgorithm> {
+        [
+            DigestAlgorithm::Sha256,
+            DigestAlgorithm::Sha384,
+            DigestAlgorithm::Sha512,
+        ]
+        .iter()
+        .cloned()
+        .find(|alg| s.eq_ignore_ascii_case(alg.sri_name()))
+    }
+
+    /// The name used for this algorithm in the
+    /// [HTTP Digest Algorithm Values](https://www.iana.org/assignments/http-digest-hash-alg/)
+    /// registry, as used by `Content-Digest`.
+    ///
+    /// SHA-384 isn't registered there, so it has no name.
+    pub(crate) fn http_name(self) -> Option<&'static str> {
+        match self {
+            DigestAlgorithm::Sha256 => Some("sha-256"),
+            DigestAlgorithm::Sha384 => None,
+            DigestAlgorithm::Sha512 => Some("sha-512"),
+        }
+    }
+
+    pub(crate) fn from_http_name(s: &str) -> Option<DigestAlgorithm> {
+        match s {
+            "sha-256" => Some(DigestAlgorithm::Sha256),
+            "sha-512" => Some(DigestAlgorithm::Sha512),
+            _ => None,
+        }
+    }
+
+    /// Hash `data` with this algorithm.
+    #[cfg(feature = "digest")]
+    pub fn hash(self, data: &[u8]) -> Vec<u8> {
+        use sha2::{Digest, Sha256, Sha384, Sha512};
+
+        match self {
+            DigestAlgorithm::Sha256 => Sha256::digest(data).to_vec(),
+            DigestAlgorithm::Sha384 => Sha384::digest(data).to_vec(),
+            DigestAlgorithm::Sha512 => Sha512::digest(data).to_vec(),
+        }
+    }
+}
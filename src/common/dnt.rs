@@ -0,0 +1,97 @@
+use util::IterExt;
+use {HeaderName, HeaderValue};
+
+const DNT: HeaderName = HeaderName::from_static("dnt");
+
+/// `DNT` header, defined in
+/// [Tracking Preference Expression](https://www.w3.org/TR/tracking-dnt/#dnt-header-field)
+///
+/// The `DNT` request header field carries the user's tracking preference.
+/// Tracking Preference Expression has been discontinued, but some services
+/// still honor the header.
+///
+/// # ABNF
+///
+/// ```text
+/// DNT = ( "0" / "1" ) *DNT-extension
+/// ```
+///
+/// Extensions are not supported, and fail to decode.
+///
+/// # Example values
+///
+/// * `1`
+/// * `0`
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// use headers::Dnt;
+///
+/// assert!(Dnt::On.is_on());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Dnt {
+    /// `1`, the user prefers not to be tracked.
+    On,
+    /// `0`, the user allows tracking.
+    Off,
+}
+
+impl Dnt {
+    /// Whether the user prefers not to be tracked.
+    pub fn is_on(&self) -> bool {
+        *self == Dnt::On
+    }
+}
+
+impl ::Header for Dnt {
+    fn name() -> &'static HeaderName {
+        static NAME: HeaderName = DNT;
+        &NAME
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        match values.just_one().map(HeaderValue::as_bytes) {
+            Some(b"1") => Ok(Dnt::On),
+            Some(b"0") => Ok(Dnt::Off),
+            _ => Err(::Error::invalid()),
+        }
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        let value = match *self {
+            Dnt::On => "1",
+            Dnt::Off => "0",
+        };
+        values.extend(::std::iter::once(HeaderValue::from_static(value)));
+    }
+}
+
+impl ::NamedHeader for Dnt {
+    const NAME: ::HeaderName = DNT;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::Dnt;
+
+    #[test]
+    fn decode() {
+        assert_eq!(test_decode::<Dnt>(&["1"]), Some(Dnt::On));
+        assert_eq!(test_decode::<Dnt>(&["0"]), Some(Dnt::Off));
+        assert!(!Dnt::Off.is_on());
+
+        assert_eq!(test_decode::<Dnt>(&["2"]), None);
+        assert_eq!(test_decode::<Dnt>(&[""]), None);
+        assert_eq!(test_decode::<Dnt>(&["1", "0"]), None);
+    }
+
+    #[test]
+    fn encode() {
+        let headers = test_encode(Dnt::On);
+        assert_eq!(headers["dnt"], "1");
+    }
+}
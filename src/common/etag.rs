@@ -39,6 +39,43 @@ derive_header! {
 }
 
 impl ETag {
+    /// Create an `ETag` from a tag, adding the quotes if they're missing.
+    ///
+    /// The tag may be bare, such as `xyzzy`, or already quoted, such as
+    /// `"xyzzy"`, and either may start with `W/` to make a weak tag. A
+    /// leading `W/` is always read as the weakness indicator.
+    ///
+    /// Fails if the tag contains a `"`, or any character besides visible
+    /// ASCII.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use headers::ETag;
+    ///
+    /// assert_eq!(ETag::new("xyzzy").unwrap(), "\"xyzzy\"".parse().unwrap());
+    /// assert_eq!(ETag::new("W/\"xyzzy\"").unwrap(), "W/\"xyzzy\"".parse().unwrap());
+    /// ```
+    pub fn new(tag: &str) -> Result<ETag, InvalidETag> {
+        let (weak, opaque) = match tag.strip_prefix("W/") {
+            Some(rest) => ("W/", rest),
+            None => ("", tag),
+        };
+        let opaque = if opaque.len() >= 2 && opaque.starts_with('"') && opaque.ends_with('"') {
+            &opaque[1..opaque.len() - 1]
+        } else {
+            opaque
+        };
+
+        let valid = opaque
+            .bytes()
+            .all(|c| c == b'\x21' || (b'\x23'..=b'\x7e').contains(&c));
+        if !valid {
+            return Err(InvalidETag { _inner: () });
+        }
+        format!("{}\"{}\"", weak, opaque).parse()
+    }
+
     #[cfg(test)]
     pub(crate) fn from_static(src: &'static str) -> ETag {
         ETag(EntityTag::from_static(src))
@@ -54,7 +91,7 @@ impl FromStr for ETag {
 
         EntityTag::from_owned(val)
             .map(ETag)
-            .ok_or_else(|| InvalidETag { _inner: () })
+            .ok_or(InvalidETag { _inner: () })
     }
 }
 
@@ -109,3 +146,33 @@ test_etag {
         None::<ETag>);
 }
 */
+
+#[cfg(test)]
+mod tests {
+    use super::ETag;
+
+    #[test]
+    fn new_normalizes_quotes() {
+        let strong = ETag::from_static("\"xyzzy\"");
+        assert_eq!(ETag::new("xyzzy").unwrap(), strong);
+        assert_eq!(ETag::new("\"xyzzy\"").unwrap(), strong);
+
+        let weak = ETag::from_static("W/\"xyzzy\"");
+        assert_eq!(ETag::new("W/xyzzy").unwrap(), weak);
+        assert_eq!(ETag::new("W/\"xyzzy\"").unwrap(), weak);
+
+        assert_eq!(ETag::new("").unwrap(), ETag::from_static("\"\""));
+        assert_eq!(ETag::new("\"\"").unwrap(), ETag::from_static("\"\""));
+    }
+
+    #[test]
+    fn new_invalid() {
+        assert!(ETag::new("\"").is_err());
+        assert!(ETag::new("\"xyzzy").is_err());
+        assert!(ETag::new("\"\"xyzzy\"\"").is_err());
+        assert!(ETag::new("xy zzy").is_err());
+        assert!(ETag::new("xyzzy\n").is_err());
+        assert!(ETag::new("caf\u{e9}").is_err());
+        assert!(ETag::new("W/\"").is_err());
+    }
+}
@@ -52,6 +52,10 @@ impl ::Header for Expect {
     }
 }
 
+impl ::NamedHeader for Expect {
+    const NAME: ::HeaderName = ::http::header::EXPECT;
+}
+
 impl fmt::Debug for Expect {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_tuple("Expect").field(&"100-continue").finish()
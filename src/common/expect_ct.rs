@@ -0,0 +1,241 @@
+use std::fmt;
+use std::time::Duration;
+
+use util::{self, unquote, FlatCsv, Seconds};
+use {HeaderName, HeaderValue};
+
+const EXPECT_CT: HeaderName = HeaderName::from_static("expect-ct");
+
+/// `Expect-CT` header, defined in
+/// [RFC9163](https://www.rfc-editor.org/rfc/rfc9163.html)
+///
+/// The `Expect-CT` response header field asks the user agent to check that
+/// the certificate of the host is logged in public Certificate Transparency
+/// logs, and either enforce this or report failures to `report-uri`.
+///
+/// It is obsolete, since browsers now require Certificate Transparency
+/// anyway, but is still sent by many deployments.
+///
+/// # ABNF
+///
+/// ```text
+/// Expect-CT           = 1#expect-ct-directive
+/// expect-ct-directive = directive-name [ "=" directive-value ]
+/// directive-name      = token
+/// directive-value     = token / quoted-string
+/// ```
+///
+/// # Example values
+///
+/// * `max-age=86400, enforce`
+/// * `max-age=86400, enforce, report-uri="https://example.com/report"`
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// use std::time::Duration;
+/// use headers::ExpectCT;
+///
+/// let expect_ct = ExpectCT::new(Duration::from_secs(86400))
+///     .with_enforce()
+///     .with_report_uri("https://example.com/report")
+///     .unwrap();
+/// assert!(expect_ct.enforce());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExpectCT {
+    max_age: Option<Seconds>,
+    enforce: bool,
+    report_uri: Option<String>,
+}
+
+error_type!(InvalidExpectCT);
+
+impl ExpectCT {
+    /// Create an `Expect-CT` that reports, but doesn't enforce, for
+    /// `max_age`.
+    pub fn new(max_age: Duration) -> ExpectCT {
+        ExpectCT {
+            max_age: Some(Seconds::from_secs(max_age.as_secs())),
+            enforce: false,
+            report_uri: None,
+        }
+    }
+
+    /// Ask the user agent to refuse connections that fail the policy.
+    pub fn with_enforce(mut self) -> Self {
+        self.enforce = true;
+        self
+    }
+
+    /// Set the URI that failures are reported to.
+    ///
+    /// The URI must be made of visible ASCII characters.
+    pub fn with_report_uri(mut self, uri: &str) -> Result<Self, InvalidExpectCT> {
+        if uri.is_empty() || !uri.bytes().all(|b| (b'\x21'..=b'\x7e').contains(&b)) {
+            return Err(InvalidExpectCT { _inner: () });
+        }
+        self.report_uri = Some(uri.to_owned());
+        Ok(self)
+    }
+
+    /// How long the user agent should keep the policy, if sent.
+    pub fn max_age(&self) -> Option<Duration> {
+        self.max_age.map(Into::into)
+    }
+
+    /// Whether the policy is enforced.
+    pub fn enforce(&self) -> bool {
+        self.enforce
+    }
+
+    /// The URI that failures are reported to, if any.
+    pub fn report_uri(&self) -> Option<&str> {
+        self.report_uri.as_deref()
+    }
+}
+
+impl ::Header for ExpectCT {
+    fn name() -> &'static HeaderName {
+        static NAME: HeaderName = EXPECT_CT;
+        &NAME
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        let csv = values.collect::<FlatCsv>();
+        let mut expect_ct = ExpectCT {
+            max_age: None,
+            enforce: false,
+            report_uri: None,
+        };
+        let mut any = false;
+
+        for directive in csv.iter().filter(|s| !s.is_empty()) {
+            any = true;
+            let mut parts = directive.splitn(2, '=');
+            let name = parts.next().unwrap_or("").trim();
+            let value = parts.next().map(str::trim);
+
+            if name.eq_ignore_ascii_case("max-age") {
+                let secs = value
+                    .and_then(unquote)
+                    .and_then(|v| v.parse().ok())
+                    .ok_or_else(::Error::invalid)?;
+                if expect_ct
+                    .max_age
+                    .replace(Seconds::from_secs(secs))
+                    .is_some()
+                {
+                    return Err(::Error::invalid());
+                }
+            } else if name.eq_ignore_ascii_case("enforce") {
+                if value.is_some() || expect_ct.enforce {
+                    return Err(::Error::invalid());
+                }
+                expect_ct.enforce = true;
+            } else if name.eq_ignore_ascii_case("report-uri") {
+                let uri = value.and_then(unquote).ok_or_else(::Error::invalid)?;
+                if expect_ct.report_uri.replace(uri).is_some() {
+                    return Err(::Error::invalid());
+                }
+            }
+        }
+
+        if any {
+            Ok(expect_ct)
+        } else {
+            Err(::Error::invalid())
+        }
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once(util::fmt(self)));
+    }
+}
+
+impl ::NamedHeader for ExpectCT {
+    const NAME: ::HeaderName = EXPECT_CT;
+}
+
+impl fmt::Display for ExpectCT {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut directives = Vec::new();
+        if let Some(max_age) = self.max_age {
+            directives.push(format!("max-age={}", max_age));
+        }
+        if self.enforce {
+            directives.push("enforce".to_owned());
+        }
+        if let Some(ref uri) = self.report_uri {
+            let escaped = uri.replace('\\', "\\\\").replace('"', "\\\"");
+            directives.push(format!("report-uri=\"{}\"", escaped));
+        }
+        util::csv::fmt_comma_delimited(f, directives.iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn enforce_and_report() {
+        let expect_ct = test_decode::<ExpectCT>(&[
+            "enforce, report-uri=\"https://example.com/report?a=1,b=2\", max-age=86400",
+        ])
+        .unwrap();
+        assert_eq!(expect_ct.max_age(), Some(Duration::from_secs(86400)));
+        assert!(expect_ct.enforce());
+        assert_eq!(
+            expect_ct.report_uri(),
+            Some("https://example.com/report?a=1,b=2")
+        );
+
+        let headers = test_encode(expect_ct.clone());
+        assert_eq!(
+            headers["expect-ct"],
+            "max-age=86400, enforce, report-uri=\"https://example.com/report?a=1,b=2\""
+        );
+        assert_eq!(
+            test_decode::<ExpectCT>(&[headers["expect-ct"].to_str().unwrap()]),
+            Some(expect_ct)
+        );
+    }
+
+    #[test]
+    fn builder() {
+        let expect_ct = ExpectCT::new(Duration::from_secs(0))
+            .with_report_uri("/ct")
+            .unwrap();
+        assert!(!expect_ct.enforce());
+
+        let headers = test_encode(expect_ct);
+        assert_eq!(headers["expect-ct"], "max-age=0, report-uri=\"/ct\"");
+
+        assert!(ExpectCT::new(Duration::from_secs(1))
+            .with_report_uri("a b")
+            .is_err());
+    }
+
+    #[test]
+    fn decode_lenient() {
+        let expect_ct = test_decode::<ExpectCT>(&["Enforce", "future-directive=1"]).unwrap();
+        assert_eq!(expect_ct.max_age(), None);
+        assert!(expect_ct.enforce());
+        assert_eq!(expect_ct.report_uri(), None);
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<ExpectCT>(&[""]), None);
+        assert_eq!(test_decode::<ExpectCT>(&["max-age=x"]), None);
+        assert_eq!(test_decode::<ExpectCT>(&["max-age=1, max-age=2"]), None);
+        assert_eq!(
+            test_decode::<ExpectCT>(&["report-uri=\"/unterminated"]),
+            None
+        );
+        assert_eq!(test_decode::<ExpectCT>(&["enforce=yes"]), None);
+    }
+}
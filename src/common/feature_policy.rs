@@ -0,0 +1,350 @@
+//! Feature-Policy header and types.
+
+use std::fmt;
+
+use util;
+use {HeaderName, HeaderValue};
+
+const FEATURE_POLICY: HeaderName = HeaderName::from_static("feature-policy");
+
+/// `Feature-Policy` header, defined in
+/// [Feature Policy](https://w3c.github.io/webappsec-permissions-policy/archive/feature-policy.html)
+///
+/// The `Feature-Policy` response header field controls which origins may
+/// use browser features, such as the camera or geolocation, in the
+/// document and any frames it embeds. It has been replaced by
+/// `Permissions-Policy`, which has a different syntax, but is still sent
+/// by many deployments.
+///
+/// # ABNF
+///
+/// ```text
+/// Feature-Policy   = policy-directive *( ";" [ policy-directive ] )
+/// policy-directive = feature-name *( RWS allow-list-value )
+/// feature-name     = 1*( ALPHA / DIGIT / "-" )
+/// allow-list-value = origin / "*" / "'self'" / "'none'"
+/// ```
+///
+/// # Example values
+///
+/// * `geolocation 'self' https://a.example; camera 'none'`
+/// * `fullscreen *`
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// use headers::FeaturePolicy;
+/// use headers::feature_policy::Allowlist;
+///
+/// let policy = FeaturePolicy::new()
+///     .with_feature("geolocation", Allowlist::self_origin())
+///     .unwrap()
+///     .with_feature("camera", Allowlist::none())
+///     .unwrap();
+/// assert!(policy.feature("geolocation").unwrap().allows_self());
+/// assert!(policy.feature("camera").unwrap().is_none());
+/// assert!(policy.feature("microphone").is_none());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FeaturePolicy {
+    features: Vec<(String, Allowlist)>,
+}
+
+/// The origins allowed to use a feature, in a `Feature-Policy` header.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Allowlist {
+    any: bool,
+    self_origin: bool,
+    origins: Vec<String>,
+}
+
+error_type!(InvalidFeaturePolicy);
+
+impl FeaturePolicy {
+    /// Create an empty `Feature-Policy`.
+    pub fn new() -> FeaturePolicy {
+        FeaturePolicy::default()
+    }
+
+    /// Add a feature and its allowlist.
+    ///
+    /// Fails if `name` isn't a valid feature name, or the feature is
+    /// already in the policy.
+    pub fn with_feature(
+        mut self,
+        name: &str,
+        allowlist: Allowlist,
+    ) -> Result<Self, InvalidFeaturePolicy> {
+        if !is_feature_name(name) || self.feature(name).is_some() {
+            return Err(InvalidFeaturePolicy { _inner: () });
+        }
+        self.features.push((name.to_owned(), allowlist));
+        Ok(self)
+    }
+
+    /// Get the allowlist of a feature, if the policy names it.
+    ///
+    /// Feature names are compared ignoring ASCII case.
+    pub fn feature(&self, name: &str) -> Option<&Allowlist> {
+        self.features
+            .iter()
+            .find(|(feature, _)| feature.eq_ignore_ascii_case(name))
+            .map(|(_, allowlist)| allowlist)
+    }
+
+    /// Iterate the feature names, in the order they were sent.
+    pub fn features(&self) -> impl Iterator<Item = &str> {
+        self.features.iter().map(|(feature, _)| feature.as_str())
+    }
+}
+
+impl Allowlist {
+    /// `'none'`, no origin may use the feature.
+    pub fn none() -> Allowlist {
+        Allowlist::default()
+    }
+
+    /// `'self'`, only the document's own origin may use the feature.
+    pub fn self_origin() -> Allowlist {
+        Allowlist {
+            self_origin: true,
+            ..Allowlist::default()
+        }
+    }
+
+    /// `*`, any origin may use the feature.
+    pub fn any() -> Allowlist {
+        Allowlist {
+            any: true,
+            ..Allowlist::default()
+        }
+    }
+
+    /// Also allow `origin`, such as `https://a.example`.
+    ///
+    /// Fails if `origin` isn't made of visible ASCII characters, or is one
+    /// of the keywords.
+    pub fn with_origin(mut self, origin: &str) -> Result<Self, InvalidFeaturePolicy> {
+        if !is_origin(origin) {
+            return Err(InvalidFeaturePolicy { _inner: () });
+        }
+        self.origins.push(origin.to_owned());
+        Ok(self)
+    }
+
+    /// Whether no origin may use the feature.
+    pub fn is_none(&self) -> bool {
+        !self.any && !self.self_origin && self.origins.is_empty()
+    }
+
+    /// Whether any origin may use the feature.
+    pub fn allows_any(&self) -> bool {
+        self.any
+    }
+
+    /// Whether the document's own origin may use the feature.
+    pub fn allows_self(&self) -> bool {
+        self.any || self.self_origin
+    }
+
+    /// Iterate the origins explicitly allowed to use the feature.
+    pub fn origins(&self) -> impl Iterator<Item = &str> {
+        self.origins.iter().map(String::as_str)
+    }
+}
+
+fn is_feature_name(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-')
+}
+
+fn is_keyword(s: &str) -> bool {
+    s.len() > 2 && s.starts_with('\'') && s.ends_with('\'') && is_feature_name(&s[1..s.len() - 1])
+}
+
+fn is_origin(s: &str) -> bool {
+    !s.is_empty()
+        && !s.starts_with('\'')
+        && s != "*"
+        && s.bytes()
+            .all(|b| (b'\x21'..=b'\x7e').contains(&b) && b != b';' && b != b',')
+}
+
+fn parse_directive(directive: &str) -> Option<(String, Allowlist)> {
+    let mut values = directive.split_whitespace();
+    let name = values.next()?;
+    if !is_feature_name(name) {
+        return None;
+    }
+
+    let mut allowlist = Allowlist::none();
+    for value in values {
+        match value {
+            "*" | "'*'" => allowlist.any = true,
+            "'self'" => allowlist.self_origin = true,
+            // `'none'` only means anything on its own, which is already what
+            // an empty allowlist is.
+            "'none'" => {}
+            // Unknown keywords, such as `'src'` from the `allow` attribute,
+            // are ignored.
+            _ if is_keyword(value) => {}
+            _ if is_origin(value) => allowlist.origins.push(value.to_owned()),
+            _ => return None,
+        }
+    }
+    Some((name.to_owned(), allowlist))
+}
+
+impl ::Header for FeaturePolicy {
+    fn name() -> &'static HeaderName {
+        static NAME: HeaderName = FEATURE_POLICY;
+        &NAME
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        let mut policy = FeaturePolicy::new();
+        for value in values {
+            let s = value.to_str().map_err(|_| ::Error::invalid())?;
+            for directive in s.split(';').filter(|d| !d.trim().is_empty()) {
+                let (name, allowlist) = parse_directive(directive).ok_or_else(::Error::invalid)?;
+                // The first declaration of a feature wins.
+                if policy.feature(&name).is_none() {
+                    policy.features.push((name, allowlist));
+                }
+            }
+        }
+
+        if policy.features.is_empty() {
+            Err(::Error::invalid())
+        } else {
+            Ok(policy)
+        }
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once(util::fmt(self)));
+    }
+}
+
+impl ::NamedHeader for FeaturePolicy {
+    const NAME: ::HeaderName = FEATURE_POLICY;
+}
+
+impl fmt::Display for FeaturePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, (name, allowlist)) in self.features.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{} {}", name, allowlist)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Allowlist {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_none() {
+            return f.write_str("'none'");
+        }
+
+        let mut values = Vec::new();
+        if self.any {
+            values.push("*");
+        }
+        if self.self_origin {
+            values.push("'self'");
+        }
+        values.extend(self.origins());
+        f.write_str(&values.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn self_and_none() {
+        let policy =
+            test_decode::<FeaturePolicy>(&["geolocation 'self' https://a.example; camera 'none'"])
+                .unwrap();
+        assert_eq!(
+            policy.features().collect::<Vec<_>>(),
+            ["geolocation", "camera"]
+        );
+
+        let geolocation = policy.feature("geolocation").unwrap();
+        assert!(geolocation.allows_self());
+        assert!(!geolocation.allows_any());
+        assert!(!geolocation.is_none());
+        assert_eq!(
+            geolocation.origins().collect::<Vec<_>>(),
+            ["https://a.example"]
+        );
+
+        let camera = policy.feature("Camera").unwrap();
+        assert!(camera.is_none());
+        assert!(!camera.allows_self());
+        assert_eq!(camera, &Allowlist::none());
+
+        assert_eq!(policy.feature("microphone"), None);
+
+        let headers = test_encode(policy);
+        assert_eq!(
+            headers["feature-policy"],
+            "geolocation 'self' https://a.example; camera 'none'"
+        );
+    }
+
+    #[test]
+    fn any_and_duplicates() {
+        let policy =
+            test_decode::<FeaturePolicy>(&["fullscreen '*';; fullscreen 'none'", "usb 'src'"])
+                .unwrap();
+        let fullscreen = policy.feature("fullscreen").unwrap();
+        assert!(fullscreen.allows_any());
+        assert!(fullscreen.allows_self());
+        assert!(policy.feature("usb").unwrap().is_none());
+
+        let headers = test_encode(policy);
+        assert_eq!(headers["feature-policy"], "fullscreen *; usb 'none'");
+    }
+
+    #[test]
+    fn builder() {
+        let allowlist = Allowlist::self_origin()
+            .with_origin("https://b.example")
+            .unwrap();
+        let policy = FeaturePolicy::new()
+            .with_feature("payment", allowlist)
+            .unwrap();
+        let headers = test_encode(policy);
+        assert_eq!(
+            headers["feature-policy"],
+            "payment 'self' https://b.example"
+        );
+
+        assert!(FeaturePolicy::new()
+            .with_feature("pay ment", Allowlist::any())
+            .is_err());
+        assert!(FeaturePolicy::new()
+            .with_feature("payment", Allowlist::any())
+            .unwrap()
+            .with_feature("payment", Allowlist::none())
+            .is_err());
+        assert!(Allowlist::none().with_origin("'self'").is_err());
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<FeaturePolicy>(&[""]), None);
+        assert_eq!(test_decode::<FeaturePolicy>(&[" ; "]), None);
+        assert_eq!(test_decode::<FeaturePolicy>(&["camera=()"]), None);
+        assert_eq!(
+            test_decode::<FeaturePolicy>(&["camera 'self',https://a.example"]),
+            None
+        );
+    }
+}
@@ -0,0 +1,325 @@
+//! Forwarded header and types.
+
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use util::{self, is_token};
+use HeaderValue;
+
+/// `Forwarded` header, defined in
+/// [RFC7239](https://tools.ietf.org/html/rfc7239)
+///
+/// The `Forwarded` header field discloses information that proxies would
+/// otherwise lose, such as the address of the client and the `Host` it
+/// asked for. Each proxy appends an element, so the first is the one
+/// closest to the client.
+///
+/// A node, in `for` or `by`, may be an IP address, `unknown`, or an
+/// obfuscated identifier such as `_gazonk`, and its port may be obfuscated
+/// too. Nodes are kept as sent, so every form decodes.
+///
+/// # ABNF
+///
+/// ```text
+/// Forwarded         = 1#forwarded-element
+/// forwarded-element = [ forwarded-pair ] *( ";" [ forwarded-pair ] )
+/// forwarded-pair    = token "=" value
+/// value             = token / quoted-string
+/// ```
+///
+/// # Example values
+///
+/// * `for=192.0.2.43, for=198.51.100.17`
+/// * `for="[2001:db8:cafe::17]:4711";proto=https`
+/// * `for=_gazonk;by=_hidden`
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// # extern crate http;
+/// use headers::{Forwarded, HeaderMapExt};
+///
+/// let mut map = http::HeaderMap::new();
+/// map.insert("forwarded", "for=192.0.2.43;proto=https, for=_gazonk".parse().unwrap());
+/// let forwarded = map.typed_get::<Forwarded>().unwrap();
+///
+/// let client = forwarded.elements().next().unwrap();
+/// assert_eq!(client.for_ip(), Some([192, 0, 2, 43].into()));
+/// assert_eq!(client.proto(), Some("https"));
+///
+/// let proxy = forwarded.elements().nth(1).unwrap();
+/// assert_eq!(proxy.for_node(), Some("_gazonk"));
+/// assert_eq!(proxy.for_ip(), None);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Forwarded(Vec<ForwardedElement>);
+
+/// One element of a `Forwarded` header, added by one proxy.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ForwardedElement {
+    // Names are lowercase, and values are unquoted.
+    pairs: Vec<(String, String)>,
+}
+
+impl Forwarded {
+    /// Iterate the elements, the one closest to the client first.
+    pub fn elements(&self) -> impl Iterator<Item = &ForwardedElement> {
+        self.0.iter()
+    }
+}
+
+impl ForwardedElement {
+    /// Get the value of a parameter, such as `for`, with any quotes
+    /// removed.
+    ///
+    /// Parameter names are compared ignoring ASCII case.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.pairs
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The node the request came from, as sent, such as `192.0.2.43`,
+    /// `[2001:db8::1]:_obf` or `_gazonk`.
+    pub fn for_node(&self) -> Option<&str> {
+        self.get("for")
+    }
+
+    /// The IP address the request came from, without any port.
+    ///
+    /// Returns `None` if there is no `for`, or it is `unknown` or
+    /// obfuscated. An obfuscated port doesn't hide the address.
+    pub fn for_ip(&self) -> Option<IpAddr> {
+        self.for_node().and_then(node_ip)
+    }
+
+    /// The node of the proxy that received the request, as sent.
+    pub fn by_node(&self) -> Option<&str> {
+        self.get("by")
+    }
+
+    /// The `Host` the proxy received the request for.
+    pub fn host(&self) -> Option<&str> {
+        self.get("host")
+    }
+
+    /// The protocol the proxy received the request over, such as `https`.
+    pub fn proto(&self) -> Option<&str> {
+        self.get("proto")
+    }
+}
+
+/// Parse the IP address of a node, ignoring its port.
+fn node_ip(node: &str) -> Option<IpAddr> {
+    if node.starts_with('[') {
+        let end = node.find(']')?;
+        let rest = &node[end + 1..];
+        if !rest.is_empty() && !rest.starts_with(':') {
+            return None;
+        }
+        node[1..end].parse::<Ipv6Addr>().ok().map(IpAddr::V6)
+    } else {
+        let name = node.split(':').next().unwrap_or("");
+        name.parse::<Ipv4Addr>().ok().map(IpAddr::V4)
+    }
+}
+
+/// Split `s` on `sep` outside quoted strings, with the offset of each part.
+///
+/// A quoted string left open is an error at the end of `s`.
+fn split_unquoted(s: &str, sep: u8) -> Result<Vec<(usize, &str)>, ::Error> {
+    let mut parts = Vec::new();
+    let mut in_quotes = false;
+    let mut escaped = false;
+    let mut start = 0;
+    for (i, b) in s.bytes().enumerate() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match b {
+            b'\\' if in_quotes => escaped = true,
+            b'"' => in_quotes = !in_quotes,
+            _ if b == sep && !in_quotes => {
+                parts.push((start, &s[start..i]));
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if in_quotes {
+        return Err(::Error::invalid_at(s.len()));
+    }
+    parts.push((start, &s[start..]));
+    Ok(parts)
+}
+
+/// Parse an element that starts `offset` bytes into the header value.
+fn parse_element(element: &str, offset: usize) -> Result<ForwardedElement, ::Error> {
+    let mut pairs = Vec::new();
+    for (start, pair) in split_unquoted(element, b';')? {
+        let trimmed = pair.trim_start();
+        let error = || ::Error::invalid_at(offset + start + pair.len() - trimmed.len());
+        let pair = trimmed.trim_end();
+        if pair.is_empty() {
+            continue;
+        }
+        let eq = pair.find('=').ok_or_else(error)?;
+        let (name, value) = (&pair[..eq], &pair[eq + 1..]);
+        if !is_token(name) || !(is_token(value) || value.starts_with('"')) {
+            return Err(error());
+        }
+        pairs.push((
+            name.to_ascii_lowercase(),
+            util::unquote(value).ok_or_else(error)?,
+        ));
+    }
+    Ok(ForwardedElement { pairs })
+}
+
+impl ::Header for Forwarded {
+    fn name() -> &'static ::HeaderName {
+        &::http::header::FORWARDED
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        let mut elements = Vec::new();
+        for value in values {
+            let s = value.to_str().map_err(|_| ::Error::invalid())?;
+            for (offset, element) in split_unquoted(s, b',')? {
+                if element.trim().is_empty() {
+                    continue;
+                }
+                elements.push(parse_element(element, offset)?);
+            }
+        }
+
+        if elements.is_empty() {
+            Err(::Error::invalid())
+        } else {
+            Ok(Forwarded(elements))
+        }
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once(util::fmt(self)));
+    }
+}
+
+impl ::NamedHeader for Forwarded {
+    const NAME: ::HeaderName = ::http::header::FORWARDED;
+}
+
+impl fmt::Display for Forwarded {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        util::csv::fmt_comma_delimited(f, self.0.iter())
+    }
+}
+
+impl fmt::Display for ForwardedElement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, (name, value)) in self.pairs.iter().enumerate() {
+            if i > 0 {
+                f.write_str(";")?;
+            }
+            if is_token(value) {
+                write!(f, "{}={}", name, value)?;
+            } else {
+                write!(f, "{}=\"", name)?;
+                for c in value.chars() {
+                    if c == '"' || c == '\\' {
+                        f.write_str("\\")?;
+                    }
+                    write!(f, "{}", c)?;
+                }
+                f.write_str("\"")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::IpAddr;
+
+    use super::super::{test_decode, test_encode};
+    use super::Forwarded;
+
+    #[test]
+    fn concrete() {
+        let forwarded = test_decode::<Forwarded>(&[
+            "for=\"192.0.2.43:47011\";Proto=https;host=a.example",
+            "for=\"[2001:db8:cafe::17]:4711\";by=203.0.113.60",
+        ])
+        .unwrap();
+        let elements = forwarded.elements().collect::<Vec<_>>();
+        assert_eq!(elements.len(), 2);
+
+        assert_eq!(elements[0].for_node(), Some("192.0.2.43:47011"));
+        assert_eq!(elements[0].for_ip(), Some([192, 0, 2, 43].into()));
+        assert_eq!(elements[0].proto(), Some("https"));
+        assert_eq!(elements[0].host(), Some("a.example"));
+
+        let ip = "2001:db8:cafe::17".parse::<IpAddr>().unwrap();
+        assert_eq!(elements[1].for_node(), Some("[2001:db8:cafe::17]:4711"));
+        assert_eq!(elements[1].for_ip(), Some(ip));
+        assert_eq!(elements[1].by_node(), Some("203.0.113.60"));
+        assert_eq!(elements[1].proto(), None);
+
+        let headers = test_encode(forwarded);
+        assert_eq!(
+            headers["forwarded"],
+            "for=\"192.0.2.43:47011\";proto=https;host=a.example, \
+             for=\"[2001:db8:cafe::17]:4711\";by=203.0.113.60"
+        );
+    }
+
+    #[test]
+    fn obfuscated() {
+        let forwarded = test_decode::<Forwarded>(&[
+            "for=\"_gazonk\", for=\"[2001:db8::1]:_obf\";by=_hidden, for=unknown, for=\"192.0.2.1:_p\"",
+        ])
+        .unwrap();
+        let elements = forwarded.elements().collect::<Vec<_>>();
+
+        assert_eq!(elements[0].for_node(), Some("_gazonk"));
+        assert_eq!(elements[0].for_ip(), None);
+
+        let ip = "2001:db8::1".parse::<IpAddr>().unwrap();
+        assert_eq!(elements[1].for_node(), Some("[2001:db8::1]:_obf"));
+        assert_eq!(elements[1].for_ip(), Some(ip));
+        assert_eq!(elements[1].by_node(), Some("_hidden"));
+
+        assert_eq!(elements[2].for_node(), Some("unknown"));
+        assert_eq!(elements[2].for_ip(), None);
+
+        assert_eq!(elements[3].for_ip(), Some([192, 0, 2, 1].into()));
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<Forwarded>(&[""]), None);
+        assert_eq!(test_decode::<Forwarded>(&["for"]), None);
+        assert_eq!(test_decode::<Forwarded>(&["for=[::1]"]), None);
+        assert_eq!(test_decode::<Forwarded>(&["for=192.0.2.43:80"]), None);
+        assert_eq!(test_decode::<Forwarded>(&["for=\"unterminated"]), None);
+    }
+
+    #[test]
+    fn invalid_offset() {
+        let offset = |s: &'static str| {
+            let value = ::HeaderValue::from_static(s);
+            <Forwarded as ::Header>::decode(&mut ::std::iter::once(&value))
+                .unwrap_err()
+                .offset()
+        };
+        assert_eq!(offset("for"), Some(0));
+        assert_eq!(offset("for=a, proto=https; host"), Some(20));
+        assert_eq!(offset("for=a;  by=[::1]"), Some(8));
+        assert_eq!(offset("for=\"unterminated"), Some(17));
+        assert_eq!(offset(""), None);
+    }
+}
@@ -1,9 +1,18 @@
 use std::convert::TryFrom;
 use std::fmt;
+use std::net::Ipv6Addr;
 
 use http::uri::Authority;
 
+use util::TryFromValues;
+use HeaderValue;
+
 /// The `Host` header.
+///
+/// Decoding is strict, to guard against host header injection: the value
+/// must be just a host and optional port, with no whitespace, control
+/// characters, userinfo or lists of hosts. Several `Host` values are only
+/// accepted if they are all the same.
 #[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd)]
 pub struct Host(Authority);
 
@@ -17,6 +26,53 @@ impl Host {
     pub fn port(&self) -> Option<u16> {
         self.0.port_u16()
     }
+
+    /// Check if the hostname is one of `allowed`, such as to refuse
+    /// requests for hosts a virtual host doesn't serve.
+    ///
+    /// The port is ignored, and hostnames are compared ignoring ASCII case
+    /// and a trailing dot. IPv6 addresses may be listed with or without
+    /// brackets, and match however they are written.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate headers;
+    /// # extern crate http;
+    /// use headers::{HeaderMapExt, Host};
+    ///
+    /// let mut map = http::HeaderMap::new();
+    /// map.insert("host", "WWW.Example.com.:8443".parse().unwrap());
+    /// let host = map.typed_get::<Host>().unwrap();
+    ///
+    /// assert!(host.matches_any(&["example.com", "www.example.com"]));
+    /// assert!(!host.matches_any(&["example.net"]));
+    /// ```
+    pub fn matches_any(&self, allowed: &[&str]) -> bool {
+        let hostname = self.hostname();
+        allowed.iter().any(|host| same_host(hostname, host))
+    }
+}
+
+fn same_host(a: &str, b: &str) -> bool {
+    fn unbracket(host: &str) -> &str {
+        if host.starts_with('[') && host.ends_with(']') {
+            &host[1..host.len() - 1]
+        } else {
+            host
+        }
+    }
+
+    let (a, b) = (unbracket(a), unbracket(b));
+    if let (Ok(a), Ok(b)) = (a.parse::<Ipv6Addr>(), b.parse::<Ipv6Addr>()) {
+        return a == b;
+    }
+
+    let (a, b) = (
+        a.strip_suffix('.').unwrap_or(a),
+        b.strip_suffix('.').unwrap_or(b),
+    );
+    !a.is_empty() && a.eq_ignore_ascii_case(b)
 }
 
 impl ::Header for Host {
@@ -25,12 +81,7 @@ impl ::Header for Host {
     }
 
     fn decode<'i, I: Iterator<Item = &'i ::HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
-        values
-            .next()
-            .cloned()
-            .and_then(|val| Authority::try_from(val.as_bytes()).ok())
-            .map(Host)
-            .ok_or_else(::Error::invalid)
+        Host::try_from_values(values)
     }
 
     fn encode<E: Extend<::HeaderValue>>(&self, values: &mut E) {
@@ -41,6 +92,36 @@ impl ::Header for Host {
     }
 }
 
+impl ::NamedHeader for Host {
+    const NAME: ::HeaderName = ::http::header::HOST;
+}
+
+impl TryFromValues for Host {
+    fn try_from_values<'i, I>(values: &mut I) -> Result<Self, ::Error>
+    where
+        I: Iterator<Item = &'i HeaderValue>,
+    {
+        let first = values.next().ok_or_else(::Error::invalid)?;
+        if values.any(|val| val != first) {
+            return Err(::Error::invalid());
+        }
+
+        let bytes = first.as_bytes();
+        let valid = bytes
+            .iter()
+            .all(|&b| (b'\x21'..=b'\x7e').contains(&b) && b != b'@' && b != b',');
+        if !valid {
+            return Err(::Error::invalid());
+        }
+
+        Authority::try_from(bytes)
+            .ok()
+            .filter(|auth| !auth.host().is_empty())
+            .map(Host)
+            .ok_or_else(::Error::invalid)
+    }
+}
+
 impl From<Authority> for Host {
     fn from(auth: Authority) -> Host {
         Host(auth)
@@ -52,3 +133,61 @@ impl fmt::Display for Host {
         fmt::Display::fmt(&self.0, f)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_decode;
+    use super::*;
+
+    #[test]
+    fn decode() {
+        let host = test_decode::<Host>(&["example.com:8080"]).unwrap();
+        assert_eq!(host.hostname(), "example.com");
+        assert_eq!(host.port(), Some(8080));
+
+        let host = test_decode::<Host>(&["[::1]", "[::1]"]).unwrap();
+        assert_eq!(host.hostname(), "[::1]");
+    }
+
+    #[test]
+    fn decode_rejects_injection() {
+        // `HeaderValue` itself refuses CR and LF, so `evil\r\nHost: x` can't
+        // get this far; everything else it lets through is refused here.
+        assert!("evil\r\nHost: x".parse::<HeaderValue>().is_err());
+
+        assert_eq!(test_decode::<Host>(&["evil Host: x"]), None);
+        assert_eq!(test_decode::<Host>(&["evil\tx"]), None);
+        assert_eq!(test_decode::<Host>(&[" example.com"]), None);
+        assert_eq!(test_decode::<Host>(&["a.example, b.example"]), None);
+        assert_eq!(test_decode::<Host>(&["user@example.com"]), None);
+        assert_eq!(test_decode::<Host>(&[""]), None);
+        assert_eq!(test_decode::<Host>(&[":80"]), None);
+
+        let mut map = ::http::HeaderMap::new();
+        map.insert("host", HeaderValue::from_bytes(b"evil\x80x").unwrap());
+        assert_eq!(::HeaderMapExt::typed_get::<Host>(&map), None);
+    }
+
+    #[test]
+    fn matches_any() {
+        let host = test_decode::<Host>(&["Example.COM:8080"]).unwrap();
+        assert!(host.matches_any(&["example.com"]));
+        assert!(host.matches_any(&["other.example", "EXAMPLE.com."]));
+        assert!(!host.matches_any(&["www.example.com", "example.co"]));
+        assert!(!host.matches_any(&[]));
+
+        let host = test_decode::<Host>(&["example.com."]).unwrap();
+        assert!(host.matches_any(&["example.com"]));
+        assert!(!host.matches_any(&[""]));
+
+        let host = test_decode::<Host>(&["[0:0::1]:443"]).unwrap();
+        assert!(host.matches_any(&["::1"]));
+        assert!(host.matches_any(&["[::1]"]));
+        assert!(!host.matches_any(&["::2", "127.0.0.1"]));
+    }
+
+    #[test]
+    fn decode_rejects_differing_duplicates() {
+        assert_eq!(test_decode::<Host>(&["a.example", "b.example"]), None);
+    }
+}
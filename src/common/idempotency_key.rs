@@ -0,0 +1,129 @@
+use std::fmt;
+use std::str::FromStr;
+
+use util::{HeaderValueString, IterExt};
+use {HeaderName, HeaderValue};
+
+const IDEMPOTENCY_KEY: HeaderName = HeaderName::from_static("idempotency-key");
+
+/// The longest key that will be accepted.
+const MAX_LEN: usize = 255;
+
+/// `Idempotency-Key` header, defined in
+/// [draft-ietf-httpapi-idempotency-key-header](https://datatracker.ietf.org/doc/draft-ietf-httpapi-idempotency-key-header/)
+///
+/// The `Idempotency-Key` request header field carries a unique key chosen
+/// by the client, so a server can recognize retries of the same `POST` or
+/// `PATCH` request and avoid performing it more than once.
+///
+/// The key is treated as opaque, but must be between 1 and 255 visible
+/// ASCII characters.
+///
+/// # Example values
+///
+/// * `"8e03978e-40d5-43e8-bc93-6894a57f9324"`
+/// * `payment-2024-0001`
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// use headers::IdempotencyKey;
+///
+/// let key = IdempotencyKey::new("8e03978e-40d5-43e8-bc93-6894a57f9324").unwrap();
+/// assert_eq!(key.as_str(), "8e03978e-40d5-43e8-bc93-6894a57f9324");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct IdempotencyKey(HeaderValueString);
+
+error_type!(InvalidIdempotencyKey);
+
+impl IdempotencyKey {
+    /// Try to create an `Idempotency-Key` from a string.
+    pub fn new(key: &str) -> Result<IdempotencyKey, InvalidIdempotencyKey> {
+        key.parse()
+    }
+
+    /// View the key as a `&str`.
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+fn is_valid(key: &str) -> bool {
+    !key.is_empty() && key.len() <= MAX_LEN && key.bytes().all(|b| (b'\x21'..=b'\x7e').contains(&b))
+}
+
+impl ::Header for IdempotencyKey {
+    fn name() -> &'static HeaderName {
+        static NAME: HeaderName = IDEMPOTENCY_KEY;
+        &NAME
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        values
+            .just_one()
+            .and_then(|val| HeaderValueString::from_val(val).ok())
+            .filter(|s| is_valid(s.as_str()))
+            .map(IdempotencyKey)
+            .ok_or_else(::Error::invalid)
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once((&self.0).into()));
+    }
+}
+
+impl ::NamedHeader for IdempotencyKey {
+    const NAME: ::HeaderName = IDEMPOTENCY_KEY;
+}
+
+impl FromStr for IdempotencyKey {
+    type Err = InvalidIdempotencyKey;
+
+    fn from_str(src: &str) -> Result<Self, Self::Err> {
+        if !is_valid(src) {
+            return Err(InvalidIdempotencyKey { _inner: () });
+        }
+        HeaderValueString::from_str(src)
+            .map(IdempotencyKey)
+            .map_err(|_| InvalidIdempotencyKey { _inner: () })
+    }
+}
+
+impl fmt::Display for IdempotencyKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::IdempotencyKey;
+
+    #[test]
+    fn round_trip() {
+        let key = test_decode::<IdempotencyKey>(&["\"8e03978e-40d5\""]).unwrap();
+        assert_eq!(key.as_str(), "\"8e03978e-40d5\"");
+
+        let headers = test_encode(key);
+        assert_eq!(headers["idempotency-key"], "\"8e03978e-40d5\"");
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<IdempotencyKey>(&[""]), None);
+        assert_eq!(test_decode::<IdempotencyKey>(&["with space"]), None);
+        assert_eq!(test_decode::<IdempotencyKey>(&["a", "b"]), None);
+        assert_eq!(test_decode::<IdempotencyKey>(&[&"k".repeat(256)]), None);
+    }
+
+    #[test]
+    fn new() {
+        assert!(IdempotencyKey::new(&"k".repeat(255)).is_ok());
+        assert!(IdempotencyKey::new("").is_err());
+        assert!(IdempotencyKey::new("tab\tkey").is_err());
+        assert!(IdempotencyKey::new("caf\u{e9}").is_err());
+    }
+}
@@ -56,6 +56,8 @@ impl From<IfModifiedSince> for SystemTime {
     }
 }
 
+date_conversions!(IfModifiedSince);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2,7 +2,7 @@ use std::time::SystemTime;
 
 use super::{ETag, LastModified};
 use util::{EntityTag, HttpDate};
-use HeaderValue;
+use {DecodeOptions, HeaderValue};
 
 /// `If-Range` header, defined in [RFC7233](http://tools.ietf.org/html/rfc7233#section-3.2)
 ///
@@ -81,6 +81,13 @@ enum IfRange_ {
 
 impl ::util::TryFromValues for IfRange_ {
     fn try_from_values<'i, I>(values: &mut I) -> Result<Self, ::Error>
+    where
+        I: Iterator<Item = &'i HeaderValue>,
+    {
+        Self::try_from_values_with(values, &DecodeOptions::default())
+    }
+
+    fn try_from_values_with<'i, I>(values: &mut I, options: &DecodeOptions) -> Result<Self, ::Error>
     where
         I: Iterator<Item = &'i HeaderValue>,
     {
@@ -91,7 +98,7 @@ impl ::util::TryFromValues for IfRange_ {
                     return Some(IfRange_::EntityTag(tag));
                 }
 
-                let date = HttpDate::from_val(val)?;
+                let date = HttpDate::from_val_with(val, options)?;
                 Some(IfRange_::Date(date))
             })
             .ok_or_else(::Error::invalid)
@@ -133,4 +140,17 @@ mod tests {
         let etag = ETag::from_static("W/\"xyzzy\"");
         assert!(if_range.is_modified(Some(&etag), None));
     }
+
+    #[test]
+    fn decode_strict() {
+        let decode = |s: &'static str| {
+            let value = HeaderValue::from_static(s);
+            let strict = DecodeOptions::strict();
+            <IfRange as ::Header>::decode_with(&mut ::std::iter::once(&value), &strict).ok()
+        };
+        assert!(decode("\"xyzzy\"").is_some());
+        assert!(decode("Sat, 29 Oct 1994 19:43:31 GMT").is_some());
+        assert_eq!(decode("Saturday, 29-Oct-94 19:43:31 GMT"), None);
+        assert_eq!(decode("Sat Oct 29 19:43:31 1994"), None);
+    }
 }
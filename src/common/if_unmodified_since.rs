@@ -57,6 +57,8 @@ impl From<IfUnmodifiedSince> for SystemTime {
     }
 }
 
+date_conversions!(IfUnmodifiedSince);
+
 #[cfg(test)]
 mod tests {
     use super::*;
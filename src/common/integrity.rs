@@ -0,0 +1,198 @@
+use std::fmt;
+use std::str::FromStr;
+
+use base64::engine::general_purpose::STANDARD as ENGINE;
+use base64::Engine;
+
+use super::DigestAlgorithm;
+
+/// Integrity metadata, defined in
+/// [Subresource Integrity](https://www.w3.org/TR/SRI/#the-integrity-attribute)
+///
+/// A whitespace-separated list of hashes of a resource, each prefixed with
+/// the algorithm that made it, as used by the HTML `integrity` attribute
+/// and by some headers. A resource matches if it matches any of the hashes
+/// made by the strongest algorithm in the list.
+///
+/// This is not a header on its own, so it is parsed and formatted with
+/// `FromStr` and `Display`. Hashes using an unknown algorithm, and any
+/// options after a `?`, are ignored.
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// use headers::{DigestAlgorithm, Integrity};
+///
+/// let integrity = "sha384-oqVuAfXRKap7fdgcCY5uykM6+R9GqQ8K/uxy9rx7HNQlGYl1kPzQho1wx4JwY8wC"
+///     .parse::<Integrity>()
+///     .unwrap();
+/// let (alg, hash) = integrity.iter().next().unwrap();
+/// assert_eq!(alg, DigestAlgorithm::Sha384);
+/// assert_eq!(hash.len(), 48);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Integrity(Vec<(DigestAlgorithm, Vec<u8>)>);
+
+error_type!(InvalidIntegrity);
+
+impl Integrity {
+    /// Create an `Integrity` with a single hash.
+    ///
+    /// Fails if the hash has the wrong length for the algorithm.
+    pub fn new(alg: DigestAlgorithm, hash: &[u8]) -> Result<Integrity, InvalidIntegrity> {
+        Integrity(Vec::new()).with_hash(alg, hash)
+    }
+
+    /// Create an `Integrity` with the hash of `body`.
+    #[cfg(feature = "digest")]
+    pub fn from_body(alg: DigestAlgorithm, body: &[u8]) -> Integrity {
+        Integrity(vec![(alg, alg.hash(body))])
+    }
+
+    /// Add another hash.
+    ///
+    /// Fails if the hash has the wrong length for the algorithm.
+    pub fn with_hash(
+        mut self,
+        alg: DigestAlgorithm,
+        hash: &[u8],
+    ) -> Result<Self, InvalidIntegrity> {
+        if hash.len() != alg.output_len() {
+            return Err(InvalidIntegrity { _inner: () });
+        }
+        self.0.push((alg, hash.to_vec()));
+        Ok(self)
+    }
+
+    /// Iterate the hashes, with the algorithm that made each.
+    pub fn iter(&self) -> impl Iterator<Item = (DigestAlgorithm, &[u8])> {
+        self.0.iter().map(|&(alg, ref hash)| (alg, &hash[..]))
+    }
+
+    /// The strongest algorithm in the list.
+    pub fn strongest(&self) -> DigestAlgorithm {
+        self.0
+            .iter()
+            .map(|&(alg, _)| alg)
+            .max()
+            .expect("Integrity is never empty")
+    }
+
+    /// Check if `body` matches any hash made by the strongest algorithm.
+    ///
+    /// Hashes made by weaker algorithms are not checked, so a downgrade
+    /// can't be used to pass a body off as another.
+    #[cfg(feature = "digest")]
+    pub fn verify(&self, body: &[u8]) -> bool {
+        let strongest = self.strongest();
+        let actual = strongest.hash(body);
+        self.0
+            .iter()
+            .filter(|&&(alg, _)| alg == strongest)
+            .any(|(_, hash)| *hash == actual)
+    }
+}
+
+impl FromStr for Integrity {
+    type Err = InvalidIntegrity;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut hashes = Vec::new();
+        for item in s.split_ascii_whitespace() {
+            let mut parts = item.splitn(2, '-');
+            let alg = match parts.next().and_then(DigestAlgorithm::from_sri_name) {
+                Some(alg) => alg,
+                None => continue,
+            };
+            let value = parts.next().ok_or(InvalidIntegrity { _inner: () })?;
+            let value = value.split('?').next().unwrap_or("");
+            let hash = ENGINE
+                .decode(value)
+                .map_err(|_| InvalidIntegrity { _inner: () })?;
+            if hash.len() != alg.output_len() {
+                return Err(InvalidIntegrity { _inner: () });
+            }
+            hashes.push((alg, hash));
+        }
+
+        if hashes.is_empty() {
+            Err(InvalidIntegrity { _inner: () })
+        } else {
+            Ok(Integrity(hashes))
+        }
+    }
+}
+
+impl fmt::Display for Integrity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, &(alg, ref hash)) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{}-{}", alg.sri_name(), ENGINE.encode(hash))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The example from the SRI spec, a hash of `alert('Hello, world.');`.
+    const SHA384: &str = "sha384-H8BRh8j48O9oYatfu5AZzq6A9RINhZO5H16dQZngK7T62em8MUt1FLm52t+eX6xO";
+
+    #[test]
+    fn parse_sri() {
+        let s = format!(
+            "{} sha256-47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU=?ct=text/plain md5-x",
+            SHA384
+        );
+        let integrity = s.parse::<Integrity>().unwrap();
+        let algs = integrity.iter().map(|(alg, _)| alg).collect::<Vec<_>>();
+        assert_eq!(algs, [DigestAlgorithm::Sha384, DigestAlgorithm::Sha256]);
+        assert_eq!(integrity.strongest(), DigestAlgorithm::Sha384);
+
+        assert_eq!(
+            integrity.to_string(),
+            format!(
+                "{} sha256-47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU=",
+                SHA384
+            )
+        );
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert!("".parse::<Integrity>().is_err());
+        assert!("md5-deadbeef".parse::<Integrity>().is_err());
+        assert!("sha256-not base64".parse::<Integrity>().is_err());
+        assert!("sha256-AAAA".parse::<Integrity>().is_err());
+        assert!("sha256".parse::<Integrity>().is_err());
+    }
+
+    #[test]
+    fn new_checks_length() {
+        assert!(Integrity::new(DigestAlgorithm::Sha256, &[0; 32]).is_ok());
+        assert!(Integrity::new(DigestAlgorithm::Sha256, &[0; 48]).is_err());
+    }
+
+    #[cfg(feature = "digest")]
+    #[test]
+    fn verify() {
+        let integrity = SHA384.parse::<Integrity>().unwrap();
+        assert!(integrity.verify(b"alert('Hello, world.');"));
+        assert!(!integrity.verify(b"alert('Hello, world!');"));
+
+        let body = b"body";
+        let integrity = Integrity::from_body(DigestAlgorithm::Sha256, body);
+        assert!(integrity.verify(body));
+
+        // Only the strongest algorithm is checked.
+        let integrity = integrity
+            .with_hash(DigestAlgorithm::Sha512, &[0; 64])
+            .unwrap();
+        assert!(!integrity.verify(body));
+    }
+}
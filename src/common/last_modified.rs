@@ -38,6 +38,14 @@ derive_header! {
     name: LAST_MODIFIED
 }
 
+impl LastModified {
+    /// Create a `LastModified` for the current time, read from the system
+    /// clock.
+    pub fn now() -> LastModified {
+        LastModified::from(SystemTime::now())
+    }
+}
+
 impl From<SystemTime> for LastModified {
     fn from(time: SystemTime) -> LastModified {
         LastModified(time.into())
@@ -49,3 +57,19 @@ impl From<LastModified> for SystemTime {
         date.0.into()
     }
 }
+
+date_conversions!(LastModified);
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::LastModified;
+
+    #[test]
+    fn now() {
+        let modified = LastModified::now();
+        let headers = test_encode(modified);
+        let value = headers["last-modified"].to_str().unwrap();
+        assert_eq!(test_decode::<LastModified>(&[value]), Some(modified));
+    }
+}
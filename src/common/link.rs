@@ -0,0 +1,441 @@
+//! Link header and types.
+
+use std::fmt;
+use std::iter::FromIterator;
+
+use util::{self, is_token};
+use HeaderValue;
+
+/// `Link` header, defined in
+/// [RFC8288](https://tools.ietf.org/html/rfc8288#section-3)
+///
+/// The `Link` header field relates the resource to other resources, such
+/// as the next page of results. In a `103 Early Hints` response, it lists
+/// resources the client can start fetching, or origins it can connect to,
+/// before the final response arrives.
+///
+/// # ABNF
+///
+/// ```text
+/// Link       = #link-value
+/// link-value = "<" URI-Reference ">" *( OWS ";" OWS link-param )
+/// link-param = token BWS [ "=" BWS ( token / quoted-string ) ]
+/// ```
+///
+/// # Example values
+///
+/// * `</style.css>; rel=preload; as=style`
+/// * `<https://cdn.example>; rel=preconnect`
+/// * `<https://a.example/page/2>; rel="next"; title="Page 2"`
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// # extern crate http;
+/// use headers::{HeaderMapExt, Link};
+///
+/// let link = vec![
+///     Link::preload("/style.css", "style").unwrap(),
+///     Link::preconnect("https://cdn.example").unwrap(),
+/// ]
+/// .into_iter()
+/// .collect::<Link>();
+///
+/// let mut early_hints = http::HeaderMap::new();
+/// early_hints.typed_insert(link);
+/// assert_eq!(
+///     early_hints["link"],
+///     "</style.css>; rel=preload; as=style, <https://cdn.example>; rel=preconnect"
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Link(Vec<LinkValue>);
+
+/// One link in a `Link` header, a target URI and its parameters.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LinkValue {
+    uri: String,
+    // Names are lowercase, and values are unquoted.
+    params: Vec<(String, String)>,
+}
+
+error_type!(InvalidLink);
+
+impl Link {
+    /// A link to preload `uri` as the destination `as_`, such as `script`,
+    /// `style` or `font`.
+    ///
+    /// Fails if `uri` isn't valid in a link, or `as_` isn't a token.
+    pub fn preload(uri: &str, as_: &str) -> Result<LinkValue, InvalidLink> {
+        if !is_token(as_) {
+            return Err(InvalidLink { _inner: () });
+        }
+        LinkValue::new(uri)?
+            .with_param("rel", "preload")?
+            .with_param("as", as_)
+    }
+
+    /// A link to preload `uri` as a JavaScript module.
+    pub fn modulepreload(uri: &str) -> Result<LinkValue, InvalidLink> {
+        LinkValue::new(uri)?.with_param("rel", "modulepreload")
+    }
+
+    /// A link to connect to the origin `uri` ahead of fetching from it.
+    pub fn preconnect(uri: &str) -> Result<LinkValue, InvalidLink> {
+        LinkValue::new(uri)?.with_param("rel", "preconnect")
+    }
+
+    /// Iterate the links, in the order they were sent.
+    pub fn iter(&self) -> impl Iterator<Item = &LinkValue> {
+        self.0.iter()
+    }
+}
+
+impl LinkValue {
+    /// Create a link to `uri`, with no parameters.
+    ///
+    /// Fails if `uri` contains `>`, whitespace or control characters, so
+    /// it can't end the link early.
+    pub fn new(uri: &str) -> Result<LinkValue, InvalidLink> {
+        if !is_uri_reference(uri) {
+            return Err(InvalidLink { _inner: () });
+        }
+        Ok(LinkValue {
+            uri: uri.to_owned(),
+            params: Vec::new(),
+        })
+    }
+
+    /// Add a parameter, replacing any existing one with the same name.
+    ///
+    /// The value is quoted when sent, unless it is a token.
+    pub fn with_param(mut self, name: &str, value: &str) -> Result<Self, InvalidLink> {
+        if !is_token(name) || !value.bytes().all(is_quotable) {
+            return Err(InvalidLink { _inner: () });
+        }
+
+        let name = name.to_ascii_lowercase();
+        let value = value.to_owned();
+        match self.params.iter_mut().find(|(n, _)| *n == name) {
+            Some(existing) => existing.1 = value,
+            None => self.params.push((name, value)),
+        }
+        Ok(self)
+    }
+
+    /// The target URI, as sent.
+    pub fn uri(&self) -> &str {
+        &self.uri
+    }
+
+    /// The `rel` parameter, the relation types separated by spaces.
+    pub fn rel(&self) -> Option<&str> {
+        self.param("rel")
+    }
+
+    /// Get the value of a parameter, with any quotes removed.
+    ///
+    /// Parameter names are compared ignoring ASCII case, and a parameter
+    /// without a value is `""`.
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+impl FromIterator<LinkValue> for Link {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = LinkValue>,
+    {
+        Link(iter.into_iter().collect())
+    }
+}
+
+impl From<LinkValue> for Link {
+    fn from(value: LinkValue) -> Link {
+        Link(vec![value])
+    }
+}
+
+fn is_uri_reference(s: &str) -> bool {
+    s.bytes()
+        .all(|b| (b'\x21'..=b'\x7e').contains(&b) && b != b'<' && b != b'>')
+}
+
+fn is_quotable(b: u8) -> bool {
+    b == b'\t' || (b' '..=b'\x7e').contains(&b)
+}
+
+struct Parser<'a> {
+    s: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn parse_into(s: &'a str, links: &mut Vec<LinkValue>) -> Result<(), ::Error> {
+        let mut parser = Parser {
+            s: s.as_bytes(),
+            pos: 0,
+        };
+        loop {
+            parser.skip_while(|b| b == b' ' || b == b'\t' || b == b',');
+            if parser.pos == parser.s.len() {
+                return Ok(());
+            }
+            links.push(parser.link()?);
+            parser.skip_ows();
+            match parser.peek() {
+                None | Some(b',') => {}
+                Some(_) => return Err(parser.error()),
+            }
+        }
+    }
+
+    fn link(&mut self) -> Result<LinkValue, ::Error> {
+        self.expect(b'<')?;
+        let start = self.pos;
+        let uri = self.take_while(|b| b != b'>');
+        self.expect(b'>')?;
+        let mut link = LinkValue::new(uri).map_err(|_| ::Error::invalid_at(start))?;
+
+        loop {
+            self.skip_ows();
+            if self.peek() != Some(b';') {
+                return Ok(link);
+            }
+            self.pos += 1;
+            self.skip_ows();
+            let start = self.pos;
+            let name = self.take_while(|b| b != b'=' && b != b';' && b != b',' && b != b' ');
+            self.skip_ows();
+            let value = if self.peek() == Some(b'=') {
+                self.pos += 1;
+                self.skip_ows();
+                self.value()?
+            } else {
+                String::new()
+            };
+            // The first occurrence of a parameter is the one that counts.
+            if link.param(name).is_none() {
+                link = link
+                    .with_param(name, &value)
+                    .map_err(|_| ::Error::invalid_at(start))?;
+            }
+        }
+    }
+
+    fn value(&mut self) -> Result<String, ::Error> {
+        let start = self.pos;
+        if self.peek() != Some(b'"') {
+            let token = self.take_while(|b| b != b';' && b != b',' && b != b' ' && b != b'\t');
+            return if is_token(token) {
+                Ok(token.to_owned())
+            } else {
+                Err(::Error::invalid_at(start))
+            };
+        }
+
+        self.pos += 1;
+        loop {
+            match self.peek() {
+                Some(b'"') => break,
+                Some(b'\\') if self.pos + 1 < self.s.len() => self.pos += 2,
+                Some(b) if b != b'\\' => self.pos += 1,
+                _ => return Err(self.error()),
+            }
+        }
+        self.pos += 1;
+        ::std::str::from_utf8(&self.s[start..self.pos])
+            .ok()
+            .and_then(util::unquote)
+            .ok_or_else(|| ::Error::invalid_at(start))
+    }
+
+    fn expect(&mut self, b: u8) -> Result<(), ::Error> {
+        if self.peek() == Some(b) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.error())
+        }
+    }
+
+    /// A syntax error at the current position.
+    fn error(&self) -> ::Error {
+        ::Error::invalid_at(self.pos)
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.s.get(self.pos).cloned()
+    }
+
+    fn skip_ows(&mut self) {
+        self.skip_while(|b| b == b' ' || b == b'\t');
+    }
+
+    fn skip_while(&mut self, f: impl Fn(u8) -> bool) {
+        while self.peek().map_or(false, &f) {
+            self.pos += 1;
+        }
+    }
+
+    fn take_while(&mut self, f: impl Fn(u8) -> bool) -> &'a str {
+        let start = self.pos;
+        self.skip_while(f);
+        // The input is a `str`, and every stop is at an ASCII byte.
+        ::std::str::from_utf8(&self.s[start..self.pos]).unwrap_or("")
+    }
+}
+
+impl ::Header for Link {
+    fn name() -> &'static ::HeaderName {
+        &::http::header::LINK
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        let mut links = Vec::new();
+        for value in values {
+            let s = value.to_str().map_err(|_| ::Error::invalid())?;
+            Parser::parse_into(s, &mut links)?;
+        }
+
+        if links.is_empty() {
+            Err(::Error::invalid())
+        } else {
+            Ok(Link(links))
+        }
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once(util::fmt(self)));
+    }
+}
+
+impl ::NamedHeader for Link {
+    const NAME: ::HeaderName = ::http::header::LINK;
+}
+
+impl fmt::Display for Link {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        util::csv::fmt_comma_delimited(f, self.0.iter())
+    }
+}
+
+impl fmt::Display for LinkValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<{}>", self.uri)?;
+        for (name, value) in &self.params {
+            if value.is_empty() {
+                write!(f, "; {}", name)?;
+            } else if is_token(value) {
+                write!(f, "; {}={}", name, value)?;
+            } else {
+                write!(f, "; {}=\"", name)?;
+                for c in value.chars() {
+                    if c == '"' || c == '\\' {
+                        f.write_str("\\")?;
+                    }
+                    write!(f, "{}", c)?;
+                }
+                f.write_str("\"")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn early_hints() {
+        let link = vec![
+            Link::preload("/style.css", "style").unwrap(),
+            Link::preload("/font.woff2", "font")
+                .and_then(|l| l.with_param("crossorigin", ""))
+                .unwrap(),
+            Link::modulepreload("/app.js").unwrap(),
+            Link::preconnect("https://cdn.example").unwrap(),
+        ]
+        .into_iter()
+        .collect::<Link>();
+
+        let headers = test_encode(link.clone());
+        assert_eq!(
+            headers["link"],
+            "</style.css>; rel=preload; as=style, \
+             </font.woff2>; rel=preload; as=font; crossorigin, \
+             </app.js>; rel=modulepreload, \
+             <https://cdn.example>; rel=preconnect"
+        );
+
+        let decoded = test_decode::<Link>(&[headers["link"].to_str().unwrap()]).unwrap();
+        assert_eq!(decoded, link);
+    }
+
+    #[test]
+    fn quoting() {
+        let link = Link::preload("/a.js", "script")
+            .and_then(|l| l.with_param("title", "say \"hi\", then; leave"))
+            .unwrap();
+        let headers = test_encode(Link::from(link));
+        assert_eq!(
+            headers["link"],
+            "</a.js>; rel=preload; as=script; title=\"say \\\"hi\\\", then; leave\""
+        );
+
+        assert!(Link::preload("/a.js>; rel=stylesheet", "script").is_err());
+        assert!(Link::preload("/a.js", "script\"").is_err());
+        assert!(Link::preconnect("https://a.example/ b").is_err());
+        assert!(LinkValue::new("/").unwrap().with_param("a b", "c").is_err());
+    }
+
+    #[test]
+    fn decode() {
+        let link = test_decode::<Link>(&[
+            "<https://a.example/search?q=a,b>; REL=\"next\"; title=\"Page 2\"; rel=prev",
+            "</s.css>;rel=preload;as=style",
+        ])
+        .unwrap();
+        let links = link.iter().collect::<Vec<_>>();
+        assert_eq!(links.len(), 2);
+        assert_eq!(links[0].uri(), "https://a.example/search?q=a,b");
+        assert_eq!(links[0].rel(), Some("next"));
+        assert_eq!(links[0].param("Title"), Some("Page 2"));
+        assert_eq!(links[1].uri(), "/s.css");
+        assert_eq!(links[1].param("as"), Some("style"));
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<Link>(&[""]), None);
+        assert_eq!(test_decode::<Link>(&["/no-brackets"]), None);
+        assert_eq!(test_decode::<Link>(&["</unterminated"]), None);
+        assert_eq!(test_decode::<Link>(&["</a>; rel=\"unterminated"]), None);
+        assert_eq!(test_decode::<Link>(&["</a> junk"]), None);
+        assert_eq!(test_decode::<Link>(&["</a>; rel=a b"]), None);
+    }
+
+    #[test]
+    fn invalid_offset() {
+        let offset = |s: &'static str| {
+            let value = HeaderValue::from_static(s);
+            <Link as ::Header>::decode(&mut ::std::iter::once(&value))
+                .unwrap_err()
+                .offset()
+        };
+        assert_eq!(offset("/no-brackets"), Some(0));
+        assert_eq!(offset("</unterminated"), Some(14));
+        assert_eq!(offset("</a>; rel=preload, </b> junk"), Some(24));
+        assert_eq!(offset("</a>; rel=\"unterminated"), Some(23));
+        assert_eq!(offset("</a>; rel=a/b"), Some(10));
+        assert_eq!(offset("</a>; a b=c"), Some(8));
+        assert_eq!(offset(""), None);
+    }
+}
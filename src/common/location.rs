@@ -1,3 +1,6 @@
+use http::Uri;
+
+use util;
 use HeaderValue;
 
 /// `Location` header, defined in
@@ -23,11 +26,38 @@ use HeaderValue;
 #[derive(Clone, Debug, PartialEq)]
 pub struct Location(HeaderValue);
 
+error_type!(InvalidLocation);
+
 derive_header! {
     Location(_),
     name: LOCATION
 }
 
+impl Location {
+    /// Create a `Location` from a URI reference.
+    ///
+    /// Fails if `uri` is empty, or has anything but visible ASCII, which
+    /// URI references never need. So a location built from user input
+    /// can't end the header early with a CR or LF.
+    pub fn new(uri: &str) -> Result<Location, InvalidLocation> {
+        if uri.is_empty() || !uri.bytes().all(|b| (b'!'..=b'~').contains(&b)) {
+            return Err(InvalidLocation { _inner: () });
+        }
+        HeaderValue::from_str(uri)
+            .map(Location)
+            .map_err(|_| InvalidLocation { _inner: () })
+    }
+
+    /// Resolve this location against the URI of the request, such as when
+    /// following a redirect to a relative reference.
+    ///
+    /// Returns `None` if `base` is not absolute, or the location can't be
+    /// resolved into a `Uri`.
+    pub fn try_resolve(&self, base: &Uri) -> Option<Uri> {
+        util::uri::resolve(base, self.0.to_str().ok()?)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::super::test_decode;
@@ -48,4 +78,23 @@ mod tests {
 
         assert_eq!(loc, Location(HeaderValue::from_static(s)));
     }
+
+    #[test]
+    fn new() {
+        let loc = Location::new("/People.html#tim").unwrap();
+        assert_eq!(loc, Location(HeaderValue::from_static("/People.html#tim")));
+
+        assert!(Location::new("/a\r\nSet-Cookie: evil").is_err());
+        assert!(Location::new("/a b").is_err());
+        assert!(Location::new("").is_err());
+    }
+
+    #[test]
+    fn try_resolve() {
+        let base = "https://example.com/a/b?page=2".parse::<Uri>().unwrap();
+        let loc = test_decode::<Location>(&["../c#top"]).unwrap();
+        assert_eq!(loc.try_resolve(&base).unwrap(), "https://example.com/c");
+
+        assert_eq!(loc.try_resolve(&"/a/b".parse().unwrap()), None);
+    }
 }
@@ -6,11 +6,13 @@
 //! strongly-typed theme, the [mime](https://docs.rs/mime) crate
 //! is used, such as `ContentType(pub Mime)`.
 
+pub use self::accept_ch::AcceptCH;
 //pub use self::accept_charset::AcceptCharset;
 pub use self::accept_encoding::AcceptEncoding;
 //pub use self::accept_language::AcceptLanguage;
+pub use self::accept::Accept;
+pub use self::accept_post::AcceptPost;
 pub use self::accept_ranges::AcceptRanges;
-//pub use self::accept::Accept;
 pub use self::access_control_allow_credentials::AccessControlAllowCredentials;
 pub use self::access_control_allow_headers::AccessControlAllowHeaders;
 pub use self::access_control_allow_methods::AccessControlAllowMethods;
@@ -21,54 +23,90 @@ pub use self::access_control_request_headers::AccessControlRequestHeaders;
 pub use self::access_control_request_method::AccessControlRequestMethod;
 pub use self::age::Age;
 pub use self::allow::Allow;
+pub use self::alt_used::AltUsed;
 pub use self::authorization::Authorization;
 pub use self::cache_control::CacheControl;
+pub use self::cache_status::CacheStatus;
 pub use self::connection::Connection;
 pub use self::content_coding::ContentCoding;
+pub use self::content_digest::ContentDigest;
 pub use self::content_disposition::ContentDisposition;
 pub use self::content_encoding::ContentEncoding;
 //pub use self::content_language::ContentLanguage;
 pub use self::content_length::ContentLength;
 pub use self::content_location::ContentLocation;
 pub use self::content_range::ContentRange;
+pub use self::content_security_policy::ContentSecurityPolicy;
+pub use self::content_security_policy_report_only::ContentSecurityPolicyReportOnly;
 pub use self::content_type::ContentType;
 pub use self::cookie::Cookie;
 pub use self::date::Date;
+pub use self::device_memory::DeviceMemory;
+pub use self::digest_algorithm::DigestAlgorithm;
+pub use self::dnt::Dnt;
 pub use self::etag::ETag;
 pub use self::expect::Expect;
+pub use self::expect_ct::ExpectCT;
 pub use self::expires::Expires;
+pub use self::feature_policy::FeaturePolicy;
+pub use self::forwarded::Forwarded;
 //pub use self::from::From;
 pub use self::host::Host;
+pub use self::idempotency_key::IdempotencyKey;
 pub use self::if_match::IfMatch;
 pub use self::if_modified_since::IfModifiedSince;
 pub use self::if_none_match::IfNoneMatch;
 pub use self::if_range::IfRange;
 pub use self::if_unmodified_since::IfUnmodifiedSince;
+pub use self::integrity::Integrity;
 //pub use self::last_event_id::LastEventId;
 pub use self::last_modified::LastModified;
-//pub use self::link::{Link, LinkValue, RelationType, MediaDesc};
+pub use self::link::Link;
 pub use self::location::Location;
+pub use self::nel::Nel;
 pub use self::origin::Origin;
 pub use self::pragma::Pragma;
+pub use self::priority::Priority;
 //pub use self::prefer::{Prefer, Preference};
 //pub use self::preference_applied::PreferenceApplied;
+pub use self::product::Product;
 pub use self::proxy_authorization::ProxyAuthorization;
-pub use self::range::Range;
+pub use self::proxy_connection::ProxyConnection;
+#[allow(deprecated)]
+pub use self::public_key_pins::PublicKeyPins;
+pub use self::quality_value::QualityValue;
+pub use self::range::{Range, RangeConfig};
+pub use self::raw::Raw;
 pub use self::referer::Referer;
 pub use self::referrer_policy::ReferrerPolicy;
+pub use self::refresh::Refresh;
+pub use self::report_to::ReportTo;
 pub use self::retry_after::RetryAfter;
+pub use self::sec_ch_ua::SecChUa;
+pub use self::sec_purpose::SecPurpose;
 pub use self::sec_websocket_accept::SecWebsocketAccept;
 pub use self::sec_websocket_key::SecWebsocketKey;
 pub use self::sec_websocket_version::SecWebsocketVersion;
 pub use self::server::Server;
-pub use self::set_cookie::SetCookie;
+pub use self::set_cookie::{SetCookie, SetCookies};
+pub use self::signature::Signature;
+pub use self::signature_input::SignatureInput;
+pub use self::source_map::SourceMap;
 pub use self::strict_transport_security::StrictTransportSecurity;
 pub use self::te::Te;
+pub use self::tk::Tk;
 pub use self::transfer_encoding::TransferEncoding;
 pub use self::upgrade::Upgrade;
+pub use self::upgrade_insecure_requests::UpgradeInsecureRequests;
 pub use self::user_agent::UserAgent;
 pub use self::vary::Vary;
+pub use self::via::Via;
+pub use self::viewport_width::ViewportWidth;
+pub use self::want_content_digest::WantContentDigest;
+pub use self::want_repr_digest::WantReprDigest;
 //pub use self::warning::Warning;
+pub use self::www_authenticate::WwwAuthenticate;
+pub use self::x_robots_tag::XRobotsTag;
 
 #[cfg(test)]
 fn test_decode<T: ::Header>(values: &[&str]) -> Option<T> {
@@ -127,10 +165,12 @@ macro_rules! bench_header {
     };
 }
 
-//mod accept;
+pub mod accept;
+mod accept_ch;
 //mod accept_charset;
 mod accept_encoding;
 //mod accept_language;
+pub mod accept_post;
 mod accept_ranges;
 mod access_control_allow_credentials;
 mod access_control_allow_headers;
@@ -142,51 +182,130 @@ mod access_control_request_headers;
 mod access_control_request_method;
 mod age;
 mod allow;
+mod alt_used;
 pub mod authorization;
 mod cache_control;
+pub mod cache_status;
 mod connection;
 mod content_coding;
+mod content_digest;
 mod content_disposition;
 mod content_encoding;
 //mod content_language;
 mod content_length;
 mod content_location;
 mod content_range;
+pub mod content_security_policy;
+mod content_security_policy_report_only;
 mod content_type;
 mod cookie;
 mod date;
+mod device_memory;
+mod digest_algorithm;
+mod dnt;
 mod etag;
 mod expect;
+mod expect_ct;
 mod expires;
+pub mod feature_policy;
+pub mod forwarded;
 //mod from;
 mod host;
+mod idempotency_key;
 mod if_match;
 mod if_modified_since;
 mod if_none_match;
 mod if_range;
 mod if_unmodified_since;
+mod integrity;
 //mod last_event_id;
 mod last_modified;
-//mod link;
+pub mod link;
 mod location;
+mod nel;
 mod origin;
 mod pragma;
+mod priority;
 //mod prefer;
 //mod preference_applied;
+mod product;
 mod proxy_authorization;
+mod proxy_connection;
+pub mod public_key_pins;
+mod quality_value;
 mod range;
+pub mod raw;
 mod referer;
 mod referrer_policy;
+mod refresh;
+pub mod report_to;
 mod retry_after;
+pub mod sec_ch_ua;
+pub mod sec_purpose;
 mod sec_websocket_accept;
 mod sec_websocket_key;
 mod sec_websocket_version;
 mod server;
 mod set_cookie;
+mod signature;
+pub mod signature_input;
+mod source_map;
 mod strict_transport_security;
 mod te;
+pub mod tk;
 mod transfer_encoding;
-mod upgrade;
+pub mod upgrade;
+mod upgrade_insecure_requests;
 mod user_agent;
 mod vary;
+pub mod via;
+mod viewport_width;
+pub mod want_content_digest;
+mod want_repr_digest;
 //mod warning;
+pub mod www_authenticate;
+pub mod x_robots_tag;
+
+#[cfg(test)]
+mod round_trip;
+
+#[cfg(test)]
+mod tests {
+    use {HeaderName, NamedHeader};
+
+    fn assert_named<H: NamedHeader>() {
+        assert_eq!(&H::NAME, H::name());
+    }
+
+    #[test]
+    fn named_header_consts() {
+        const ROUTES: [HeaderName; 3] = [
+            super::ContentType::NAME,
+            super::AltUsed::NAME,
+            <super::Authorization<super::authorization::Bearer> as NamedHeader>::NAME,
+        ];
+        assert_eq!(ROUTES[0], "content-type");
+        assert_eq!(ROUTES[1], "alt-used");
+        assert_eq!(ROUTES[2], "authorization");
+
+        assert_named::<super::AcceptCH>();
+        assert_named::<super::AcceptPost>();
+        assert_named::<super::CacheStatus>();
+        assert_named::<super::ContentLength>();
+        assert_named::<super::DeviceMemory>();
+        assert_named::<super::FeaturePolicy>();
+        assert_named::<super::Forwarded>();
+        assert_named::<super::IdempotencyKey>();
+        #[allow(deprecated)]
+        assert_named::<super::PublicKeyPins>();
+        assert_named::<super::SecChUa>();
+        assert_named::<super::SecPurpose>();
+        assert_named::<super::Tk>();
+        assert_named::<super::Upgrade>();
+        assert_named::<super::UpgradeInsecureRequests>();
+        assert_named::<super::Via>();
+        assert_named::<super::ViewportWidth>();
+        assert_named::<super::WantContentDigest>();
+        assert_named::<super::WantReprDigest>();
+    }
+}
@@ -0,0 +1,250 @@
+use std::fmt;
+
+use util::{self, json};
+use {HeaderName, HeaderValue};
+
+const NEL: HeaderName = HeaderName::from_static("nel");
+
+/// `NEL` header, defined in
+/// [Network Error Logging](https://www.w3.org/TR/network-error-logging/)
+///
+/// The `NEL` response header field sets a policy asking the user agent to
+/// report failed, and optionally successful, requests to the origin's
+/// reporting endpoint group named by `report_to`.
+///
+/// The value is a JSON object. Members that aren't part of the policy are
+/// ignored, and the header is always encoded with the members in a fixed
+/// order, leaving out any that have their default value.
+///
+/// # Example values
+///
+/// * `{"report_to":"default","max_age":2592000}`
+/// * `{"report_to":"nel","max_age":86400,"include_subdomains":true,"failure_fraction":0.5}`
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// use headers::Nel;
+///
+/// let nel = Nel::new("default", 2592000)
+///     .with_include_subdomains()
+///     .with_failure_fraction(0.5);
+/// assert_eq!(nel.report_to(), "default");
+/// assert_eq!(nel.failure_fraction(), 0.5);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Nel {
+    report_to: String,
+    max_age: u32,
+    include_subdomains: bool,
+    success_fraction: f64,
+    failure_fraction: f64,
+}
+
+impl Nel {
+    /// Create a policy reporting to the endpoint group `report_to`, for
+    /// `max_age` seconds.
+    ///
+    /// By default, no successful requests and all failed requests are
+    /// reported, and subdomains are not included.
+    pub fn new(report_to: &str, max_age: u32) -> Nel {
+        Nel {
+            report_to: report_to.to_owned(),
+            max_age,
+            include_subdomains: false,
+            success_fraction: 0.0,
+            failure_fraction: 1.0,
+        }
+    }
+
+    /// Also apply the policy to subdomains of the origin.
+    pub fn with_include_subdomains(mut self) -> Self {
+        self.include_subdomains = true;
+        self
+    }
+
+    /// Set the fraction of successful requests to report.
+    ///
+    /// The fraction is clamped to be between 0 and 1, and NaN becomes 0.
+    pub fn with_success_fraction(mut self, fraction: f64) -> Self {
+        self.success_fraction = clamp_fraction(fraction);
+        self
+    }
+
+    /// Set the fraction of failed requests to report.
+    ///
+    /// The fraction is clamped to be between 0 and 1, and NaN becomes 0.
+    pub fn with_failure_fraction(mut self, fraction: f64) -> Self {
+        self.failure_fraction = clamp_fraction(fraction);
+        self
+    }
+
+    /// The name of the reporting endpoint group.
+    pub fn report_to(&self) -> &str {
+        &self.report_to
+    }
+
+    /// How many seconds the policy applies for.
+    ///
+    /// A value of 0 asks the user agent to remove any policy it has stored.
+    pub fn max_age(&self) -> u32 {
+        self.max_age
+    }
+
+    /// Whether the policy also applies to subdomains.
+    pub fn include_subdomains(&self) -> bool {
+        self.include_subdomains
+    }
+
+    /// The fraction of successful requests to report.
+    pub fn success_fraction(&self) -> f64 {
+        self.success_fraction
+    }
+
+    /// The fraction of failed requests to report.
+    pub fn failure_fraction(&self) -> f64 {
+        self.failure_fraction
+    }
+
+    fn from_json(value: &json::Value) -> Option<Nel> {
+        let report_to = value.get("report_to")?.as_str()?;
+        let max_age = value.get("max_age")?.as_u32()?;
+        let mut nel = Nel::new(report_to, max_age);
+
+        if let Some(include) = value.get("include_subdomains") {
+            nel.include_subdomains = include.as_bool()?;
+        }
+        if let Some(fraction) = value.get("success_fraction") {
+            nel.success_fraction = parse_fraction(fraction)?;
+        }
+        if let Some(fraction) = value.get("failure_fraction") {
+            nel.failure_fraction = parse_fraction(fraction)?;
+        }
+        Some(nel)
+    }
+}
+
+fn clamp_fraction(fraction: f64) -> f64 {
+    if fraction.is_nan() {
+        0.0
+    } else {
+        fraction.clamp(0.0, 1.0)
+    }
+}
+
+fn parse_fraction(value: &json::Value) -> Option<f64> {
+    value.as_f64().filter(|n| (0.0..=1.0).contains(n))
+}
+
+impl ::Header for Nel {
+    fn name() -> &'static HeaderName {
+        static NAME: HeaderName = NEL;
+        &NAME
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        // Like the spec, only the first policy is used when several are sent.
+        json::parse_values(values)
+            .as_ref()
+            .and_then(|policies| policies.first())
+            .and_then(Nel::from_json)
+            .ok_or_else(::Error::invalid)
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once(util::fmt(self)));
+    }
+}
+
+impl ::NamedHeader for Nel {
+    const NAME: ::HeaderName = NEL;
+}
+
+impl fmt::Display for Nel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("{\"report_to\":")?;
+        json::write_str(&self.report_to, f)?;
+        write!(f, ",\"max_age\":{}", self.max_age)?;
+        if self.include_subdomains {
+            f.write_str(",\"include_subdomains\":true")?;
+        }
+        if self.success_fraction != 0.0 {
+            write!(f, ",\"success_fraction\":{}", self.success_fraction)?;
+        }
+        if self.failure_fraction != 1.0 {
+            write!(f, ",\"failure_fraction\":{}", self.failure_fraction)?;
+        }
+        f.write_str("}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn decode() {
+        let nel = test_decode::<Nel>(&[
+            r#"{ "report_to": "default", "max_age": 2592000, "include_subdomains": true, "failure_fraction": 0.25, "other": [1] }"#,
+        ])
+        .unwrap();
+        assert_eq!(nel.report_to(), "default");
+        assert_eq!(nel.max_age(), 2592000);
+        assert!(nel.include_subdomains());
+        assert_eq!(nel.success_fraction(), 0.0);
+        assert_eq!(nel.failure_fraction(), 0.25);
+    }
+
+    #[test]
+    fn decode_first_policy() {
+        let nel = test_decode::<Nel>(&[
+            r#"{"report_to":"a","max_age":1}"#,
+            r#"{"report_to":"b","max_age":2}"#,
+        ])
+        .unwrap();
+        assert_eq!(nel, Nel::new("a", 1));
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<Nel>(&[r#"{"max_age":1}"#]), None);
+        assert_eq!(test_decode::<Nel>(&[r#"{"report_to":"a"}"#]), None);
+        assert_eq!(
+            test_decode::<Nel>(&[r#"{"report_to":"a","max_age":-1}"#]),
+            None
+        );
+        assert_eq!(
+            test_decode::<Nel>(&[r#"{"report_to":"a","max_age":1,"failure_fraction":2}"#]),
+            None
+        );
+        assert_eq!(
+            test_decode::<Nel>(&[r#"{"report_to":"a","max_age":1,"include_subdomains":1}"#]),
+            None
+        );
+        assert_eq!(test_decode::<Nel>(&["report_to=a"]), None);
+    }
+
+    #[test]
+    fn encode_canonical() {
+        let nel = test_decode::<Nel>(&[
+            r#"{"failure_fraction":1.0,"max_age":86400,"report_to":"nel","success_fraction":0.5}"#,
+        ])
+        .unwrap();
+        let headers = test_encode(nel);
+        assert_eq!(
+            headers["nel"],
+            r#"{"report_to":"nel","max_age":86400,"success_fraction":0.5}"#
+        );
+
+        let nel = Nel::new("d\u{e9}fault", 60)
+            .with_include_subdomains()
+            .with_failure_fraction(-1.0);
+        let headers = test_encode(nel);
+        assert_eq!(
+            headers["nel"],
+            r#"{"report_to":"d\u00e9fault","max_age":60,"include_subdomains":true,"failure_fraction":0}"#
+        );
+    }
+}
@@ -46,10 +46,7 @@ impl Origin {
     /// Checks if `Origin` is `null`.
     #[inline]
     pub fn is_null(&self) -> bool {
-        match self.0 {
-            OriginOrNull::Null => true,
-            _ => false,
-        }
+        matches!(self.0, OriginOrNull::Null)
     }
 
     /// Get the "scheme" part of this origin.
@@ -101,7 +98,7 @@ impl Origin {
         HeaderValue::from_maybe_shared(bytes)
             .ok()
             .and_then(|val| Self::try_from_value(&val))
-            .ok_or_else(|| InvalidOrigin { _inner: () })
+            .ok_or(InvalidOrigin { _inner: () })
     }
 
     // Used in AccessControlAllowOrigin
@@ -109,7 +106,7 @@ impl Origin {
         OriginOrNull::try_from_value(value).map(Origin)
     }
 
-    pub(super) fn into_value(&self) -> HeaderValue {
+    pub(super) fn to_value(&self) -> HeaderValue {
         (&self.0).into()
     }
 }
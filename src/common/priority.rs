@@ -0,0 +1,227 @@
+use std::fmt;
+
+use util;
+use util::sf::{self, BareItem, Member};
+use {HeaderName, HeaderValue};
+
+const PRIORITY: HeaderName = HeaderName::from_static("priority");
+
+/// `Priority` header, defined in
+/// [RFC9218](https://www.rfc-editor.org/rfc/rfc9218.html#section-5)
+///
+/// The `Priority` header field signals how urgently a response is wanted,
+/// and whether it can be used incrementally, such as an image that can be
+/// shown as it arrives.
+///
+/// Any member may be left out, including all of them, in which case its
+/// default applies: an urgency of 3, and not incremental. Members with the
+/// wrong type or out of range are ignored, as are unknown members.
+///
+/// # ABNF
+///
+/// ```text
+/// Priority = sf-dictionary
+/// u        = sf-integer   ; 0 (most urgent) to 7
+/// i        = sf-boolean   ; a bare `i` is true
+/// ```
+///
+/// # Example values
+///
+/// * `u=5, i`
+/// * `i`
+/// * `u=0`
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// # extern crate http;
+/// use headers::{HeaderMapExt, Priority};
+///
+/// let mut map = http::HeaderMap::new();
+/// map.insert("priority", "i".parse().unwrap());
+/// let priority = map.typed_get::<Priority>().unwrap();
+///
+/// assert_eq!(priority.urgency(), None);
+/// assert_eq!(priority.effective_urgency(), 3);
+/// assert!(priority.is_incremental());
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Priority {
+    urgency: Option<u8>,
+    incremental: Option<bool>,
+}
+
+error_type!(InvalidPriority);
+
+impl Priority {
+    /// The urgency used when none is sent.
+    pub const DEFAULT_URGENCY: u8 = 3;
+
+    /// The lowest urgency, which is the highest number.
+    pub const MAX_URGENCY: u8 = 7;
+
+    /// Create a `Priority` with every member left to its default.
+    pub fn new() -> Priority {
+        Priority::default()
+    }
+
+    /// Set the urgency, from 0, the most urgent, to 7.
+    pub fn with_urgency(mut self, urgency: u8) -> Result<Self, InvalidPriority> {
+        if urgency > Priority::MAX_URGENCY {
+            return Err(InvalidPriority { _inner: () });
+        }
+        self.urgency = Some(urgency);
+        Ok(self)
+    }
+
+    /// Set whether the response can be used incrementally.
+    pub fn with_incremental(mut self, incremental: bool) -> Self {
+        self.incremental = Some(incremental);
+        self
+    }
+
+    /// The urgency, if one was sent.
+    pub fn urgency(&self) -> Option<u8> {
+        self.urgency
+    }
+
+    /// The urgency, or the default of 3 if none was sent.
+    pub fn effective_urgency(&self) -> u8 {
+        self.urgency.unwrap_or(Priority::DEFAULT_URGENCY)
+    }
+
+    /// Whether the response can be used incrementally, if this was sent.
+    pub fn incremental(&self) -> Option<bool> {
+        self.incremental
+    }
+
+    /// Whether the response can be used incrementally, which by default it
+    /// can't.
+    pub fn is_incremental(&self) -> bool {
+        self.incremental.unwrap_or(false)
+    }
+}
+
+impl ::Header for Priority {
+    fn name() -> &'static HeaderName {
+        static NAME: HeaderName = PRIORITY;
+        &NAME
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        let dict = sf::parse_dictionary(values)?;
+        let bare = |key| match dict.get(key) {
+            Some(Member::Item(item)) => Some(&item.bare),
+            _ => None,
+        };
+
+        let urgency = bare("u")
+            .and_then(BareItem::as_integer)
+            .filter(|&u| (0..=i64::from(Priority::MAX_URGENCY)).contains(&u))
+            .map(|u| u as u8);
+        let incremental = bare("i").and_then(BareItem::as_bool);
+        Ok(Priority {
+            urgency,
+            incremental,
+        })
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once(util::fmt(self)));
+    }
+}
+
+impl ::NamedHeader for Priority {
+    const NAME: ::HeaderName = PRIORITY;
+}
+
+impl fmt::Display for Priority {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(urgency) = self.urgency {
+            write!(f, "u={}", urgency)?;
+        }
+        if let Some(incremental) = self.incremental {
+            if self.urgency.is_some() {
+                f.write_str(", ")?;
+            }
+            f.write_str(if incremental { "i" } else { "i=?0" })?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn urgency_and_incremental() {
+        let priority = test_decode::<Priority>(&["u=5, i"]).unwrap();
+        assert_eq!(priority.urgency(), Some(5));
+        assert!(priority.is_incremental());
+
+        let headers = test_encode(priority);
+        assert_eq!(headers["priority"], "u=5, i");
+    }
+
+    #[test]
+    fn incremental_only() {
+        let priority = test_decode::<Priority>(&["i"]).unwrap();
+        assert_eq!(priority.urgency(), None);
+        assert_eq!(priority.effective_urgency(), 3);
+        assert_eq!(priority.incremental(), Some(true));
+        assert!(priority.is_incremental());
+
+        let headers = test_encode(priority);
+        assert_eq!(headers["priority"], "i");
+    }
+
+    #[test]
+    fn urgency_only() {
+        let priority = test_decode::<Priority>(&["u=5"]).unwrap();
+        assert_eq!(priority.effective_urgency(), 5);
+        assert_eq!(priority.incremental(), None);
+        assert!(!priority.is_incremental());
+
+        let priority = test_decode::<Priority>(&["u=1, i=?0"]).unwrap();
+        assert_eq!(priority.incremental(), Some(false));
+        assert!(!priority.is_incremental());
+    }
+
+    #[test]
+    fn empty() {
+        let priority = test_decode::<Priority>(&[""]).unwrap();
+        assert_eq!(priority, Priority::new());
+        assert_eq!(priority.effective_urgency(), 3);
+        assert!(!priority.is_incremental());
+
+        let headers = test_encode(priority);
+        assert_eq!(headers["priority"], "");
+    }
+
+    #[test]
+    fn ignores_invalid_members() {
+        let priority = test_decode::<Priority>(&["u=8, i=1, x=?1"]).unwrap();
+        assert_eq!(priority, Priority::new());
+
+        let priority = test_decode::<Priority>(&["u=\"2\"", "u=2;p, i=(a b)"]).unwrap();
+        assert_eq!(priority.urgency(), Some(2));
+        assert_eq!(priority.incremental(), None);
+
+        assert_eq!(test_decode::<Priority>(&["u=="]), None);
+    }
+
+    #[test]
+    fn builder() {
+        let priority = Priority::new()
+            .with_urgency(0)
+            .unwrap()
+            .with_incremental(false);
+        let headers = test_encode(priority);
+        assert_eq!(headers["priority"], "u=0, i=?0");
+
+        assert!(Priority::new().with_urgency(8).is_err());
+    }
+}
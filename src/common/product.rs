@@ -0,0 +1,169 @@
+/// A product and its version, from a `Server` or `User-Agent` header.
+///
+/// Those headers list the software involved, most significant first, each
+/// optionally followed by a comment, such as `nginx/1.25.3 (Ubuntu)`.
+///
+/// # ABNF
+///
+/// ```text
+/// product         = token ["/" product-version]
+/// product-version = token
+/// comment         = "(" *( ctext / quoted-pair / comment ) ")"
+/// ```
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// use headers::Server;
+///
+/// let server = Server::from_static("nginx/1.25.3 (Ubuntu)");
+/// let product = server.products().next().unwrap();
+/// assert_eq!(product.name(), "nginx");
+/// assert_eq!(product.version(), Some("1.25.3"));
+/// assert_eq!(product.comment(), Some("Ubuntu"));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Product<'a> {
+    name: &'a str,
+    version: Option<&'a str>,
+    comment: Option<&'a str>,
+}
+
+impl<'a> Product<'a> {
+    /// The product name, such as `nginx`.
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    /// The product version, such as `1.25.3`, if there is one.
+    pub fn version(&self) -> Option<&'a str> {
+        self.version
+    }
+
+    /// The comment after the product, without its parentheses, if there is
+    /// one.
+    ///
+    /// If several comments follow a product, this is the first.
+    pub fn comment(&self) -> Option<&'a str> {
+        self.comment
+    }
+}
+
+/// Iterate the products in a `Server` or `User-Agent` value.
+///
+/// This is lenient, as these headers often aren't quite valid. Comments
+/// before the first product are skipped, and an unterminated comment runs
+/// to the end of the value.
+pub(super) fn products(s: &str) -> impl Iterator<Item = Product<'_>> {
+    let mut rest = s;
+    ::std::iter::from_fn(move || loop {
+        rest = rest.trim_start_matches(|c| c == ' ' || c == '\t');
+        if rest.is_empty() {
+            return None;
+        }
+        if rest.starts_with('(') {
+            // A comment without a product to belong to.
+            skip_comment(&mut rest);
+            continue;
+        }
+
+        let end = rest
+            .find(|c| c == ' ' || c == '\t' || c == '(')
+            .unwrap_or(rest.len());
+        let (token, after) = rest.split_at(end);
+        rest = after;
+        let (name, version) = match token.find('/') {
+            Some(i) => (&token[..i], Some(&token[i + 1..])),
+            None => (token, None),
+        };
+
+        let mut comment = None;
+        loop {
+            rest = rest.trim_start_matches(|c| c == ' ' || c == '\t');
+            if !rest.starts_with('(') {
+                break;
+            }
+            let text = skip_comment(&mut rest);
+            comment = comment.or(Some(text));
+        }
+
+        return Some(Product {
+            name,
+            version,
+            comment,
+        });
+    })
+}
+
+/// Consume the comment at the start of `rest`, and return its text.
+fn skip_comment<'a>(rest: &mut &'a str) -> &'a str {
+    let s = *rest;
+    let mut depth = 0;
+    let mut escaped = false;
+    for (i, b) in s.bytes().enumerate() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match b {
+            b'\\' => escaped = true,
+            b'(' => depth += 1,
+            b')' => {
+                depth -= 1;
+                if depth == 0 {
+                    *rest = &s[i + 1..];
+                    return &s[1..i];
+                }
+            }
+            _ => {}
+        }
+    }
+    *rest = "";
+    &s[1..]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::products;
+
+    fn parse(s: &str) -> Vec<(&str, Option<&str>, Option<&str>)> {
+        products(s)
+            .map(|p| (p.name(), p.version(), p.comment()))
+            .collect()
+    }
+
+    #[test]
+    fn products_and_comments() {
+        assert_eq!(
+            parse("nginx/1.25.3 (Ubuntu)"),
+            [("nginx", Some("1.25.3"), Some("Ubuntu"))]
+        );
+        assert_eq!(
+            parse("Mozilla/5.0 (X11; Linux x86_64) (extra) Gecko/20100101 Firefox/121.0"),
+            [
+                ("Mozilla", Some("5.0"), Some("X11; Linux x86_64")),
+                ("Gecko", Some("20100101"), None),
+                ("Firefox", Some("121.0"), None),
+            ]
+        );
+        assert_eq!(parse("Bunnies"), [("Bunnies", None, None)]);
+        assert_eq!(
+            parse("a(nested (b) \\) c)b/1"),
+            [
+                ("a", None, Some("nested (b) \\) c")),
+                ("b", Some("1"), None)
+            ]
+        );
+    }
+
+    #[test]
+    fn lenient() {
+        assert_eq!(parse(""), []);
+        assert_eq!(parse("(orphan) x/1"), [("x", Some("1"), None)]);
+        assert_eq!(
+            parse("x/1 (unterminated"),
+            [("x", Some("1"), Some("unterminated"))]
+        );
+    }
+}
@@ -45,3 +45,7 @@ impl<C: Credentials> ::Header for ProxyAuthorization<C> {
         values.extend(::std::iter::once(value));
     }
 }
+
+impl<C: Credentials> ::NamedHeader for ProxyAuthorization<C> {
+    const NAME: ::HeaderName = ::http::header::PROXY_AUTHORIZATION;
+}
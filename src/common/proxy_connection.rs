@@ -0,0 +1,107 @@
+use super::connection::contains_option;
+use super::connection::sealed::AsConnectionOption;
+use util::{FlatCsv, TryFromValues};
+use {HeaderName, HeaderValue};
+
+const PROXY_CONNECTION: HeaderName = HeaderName::from_static("proxy-connection");
+
+/// `Proxy-Connection` header
+///
+/// The `Proxy-Connection` header field is a non-standard stand-in for
+/// `Connection`, sent by some HTTP/1.0 clients when talking to a proxy. It
+/// takes the same connection options, such as `keep-alive`, and like
+/// `Connection` must not be forwarded.
+///
+/// # Example values
+///
+/// * `keep-alive`
+/// * `close`
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// use headers::ProxyConnection;
+///
+/// let conn = ProxyConnection::keep_alive();
+/// assert!(conn.is_keep_alive());
+/// ```
+#[derive(Clone, Debug)]
+pub struct ProxyConnection(FlatCsv);
+
+impl ::Header for ProxyConnection {
+    fn name() -> &'static HeaderName {
+        static NAME: HeaderName = PROXY_CONNECTION;
+        &NAME
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        FlatCsv::try_from_values(values).map(ProxyConnection)
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once((&self.0).into()));
+    }
+}
+
+impl ::NamedHeader for ProxyConnection {
+    const NAME: ::HeaderName = PROXY_CONNECTION;
+}
+
+impl ProxyConnection {
+    /// A constructor to easily create a `Proxy-Connection: close` header.
+    #[inline]
+    pub fn close() -> ProxyConnection {
+        ProxyConnection(HeaderValue::from_static("close").into())
+    }
+
+    /// A constructor to easily create a `Proxy-Connection: keep-alive`
+    /// header.
+    #[inline]
+    pub fn keep_alive() -> ProxyConnection {
+        ProxyConnection(HeaderValue::from_static("keep-alive").into())
+    }
+
+    /// Check if this header contains a given "connection option".
+    ///
+    /// This takes the same argument types as `Connection::contains`.
+    pub fn contains(&self, name: impl AsConnectionOption) -> bool {
+        contains_option(&self.0, name.as_connection_option())
+    }
+
+    /// Check if this header contains the `close` option.
+    pub fn is_close(&self) -> bool {
+        self.contains("close")
+    }
+
+    /// Check if this header contains the `keep-alive` option.
+    pub fn is_keep_alive(&self) -> bool {
+        self.contains("keep-alive")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn keep_alive() {
+        let conn = test_decode::<ProxyConnection>(&["Keep-Alive"]).unwrap();
+        assert!(conn.is_keep_alive());
+        assert!(!conn.is_close());
+        assert!(conn.contains("keep-alive"));
+
+        let headers = test_encode(conn);
+        assert_eq!(headers["proxy-connection"], "Keep-Alive");
+    }
+
+    #[test]
+    fn options() {
+        let conn = test_decode::<ProxyConnection>(&["close, x-custom", "te"]).unwrap();
+        assert!(conn.is_close());
+        assert!(conn.contains(::http::header::TE));
+        assert!(conn.contains("X-Custom"));
+        assert!(!conn.is_keep_alive());
+    }
+}
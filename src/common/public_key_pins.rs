@@ -0,0 +1,280 @@
+//! Public-Key-Pins header and types.
+
+// The type is deprecated for users, but this module still has to name it.
+#![allow(deprecated)]
+
+use std::fmt;
+use std::time::Duration;
+
+use base64::engine::general_purpose::STANDARD as ENGINE;
+use base64::Engine;
+
+use util::{self, unquote, FlatCsv, IterExt, Seconds, SemiColon};
+use {HeaderName, HeaderValue};
+
+const PUBLIC_KEY_PINS: HeaderName = HeaderName::from_static("public-key-pins");
+
+/// `Public-Key-Pins` header, defined in
+/// [RFC7469](https://www.rfc-editor.org/rfc/rfc7469.html)
+///
+/// The `Public-Key-Pins` response header field asks the user agent to only
+/// accept certificate chains for the host that include one of the pinned
+/// public keys, identified by the SHA-256 hash of their
+/// SubjectPublicKeyInfo.
+///
+/// HTTP Public Key Pinning is deprecated, and has been removed from
+/// browsers, since a bad pin can lock users out of a site. This type is
+/// for tools that need to find and flag it, and shouldn't be used to send
+/// it.
+///
+/// # ABNF
+///
+/// ```text
+/// Public-Key-Pins = directive *( OWS ";" OWS directive )
+/// directive       = directive-name [ "=" directive-value ]
+/// directive-name  = token
+/// directive-value = token / quoted-string
+/// ```
+///
+/// # Example values
+///
+/// * `pin-sha256="d6qzRu9zOECb90Uez27xWltNsj0e1Md7GkYYkVoZWmM="; max-age=5184000`
+/// * `pin-sha256="E9CZ9INDbd+2eRQozYqqbQ2yXLVKB9+xcprMF+44U1g="; max-age=3000;
+///   includeSubDomains; report-uri="https://example.com/hpkp"`
+///
+/// # Example
+///
+/// ```
+/// # #![allow(deprecated)]
+/// # extern crate headers;
+/// # extern crate http;
+/// use headers::{HeaderMapExt, PublicKeyPins};
+///
+/// let mut map = http::HeaderMap::new();
+/// map.insert(
+///     "public-key-pins",
+///     "pin-sha256=\"d6qzRu9zOECb90Uez27xWltNsj0e1Md7GkYYkVoZWmM=\"; max-age=5184000"
+///         .parse()
+///         .unwrap(),
+/// );
+///
+/// let hpkp = map.typed_get::<PublicKeyPins>().unwrap();
+/// assert_eq!(hpkp.pins().len(), 1);
+/// ```
+#[deprecated(note = "HPKP has been removed from browsers, and should only be read to flag it")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct PublicKeyPins {
+    pins: Vec<String>,
+    max_age: Option<Seconds>,
+    include_subdomains: bool,
+    report_uri: Option<String>,
+}
+
+error_type!(InvalidPublicKeyPins);
+
+impl PublicKeyPins {
+    /// Create a `Public-Key-Pins` with no pins, kept for `max_age`.
+    pub fn new(max_age: Duration) -> PublicKeyPins {
+        PublicKeyPins {
+            pins: Vec::new(),
+            max_age: Some(Seconds::from_secs(max_age.as_secs())),
+            include_subdomains: false,
+            report_uri: None,
+        }
+    }
+
+    /// Add a `pin-sha256`, the base64 SHA-256 hash of a SubjectPublicKeyInfo.
+    ///
+    /// Fails if `pin` isn't the base64 encoding of 32 bytes.
+    pub fn with_pin_sha256(mut self, pin: &str) -> Result<Self, InvalidPublicKeyPins> {
+        match ENGINE.decode(pin) {
+            Ok(ref hash) if hash.len() == 32 => {
+                self.pins.push(pin.to_owned());
+                Ok(self)
+            }
+            _ => Err(InvalidPublicKeyPins { _inner: () }),
+        }
+    }
+
+    /// Apply the pins to subdomains of the host too.
+    pub fn with_include_subdomains(mut self) -> Self {
+        self.include_subdomains = true;
+        self
+    }
+
+    /// Set the URI that pin validation failures are reported to.
+    ///
+    /// The URI must be made of visible ASCII characters.
+    pub fn with_report_uri(mut self, uri: &str) -> Result<Self, InvalidPublicKeyPins> {
+        if uri.is_empty() || !uri.bytes().all(|b| (b'\x21'..=b'\x7e').contains(&b)) {
+            return Err(InvalidPublicKeyPins { _inner: () });
+        }
+        self.report_uri = Some(uri.to_owned());
+        Ok(self)
+    }
+
+    /// The `pin-sha256` values, as base64 text, in the order they were
+    /// sent.
+    pub fn pins(&self) -> &[String] {
+        &self.pins
+    }
+
+    /// How long the user agent should keep the pins, if sent.
+    pub fn max_age(&self) -> Option<Duration> {
+        self.max_age.map(Into::into)
+    }
+
+    /// Whether the pins apply to subdomains.
+    pub fn include_subdomains(&self) -> bool {
+        self.include_subdomains
+    }
+
+    /// The URI that failures are reported to, if any.
+    pub fn report_uri(&self) -> Option<&str> {
+        self.report_uri.as_deref()
+    }
+}
+
+impl ::Header for PublicKeyPins {
+    fn name() -> &'static HeaderName {
+        static NAME: HeaderName = PUBLIC_KEY_PINS;
+        &NAME
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        let csv = values
+            .just_one()
+            .map(|value| FlatCsv::<SemiColon>::from(value.clone()))
+            .ok_or_else(::Error::invalid)?;
+        let mut hpkp = PublicKeyPins {
+            pins: Vec::new(),
+            max_age: None,
+            include_subdomains: false,
+            report_uri: None,
+        };
+        let mut any = false;
+
+        for directive in csv.iter().filter(|s| !s.is_empty()) {
+            any = true;
+            let mut parts = directive.splitn(2, '=');
+            let name = parts.next().unwrap_or("").trim();
+            let value = parts.next().map(str::trim);
+
+            if name.eq_ignore_ascii_case("pin-sha256") {
+                let pin = value.and_then(unquote).ok_or_else(::Error::invalid)?;
+                hpkp.pins.push(pin);
+            } else if name.eq_ignore_ascii_case("max-age") {
+                let secs = value
+                    .and_then(unquote)
+                    .and_then(|v| v.parse().ok())
+                    .ok_or_else(::Error::invalid)?;
+                if hpkp.max_age.replace(Seconds::from_secs(secs)).is_some() {
+                    return Err(::Error::invalid());
+                }
+            } else if name.eq_ignore_ascii_case("includeSubDomains") {
+                if value.is_some() || hpkp.include_subdomains {
+                    return Err(::Error::invalid());
+                }
+                hpkp.include_subdomains = true;
+            } else if name.eq_ignore_ascii_case("report-uri") {
+                let uri = value.and_then(unquote).ok_or_else(::Error::invalid)?;
+                if hpkp.report_uri.replace(uri).is_some() {
+                    return Err(::Error::invalid());
+                }
+            }
+        }
+
+        if any {
+            Ok(hpkp)
+        } else {
+            Err(::Error::invalid())
+        }
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once(util::fmt(self)));
+    }
+}
+
+impl ::NamedHeader for PublicKeyPins {
+    const NAME: ::HeaderName = PUBLIC_KEY_PINS;
+}
+
+impl fmt::Display for PublicKeyPins {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut directives = Vec::new();
+        for pin in &self.pins {
+            directives.push(format!("pin-sha256=\"{}\"", pin));
+        }
+        if let Some(max_age) = self.max_age {
+            directives.push(format!("max-age={}", max_age));
+        }
+        if self.include_subdomains {
+            directives.push("includeSubDomains".to_owned());
+        }
+        if let Some(ref uri) = self.report_uri {
+            let escaped = uri.replace('\\', "\\\\").replace('"', "\\\"");
+            directives.push(format!("report-uri=\"{}\"", escaped));
+        }
+        f.write_str(&directives.join("; "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let value = "pin-sha256=\"d6qzRu9zOECb90Uez27xWltNsj0e1Md7GkYYkVoZWmM=\"; \
+                     pin-sha256=\"E9CZ9INDbd+2eRQozYqqbQ2yXLVKB9+xcprMF+44U1g=\"; \
+                     max-age=5184000; includeSubDomains; \
+                     report-uri=\"https://www.example.org/hpkp-report\"";
+        let hpkp = test_decode::<PublicKeyPins>(&[value]).unwrap();
+        assert_eq!(
+            hpkp.pins(),
+            [
+                "d6qzRu9zOECb90Uez27xWltNsj0e1Md7GkYYkVoZWmM=",
+                "E9CZ9INDbd+2eRQozYqqbQ2yXLVKB9+xcprMF+44U1g=",
+            ]
+        );
+        assert_eq!(hpkp.max_age(), Some(Duration::from_secs(5_184_000)));
+        assert!(hpkp.include_subdomains());
+        assert_eq!(
+            hpkp.report_uri(),
+            Some("https://www.example.org/hpkp-report")
+        );
+
+        let headers = test_encode(hpkp.clone());
+        assert_eq!(headers["public-key-pins"], value);
+
+        let built = PublicKeyPins::new(Duration::from_secs(5_184_000))
+            .with_pin_sha256("d6qzRu9zOECb90Uez27xWltNsj0e1Md7GkYYkVoZWmM=")
+            .unwrap()
+            .with_pin_sha256("E9CZ9INDbd+2eRQozYqqbQ2yXLVKB9+xcprMF+44U1g=")
+            .unwrap()
+            .with_include_subdomains()
+            .with_report_uri("https://www.example.org/hpkp-report")
+            .unwrap();
+        assert_eq!(built, hpkp);
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<PublicKeyPins>(&[""]), None);
+        assert_eq!(test_decode::<PublicKeyPins>(&["pin-sha256"]), None);
+        assert_eq!(
+            test_decode::<PublicKeyPins>(&["max-age=1; max-age=2"]),
+            None
+        );
+        assert_eq!(test_decode::<PublicKeyPins>(&["includeSubDomains=1"]), None);
+        assert_eq!(
+            test_decode::<PublicKeyPins>(&["max-age=1", "max-age=1"]),
+            None
+        );
+        assert!(PublicKeyPins::new(Duration::from_secs(1))
+            .with_pin_sha256("c2hvcnQ=")
+            .is_err());
+    }
+}
@@ -0,0 +1,155 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// A quality value, or "qvalue", defined in
+/// [RFC9110](https://www.rfc-editor.org/rfc/rfc9110.html#section-12.4.2)
+///
+/// Headers used for proactive negotiation, such as `Accept`, give each
+/// item a weight between 0 and 1 with the `q` parameter. A weight of 0
+/// means "not acceptable", and an item without a `q` has a weight of 1,
+/// which is also the `Default`.
+///
+/// Quality values have at most three decimal places, so they are stored
+/// in thousandths, and compare and hash exactly. Values are ordered from
+/// least to most preferred; sort by `Reverse` to put the most preferred
+/// first.
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// use std::cmp::Reverse;
+/// use headers::QualityValue;
+///
+/// let mut qualities = vec![
+///     "0.5".parse::<QualityValue>().unwrap(),
+///     QualityValue::from_f32(0.8),
+///     QualityValue::default(),
+/// ];
+/// qualities.sort_by_key(|&q| Reverse(q));
+///
+/// assert_eq!(qualities[0], QualityValue::ONE);
+/// assert_eq!(qualities[1].to_string(), "0.8");
+/// assert_eq!(qualities[2].as_f32(), 0.5);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct QualityValue(u16);
+
+error_type!(InvalidQualityValue);
+
+impl QualityValue {
+    /// A weight of 0, meaning "not acceptable".
+    pub const ZERO: QualityValue = QualityValue(0);
+
+    /// A weight of 1, the most preferred.
+    pub const ONE: QualityValue = QualityValue(1000);
+
+    /// Create a `QualityValue` from a float.
+    ///
+    /// The value is clamped to between 0 and 1, and rounded to three
+    /// decimal places. `NaN` is treated as 0.
+    pub fn from_f32(value: f32) -> QualityValue {
+        if value.is_nan() {
+            return QualityValue::ZERO;
+        }
+        QualityValue((value.clamp(0.0, 1.0) * 1000.0).round() as u16)
+    }
+
+    /// The quality as a float, between 0 and 1.
+    pub fn as_f32(self) -> f32 {
+        f32::from(self.0) / 1000.0
+    }
+}
+
+impl Default for QualityValue {
+    fn default() -> QualityValue {
+        QualityValue::ONE
+    }
+}
+
+/// Parses the strict `qvalue` grammar.
+///
+/// ```text
+/// qvalue = ( "0" [ "." 0*3DIGIT ] )
+///        / ( "1" [ "." 0*3("0") ] )
+/// ```
+impl FromStr for QualityValue {
+    type Err = InvalidQualityValue;
+
+    fn from_str(s: &str) -> Result<QualityValue, InvalidQualityValue> {
+        let bytes = s.as_bytes();
+        let (int, frac) = match bytes.iter().position(|&b| b == b'.') {
+            Some(dot) => (&bytes[..dot], &bytes[dot + 1..]),
+            None => (bytes, &[][..]),
+        };
+        if frac.len() > 3 || !frac.iter().all(u8::is_ascii_digit) {
+            return Err(InvalidQualityValue { _inner: () });
+        }
+
+        let mut quality = match int {
+            b"0" => 0,
+            b"1" => 1000,
+            _ => return Err(InvalidQualityValue { _inner: () }),
+        };
+        for (i, &digit) in frac.iter().enumerate() {
+            quality += u16::from(digit - b'0') * [100, 10, 1][i];
+        }
+        if quality > 1000 {
+            Err(InvalidQualityValue { _inner: () })
+        } else {
+            Ok(QualityValue(quality))
+        }
+    }
+}
+
+/// Formats the shortest form, such as `1`, `0.5`, or `0.125`.
+impl fmt::Display for QualityValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            0 => f.write_str("0"),
+            1000 => f.write_str("1"),
+            q if q % 100 == 0 => write!(f, "0.{}", q / 100),
+            q if q % 10 == 0 => write!(f, "0.{:02}", q / 10),
+            q => write!(f, "0.{:03}", q),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert_eq!("1".parse::<QualityValue>().unwrap(), QualityValue::ONE);
+        assert_eq!("1.000".parse::<QualityValue>().unwrap(), QualityValue::ONE);
+        assert_eq!("0".parse::<QualityValue>().unwrap(), QualityValue::ZERO);
+        assert_eq!("0.".parse::<QualityValue>().unwrap(), QualityValue::ZERO);
+        assert_eq!("0.25".parse::<QualityValue>().unwrap().as_f32(), 0.25);
+
+        assert!("".parse::<QualityValue>().is_err());
+        assert!("1.001".parse::<QualityValue>().is_err());
+        assert!("0.1234".parse::<QualityValue>().is_err());
+        assert!(".5".parse::<QualityValue>().is_err());
+        assert!("2".parse::<QualityValue>().is_err());
+        assert!("0.-1".parse::<QualityValue>().is_err());
+    }
+
+    #[test]
+    fn display() {
+        let shown = ["0", "0.001", "0.01", "0.1", "0.125", "0.25", "1"]
+            .iter()
+            .map(|s| s.parse::<QualityValue>().unwrap().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(shown, ["0", "0.001", "0.01", "0.1", "0.125", "0.25", "1"]);
+    }
+
+    #[test]
+    fn from_f32_clamps_and_rounds() {
+        assert_eq!(QualityValue::from_f32(0.8).to_string(), "0.8");
+        assert_eq!(QualityValue::from_f32(0.1236).to_string(), "0.124");
+        assert_eq!(QualityValue::from_f32(1.5), QualityValue::ONE);
+        assert_eq!(QualityValue::from_f32(-1.0), QualityValue::ZERO);
+        assert_eq!(QualityValue::from_f32(f32::NAN), QualityValue::ZERO);
+    }
+}
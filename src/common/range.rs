@@ -1,3 +1,4 @@
+use std::fmt;
 use std::ops::{Bound, RangeBounds};
 
 /// `Range` header, defined in [RFC7233](https://tools.ietf.org/html/rfc7233#section-3.1)
@@ -10,7 +11,7 @@ use std::ops::{Bound, RangeBounds};
 /// # ABNF
 ///
 /// ```text
-/// Range =	byte-ranges-specifier / other-ranges-specifier
+/// Range = byte-ranges-specifier / other-ranges-specifier
 /// other-ranges-specifier = other-range-unit "=" other-range-set
 /// other-range-set = 1*VCHAR
 ///
@@ -39,10 +40,35 @@ use std::ops::{Bound, RangeBounds};
 ///
 /// let range = Range::bytes(0..1234).unwrap();
 /// ```
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, PartialEq)]
 pub struct Range(::HeaderValue);
 
+/// Limits on how a `Range` header is resolved, for `Range::resolve_with`.
+///
+/// The `Default` allows 200 ranges, the same as Apache httpd, and merges
+/// overlapping ranges.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RangeConfig {
+    /// The most ranges a request may have.
+    ///
+    /// Requests for many small or overlapping ranges are a known denial of
+    /// service, so servers should reject them rather than do the work.
+    pub max_ranges: usize,
+    /// Whether to sort and merge overlapping or adjacent ranges.
+    pub merge_overlaps: bool,
+}
+
+impl Default for RangeConfig {
+    fn default() -> RangeConfig {
+        RangeConfig {
+            max_ranges: 200,
+            merge_overlaps: true,
+        }
+    }
+}
+
 error_type!(InvalidRange);
+error_type!(TooManyRanges);
 
 impl Range {
     /// Creates a `Range` header from bounds.
@@ -96,6 +122,139 @@ impl Range {
             Some((start, end))
         })
     }
+
+    /// Resolve the ranges against the length of the content, and merge
+    /// them into the fewest ranges that cover the same bytes.
+    ///
+    /// Each range is an inclusive `(first, last)` pair of byte positions.
+    /// Suffix and open-ended ranges are resolved and clamped to the
+    /// content, ranges that can't be satisfied are dropped, and what's left
+    /// is sorted, with overlapping or adjacent ranges coalesced, as
+    /// [RFC9110](https://www.rfc-editor.org/rfc/rfc9110.html#section-14.2)
+    /// allows. An empty result means the request can't be satisfied.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate headers;
+    /// # extern crate http;
+    /// use headers::{HeaderMapExt, Range};
+    ///
+    /// let mut map = http::HeaderMap::new();
+    /// map.insert("range", "bytes=50-150, 0-100, -10".parse().unwrap());
+    ///
+    /// let range = map.typed_get::<Range>().unwrap();
+    /// assert_eq!(range.normalized(1000), [(0, 150), (990, 999)]);
+    /// ```
+    pub fn normalized(&self, content_length: u64) -> Vec<(u64, u64)> {
+        let mut ranges = self
+            .specs()
+            .filter_map(|spec| resolve_spec(spec, content_length))
+            .collect::<Vec<_>>();
+        ranges.sort_unstable();
+
+        let mut merged: Vec<(u64, u64)> = Vec::with_capacity(ranges.len());
+        for (first, last) in ranges {
+            match merged.last_mut() {
+                Some(prev) if first <= prev.1.saturating_add(1) => prev.1 = prev.1.max(last),
+                _ => merged.push((first, last)),
+            }
+        }
+        merged
+    }
+
+    /// Resolve the ranges against the length of the content, as `config`
+    /// says.
+    ///
+    /// Fails if the header has more than `config.max_ranges` ranges,
+    /// counted as sent, in which case a server should ignore the header and
+    /// respond 200, or respond 416. If `config.merge_overlaps` is set, the
+    /// ranges are merged as in `normalized`; otherwise they're kept in the
+    /// order they were sent, with unsatisfiable ones dropped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate headers;
+    /// use headers::{Range, RangeConfig};
+    ///
+    /// let range = Range::bytes(0..100).unwrap();
+    /// let config = RangeConfig {
+    ///     max_ranges: 1,
+    ///     ..RangeConfig::default()
+    /// };
+    /// assert_eq!(range.resolve_with(1000, &config).unwrap(), [(0, 99)]);
+    /// ```
+    pub fn resolve_with(
+        &self,
+        content_length: u64,
+        config: &RangeConfig,
+    ) -> Result<Vec<(u64, u64)>, TooManyRanges> {
+        if self.specs().count() > config.max_ranges {
+            return Err(TooManyRanges { _inner: () });
+        }
+        if config.merge_overlaps {
+            Ok(self.normalized(content_length))
+        } else {
+            Ok(self
+                .specs()
+                .filter_map(|spec| resolve_spec(spec, content_length))
+                .collect())
+        }
+    }
+
+    fn specs(&self) -> impl Iterator<Item = &str> {
+        let s = self
+            .0
+            .to_str()
+            .expect("valid string checked in Header::decode()");
+
+        s["bytes=".len()..]
+            .split(',')
+            .map(str::trim)
+            .filter(|spec| !spec.is_empty())
+    }
+}
+
+/// Resolve one range spec to an inclusive pair, if it's satisfiable.
+fn resolve_spec(spec: &str, len: u64) -> Option<(u64, u64)> {
+    let mut iter = spec.splitn(2, '-');
+    let start = parse_bound(iter.next()?)?;
+    let end = parse_bound(iter.next()?)?;
+    if len == 0 {
+        return None;
+    }
+
+    match (start, end) {
+        (Bound::Included(first), Bound::Included(last)) if first <= last && first < len => {
+            Some((first, last.min(len - 1)))
+        }
+        (Bound::Included(first), Bound::Unbounded) if first < len => Some((first, len - 1)),
+        (Bound::Unbounded, Bound::Included(suffix)) if suffix > 0 => {
+            Some((len.saturating_sub(suffix), len - 1))
+        }
+        _ => None,
+    }
+}
+
+impl fmt::Debug for Range {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = self
+            .0
+            .to_str()
+            .expect("valid string checked in Header::decode()");
+        let (unit, specs) = s.split_at(s.find('=').unwrap_or(s.len()));
+        let specs = specs
+            .trim_start_matches('=')
+            .split(',')
+            .map(str::trim)
+            .collect::<Vec<_>>();
+
+        f.debug_struct("Range")
+            .field("unit", &unit)
+            .field("ranges", &specs)
+            .finish()
+    }
 }
 
 fn parse_bound(s: &str) -> Option<Bound<u64>> {
@@ -129,6 +288,72 @@ impl ::Header for Range {
     }
 }
 
+impl ::NamedHeader for Range {
+    const NAME: ::HeaderName = ::http::header::RANGE;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_decode;
+    use super::{Range, RangeConfig};
+
+    #[test]
+    fn normalized() {
+        let normalized = |s: &str, len| test_decode::<Range>(&[s]).unwrap().normalized(len);
+
+        assert_eq!(normalized("bytes=0-100, 50-150", 1000), [(0, 150)]);
+        assert_eq!(normalized("bytes=50-150,0-100", 1000), [(0, 150)]);
+        assert_eq!(normalized("bytes=0-9, 10-19", 1000), [(0, 19)]);
+        assert_eq!(normalized("bytes=20-29, 0-9", 1000), [(0, 9), (20, 29)]);
+        assert_eq!(normalized("bytes=0-5, 1-2", 1000), [(0, 5)]);
+
+        // Open and suffix ranges are resolved against the length.
+        assert_eq!(normalized("bytes=900-", 1000), [(900, 999)]);
+        assert_eq!(normalized("bytes=-100", 1000), [(900, 999)]);
+        assert_eq!(normalized("bytes=-2000", 1000), [(0, 999)]);
+        assert_eq!(normalized("bytes=990-2000", 1000), [(990, 999)]);
+
+        // Unsatisfiable and invalid ranges are dropped.
+        assert_eq!(normalized("bytes=1000-, 5-1, -0, x-y", 1000), []);
+        assert_eq!(normalized("bytes=0-10", 0), []);
+    }
+
+    #[test]
+    fn resolve_with() {
+        let range = test_decode::<Range>(&["bytes=50-59, 0-9, 5-14, 2000-"]).unwrap();
+        let config = |max_ranges, merge_overlaps| RangeConfig {
+            max_ranges,
+            merge_overlaps,
+        };
+
+        // The limit counts every range as sent, even unsatisfiable ones.
+        assert!(range.resolve_with(100, &config(3, true)).is_err());
+        assert_eq!(
+            range.resolve_with(100, &config(4, true)).unwrap(),
+            [(0, 14), (50, 59)]
+        );
+        assert_eq!(
+            range.resolve_with(100, &config(4, false)).unwrap(),
+            [(50, 59), (0, 9), (5, 14)]
+        );
+        assert!(range.resolve_with(100, &config(0, false)).is_err());
+
+        assert_eq!(
+            range.resolve_with(100, &RangeConfig::default()).unwrap(),
+            range.normalized(100)
+        );
+    }
+
+    #[test]
+    fn debug() {
+        let range = test_decode::<Range>(&["bytes=0-10, 20-90,-100"]).unwrap();
+        assert_eq!(
+            format!("{:?}", range),
+            r#"Range { unit: "bytes", ranges: ["0-10", "20-90", "-100"] }"#
+        );
+    }
+}
+
 /*
 
 impl ByteRangeSpec {
@@ -0,0 +1,163 @@
+//! Raw header and types.
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use {HeaderName, HeaderValue};
+
+/// A header this crate doesn't model, with its values kept as sent.
+///
+/// The header name comes from a [`RawName`] type, so `Raw` can be used
+/// with `typed_get` and `typed_insert` like any other header. Decoding
+/// never fails as long as the header is present, and encoding writes
+/// back exactly the values that were decoded.
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// # extern crate http;
+/// use headers::raw::{Raw, RawName};
+/// use headers::HeaderMapExt;
+/// use http::header::HeaderName;
+///
+/// struct XRequestId;
+///
+/// impl RawName for XRequestId {
+///     fn name() -> &'static HeaderName {
+///         static NAME: HeaderName = HeaderName::from_static("x-request-id");
+///         &NAME
+///     }
+/// }
+///
+/// let mut map = http::HeaderMap::new();
+/// map.typed_insert(Raw::<XRequestId>::from_static("f058ebd6-02f7-4d3f"));
+/// assert_eq!(map["x-request-id"], "f058ebd6-02f7-4d3f");
+///
+/// let request_id = map.typed_get::<Raw<XRequestId>>().unwrap();
+/// assert_eq!(request_id.value(), "f058ebd6-02f7-4d3f");
+/// ```
+pub struct Raw<N> {
+    values: Vec<HeaderValue>,
+    // `fn() -> N` keeps `Raw` `Send` and `Sync` whatever `N` is.
+    name: PhantomData<fn() -> N>,
+}
+
+/// The name of a [`Raw`] header.
+pub trait RawName {
+    /// The header name, which should be lowercase.
+    fn name() -> &'static HeaderName;
+}
+
+impl<N: RawName> Raw<N> {
+    /// Create a header with one value.
+    pub fn new(value: HeaderValue) -> Raw<N> {
+        Raw {
+            values: vec![value],
+            name: PhantomData,
+        }
+    }
+
+    /// Create a header with one value from a static string.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the string isn't a valid header value.
+    pub fn from_static(value: &'static str) -> Raw<N> {
+        Raw::new(HeaderValue::from_static(value))
+    }
+
+    /// The first value.
+    pub fn value(&self) -> &HeaderValue {
+        &self.values[0]
+    }
+
+    /// Iterate every value, in the order they were sent.
+    pub fn values(&self) -> impl Iterator<Item = &HeaderValue> {
+        self.values.iter()
+    }
+}
+
+impl<N: RawName> ::Header for Raw<N> {
+    fn name() -> &'static HeaderName {
+        N::name()
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        let values = values.cloned().collect::<Vec<_>>();
+        if values.is_empty() {
+            Err(::Error::invalid())
+        } else {
+            Ok(Raw {
+                values,
+                name: PhantomData,
+            })
+        }
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(self.values.iter().cloned());
+    }
+}
+
+impl<N> Clone for Raw<N> {
+    fn clone(&self) -> Self {
+        Raw {
+            values: self.values.clone(),
+            name: PhantomData,
+        }
+    }
+}
+
+impl<N> PartialEq for Raw<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.values == other.values
+    }
+}
+
+impl<N> Eq for Raw<N> {}
+
+impl<N: RawName> fmt::Debug for Raw<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Raw")
+            .field("name", N::name())
+            .field("values", &self.values)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[derive(Debug)]
+    struct XRequestId;
+
+    impl RawName for XRequestId {
+        fn name() -> &'static HeaderName {
+            static NAME: HeaderName = HeaderName::from_static("x-request-id");
+            &NAME
+        }
+    }
+
+    #[test]
+    fn x_request_id() {
+        let request_id = test_decode::<Raw<XRequestId>>(&["abc 123", "def"]).unwrap();
+        assert_eq!(request_id.value(), "abc 123");
+        assert_eq!(request_id.values().collect::<Vec<_>>(), ["abc 123", "def"]);
+
+        let headers = test_encode(request_id);
+        let values = headers.get_all("x-request-id").iter().collect::<Vec<_>>();
+        assert_eq!(values, ["abc 123", "def"]);
+    }
+
+    #[test]
+    fn opaque_bytes() {
+        let value = HeaderValue::from_bytes(b"caf\xe9").unwrap();
+        let headers = test_encode(Raw::<XRequestId>::new(value.clone()));
+        assert_eq!(headers["x-request-id"], value);
+
+        assert_eq!(test_decode::<Raw<XRequestId>>(&[]), None);
+    }
+}
@@ -1,6 +1,8 @@
+use std::convert::TryFrom;
 use std::str::FromStr;
 
 use http::header::HeaderValue;
+use http::Uri;
 
 /// `Referer` header, defined in
 /// [RFC7231](http://tools.ietf.org/html/rfc7231#section-5.5.2)
@@ -27,7 +29,12 @@ use http::header::HeaderValue;
 /// # extern crate headers;
 /// use headers::Referer;
 ///
+/// let r = Referer::from_static("https://www.example.org/hypertext/Overview.html");
+/// assert_eq!(r.scheme(), Some("https"));
+/// assert_eq!(r.host(), Some("www.example.org"));
+///
 /// let r = Referer::from_static("/People.html#tim");
+/// assert_eq!(r.host(), None);
 /// ```
 #[derive(Debug, Clone, PartialEq)]
 pub struct Referer(HeaderValue);
@@ -46,6 +53,75 @@ impl Referer {
     pub fn from_static(s: &'static str) -> Referer {
         Referer(HeaderValue::from_static(s))
     }
+
+    /// The referrer, as text.
+    ///
+    /// Returns `None` if the value has bytes that aren't visible ASCII.
+    pub fn as_str(&self) -> Option<&str> {
+        self.0.to_str().ok()
+    }
+
+    /// The scheme of the referrer, such as `https`.
+    ///
+    /// Returns `None` for a relative or scheme-relative referrer.
+    pub fn scheme(&self) -> Option<&str> {
+        split_scheme(self.as_str()?).0
+    }
+
+    /// The host of the referrer, such as `www.example.org`, without any
+    /// userinfo or port. An IPv6 host keeps its brackets.
+    ///
+    /// A scheme-relative referrer, such as `//www.example.org/`, has a host
+    /// too. Returns `None` if there is no host, such as for a path or an
+    /// opaque URI like `about:blank`.
+    pub fn host(&self) -> Option<&str> {
+        let rest = split_scheme(self.as_str()?).1;
+        if !rest.starts_with("//") {
+            return None;
+        }
+        let rest = &rest[2..];
+        let authority = &rest[..rest
+            .find(|c| c == '/' || c == '?' || c == '#')
+            .unwrap_or(rest.len())];
+        let host_port = match authority.rfind('@') {
+            Some(i) => &authority[i + 1..],
+            None => authority,
+        };
+        let host = if host_port.starts_with('[') {
+            &host_port[..host_port.find(']')? + 1]
+        } else {
+            &host_port[..host_port.find(':').unwrap_or(host_port.len())]
+        };
+        if host.is_empty() {
+            None
+        } else {
+            Some(host)
+        }
+    }
+
+    /// Check if the referrer is a valid absolute or relative URI.
+    pub fn is_valid_uri(&self) -> bool {
+        self.to_uri().is_some()
+    }
+
+    /// Parse the referrer as a URI, if it is one.
+    pub(crate) fn to_uri(&self) -> Option<Uri> {
+        Uri::try_from(self.0.as_bytes()).ok()
+    }
+}
+
+/// Split a URI reference into its scheme, if it has one, and the rest.
+fn split_scheme(s: &str) -> (Option<&str>, &str) {
+    if let Some(i) = s.find(':') {
+        let scheme = &s[..i];
+        let mut bytes = scheme.bytes();
+        let valid = bytes.next().map_or(false, |b| b.is_ascii_alphabetic())
+            && bytes.all(|b| b.is_ascii_alphanumeric() || b == b'+' || b == b'-' || b == b'.');
+        if valid {
+            return (Some(scheme), &s[i + 1..]);
+        }
+    }
+    (None, s)
 }
 
 error_type!(InvalidReferer);
@@ -58,3 +134,56 @@ impl FromStr for Referer {
             .map_err(|_| InvalidReferer { _inner: () })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_decode;
+    use super::Referer;
+
+    #[test]
+    fn full_url() {
+        let r = test_decode::<Referer>(&["https://user@www.example.org:8443/a/b?c=d"]).unwrap();
+        assert_eq!(
+            r.as_str(),
+            Some("https://user@www.example.org:8443/a/b?c=d")
+        );
+        assert_eq!(r.scheme(), Some("https"));
+        assert_eq!(r.host(), Some("www.example.org"));
+        assert!(r.is_valid_uri());
+
+        let r = Referer::from_static("http://[::1]:8080/");
+        assert_eq!(r.host(), Some("[::1]"));
+    }
+
+    #[test]
+    fn bare_origin() {
+        let r = Referer::from_static("https://www.example.org/");
+        assert_eq!(r.scheme(), Some("https"));
+        assert_eq!(r.host(), Some("www.example.org"));
+
+        let r = Referer::from_static("https://www.example.org");
+        assert_eq!(r.host(), Some("www.example.org"));
+        assert!(r.is_valid_uri());
+    }
+
+    #[test]
+    fn partial() {
+        let r = Referer::from_static("//www.example.org/page");
+        assert_eq!(r.scheme(), None);
+        assert_eq!(r.host(), Some("www.example.org"));
+
+        let r = Referer::from_static("/People.html");
+        assert_eq!(r.scheme(), None);
+        assert_eq!(r.host(), None);
+        assert!(r.is_valid_uri());
+
+        let r = Referer::from_static("about:blank");
+        assert_eq!(r.scheme(), Some("about"));
+        assert_eq!(r.host(), None);
+
+        let r = Referer::from_static("not a uri");
+        assert_eq!(r.scheme(), None);
+        assert_eq!(r.host(), None);
+        assert!(!r.is_valid_uri());
+    }
+}
@@ -0,0 +1,206 @@
+use std::fmt;
+
+use util::{self, IterExt};
+use {HeaderName, HeaderValue};
+
+const REFRESH: HeaderName = HeaderName::from_static("refresh");
+
+/// `Refresh` header, as defined for the equivalent `<meta http-equiv>` in
+/// [HTML](https://html.spec.whatwg.org/multipage/semantics.html#shared-declarative-refresh-steps)
+///
+/// The `Refresh` response header field asks the user agent to reload the
+/// page, or to navigate to another URL, after a delay in seconds. It isn't
+/// part of any HTTP specification, but is widely supported.
+///
+/// # Example values
+///
+/// * `5`
+/// * `0; url=https://example.com/`
+/// * `3;URL='/next'`
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// use headers::Refresh;
+///
+/// let refresh = Refresh::new(5).with_url("https://example.com/").unwrap();
+/// assert_eq!(refresh.delay(), 5);
+/// assert_eq!(refresh.url(), Some("https://example.com/"));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Refresh {
+    delay: u32,
+    url: Option<String>,
+}
+
+error_type!(InvalidRefresh);
+
+impl Refresh {
+    /// Create a `Refresh` that reloads the page after `delay` seconds.
+    pub fn new(delay: u32) -> Refresh {
+        Refresh { delay, url: None }
+    }
+
+    /// Navigate to `url` instead of reloading the page.
+    ///
+    /// The URL must be made of visible ASCII characters, and not contain
+    /// quotes.
+    pub fn with_url(mut self, url: &str) -> Result<Refresh, InvalidRefresh> {
+        let valid = !url.is_empty()
+            && url
+                .bytes()
+                .all(|b| (b'\x21'..=b'\x7e').contains(&b) && b != b'"' && b != b'\'');
+        if !valid {
+            return Err(InvalidRefresh { _inner: () });
+        }
+        self.url = Some(url.to_owned());
+        Ok(self)
+    }
+
+    /// The delay in seconds.
+    pub fn delay(&self) -> u32 {
+        self.delay
+    }
+
+    /// The URL to navigate to, if any.
+    pub fn url(&self) -> Option<&str> {
+        self.url.as_deref()
+    }
+
+    fn parse(s: &str) -> Option<Refresh> {
+        let s = s.trim();
+        let digits = s.bytes().take_while(u8::is_ascii_digit).count();
+        let delay = s[..digits].parse().ok()?;
+
+        // Any fraction of a second is ignored.
+        let rest = s[digits..].trim_start_matches(|c: char| c.is_ascii_digit() || c == '.');
+        let rest = rest.trim_start();
+        if rest.is_empty() {
+            return Some(Refresh::new(delay));
+        }
+
+        let rest = rest.strip_prefix(|c| c == ';' || c == ',')?.trim_start();
+        let url = match rest.get(..3) {
+            Some(name) if name.eq_ignore_ascii_case("url") => {
+                match rest[3..].trim_start().strip_prefix('=') {
+                    Some(url) => url.trim_start(),
+                    // Just a URL that happens to start with `url`.
+                    None => rest,
+                }
+            }
+            _ => rest,
+        };
+        let url = match url.chars().next() {
+            Some(quote) if quote == '"' || quote == '\'' => {
+                let url = &url[1..];
+                &url[..url.find(quote).unwrap_or(url.len())]
+            }
+            _ => url,
+        };
+
+        let url = url.trim_end();
+        if url.is_empty() {
+            Some(Refresh::new(delay))
+        } else {
+            Some(Refresh {
+                delay,
+                url: Some(url.to_owned()),
+            })
+        }
+    }
+}
+
+impl ::Header for Refresh {
+    fn name() -> &'static HeaderName {
+        static NAME: HeaderName = REFRESH;
+        &NAME
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        values
+            .just_one()
+            .and_then(|val| val.to_str().ok())
+            .and_then(Refresh::parse)
+            .ok_or_else(::Error::invalid)
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once(util::fmt(self)));
+    }
+}
+
+impl ::NamedHeader for Refresh {
+    const NAME: ::HeaderName = REFRESH;
+}
+
+impl fmt::Display for Refresh {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.delay, f)?;
+        if let Some(ref url) = self.url {
+            write!(f, "; url={}", url)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn round_trip_delay() {
+        let refresh = test_decode::<Refresh>(&[" 5 "]).unwrap();
+        assert_eq!(refresh.delay(), 5);
+        assert_eq!(refresh.url(), None);
+
+        let headers = test_encode(refresh);
+        assert_eq!(headers["refresh"], "5");
+    }
+
+    #[test]
+    fn round_trip_url() {
+        let refresh = test_decode::<Refresh>(&["0;  URL = https://example.com/ "]).unwrap();
+        assert_eq!(refresh.delay(), 0);
+        assert_eq!(refresh.url(), Some("https://example.com/"));
+
+        let headers = test_encode(refresh.clone());
+        assert_eq!(headers["refresh"], "0; url=https://example.com/");
+        assert_eq!(
+            test_decode::<Refresh>(&[headers["refresh"].to_str().unwrap()]),
+            Some(refresh)
+        );
+    }
+
+    #[test]
+    fn decode_lenient_forms() {
+        let refresh = test_decode::<Refresh>(&["3.5, '/next'"]).unwrap();
+        assert_eq!(refresh, Refresh::new(3).with_url("/next").unwrap());
+
+        let refresh = test_decode::<Refresh>(&["1; url=\"/a b\""]).unwrap();
+        assert_eq!(refresh.url(), Some("/a b"));
+
+        let refresh = test_decode::<Refresh>(&["1;urlish"]).unwrap();
+        assert_eq!(refresh.url(), Some("urlish"));
+
+        let refresh = test_decode::<Refresh>(&["2; url="]).unwrap();
+        assert_eq!(refresh, Refresh::new(2));
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<Refresh>(&[""]), None);
+        assert_eq!(test_decode::<Refresh>(&["url=/x"]), None);
+        assert_eq!(test_decode::<Refresh>(&["-1"]), None);
+        assert_eq!(test_decode::<Refresh>(&["5 /x"]), None);
+        assert_eq!(test_decode::<Refresh>(&["1", "2"]), None);
+    }
+
+    #[test]
+    fn with_url_invalid() {
+        assert!(Refresh::new(1).with_url("").is_err());
+        assert!(Refresh::new(1).with_url("/a b").is_err());
+        assert!(Refresh::new(1).with_url("/'quoted'").is_err());
+    }
+}
@@ -0,0 +1,348 @@
+//! Report-To header and types.
+
+use std::fmt;
+
+use util::{self, json};
+use {HeaderName, HeaderValue};
+
+const REPORT_TO: HeaderName = HeaderName::from_static("report-to");
+
+/// `Report-To` header, defined in an earlier draft of the
+/// [Reporting API](https://www.w3.org/TR/2018/WD-reporting-1-20180925/#header)
+///
+/// The `Report-To` response header field defines named groups of endpoints
+/// that the user agent can deliver reports to, such as those sent for
+/// Content Security Policy violations or by `NEL`.
+///
+/// The value is a comma-separated list of JSON objects, one per group.
+/// Groups and endpoints missing a required member are skipped, as are
+/// members that aren't part of the definition.
+///
+/// # Example values
+///
+/// * `{"group":"csp","max_age":10886400,"endpoints":[{"url":"https://example.com/csp"}]}`
+/// * `{"max_age":86400,"endpoints":[{"url":"https://a.example/r"},{"url":"https://b.example/r","priority":2}]}`
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// use headers::report_to::{Endpoint, ReportingGroup};
+/// use headers::ReportTo;
+///
+/// let report_to = ReportTo::new(
+///     ReportingGroup::new("csp", 10886400)
+///         .with_endpoint(Endpoint::new("https://example.com/csp")),
+/// );
+///
+/// let group = report_to.group("csp").unwrap();
+/// assert_eq!(group.endpoints().next().unwrap().url(), "https://example.com/csp");
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReportTo(Vec<ReportingGroup>);
+
+/// A named group of endpoints in a `Report-To` header.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReportingGroup {
+    name: String,
+    max_age: u32,
+    include_subdomains: bool,
+    endpoints: Vec<Endpoint>,
+}
+
+/// An endpoint that reports can be delivered to.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Endpoint {
+    url: String,
+    priority: u32,
+    weight: u32,
+}
+
+impl ReportTo {
+    /// Create a `Report-To` header with a single group.
+    pub fn new(group: ReportingGroup) -> Self {
+        ReportTo(vec![group])
+    }
+
+    /// Add another group.
+    pub fn with_group(mut self, group: ReportingGroup) -> Self {
+        self.0.push(group);
+        self
+    }
+
+    /// Iterate the groups, in the order they were sent.
+    pub fn iter(&self) -> impl Iterator<Item = &ReportingGroup> {
+        self.0.iter()
+    }
+
+    /// Find the group with a name.
+    ///
+    /// If several groups have the same name, the first is returned.
+    pub fn group(&self, name: &str) -> Option<&ReportingGroup> {
+        self.0.iter().find(|group| group.name == name)
+    }
+}
+
+impl From<ReportingGroup> for ReportTo {
+    fn from(group: ReportingGroup) -> Self {
+        ReportTo::new(group)
+    }
+}
+
+impl ReportingGroup {
+    /// Create a group with no endpoints, kept by the user agent for
+    /// `max_age` seconds.
+    ///
+    /// Reports that don't name a group are sent to the one named `default`.
+    pub fn new(name: &str, max_age: u32) -> Self {
+        ReportingGroup {
+            name: name.to_owned(),
+            max_age,
+            include_subdomains: false,
+            endpoints: Vec::new(),
+        }
+    }
+
+    /// Also apply the group to subdomains of the origin.
+    pub fn with_include_subdomains(mut self) -> Self {
+        self.include_subdomains = true;
+        self
+    }
+
+    /// Add an endpoint to the group.
+    pub fn with_endpoint(mut self, endpoint: Endpoint) -> Self {
+        self.endpoints.push(endpoint);
+        self
+    }
+
+    /// The name of the group.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// How many seconds the user agent keeps the group for.
+    pub fn max_age(&self) -> u32 {
+        self.max_age
+    }
+
+    /// Whether the group also applies to subdomains.
+    pub fn include_subdomains(&self) -> bool {
+        self.include_subdomains
+    }
+
+    /// Iterate the endpoints of the group.
+    pub fn endpoints(&self) -> impl Iterator<Item = &Endpoint> {
+        self.endpoints.iter()
+    }
+
+    fn from_json(value: &json::Value) -> Option<ReportingGroup> {
+        let name = match value.get("group") {
+            Some(name) => name.as_str()?,
+            None => "default",
+        };
+        let mut group = ReportingGroup::new(name, value.get("max_age")?.as_u32()?);
+        if let Some(include) = value.get("include_subdomains") {
+            group.include_subdomains = include.as_bool()?;
+        }
+        group.endpoints = value
+            .get("endpoints")?
+            .as_array()?
+            .iter()
+            .filter_map(Endpoint::from_json)
+            .collect();
+        Some(group)
+    }
+}
+
+impl Endpoint {
+    /// Create an endpoint for a URL, with a priority and weight of 1.
+    pub fn new(url: &str) -> Self {
+        Endpoint {
+            url: url.to_owned(),
+            priority: 1,
+            weight: 1,
+        }
+    }
+
+    /// Set the priority of the endpoint. Endpoints with a lower priority
+    /// are tried first.
+    pub fn with_priority(mut self, priority: u32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Set the weight of the endpoint, for balancing reports between
+    /// endpoints of the same priority.
+    pub fn with_weight(mut self, weight: u32) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    /// The URL reports are delivered to.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// The priority of the endpoint.
+    pub fn priority(&self) -> u32 {
+        self.priority
+    }
+
+    /// The weight of the endpoint.
+    pub fn weight(&self) -> u32 {
+        self.weight
+    }
+
+    fn from_json(value: &json::Value) -> Option<Endpoint> {
+        let mut endpoint = Endpoint::new(value.get("url")?.as_str()?);
+        if let Some(priority) = value.get("priority") {
+            endpoint.priority = priority.as_u32()?;
+        }
+        if let Some(weight) = value.get("weight") {
+            endpoint.weight = weight.as_u32()?;
+        }
+        Some(endpoint)
+    }
+}
+
+impl ::Header for ReportTo {
+    fn name() -> &'static HeaderName {
+        static NAME: HeaderName = REPORT_TO;
+        &NAME
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        let groups = json::parse_values(values)
+            .ok_or_else(::Error::invalid)?
+            .iter()
+            .filter_map(ReportingGroup::from_json)
+            .collect::<Vec<_>>();
+
+        if groups.is_empty() {
+            Err(::Error::invalid())
+        } else {
+            Ok(ReportTo(groups))
+        }
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(self.0.iter().map(util::fmt));
+    }
+}
+
+impl ::NamedHeader for ReportTo {
+    const NAME: ::HeaderName = REPORT_TO;
+}
+
+impl fmt::Display for ReportingGroup {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("{\"group\":")?;
+        json::write_str(&self.name, f)?;
+        write!(f, ",\"max_age\":{}", self.max_age)?;
+        if self.include_subdomains {
+            f.write_str(",\"include_subdomains\":true")?;
+        }
+        f.write_str(",\"endpoints\":[")?;
+        for (i, endpoint) in self.endpoints.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            fmt::Display::fmt(endpoint, f)?;
+        }
+        f.write_str("]}")
+    }
+}
+
+impl fmt::Display for Endpoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("{\"url\":")?;
+        json::write_str(&self.url, f)?;
+        if self.priority != 1 {
+            write!(f, ",\"priority\":{}", self.priority)?;
+        }
+        if self.weight != 1 {
+            write!(f, ",\"weight\":{}", self.weight)?;
+        }
+        f.write_str("}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn decode_groups() {
+        let report_to = test_decode::<ReportTo>(&[
+            r#"{"group":"csp","max_age":10886400,"endpoints":[{"url":"https://example.com/csp"}]}, {"max_age":60,"include_subdomains":true,"endpoints":[]}"#,
+            r#"{"group":"nel","max_age":1,"endpoints":[{"url":"https://a.example/r","priority":2,"weight":10}]}"#,
+        ])
+        .unwrap();
+        assert_eq!(report_to.iter().count(), 3);
+
+        let csp = report_to.group("csp").unwrap();
+        assert_eq!(csp.max_age(), 10886400);
+        assert!(!csp.include_subdomains());
+        let endpoints = csp.endpoints().collect::<Vec<_>>();
+        assert_eq!(endpoints, [&Endpoint::new("https://example.com/csp")]);
+
+        let default = report_to.group("default").unwrap();
+        assert!(default.include_subdomains());
+        assert_eq!(default.endpoints().count(), 0);
+
+        let nel = report_to.group("nel").unwrap();
+        let endpoint = nel.endpoints().next().unwrap();
+        assert_eq!(endpoint.priority(), 2);
+        assert_eq!(endpoint.weight(), 10);
+
+        assert_eq!(report_to.group("missing"), None);
+    }
+
+    #[test]
+    fn decode_skips_invalid() {
+        let report_to = test_decode::<ReportTo>(&[
+            r#"{"group":"a","endpoints":[]}, {"group":"b","max_age":1,"endpoints":[{"priority":1},{"url":"https://b.example"}]}"#,
+        ])
+        .unwrap();
+        assert_eq!(report_to.group("a"), None);
+        assert_eq!(
+            report_to
+                .group("b")
+                .unwrap()
+                .endpoints()
+                .collect::<Vec<_>>(),
+            [&Endpoint::new("https://b.example")]
+        );
+
+        assert_eq!(test_decode::<ReportTo>(&[r#"{"group":"a"}"#]), None);
+        assert_eq!(test_decode::<ReportTo>(&[r#"{"group":"a",}"#]), None);
+    }
+
+    #[test]
+    fn encode() {
+        let report_to = ReportTo::new(
+            ReportingGroup::new("csp", 600)
+                .with_include_subdomains()
+                .with_endpoint(Endpoint::new("https://example.com/csp"))
+                .with_endpoint(Endpoint::new("https://backup.example/csp").with_priority(2)),
+        )
+        .with_group(ReportingGroup::new("default", 0));
+
+        let headers = test_encode(report_to.clone());
+        let values = headers.get_all("report-to").iter().collect::<Vec<_>>();
+        assert_eq!(
+            values,
+            [
+                r#"{"group":"csp","max_age":600,"include_subdomains":true,"endpoints":[{"url":"https://example.com/csp"},{"url":"https://backup.example/csp","priority":2}]}"#,
+                r#"{"group":"default","max_age":0,"endpoints":[]}"#,
+            ]
+        );
+
+        let values = values
+            .into_iter()
+            .map(|v| v.to_str().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(test_decode::<ReportTo>(&values), Some(report_to));
+    }
+}
@@ -1,7 +1,7 @@
 use std::time::{Duration, SystemTime};
 
 use util::{HttpDate, Seconds, TryFromValues};
-use HeaderValue;
+use {DecodeOptions, HeaderValue};
 
 /// The `Retry-After` header.
 ///
@@ -22,7 +22,7 @@ use HeaderValue;
 /// let delay = RetryAfter::delay(Duration::from_secs(300));
 /// let date = RetryAfter::date(SystemTime::now());
 /// ```
-
+///
 /// Retry-After header, defined in [RFC7231](http://tools.ietf.org/html/rfc7231#section-7.1.3)
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RetryAfter(After);
@@ -32,6 +32,13 @@ derive_header! {
     name: RETRY_AFTER
 }
 
+#[cfg(feature = "chrono")]
+impl From<::chrono::DateTime<::chrono::Utc>> for RetryAfter {
+    fn from(time: ::chrono::DateTime<::chrono::Utc>) -> RetryAfter {
+        RetryAfter(After::DateTime(time.into()))
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum After {
     /// Retry after the given DateTime
@@ -50,10 +57,84 @@ impl RetryAfter {
     pub fn delay(dur: Duration) -> RetryAfter {
         RetryAfter(After::Delay(dur.into()))
     }
+
+    /// Get the date of this `RetryAfter`, if it was given as a date rather
+    /// than a delay.
+    #[cfg(feature = "chrono")]
+    pub fn chrono_date(&self) -> Option<::chrono::DateTime<::chrono::Utc>> {
+        match self.0 {
+            After::DateTime(date) => Some(date.into()),
+            After::Delay(_) => None,
+        }
+    }
+
+    /// Compute when a request should be retried, given the current time.
+    ///
+    /// A delay is added to `now`, while a date is used as is, unless it is
+    /// already in the past, in which case `now` is returned.
+    ///
+    /// If `jitter` is provided, a random duration up to that bound is added,
+    /// so that many clients told to retry at the same time don't all do so
+    /// at once. This requires the `jitter` feature; without it, `jitter` is
+    /// ignored.
+    ///
+    /// Returns `None` if the deadline is too far in the future for a
+    /// `SystemTime`, such as with a delay of `u64::MAX` seconds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate headers;
+    /// use std::time::{Duration, SystemTime};
+    /// use headers::RetryAfter;
+    ///
+    /// let now = SystemTime::now();
+    /// let retry = RetryAfter::delay(Duration::from_secs(30));
+    /// assert!(retry.backoff_deadline(now, None).unwrap() >= now + Duration::from_secs(30));
+    /// ```
+    pub fn backoff_deadline(
+        &self,
+        now: SystemTime,
+        jitter: Option<Duration>,
+    ) -> Option<SystemTime> {
+        let deadline = now.checked_add(self.wait(now))?;
+        match jitter {
+            Some(bound) => deadline.checked_add(random_jitter(bound)),
+            None => Some(deadline),
+        }
+    }
+
+    /// How long to wait from `now`, which is zero for a date in the past.
+    pub(crate) fn wait(&self, now: SystemTime) -> Duration {
+        match self.0 {
+            After::Delay(delay) => Duration::from(delay),
+            After::DateTime(date) => SystemTime::from(date)
+                .duration_since(now)
+                .unwrap_or_default(),
+        }
+    }
+}
+
+#[cfg(feature = "jitter")]
+fn random_jitter(bound: Duration) -> Duration {
+    let max = bound.as_nanos().min(u128::from(u64::MAX)) as u64;
+    Duration::from_nanos(::fastrand::u64(0..=max))
+}
+
+#[cfg(not(feature = "jitter"))]
+fn random_jitter(_bound: Duration) -> Duration {
+    Duration::from_secs(0)
 }
 
 impl TryFromValues for After {
     fn try_from_values<'i, I>(values: &mut I) -> Result<Self, ::Error>
+    where
+        I: Iterator<Item = &'i HeaderValue>,
+    {
+        Self::try_from_values_with(values, &DecodeOptions::default())
+    }
+
+    fn try_from_values_with<'i, I>(values: &mut I, options: &DecodeOptions) -> Result<Self, ::Error>
     where
         I: Iterator<Item = &'i HeaderValue>,
     {
@@ -64,7 +145,7 @@ impl TryFromValues for After {
                     return Some(After::Delay(delay));
                 }
 
-                let date = HttpDate::from_val(val)?;
+                let date = HttpDate::from_val_with(val, options)?;
                 Some(After::DateTime(date))
             })
             .ok_or_else(::Error::invalid)
@@ -84,7 +165,7 @@ impl<'a> From<&'a After> for HeaderValue {
 mod tests {
     use super::super::test_decode;
     use super::RetryAfter;
-    use std::time::Duration;
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
     use util::HttpDate;
 
     #[test]
@@ -105,7 +186,97 @@ mod tests {
         };
     }
 
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_date() {
+        use chrono::{TimeZone, Utc};
+
+        let time = Utc.with_ymd_and_hms(1994, 11, 6, 8, 49, 37).unwrap();
+        let r: RetryAfter = test_decode(&["Sun, 06 Nov 1994 08:49:37 GMT"]).unwrap();
+        assert_eq!(r, RetryAfter::from(time));
+        assert_eq!(r.chrono_date(), Some(time));
+        assert_eq!(
+            RetryAfter::delay(Duration::from_secs(5)).chrono_date(),
+            None
+        );
+    }
+
+    #[test]
+    fn backoff_deadline_delay() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_000_000);
+        let r = RetryAfter::delay(Duration::from_secs(120));
+
+        assert_eq!(
+            r.backoff_deadline(now, None),
+            Some(now + Duration::from_secs(120))
+        );
+    }
+
+    #[test]
+    fn backoff_deadline_overflow() {
+        let r: RetryAfter = test_decode(&["18446744073709551615"]).unwrap();
+        assert_eq!(r, RetryAfter::delay(Duration::from_secs(u64::MAX)));
+
+        let now = SystemTime::now();
+        assert_eq!(r.backoff_deadline(now, None), None);
+        assert_eq!(r.backoff_deadline(now, Some(Duration::from_secs(10))), None);
+    }
+
+    #[test]
+    fn backoff_deadline_date() {
+        let date = UNIX_EPOCH + Duration::from_secs(1_000_000);
+        let r = RetryAfter::date(date);
+
+        let before = date - Duration::from_secs(60);
+        assert_eq!(r.backoff_deadline(before, None), Some(date));
+
+        // A date in the past means retry right away.
+        let after = date + Duration::from_secs(60);
+        assert_eq!(r.backoff_deadline(after, None), Some(after));
+    }
+
+    #[test]
+    fn backoff_deadline_jitter_bounds() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_000_000);
+        let r = RetryAfter::delay(Duration::from_secs(120));
+        let jitter = Duration::from_secs(10);
+
+        for _ in 0..100 {
+            let deadline = r.backoff_deadline(now, Some(jitter)).unwrap();
+            assert!(deadline >= now + Duration::from_secs(120));
+            assert!(deadline <= now + Duration::from_secs(130));
+        }
+    }
+
+    #[cfg(not(feature = "jitter"))]
+    #[test]
+    fn backoff_deadline_jitter_ignored_without_feature() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_000_000);
+        let r = RetryAfter::delay(Duration::from_secs(120));
+
+        assert_eq!(
+            r.backoff_deadline(now, Some(Duration::from_secs(10))),
+            Some(now + Duration::from_secs(120))
+        );
+    }
+
     test_retry_after_datetime!(date_decode_rfc1123, "Sun, 06 Nov 1994 08:49:37 GMT");
     test_retry_after_datetime!(date_decode_rfc850, "Sunday, 06-Nov-94 08:49:37 GMT");
     test_retry_after_datetime!(date_decode_asctime, "Sun Nov  6 08:49:37 1994");
+
+    #[test]
+    fn decode_strict() {
+        let decode = |s: &'static str| {
+            let value = ::HeaderValue::from_static(s);
+            let strict = ::DecodeOptions::strict();
+            <RetryAfter as ::Header>::decode_with(&mut ::std::iter::once(&value), &strict).ok()
+        };
+        assert_eq!(
+            decode("120"),
+            Some(RetryAfter::delay(Duration::from_secs(120)))
+        );
+        assert!(decode("Sun, 06 Nov 1994 08:49:37 GMT").is_some());
+        assert_eq!(decode("Sunday, 06-Nov-94 08:49:37 GMT"), None);
+        assert_eq!(decode("Sun Nov  6 08:49:37 1994"), None);
+    }
 }
@@ -0,0 +1,216 @@
+//! Round-trip tests across every header.
+//!
+//! Each header must uphold this: once a value decodes, encoding the
+//! result and decoding that again gives the same header, and encoding
+//! that gives the same values. Encoding may normalize what was received,
+//! such as case or spacing, but the first encoding is then a fixed point.
+//! Headers that implement `PartialEq` are also compared directly.
+//!
+//! Every `SingleValueHeader` must be listed below, including those with
+//! a hand-written impl, and `covers_single_value_headers` fails until a new
+//! one is.
+
+use std::fmt::Debug;
+
+use common::authorization::{Basic, Bearer};
+use common::*;
+use {Header, HeaderName, HeaderValue};
+
+fn encode<H: Header>(header: &H) -> Vec<HeaderValue> {
+    let mut values = Vec::new();
+    header.encode(&mut values);
+    values
+}
+
+fn decode<H: Header>(values: &[HeaderValue], sample: &[&str]) -> H {
+    H::decode(&mut values.iter())
+        .unwrap_or_else(|_| panic!("{} failed to decode {:?}", H::name(), sample))
+}
+
+fn to_values(sample: &[&str]) -> Vec<HeaderValue> {
+    sample.iter().map(|s| s.parse().unwrap()).collect()
+}
+
+/// Check the encoding is stable, returning the decoded and re-decoded
+/// headers.
+fn stable<H: Header>(sample: &[&str]) -> (H, H) {
+    let first = decode::<H>(&to_values(sample), sample);
+    let encoded = encode(&first);
+    let second = decode::<H>(&encoded, sample);
+    assert_eq!(
+        encode(&second),
+        encoded,
+        "{} re-encoded differently from {:?}",
+        H::name(),
+        sample
+    );
+    (first, second)
+}
+
+fn symmetric<H: Header + PartialEq + Debug>(sample: &[&str]) {
+    let (first, second) = stable::<H>(sample);
+    assert_eq!(
+        first,
+        second,
+        "{} decoded differently after encoding {:?}",
+        H::name(),
+        sample
+    );
+}
+
+macro_rules! round_trip {
+    ($($(#[$attr:meta])* $check:ident $ty:ty: [$($sample:expr),+ $(,)?],)+) => {
+        fn run() -> Vec<&'static HeaderName> {
+            let mut covered = Vec::new();
+            $(
+                $(#[$attr])*
+                {
+                    for sample in &[$(&$sample[..]),+] {
+                        $check::<$ty>(sample);
+                    }
+                    covered.push(<$ty as Header>::name());
+                }
+            )+
+            covered
+        }
+    };
+}
+
+round_trip! {
+    symmetric Accept: [
+        ["text/html, application/xhtml+xml, application/xml;q=0.9, */*;q=0.8"],
+        ["text/plain; format=\"a b\"; q=0.5", "image/*"],
+    ],
+    symmetric AcceptCH: [["Sec-CH-UA-Platform, Device-Memory"]],
+    stable AcceptEncoding: [["gzip;q=1.0, identity; q=0.5, *;q=0"], ["br", "gzip"]],
+    symmetric AcceptPost: [["text/turtle, application/ld+json"], ["image/*"]],
+    symmetric AcceptRanges: [["bytes"], ["none"]],
+    symmetric AccessControlAllowCredentials: [["true"]],
+    symmetric AccessControlAllowHeaders: [["Content-Type, X-Custom", "Authorization"]],
+    symmetric AccessControlAllowMethods: [["GET, POST", "DELETE"]],
+    symmetric AccessControlAllowOrigin: [["*"], ["null"], ["https://a.example:8443"]],
+    stable AccessControlExposeHeaders: [["Content-Length, X-Kuma-Revision"]],
+    symmetric AccessControlMaxAge: [["600"]],
+    stable AccessControlRequestHeaders: [["content-type, x-custom"]],
+    symmetric AccessControlRequestMethod: [["PUT"]],
+    symmetric Age: [["0"], ["86400"]],
+    symmetric Allow: [["GET, HEAD, PUT"], ["OPTIONS", "fOObAr"]],
+    symmetric AltUsed: [["alternate.example.net"], ["[2001:db8::1]:8443"]],
+    symmetric Authorization<Basic>: [["Basic QWxhZGRpbjpvcGVuIHNlc2FtZQ=="]],
+    symmetric Authorization<Bearer>: [["Bearer mF_9.B5f-4.1JqM"]],
+    symmetric CacheControl: [
+        ["no-cache, private, max-age=0"],
+        ["public, max-age=3600, s-maxage=60", "stale-while-revalidate=30"],
+        ["no-cache=\"Set-Cookie, Cookie\", private=\"X-Custom\""],
+    ],
+    symmetric CacheStatus: [
+        ["OriginCache; hit; ttl=1100, \"CDN Company Here\"; hit; ttl=545"],
+        ["ExampleCache; fwd=uri-miss; stored"],
+    ],
+    stable Connection: [["keep-alive, Upgrade"], ["close"]],
+    symmetric ContentDigest: [["sha-256=:X48E9qOokqqrvdts8nOJRJN3OWDUoyWxBf7kbu9DBPE=:"]],
+    stable ContentDisposition: [
+        ["inline"],
+        ["attachment; filename=\"quarterly report.pdf\""],
+        ["form-data; name=\"field\""],
+    ],
+    stable ContentEncoding: [["gzip"], ["deflate, br"]],
+    symmetric ContentLength: [["0"], ["1024"]],
+    symmetric ContentLocation: [["/hypertext/Overview.html"]],
+    symmetric ContentRange: [["bytes 0-499/1234"], ["bytes */1234"], ["bytes 10-20/*"]],
+    symmetric ContentSecurityPolicy: [
+        ["default-src 'self'; img-src * data:"],
+        ["script-src 'nonce-2726c7f26c' https://cdn.example; upgrade-insecure-requests"],
+    ],
+    symmetric ContentSecurityPolicyReportOnly: [["default-src 'self'; report-to csp-endpoint"]],
+    symmetric ContentType: [
+        ["text/html; charset=utf-8"],
+        ["multipart/form-data; boundary=\"a b\""],
+    ],
+    stable Cookie: [["foo=bar; session=\"a b\""], ["a=1", "b=2"]],
+    symmetric Date: [["Tue, 15 Nov 1994 08:12:31 GMT"]],
+    symmetric DeviceMemory: [["0.5"], ["4"]],
+    symmetric Dnt: [["0"], ["1"]],
+    symmetric ETag: [["\"xyzzy\""], ["W/\"xyzzy\""], ["\"\""]],
+    symmetric Expect: [["100-continue"]],
+    symmetric ExpectCT: [["max-age=86400, enforce, report-uri=\"https://example.com/report\""]],
+    symmetric Expires: [["Thu, 01 Dec 1994 16:00:00 GMT"]],
+    symmetric FeaturePolicy: [["geolocation 'self' https://a.example; camera 'none'"]],
+    symmetric Forwarded: [
+        ["for=192.0.2.43, for=\"[2001:db8:cafe::17]:4711\";proto=https"],
+        ["for=\"_gazonk\";by=_hidden", "for=unknown;host=\"a.example:8080\""],
+    ],
+    symmetric Host: [["example.com"], ["example.com:8080"], ["[::1]:80"]],
+    symmetric IdempotencyKey: [["\"8e03978e-40d5-43e8-bc93-6894a57f9324\""]],
+    symmetric IfMatch: [["*"], ["\"xyzzy\", \"r2d2xxxx\"", "W/\"c3piozzzz\""]],
+    symmetric IfModifiedSince: [["Sat, 29 Oct 1994 19:43:31 GMT"]],
+    symmetric IfNoneMatch: [["*"], ["W/\"xyzzy\", \"r2d2xxxx\""]],
+    symmetric IfRange: [["\"xyzzy\""], ["Sat, 29 Oct 1994 19:43:31 GMT"]],
+    symmetric IfUnmodifiedSince: [["Sat, 29 Oct 1994 19:43:31 GMT"]],
+    symmetric LastModified: [["Sat, 29 Oct 1994 19:43:31 GMT"]],
+    symmetric Link: [
+        ["</style.css>; rel=preload; as=style, <https://cdn.example>; rel=preconnect"],
+        ["</a>; title=\"a, \\\"b\\\"\"; crossorigin"],
+    ],
+    symmetric Location: [["/People.html#tim"], ["https://a.example/a?b=c"]],
+    symmetric Nel: [["{\"report_to\":\"nel\",\"max_age\":86400,\"include_subdomains\":true,\"failure_fraction\":0.5}"]],
+    symmetric Origin: [["null"], ["https://a.example"], ["http://a.example:8080"]],
+    symmetric Pragma: [["no-cache"], ["x-custom"]],
+    symmetric Priority: [["u=5, i"], ["i"], ["u=0"], [""]],
+    stable ProxyConnection: [["keep-alive"]],
+    symmetric ProxyAuthorization<Basic>: [["Basic QWxhZGRpbjpvcGVuIHNlc2FtZQ=="]],
+    #[allow(deprecated)]
+    symmetric PublicKeyPins: [["pin-sha256=\"d6qzRu9zOECb90Uez27xWltNsj0e1Md7GkYYkVoZWmM=\"; max-age=5184000; includeSubDomains"]],
+    symmetric Range: [["bytes=1000-"], ["bytes=-2000"], ["bytes=0-1,30-40"]],
+    symmetric Referer: [["https://a.example/page?q=1"], ["/People.html"]],
+    symmetric ReferrerPolicy: [["no-referrer"], ["unsafe-url, strict-origin-when-cross-origin"]],
+    symmetric Refresh: [["5"], ["0; url=https://example.com/"]],
+    symmetric ReportTo: [["{\"group\":\"csp\",\"max_age\":10886400,\"endpoints\":[{\"url\":\"https://example.com/csp\"}]}"]],
+    symmetric RetryAfter: [["120"], ["Fri, 31 Dec 1999 23:59:59 GMT"]],
+    symmetric SecChUa: [["\"Chromium\";v=\"110\", \"Not A(Brand\";v=\"24\""]],
+    symmetric SecPurpose: [["prefetch"], ["prefetch;prerender"]],
+    symmetric SecWebsocketAccept: [["s3pPLMBiTxaQ9kYGzzhZRbK+xOo="]],
+    symmetric SecWebsocketKey: [["dGhlIHNhbXBsZSBub25jZQ=="]],
+    symmetric SecWebsocketVersion: [["13"], ["13, 8, 7"]],
+    symmetric Server: [["nginx/1.25.3 (Ubuntu)"]],
+    stable SetCookie: [["sid=1; Path=/; HttpOnly", "theme=dark; Max-Age=3600"]],
+    stable SetCookies: [["sid=1; Path=/; HttpOnly", "theme=dark; Max-Age=3600"]],
+    symmetric Signature: [["sig1=:P0wLUszWQjoi54udOtydf9IWTfNhy+r53jGFj9XZuP4=:"]],
+    symmetric SignatureInput: [["sig1=(\"@method\" \"@authority\" \"@path\");created=1618884473;keyid=\"test-key-rsa-pss\""]],
+    symmetric SourceMap: [["/path/to/file.js.map"]],
+    symmetric StrictTransportSecurity: [["max-age=31536000; includeSubDomains; preload"]],
+    symmetric Te: [["trailers"], ["trailers, deflate;q=0.5"]],
+    symmetric Tk: [["N"], ["T;fRx42"]],
+    stable TransferEncoding: [["gzip, chunked"]],
+    symmetric Upgrade: [["websocket"], ["HTTP/2.0, SHTTP/1.3, IRC/6.9"]],
+    symmetric UpgradeInsecureRequests: [["1"]],
+    symmetric UserAgent: [["Mozilla/5.0 (X11; Linux x86_64) Gecko/20100101 Firefox/121.0"]],
+    symmetric Vary: [["*"], ["Accept-Encoding", "Cookie"]],
+    symmetric Via: [["1.0 fred, 1.1 p.example.net (Apache/1.1)", "HTTP/2 [::1]:8080"]],
+    symmetric ViewportWidth: [["320"]],
+    symmetric WantContentDigest: [["sha-256=3, sha-512=10"]],
+    symmetric WantReprDigest: [["sha-512=3, sha-256=10"]],
+    symmetric WwwAuthenticate: [
+        ["Basic realm=\"simple\", Bearer realm=\"a, b\", error=\"invalid_token\""],
+        ["Newauth realm=\"apps\", type=1, title=\"Login to \\\"apps\\\"\""],
+    ],
+    symmetric XRobotsTag: [["noindex, nofollow"], ["googlebot: nofollow", "otherbot: noindex, max-snippet:20"]],
+}
+
+#[test]
+fn round_trips() {
+    run();
+}
+
+#[test]
+fn covers_single_value_headers() {
+    let covered = run();
+    for name in ::single_value::NAMES {
+        let name = name();
+        assert!(
+            covered.contains(&name),
+            "{} is missing from the round-trip list",
+            name
+        );
+    }
+}
@@ -0,0 +1,176 @@
+//! Sec-CH-UA header and types.
+
+use util::sf::{self, BareItem, Item, Member, Parameters};
+use util::{self, TryFromValues};
+use {HeaderName, HeaderValue};
+
+const SEC_CH_UA: HeaderName = HeaderName::from_static("sec-ch-ua");
+
+/// `Sec-CH-UA` header, defined in
+/// [User-Agent Client Hints](https://wicg.github.io/ua-client-hints/#sec-ch-ua)
+///
+/// The `Sec-CH-UA` request header field gives a server information about a
+/// user agent's branding and significant version. It is a low-entropy
+/// client hint, so user agents send it by default.
+///
+/// Brands are quoted strings, and intentionally include unusual characters
+/// (such as the "GREASE" brand `"Not A(Brand"`), so that servers don't
+/// rely on a particular format.
+///
+/// # ABNF
+///
+/// ```text
+/// Sec-CH-UA = sf-list   ; of sf-string brands, each with a "v" parameter
+/// ```
+///
+/// # Example values
+///
+/// * `"Chromium";v="110", "Not A(Brand";v="24", "Google Chrome";v="110"`
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// # extern crate http;
+/// use headers::{HeaderMapExt, SecChUa};
+///
+/// let mut map = http::HeaderMap::new();
+/// map.insert("sec-ch-ua", r#""Chromium";v="110", "Not A(Brand";v="24""#.parse().unwrap());
+///
+/// let ua = map.typed_get::<SecChUa>().unwrap();
+/// assert_eq!(ua.find("Chromium").unwrap().version(), "110");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SecChUa(Vec<Brand>);
+
+/// A single brand in a `Sec-CH-UA` header.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Brand {
+    name: String,
+    version: String,
+}
+
+impl SecChUa {
+    /// Find the brand with the given name, if present.
+    ///
+    /// Brand names are compared exactly.
+    pub fn find(&self, name: &str) -> Option<&Brand> {
+        self.0.iter().find(|brand| brand.name == name)
+    }
+
+    /// Iterate the brands of this `Sec-CH-UA`.
+    pub fn iter(&self) -> impl Iterator<Item = &Brand> {
+        self.0.iter()
+    }
+}
+
+impl Brand {
+    /// The brand name, such as `Chromium`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The brand's significant version, such as `110`.
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    fn from_member(member: Member) -> Option<Brand> {
+        let item = match member {
+            Member::Item(item) => item,
+            Member::InnerList(..) => return None,
+        };
+
+        let name = item.bare.as_str()?.to_owned();
+        let version = item.params.get("v")?.as_str()?.to_owned();
+
+        Some(Brand { name, version })
+    }
+
+    fn to_member(&self) -> Member {
+        let mut params = Parameters::default();
+        params.insert("v".to_owned(), BareItem::String(self.version.clone()));
+
+        Member::Item(Item {
+            bare: BareItem::String(self.name.clone()),
+            params,
+        })
+    }
+}
+
+impl ::Header for SecChUa {
+    fn name() -> &'static HeaderName {
+        static NAME: HeaderName = SEC_CH_UA;
+        &NAME
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        SecChUa::try_from_values(values)
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        let list = sf::List(self.0.iter().map(Brand::to_member).collect());
+        values.extend(::std::iter::once(util::fmt(list)));
+    }
+}
+
+impl ::NamedHeader for SecChUa {
+    const NAME: ::HeaderName = SEC_CH_UA;
+}
+
+impl TryFromValues for SecChUa {
+    fn try_from_values<'i, I>(values: &mut I) -> Result<Self, ::Error>
+    where
+        I: Iterator<Item = &'i HeaderValue>,
+    {
+        sf::parse_list(values)?
+            .0
+            .into_iter()
+            .map(Brand::from_member)
+            .collect::<Option<Vec<_>>>()
+            .map(SecChUa)
+            .ok_or_else(::Error::invalid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    const CHROME: &str = r#""Chromium";v="110", "Not A(Brand";v="24", "Google Chrome";v="110""#;
+
+    #[test]
+    fn decode() {
+        let ua = test_decode::<SecChUa>(&[CHROME]).unwrap();
+
+        let brands = ua.iter().collect::<Vec<_>>();
+        assert_eq!(brands.len(), 3);
+        assert_eq!(brands[1].name(), "Not A(Brand");
+        assert_eq!(brands[1].version(), "24");
+
+        assert_eq!(ua.find("Google Chrome").unwrap().version(), "110");
+        assert_eq!(ua.find("google chrome"), None);
+    }
+
+    #[test]
+    fn decode_escaped_quotes() {
+        let ua = test_decode::<SecChUa>(&[r#""Not\"A\\Brand";v="99""#]).unwrap();
+        assert_eq!(ua.iter().next().unwrap().name(), "Not\"A\\Brand");
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<SecChUa>(&["Chromium;v=\"110\""]), None);
+        assert_eq!(test_decode::<SecChUa>(&["\"Chromium\""]), None);
+        assert_eq!(test_decode::<SecChUa>(&["\"Chromium\";v=110"]), None);
+        assert_eq!(test_decode::<SecChUa>(&["\"Chromium;v=\"110\""]), None);
+    }
+
+    #[test]
+    fn round_trip() {
+        let ua = test_decode::<SecChUa>(&[CHROME]).unwrap();
+        let headers = test_encode(ua);
+        assert_eq!(headers["sec-ch-ua"], CHROME);
+    }
+}
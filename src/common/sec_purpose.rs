@@ -0,0 +1,269 @@
+//! Sec-Purpose header and types.
+
+use util::sf::{self, BareItem, Item};
+use util::{self, IterExt};
+use {HeaderMap, HeaderName, HeaderValue};
+
+const SEC_PURPOSE: HeaderName = HeaderName::from_static("sec-purpose");
+const PURPOSE: HeaderName = HeaderName::from_static("purpose");
+
+/// `Sec-Purpose` header, defined in
+/// [Fetch](https://fetch.spec.whatwg.org/#sec-purpose-header)
+///
+/// The `Sec-Purpose` request header field marks a request made
+/// speculatively, such as to prefetch or prerender a page the user might
+/// navigate to. Servers can use it to skip side effects, such as counting
+/// a page view, until the user actually navigates.
+///
+/// Older user agents send the same tokens in a `Purpose` header instead,
+/// which `SecPurpose::from_headers` falls back to.
+///
+/// # ABNF
+///
+/// ```text
+/// Sec-Purpose = sf-item   ; a token, such as prefetch, with parameters
+/// ```
+///
+/// # Example values
+///
+/// * `prefetch`
+/// * `prefetch;prerender`
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// # extern crate http;
+/// use headers::sec_purpose::Purpose;
+/// use headers::{HeaderMapExt, SecPurpose};
+///
+/// let mut map = http::HeaderMap::new();
+/// map.insert("sec-purpose", "prefetch;prerender".parse().unwrap());
+///
+/// let purpose = map.typed_get::<SecPurpose>().unwrap();
+/// assert_eq!(purpose.purpose(), &Purpose::Prerender);
+/// assert!(purpose.is_prefetch());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct SecPurpose {
+    purpose: Purpose,
+    item: Item,
+}
+
+/// The purpose of a speculative request, in a `Sec-Purpose` header.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Purpose {
+    /// `prefetch`, fetching a resource that will probably be needed.
+    Prefetch,
+    /// `prefetch;prerender`, fetching a page to render it ahead of a
+    /// navigation.
+    Prerender,
+    /// Any other token, lowercased, kept for purposes defined later.
+    Other(String),
+}
+
+impl SecPurpose {
+    /// `Sec-Purpose: prefetch`
+    pub fn prefetch() -> SecPurpose {
+        SecPurpose {
+            purpose: Purpose::Prefetch,
+            item: Item::new(BareItem::Token("prefetch".to_owned())),
+        }
+    }
+
+    /// `Sec-Purpose: prefetch;prerender`
+    pub fn prerender() -> SecPurpose {
+        let mut item = Item::new(BareItem::Token("prefetch".to_owned()));
+        item.params
+            .insert("prerender".to_owned(), BareItem::Boolean(true));
+        SecPurpose {
+            purpose: Purpose::Prerender,
+            item,
+        }
+    }
+
+    /// Decode a `SecPurpose` from a `HeaderMap`, falling back to the legacy
+    /// `Purpose` header if there is no `Sec-Purpose` header.
+    pub fn from_headers(headers: &HeaderMap) -> Option<SecPurpose> {
+        let mut values = headers.get_all(&SEC_PURPOSE).iter().peekable();
+        if values.peek().is_some() {
+            return ::Header::decode(&mut values).ok();
+        }
+        ::Header::decode(&mut headers.get_all(&PURPOSE).iter()).ok()
+    }
+
+    /// The purpose of the request.
+    pub fn purpose(&self) -> &Purpose {
+        &self.purpose
+    }
+
+    /// Whether this is a prefetch, including one to prerender.
+    pub fn is_prefetch(&self) -> bool {
+        match self.purpose {
+            Purpose::Prefetch | Purpose::Prerender => true,
+            Purpose::Other(_) => false,
+        }
+    }
+
+    /// Whether this is a prefetch to prerender the page.
+    pub fn is_prerender(&self) -> bool {
+        self.purpose == Purpose::Prerender
+    }
+}
+
+impl ::Header for SecPurpose {
+    fn name() -> &'static HeaderName {
+        static NAME: HeaderName = SEC_PURPOSE;
+        &NAME
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        let value = values.just_one().ok_or_else(::Error::invalid)?;
+        let s = value.to_str().map_err(|_| ::Error::invalid())?;
+        let item = sf::parse_item(&lowercase_keys(s))?;
+
+        let purpose = match item.bare.as_token() {
+            Some(token) if token.eq_ignore_ascii_case("prefetch") => {
+                if item.params.get("prerender") == Some(&BareItem::Boolean(true)) {
+                    Purpose::Prerender
+                } else {
+                    Purpose::Prefetch
+                }
+            }
+            Some(other) => Purpose::Other(other.to_ascii_lowercase()),
+            None => return Err(::Error::invalid()),
+        };
+        Ok(SecPurpose { purpose, item })
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once(util::fmt(&self.item)));
+    }
+}
+
+impl ::NamedHeader for SecPurpose {
+    const NAME: ::HeaderName = SEC_PURPOSE;
+}
+
+/// Lowercase the parameter keys of an item, which structured fields only
+/// allow in lowercase, leaving strings, byte sequences and tokens as sent.
+fn lowercase_keys(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    let mut in_key = false;
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                out.push(c);
+                while let Some(c) = chars.next() {
+                    out.push(c);
+                    match c {
+                        '\\' => out.extend(chars.next()),
+                        '"' => break,
+                        _ => (),
+                    }
+                }
+            }
+            ':' => {
+                out.push(c);
+                for c in chars.by_ref() {
+                    out.push(c);
+                    if c == ':' {
+                        break;
+                    }
+                }
+            }
+            ';' => {
+                in_key = true;
+                out.push(c);
+            }
+            ' ' if in_key => out.push(c),
+            _ => {
+                in_key = in_key && (c.is_ascii_alphanumeric() || "_-.*".contains(c));
+                out.push(if in_key { c.to_ascii_lowercase() } else { c });
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn prefetch() {
+        let purpose = test_decode::<SecPurpose>(&["prefetch"]).unwrap();
+        assert_eq!(purpose.purpose(), &Purpose::Prefetch);
+        assert!(purpose.is_prefetch());
+        assert!(!purpose.is_prerender());
+        assert_eq!(purpose, SecPurpose::prefetch());
+
+        let headers = test_encode(purpose);
+        assert_eq!(headers["sec-purpose"], "prefetch");
+    }
+
+    #[test]
+    fn prerender() {
+        let purpose = test_decode::<SecPurpose>(&["Prefetch;Prerender"]).unwrap();
+        assert!(purpose.is_prerender());
+        assert_eq!(purpose.purpose(), SecPurpose::prerender().purpose());
+
+        let headers = test_encode(SecPurpose::prerender());
+        assert_eq!(headers["sec-purpose"], "prefetch;prerender");
+
+        let purpose = test_decode::<SecPurpose>(&["prefetch;prerender=?0"]).unwrap();
+        assert_eq!(purpose.purpose(), &Purpose::Prefetch);
+    }
+
+    #[test]
+    fn params_kept_as_sent() {
+        let purpose =
+            test_decode::<SecPurpose>(&["PREFETCH;Prerender;Note=\"Hi; There\";Key=:SGk=:"])
+                .unwrap();
+        assert!(purpose.is_prerender());
+
+        let headers = test_encode(purpose);
+        assert_eq!(
+            headers["sec-purpose"],
+            "PREFETCH;prerender;note=\"Hi; There\";key=:SGk=:"
+        );
+    }
+
+    #[test]
+    fn other() {
+        let purpose = test_decode::<SecPurpose>(&["preview;anonymous-client-ip"]).unwrap();
+        assert_eq!(purpose.purpose(), &Purpose::Other("preview".to_owned()));
+        assert!(!purpose.is_prefetch());
+
+        let headers = test_encode(purpose);
+        assert_eq!(headers["sec-purpose"], "preview;anonymous-client-ip");
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<SecPurpose>(&[""]), None);
+        assert_eq!(test_decode::<SecPurpose>(&["\"prefetch\""]), None);
+        assert_eq!(test_decode::<SecPurpose>(&["prefetch, prerender"]), None);
+        assert_eq!(test_decode::<SecPurpose>(&["prefetch", "prefetch"]), None);
+    }
+
+    #[test]
+    fn from_headers_legacy() {
+        let mut headers = HeaderMap::new();
+        headers.insert("purpose", "prefetch".parse().unwrap());
+        assert_eq!(
+            SecPurpose::from_headers(&headers),
+            Some(SecPurpose::prefetch())
+        );
+
+        headers.insert("sec-purpose", "prefetch;prerender".parse().unwrap());
+        assert_eq!(
+            SecPurpose::from_headers(&headers),
+            Some(SecPurpose::prerender())
+        );
+
+        assert_eq!(SecPurpose::from_headers(&HeaderMap::new()), None);
+    }
+}
@@ -30,9 +30,42 @@ derive_header! {
     name: SEC_WEBSOCKET_ACCEPT
 }
 
+impl SecWebsocketAccept {
+    /// Compute the `Sec-Websocket-Accept` a server must answer `key` with,
+    /// as defined in
+    /// [RFC6455](https://tools.ietf.org/html/rfc6455#section-4.2.2).
+    pub fn from_key(key: &SecWebsocketKey) -> SecWebsocketAccept {
+        sign(key.0.as_bytes())
+    }
+
+    /// Check this is the right answer to `key`, as a client must before
+    /// using the connection.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate headers;
+    /// # extern crate http;
+    /// use headers::{HeaderMapExt, SecWebsocketAccept, SecWebsocketKey};
+    ///
+    /// let mut request = http::HeaderMap::new();
+    /// request.insert("sec-websocket-key", "dGhlIHNhbXBsZSBub25jZQ==".parse().unwrap());
+    /// let key = request.typed_get::<SecWebsocketKey>().unwrap();
+    ///
+    /// let mut response = http::HeaderMap::new();
+    /// response.typed_insert(SecWebsocketAccept::from_key(&key));
+    ///
+    /// let accept = response.typed_get::<SecWebsocketAccept>().unwrap();
+    /// assert!(accept.verify(&key));
+    /// ```
+    pub fn verify(&self, key: &SecWebsocketKey) -> bool {
+        *self == SecWebsocketAccept::from_key(key)
+    }
+}
+
 impl From<SecWebsocketKey> for SecWebsocketAccept {
     fn from(key: SecWebsocketKey) -> SecWebsocketAccept {
-        sign(key.0.as_bytes())
+        SecWebsocketAccept::from_key(&key)
     }
 }
 
@@ -40,7 +73,7 @@ fn sign(key: &[u8]) -> SecWebsocketAccept {
     let mut sha1 = Sha1::default();
     sha1.update(key);
     sha1.update(&b"258EAFA5-E914-47DA-95CA-C5AB0DC85B11"[..]);
-    let b64 = Bytes::from(ENGINE.encode(&sha1.finalize()));
+    let b64 = Bytes::from(ENGINE.encode(sha1.finalize()));
 
     let val = ::HeaderValue::from_maybe_shared(b64).expect("base64 is a valid value");
 
@@ -59,6 +92,23 @@ mod tests {
         let accept = SecWebsocketAccept::from(key);
         let headers = test_encode(accept);
 
-        assert_eq!(headers["sec-websocket-accept"], "s3pPLMBiTxaQ9kYGzzhZRbK+xOo=");
+        assert_eq!(
+            headers["sec-websocket-accept"],
+            "s3pPLMBiTxaQ9kYGzzhZRbK+xOo="
+        );
+    }
+
+    #[test]
+    fn verify() {
+        // From https://tools.ietf.org/html/rfc6455#section-1.2
+        let key = test_decode::<SecWebsocketKey>(&["dGhlIHNhbXBsZSBub25jZQ=="]).unwrap();
+        let accept = test_decode::<SecWebsocketAccept>(&["s3pPLMBiTxaQ9kYGzzhZRbK+xOo="]).unwrap();
+        assert_eq!(SecWebsocketAccept::from_key(&key), accept);
+        assert!(accept.verify(&key));
+
+        let other = test_decode::<SecWebsocketKey>(&["x3JJHMbDL1EzLkh9GBhXDw=="]).unwrap();
+        assert!(!accept.verify(&other));
+        let wrong = test_decode::<SecWebsocketAccept>(&["s3pPLMBiTxaQ9kYGzzhZRbK+xOo"]).unwrap();
+        assert!(!wrong.verify(&key));
     }
 }
@@ -1,10 +1,94 @@
+use std::fmt;
+
+use util;
+
 /// The `Sec-Websocket-Version` header.
+///
+/// A client sends the version of the WebSocket protocol it wants to use.
+/// A server that doesn't support it responds with the versions it does
+/// support, as a list, such as `13, 8, 7`.
+///
+/// The list is kept inline, so this type is `Copy`, and it holds at most
+/// 16 versions. That is more than have ever been registered, and a
+/// longer list fails to decode.
+///
+/// # ABNF
+///
+/// ```text
+/// Sec-WebSocket-Version = 1#version
+/// version               = DIGIT / ( NZDIGIT DIGIT )
+///                       / ( "1" DIGIT DIGIT ) / ( "2" DIGIT DIGIT )
+/// ```
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// use headers::SecWebsocketVersion;
+///
+/// let versions = SecWebsocketVersion::from_versions(vec![13, 8, 7]).unwrap();
+/// assert!(versions.supports(8));
+/// assert_eq!(versions.highest(), 13);
+/// ```
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
-pub struct SecWebsocketVersion(u8);
+pub struct SecWebsocketVersion {
+    // Unused entries are zero, so the derived traits only see the list.
+    versions: [u8; MAX_VERSIONS],
+    len: u8,
+}
+
+const MAX_VERSIONS: usize = 16;
+
+error_type!(InvalidSecWebsocketVersion);
 
 impl SecWebsocketVersion {
     /// `Sec-Websocket-Version: 13`
-    pub const V13: SecWebsocketVersion = SecWebsocketVersion(13);
+    pub const V13: SecWebsocketVersion = SecWebsocketVersion {
+        versions: [13, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+        len: 1,
+    };
+
+    /// Create a `Sec-Websocket-Version` listing several versions.
+    ///
+    /// Fails if `versions` is empty, or has more than 16 versions.
+    pub fn from_versions(versions: Vec<u8>) -> Result<Self, InvalidSecWebsocketVersion> {
+        if versions.is_empty() || versions.len() > MAX_VERSIONS {
+            return Err(InvalidSecWebsocketVersion { _inner: () });
+        }
+        let mut list = SecWebsocketVersion {
+            versions: [0; MAX_VERSIONS],
+            len: versions.len() as u8,
+        };
+        list.versions[..versions.len()].copy_from_slice(&versions);
+        Ok(list)
+    }
+
+    /// The versions, in the order they were sent.
+    pub fn versions(&self) -> &[u8] {
+        &self.versions[..usize::from(self.len)]
+    }
+
+    /// Check if `version` is one of the listed versions.
+    pub fn supports(&self, version: u8) -> bool {
+        self.versions().contains(&version)
+    }
+
+    /// The highest listed version.
+    pub fn highest(&self) -> u8 {
+        self.versions()
+            .iter()
+            .copied()
+            .max()
+            .expect("at least one version")
+    }
+}
+
+fn parse_version(s: &str) -> Option<u8> {
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) || (s.len() > 1 && s.starts_with('0'))
+    {
+        return None;
+    }
+    s.parse().ok()
 }
 
 impl ::Header for SecWebsocketVersion {
@@ -13,22 +97,38 @@ impl ::Header for SecWebsocketVersion {
     }
 
     fn decode<'i, I: Iterator<Item = &'i ::HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
-        values
-            .next()
-            .and_then(|value| {
-                if value == "13" {
-                    Some(SecWebsocketVersion::V13)
-                } else {
-                    None
-                }
-            })
-            .ok_or_else(::Error::invalid)
+        let mut versions = Vec::new();
+        for value in values {
+            let s = value.to_str().map_err(|_| ::Error::invalid())?;
+            for version in s.split(',').map(str::trim) {
+                versions.push(parse_version(version).ok_or_else(::Error::invalid)?);
+            }
+        }
+
+        if versions == [13] {
+            Ok(SecWebsocketVersion::V13)
+        } else {
+            SecWebsocketVersion::from_versions(versions).map_err(|_| ::Error::invalid())
+        }
     }
 
     fn encode<E: Extend<::HeaderValue>>(&self, values: &mut E) {
-        debug_assert_eq!(self.0, 13);
+        let value = if *self == SecWebsocketVersion::V13 {
+            ::HeaderValue::from_static("13")
+        } else {
+            util::fmt(self)
+        };
+        values.extend(::std::iter::once(value));
+    }
+}
+
+impl ::NamedHeader for SecWebsocketVersion {
+    const NAME: ::HeaderName = ::http::header::SEC_WEBSOCKET_VERSION;
+}
 
-        values.extend(::std::iter::once(::HeaderValue::from_static("13")));
+impl fmt::Display for SecWebsocketVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        util::csv::fmt_comma_delimited(f, self.versions().iter())
     }
 }
 
@@ -45,9 +145,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn decode_list() {
+        let versions = test_decode::<SecWebsocketVersion>(&["13, 8, 7"]).unwrap();
+        assert_eq!(versions.versions(), [13, 8, 7]);
+        assert!(versions.supports(13));
+        assert!(versions.supports(7));
+        assert!(!versions.supports(6));
+        assert_eq!(versions.highest(), 13);
+
+        // It is `Copy`, so `versions` is still usable after this.
+        let headers = test_encode(versions);
+        assert_eq!(headers["sec-websocket-version"], "13, 8, 7");
+        assert_eq!(versions.versions(), [13, 8, 7]);
+
+        let versions = test_decode::<SecWebsocketVersion>(&["8", "25"]).unwrap();
+        assert_eq!(versions.highest(), 25);
+    }
+
     #[test]
     fn decode_fail() {
-        assert_eq!(test_decode::<SecWebsocketVersion>(&["1"]), None,);
+        assert_eq!(test_decode::<SecWebsocketVersion>(&[""]), None);
+        assert_eq!(test_decode::<SecWebsocketVersion>(&["256"]), None);
+        assert_eq!(test_decode::<SecWebsocketVersion>(&["013"]), None);
+        assert_eq!(test_decode::<SecWebsocketVersion>(&["+13"]), None);
+        assert_eq!(test_decode::<SecWebsocketVersion>(&["13,"]), None);
+        assert_eq!(test_decode::<SecWebsocketVersion>(&["thirteen"]), None);
+        assert!(SecWebsocketVersion::from_versions(vec![]).is_err());
+        assert!(SecWebsocketVersion::from_versions(vec![13; 17]).is_err());
+
+        let sixteen = ["1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16"];
+        assert!(test_decode::<SecWebsocketVersion>(&sixteen).is_some());
+        let seventeen = ["1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17"];
+        assert_eq!(test_decode::<SecWebsocketVersion>(&seventeen), None);
     }
 
     #[test]
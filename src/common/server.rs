@@ -2,6 +2,7 @@ use std::fmt;
 use std::str::FromStr;
 
 use util::HeaderValueString;
+use Product;
 
 /// `Server` header, defined in [RFC7231](http://tools.ietf.org/html/rfc7231#section-7.4.2)
 ///
@@ -48,10 +49,29 @@ impl Server {
         Server(HeaderValueString::from_static(s))
     }
 
+    /// Decode the server as text, reading any obs-text as ISO-8859-1.
+    ///
+    /// `decode` rejects a value that isn't visible ASCII, while this
+    /// accepts any bytes, as browsers do. It returns a `String` because a
+    /// `Server` can only hold visible ASCII.
+    pub fn decode_lossy<'i, I>(values: &mut I) -> Result<String, ::Error>
+    where
+        I: Iterator<Item = &'i ::HeaderValue>,
+    {
+        HeaderValueString::decode_lossy(values)
+    }
+
     /// View this `Server` as a `&str`.
     pub fn as_str(&self) -> &str {
         self.0.as_str()
     }
+
+    /// Iterate the products, most significant first.
+    ///
+    /// See `Product` for how the value is split.
+    pub fn products(&self) -> impl Iterator<Item = Product<'_>> {
+        super::product::products(self.as_str())
+    }
 }
 
 error_type!(InvalidServer);
@@ -70,3 +90,36 @@ impl fmt::Display for Server {
         fmt::Display::fmt(&self.0, f)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Server;
+    use HeaderValue;
+
+    #[test]
+    fn decode_lossy_latin1() {
+        let value = HeaderValue::from_bytes(b"Caf\xe9/1.0").unwrap();
+        assert!(<Server as ::Header>::decode(&mut ::std::iter::once(&value)).is_err());
+
+        let lossy = Server::decode_lossy(&mut ::std::iter::once(&value)).unwrap();
+        assert_eq!(lossy, "Caf\u{e9}/1.0");
+
+        let ascii = HeaderValue::from_static("nginx");
+        assert_eq!(
+            Server::decode_lossy(&mut ::std::iter::once(&ascii)).unwrap(),
+            "nginx"
+        );
+    }
+
+    #[test]
+    fn products() {
+        let server = Server::from_static("nginx/1.25.3 (Ubuntu)");
+        assert_eq!(server.as_str(), "nginx/1.25.3 (Ubuntu)");
+
+        let products = server.products().collect::<Vec<_>>();
+        assert_eq!(products.len(), 1);
+        assert_eq!(products[0].name(), "nginx");
+        assert_eq!(products[0].version(), Some("1.25.3"));
+        assert_eq!(products[0].comment(), Some("Ubuntu"));
+    }
+}
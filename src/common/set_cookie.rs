@@ -1,3 +1,7 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use util::{is_token, HttpDate};
+
 /// `Set-Cookie` header, defined [RFC6265](http://tools.ietf.org/html/rfc6265#section-4.1)
 ///
 /// The Set-Cookie HTTP response header is used to send cookies from the
@@ -50,10 +54,124 @@
 /// * `lang=; Expires=Sun, 06 Nov 1994 08:49:37 GMT`
 /// * `lang=en-US; Path=/; Domain=example.com`
 ///
+/// # Multiple cookies
+///
+/// Unlike most headers, `Set-Cookie` can't be combined into a single
+/// comma-separated line, since the `Expires` date contains a comma
+/// ([RFC7230](https://tools.ietf.org/html/rfc7230#section-3.2.2)). Each
+/// `HeaderValue` is kept whole as one cookie, and is never split on commas.
+///
 /// # Example
+///
+/// ```
+/// # extern crate headers;
+/// # extern crate http;
+/// use headers::{HeaderMapExt, SetCookie};
+///
+/// let mut map = http::HeaderMap::new();
+/// map.append("set-cookie", "id=1; Expires=Wed, 21 Oct 2025 07:28:00 GMT".parse().unwrap());
+/// map.append("set-cookie", "lang=en-US".parse().unwrap());
+///
+/// let set_cookie = map.typed_get::<SetCookie>().unwrap();
+/// ```
 #[derive(Clone, Debug)]
 pub struct SetCookie(Vec<::HeaderValue>);
 
+error_type!(InvalidSetCookie);
+
+impl SetCookie {
+    /// Create a `Set-Cookie` for a single cookie, `name=value`, without
+    /// attributes.
+    ///
+    /// Fails if `name` isn't a token, or `value` has characters a cookie
+    /// value can't, such as `;`, whitespace, or a CR or LF. So a cookie
+    /// built from user input can't add attributes or headers of its own.
+    pub fn new(name: &str, value: &str) -> Result<SetCookie, InvalidSetCookie> {
+        let octets = value
+            .strip_prefix('"')
+            .and_then(|v| v.strip_suffix('"'))
+            .unwrap_or(value);
+        if !is_token(name) || !octets.bytes().all(is_cookie_octet) {
+            return Err(InvalidSetCookie { _inner: () });
+        }
+        ::HeaderValue::from_str(&format!("{}={}", name, value))
+            .map(|value| SetCookie(vec![value]))
+            .map_err(|_| InvalidSetCookie { _inner: () })
+    }
+
+    /// When the first cookie expires, as seen at the time `now`.
+    ///
+    /// As in [RFC6265](https://tools.ietf.org/html/rfc6265#section-5.3),
+    /// `Max-Age` takes precedence over `Expires` when both are present, and
+    /// a `Max-Age` of zero or less means the cookie has already expired, in
+    /// which case this returns the `UNIX_EPOCH`. Attributes that can't be
+    /// parsed are ignored.
+    ///
+    /// Returns `None` for a session cookie, which has neither attribute.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate headers;
+    /// # extern crate http;
+    /// use std::time::{Duration, SystemTime};
+    /// use headers::{HeaderMapExt, SetCookie};
+    ///
+    /// let mut map = http::HeaderMap::new();
+    /// map.append("set-cookie", "id=1; Max-Age=60; Expires=Wed, 21 Oct 2015 07:28:00 GMT".parse().unwrap());
+    ///
+    /// let now = SystemTime::now();
+    /// let set_cookie = map.typed_get::<SetCookie>().unwrap();
+    /// assert_eq!(set_cookie.effective_expiry(now), Some(now + Duration::from_secs(60)));
+    /// ```
+    pub fn effective_expiry(&self, now: SystemTime) -> Option<SystemTime> {
+        let attrs = self.0.first()?.to_str().ok()?.split(';').skip(1);
+
+        let mut max_age = None;
+        let mut expires = None;
+        for attr in attrs {
+            let mut iter = attr.splitn(2, '=');
+            let name = iter.next().unwrap_or("").trim();
+            let value = iter.next().unwrap_or("").trim();
+
+            if name.eq_ignore_ascii_case("max-age") {
+                if let Some(expiry) = parse_max_age(value, now) {
+                    max_age = Some(expiry);
+                }
+            } else if name.eq_ignore_ascii_case("expires") {
+                if let Ok(date) = value.parse::<HttpDate>() {
+                    expires = Some(SystemTime::from(date));
+                }
+            }
+        }
+        max_age.or(expires)
+    }
+}
+
+fn is_cookie_octet(b: u8) -> bool {
+    matches!(b, 0x21 | 0x23..=0x2B | 0x2D..=0x3A | 0x3C..=0x5B | 0x5D..=0x7E)
+}
+
+/// Parses a `Max-Age` value into the time it expires.
+///
+/// A value too large to represent is ignored.
+fn parse_max_age(value: &str, now: SystemTime) -> Option<SystemTime> {
+    let (negative, digits) = match value.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, value),
+    };
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    let secs = digits.parse::<u64>().unwrap_or(u64::MAX);
+    if negative || secs == 0 {
+        Some(UNIX_EPOCH)
+    } else {
+        now.checked_add(Duration::from_secs(secs))
+    }
+}
+
 impl ::Header for SetCookie {
     fn name() -> &'static ::HeaderName {
         &::http::header::SET_COOKIE
@@ -74,6 +192,78 @@ impl ::Header for SetCookie {
     }
 }
 
+impl ::NamedHeader for SetCookie {
+    const NAME: ::HeaderName = ::http::header::SET_COOKIE;
+}
+
+/// Every `Set-Cookie` header of a response, as one `SetCookie` per cookie.
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// # extern crate http;
+/// use headers::{HeaderMapExt, SetCookies};
+///
+/// let mut map = http::HeaderMap::new();
+/// map.append("set-cookie", "id=1; Expires=Wed, 21 Oct 2025 07:28:00 GMT".parse().unwrap());
+/// map.append("set-cookie", "lang=en-US".parse().unwrap());
+///
+/// let cookies = map.typed_get::<SetCookies>().unwrap();
+/// assert_eq!(cookies.iter().count(), 2);
+/// assert!(cookies.find("lang").is_some());
+/// ```
+#[derive(Clone, Debug)]
+pub struct SetCookies(Vec<SetCookie>);
+
+impl SetCookies {
+    /// Iterate the cookies, in the order they were sent.
+    pub fn iter(&self) -> impl Iterator<Item = &SetCookie> {
+        self.0.iter()
+    }
+
+    /// Find the first cookie with the given name.
+    pub fn find(&self, name: &str) -> Option<&SetCookie> {
+        self.0
+            .iter()
+            .find(|cookie| cookie.0.first().and_then(cookie_name) == Some(name))
+    }
+}
+
+impl ::Header for SetCookies {
+    fn name() -> &'static ::HeaderName {
+        &::http::header::SET_COOKIE
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i ::HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        let vec = values
+            .map(|value| SetCookie(vec![value.clone()]))
+            .collect::<Vec<_>>();
+
+        if !vec.is_empty() {
+            Ok(SetCookies(vec))
+        } else {
+            Err(::Error::invalid())
+        }
+    }
+
+    fn encode<E: Extend<::HeaderValue>>(&self, values: &mut E) {
+        values.extend(self.0.iter().flat_map(|cookie| cookie.0.iter().cloned()));
+    }
+}
+
+impl ::NamedHeader for SetCookies {
+    const NAME: ::HeaderName = ::http::header::SET_COOKIE;
+}
+
+fn cookie_name(value: &::HeaderValue) -> Option<&str> {
+    let pair = value.to_str().ok()?.split(';').next()?;
+    let mut iter = pair.splitn(2, '=');
+    let name = iter.next()?.trim();
+    iter.next()?;
+    Some(name)
+}
+
 #[cfg(test)]
 mod tests {
     use super::super::{test_decode, test_encode};
@@ -87,6 +277,114 @@ mod tests {
         assert_eq!(set_cookie.0[1], "baz=quux");
     }
 
+    #[test]
+    fn decode_never_splits_commas() {
+        let set_cookie =
+            test_decode::<SetCookie>(&["id=1; Expires=Wed, 21 Oct 2025 07:28:00 GMT"]).unwrap();
+        assert_eq!(set_cookie.0.len(), 1);
+        assert_eq!(
+            set_cookie.0[0],
+            "id=1; Expires=Wed, 21 Oct 2025 07:28:00 GMT"
+        );
+
+        let headers = test_encode(set_cookie);
+        let vals = headers
+            .get_all("set-cookie")
+            .into_iter()
+            .collect::<Vec<_>>();
+        assert_eq!(vals, ["id=1; Expires=Wed, 21 Oct 2025 07:28:00 GMT"]);
+    }
+
+    #[test]
+    fn set_cookies() {
+        let cookies = test_decode::<SetCookies>(&[
+            "id=1; Expires=Wed, 21 Oct 2025 07:28:00 GMT; Secure",
+            "lang=en-US; Path=/",
+            "theme=dark",
+        ])
+        .unwrap();
+        assert_eq!(cookies.iter().count(), 3);
+
+        let id = cookies.find("id").unwrap();
+        assert_eq!(
+            id.0,
+            ["id=1; Expires=Wed, 21 Oct 2025 07:28:00 GMT; Secure"]
+        );
+        assert_eq!(cookies.find("theme").unwrap().0, ["theme=dark"]);
+        assert!(cookies.find("Expires").is_none());
+        assert!(cookies.find("missing").is_none());
+
+        let headers = test_encode(cookies);
+        let vals = headers
+            .get_all("set-cookie")
+            .into_iter()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vals,
+            [
+                "id=1; Expires=Wed, 21 Oct 2025 07:28:00 GMT; Secure",
+                "lang=en-US; Path=/",
+                "theme=dark",
+            ]
+        );
+    }
+
+    #[test]
+    fn effective_expiry() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_600_000_000);
+        let expiry = |s: &str| {
+            test_decode::<SetCookie>(&[s])
+                .unwrap()
+                .effective_expiry(now)
+        };
+        let expires =
+            SystemTime::from("Wed, 21 Oct 2015 07:28:00 GMT".parse::<HttpDate>().unwrap());
+
+        // Max-Age wins over Expires, in either order.
+        let in_a_minute = Some(now + Duration::from_secs(60));
+        assert_eq!(
+            expiry("id=1; Max-Age=60; Expires=Wed, 21 Oct 2015 07:28:00 GMT"),
+            in_a_minute
+        );
+        assert_eq!(
+            expiry("id=1; expires=Wed, 21 Oct 2015 07:28:00 GMT; max-age=60"),
+            in_a_minute
+        );
+
+        assert_eq!(expiry("id=1; Max-Age=60"), in_a_minute);
+        assert_eq!(
+            expiry("id=1; Expires=Wed, 21 Oct 2015 07:28:00 GMT"),
+            Some(expires)
+        );
+        assert_eq!(expiry("id=1"), None);
+
+        // Zero and negative mean it has already expired.
+        assert_eq!(expiry("id=1; Max-Age=0"), Some(UNIX_EPOCH));
+        assert_eq!(expiry("id=1; Max-Age=-5"), Some(UNIX_EPOCH));
+
+        // Invalid attributes are ignored.
+        assert_eq!(
+            expiry("id=1; Max-Age=soon; Expires=Wed, 21 Oct 2015 07:28:00 GMT"),
+            Some(expires)
+        );
+        assert_eq!(expiry("id=1; Max-Age=+60; Expires=never"), None);
+    }
+
+    #[test]
+    fn new() {
+        let headers = test_encode(SetCookie::new("id", "31d4d96e407aad42").unwrap());
+        assert_eq!(headers["set-cookie"], "id=31d4d96e407aad42");
+
+        let headers = test_encode(SetCookie::new("lang", "\"en-US\"").unwrap());
+        assert_eq!(headers["set-cookie"], "lang=\"en-US\"");
+
+        assert!(SetCookie::new("id", "").is_ok());
+        assert!(SetCookie::new("id", "1\r\nSet-Cookie: evil").is_err());
+        assert!(SetCookie::new("id", "1; Domain=evil.example").is_err());
+        assert!(SetCookie::new("id\r\nX", "1").is_err());
+        assert!(SetCookie::new("", "1").is_err());
+    }
+
     #[test]
     fn encode() {
         let set_cookie = SetCookie(vec![
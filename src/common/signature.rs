@@ -0,0 +1,162 @@
+use util::sf::{self, BareItem, Item, Member};
+use util::{self, TryFromValues};
+use {HeaderName, HeaderValue};
+
+const SIGNATURE: HeaderName = HeaderName::from_static("signature");
+
+/// `Signature` header, defined in
+/// [RFC9421](https://www.rfc-editor.org/rfc/rfc9421.html#section-4.2)
+///
+/// The `Signature` header field carries one or more HTTP message
+/// signatures, each under a label. The components and parameters each
+/// signature covers are sent under the same label in a `Signature-Input`
+/// header.
+///
+/// This only carries the signatures; creating and checking them is left to
+/// the application.
+///
+/// # ABNF
+///
+/// ```text
+/// Signature = sf-dictionary
+/// ```
+///
+/// # Example values
+///
+/// * `sig1=:P0wLUszWQjoi54udOtydf9IWTfNhy+r53jGFj9XZuP4=:`
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// use headers::Signature;
+///
+/// let sig = Signature::new("sig1", b"signature bytes").unwrap();
+/// assert_eq!(sig.get("sig1"), Some(&b"signature bytes"[..]));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Signature(Vec<(String, Vec<u8>)>);
+
+error_type!(InvalidSignature);
+
+impl Signature {
+    /// Create a `Signature` with a single labeled signature.
+    ///
+    /// Fails if `label` isn't a valid structured field key, which must
+    /// start with a lowercase letter or `*`.
+    pub fn new(label: &str, signature: &[u8]) -> Result<Signature, InvalidSignature> {
+        Signature(Vec::new()).with_signature(label, signature)
+    }
+
+    /// Add another labeled signature, replacing any with the same label.
+    ///
+    /// Fails if `label` isn't a valid structured field key.
+    pub fn with_signature(
+        mut self,
+        label: &str,
+        signature: &[u8],
+    ) -> Result<Self, InvalidSignature> {
+        if !sf::is_key(label) {
+            return Err(InvalidSignature { _inner: () });
+        }
+        match self.0.iter_mut().find(|entry| entry.0 == label) {
+            Some(entry) => entry.1 = signature.to_vec(),
+            None => self.0.push((label.to_owned(), signature.to_vec())),
+        }
+        Ok(self)
+    }
+
+    /// Get the signature with this label, if there is one.
+    pub fn get(&self, label: &str) -> Option<&[u8]> {
+        self.iter().find(|&(l, _)| l == label).map(|(_, sig)| sig)
+    }
+
+    /// Iterate the labels and their signatures.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[u8])> {
+        self.0.iter().map(|(label, sig)| (label.as_str(), &sig[..]))
+    }
+}
+
+impl ::Header for Signature {
+    fn name() -> &'static HeaderName {
+        static NAME: HeaderName = SIGNATURE;
+        &NAME
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        Signature::try_from_values(values)
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        let mut dict = sf::Dictionary::default();
+        for (label, sig) in &self.0 {
+            let item = Item::new(BareItem::ByteSeq(sig.clone()));
+            dict.insert(label.clone(), Member::Item(item));
+        }
+        values.extend(::std::iter::once(util::fmt(dict)));
+    }
+}
+
+impl ::NamedHeader for Signature {
+    const NAME: ::HeaderName = SIGNATURE;
+}
+
+impl TryFromValues for Signature {
+    fn try_from_values<'i, I>(values: &mut I) -> Result<Self, ::Error>
+    where
+        I: Iterator<Item = &'i HeaderValue>,
+    {
+        let dict = sf::parse_dictionary(values)?;
+        let sigs = dict
+            .iter()
+            .map(
+                |(label, member)| match member.as_item().map(|item| &item.bare) {
+                    Some(BareItem::ByteSeq(sig)) => Some((label.to_owned(), sig.clone())),
+                    _ => None,
+                },
+            )
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(::Error::invalid)?;
+
+        if sigs.is_empty() {
+            Err(::Error::invalid())
+        } else {
+            Ok(Signature(sigs))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let sig = test_decode::<Signature>(&["sig1=:AQID:", "proxy_sig=:BAU=:"]).unwrap();
+        assert_eq!(sig.get("sig1"), Some(&[1, 2, 3][..]));
+        assert_eq!(sig.get("proxy_sig"), Some(&[4, 5][..]));
+        assert_eq!(sig.get("sig2"), None);
+
+        let headers = test_encode(sig);
+        assert_eq!(headers["signature"], "sig1=:AQID:, proxy_sig=:BAU=:");
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<Signature>(&[""]), None);
+        assert_eq!(test_decode::<Signature>(&["sig1"]), None);
+        assert_eq!(test_decode::<Signature>(&["sig1=\"AQID\""]), None);
+        assert_eq!(test_decode::<Signature>(&["sig1=:AQID:, sig2=(1)"]), None);
+    }
+
+    #[test]
+    fn new_checks_label() {
+        assert!(Signature::new("Sig1", b"x").is_err());
+        assert!(Signature::new("", b"x").is_err());
+
+        let sig = Signature::new("a", b"x").unwrap();
+        let sig = sig.with_signature("a", b"y").unwrap();
+        assert_eq!(sig.iter().collect::<Vec<_>>(), [("a", &b"y"[..])]);
+    }
+}
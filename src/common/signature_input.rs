@@ -0,0 +1,459 @@
+//! Signature-Input header and types.
+
+use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use util::sf::{self, BareItem, Item, Member, Parameters};
+use util::{self, TryFromValues};
+use {HeaderName, HeaderValue};
+
+const SIGNATURE_INPUT: HeaderName = HeaderName::from_static("signature-input");
+
+/// `Signature-Input` header, defined in
+/// [RFC9421](https://www.rfc-editor.org/rfc/rfc9421.html#section-4.1)
+///
+/// The `Signature-Input` header field describes what each signature in a
+/// `Signature` header covers: the list of message components, such as
+/// `@method` or `content-digest`, and parameters like when it was created
+/// and which key made it. Both headers use the same labels.
+///
+/// # ABNF
+///
+/// ```text
+/// Signature-Input = sf-dictionary
+/// ```
+///
+/// # Example values
+///
+/// * `sig1=("@method" "@authority" "@path");created=1618884473;keyid="test-key-rsa-pss"`
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// use headers::signature_input::{SignatureInput, SignatureParams};
+///
+/// let params = SignatureParams::new(vec!["@method", "@path", "content-digest"])
+///     .unwrap()
+///     .with_keyid("test-key")
+///     .unwrap();
+/// let input = SignatureInput::new("sig1", params).unwrap();
+///
+/// let params = input.get("sig1").unwrap();
+/// assert_eq!(params.keyid(), Some("test-key"));
+/// assert_eq!(params.components().count(), 3);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct SignatureInput(Vec<(String, SignatureParams)>);
+
+/// The components and parameters covered by a single signature.
+///
+/// The `Display` impl gives the serialized form used as the value of the
+/// `@signature-params` line in the signature base.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SignatureParams {
+    components: Vec<Component>,
+    params: Parameters,
+}
+
+/// A message component covered by a signature, such as `@method` or a
+/// header field name.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Component(Item);
+
+error_type!(InvalidSignatureInput);
+
+impl SignatureInput {
+    /// Create a `Signature-Input` with a single labeled signature.
+    ///
+    /// Fails if `label` isn't a valid structured field key, which must
+    /// start with a lowercase letter or `*`.
+    pub fn new(
+        label: &str,
+        params: SignatureParams,
+    ) -> Result<SignatureInput, InvalidSignatureInput> {
+        SignatureInput(Vec::new()).with_signature(label, params)
+    }
+
+    /// Add another labeled signature, replacing any with the same label.
+    ///
+    /// Fails if `label` isn't a valid structured field key.
+    pub fn with_signature(
+        mut self,
+        label: &str,
+        params: SignatureParams,
+    ) -> Result<Self, InvalidSignatureInput> {
+        if !sf::is_key(label) {
+            return Err(InvalidSignatureInput { _inner: () });
+        }
+        match self.0.iter_mut().find(|entry| entry.0 == label) {
+            Some(entry) => entry.1 = params,
+            None => self.0.push((label.to_owned(), params)),
+        }
+        Ok(self)
+    }
+
+    /// Get what the signature with this label covers, if there is one.
+    pub fn get(&self, label: &str) -> Option<&SignatureParams> {
+        self.iter()
+            .find(|&(l, _)| l == label)
+            .map(|(_, params)| params)
+    }
+
+    /// Iterate the labels and what each signature covers.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &SignatureParams)> {
+        self.0
+            .iter()
+            .map(|(label, params)| (label.as_str(), params))
+    }
+}
+
+impl ::Header for SignatureInput {
+    fn name() -> &'static HeaderName {
+        static NAME: HeaderName = SIGNATURE_INPUT;
+        &NAME
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        SignatureInput::try_from_values(values)
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        let mut dict = sf::Dictionary::default();
+        for (label, params) in &self.0 {
+            dict.insert(label.clone(), params.to_member());
+        }
+        values.extend(::std::iter::once(util::fmt(dict)));
+    }
+}
+
+impl ::NamedHeader for SignatureInput {
+    const NAME: ::HeaderName = SIGNATURE_INPUT;
+}
+
+impl TryFromValues for SignatureInput {
+    fn try_from_values<'i, I>(values: &mut I) -> Result<Self, ::Error>
+    where
+        I: Iterator<Item = &'i HeaderValue>,
+    {
+        let dict = sf::parse_dictionary(values)?;
+        let inputs = dict
+            .iter()
+            .map(|(label, member)| {
+                SignatureParams::from_member(member).map(|params| (label.to_owned(), params))
+            })
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(::Error::invalid)?;
+
+        if inputs.is_empty() {
+            Err(::Error::invalid())
+        } else {
+            Ok(SignatureInput(inputs))
+        }
+    }
+}
+
+// ===== impl SignatureParams =====
+
+impl SignatureParams {
+    /// Create the parameters of a signature covering `components`, in order.
+    ///
+    /// Fails if a component name isn't printable ASCII.
+    pub fn new<'a, I>(components: I) -> Result<SignatureParams, InvalidSignatureInput>
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        components
+            .into_iter()
+            .map(Component::new)
+            .collect::<Result<Vec<_>, _>>()
+            .map(|components| SignatureParams {
+                components,
+                params: Parameters::default(),
+            })
+    }
+
+    /// Set when the signature was created.
+    pub fn with_created(mut self, created: SystemTime) -> Self {
+        self.params
+            .insert("created".to_owned(), BareItem::Integer(unix_secs(created)));
+        self
+    }
+
+    /// Set when the signature expires.
+    pub fn with_expires(mut self, expires: SystemTime) -> Self {
+        self.params
+            .insert("expires".to_owned(), BareItem::Integer(unix_secs(expires)));
+        self
+    }
+
+    /// Set the identifier of the key that made the signature.
+    ///
+    /// Fails if `keyid` isn't printable ASCII.
+    pub fn with_keyid(self, keyid: &str) -> Result<Self, InvalidSignatureInput> {
+        self.with_string("keyid", keyid)
+    }
+
+    /// Set the signature algorithm, such as `ed25519`.
+    ///
+    /// Fails if `alg` isn't printable ASCII.
+    pub fn with_alg(self, alg: &str) -> Result<Self, InvalidSignatureInput> {
+        self.with_string("alg", alg)
+    }
+
+    /// Set a nonce, to help detect replayed signatures.
+    ///
+    /// Fails if `nonce` isn't printable ASCII.
+    pub fn with_nonce(self, nonce: &str) -> Result<Self, InvalidSignatureInput> {
+        self.with_string("nonce", nonce)
+    }
+
+    /// Set an application-specific tag for the signature.
+    ///
+    /// Fails if `tag` isn't printable ASCII.
+    pub fn with_tag(self, tag: &str) -> Result<Self, InvalidSignatureInput> {
+        self.with_string("tag", tag)
+    }
+
+    fn with_string(mut self, key: &str, value: &str) -> Result<Self, InvalidSignatureInput> {
+        if !sf::is_string(value) {
+            return Err(InvalidSignatureInput { _inner: () });
+        }
+        self.params
+            .insert(key.to_owned(), BareItem::String(value.to_owned()));
+        Ok(self)
+    }
+
+    /// Iterate the covered components, in order.
+    pub fn components(&self) -> impl Iterator<Item = &Component> {
+        self.components.iter()
+    }
+
+    /// When the signature was created, if sent.
+    pub fn created(&self) -> Option<SystemTime> {
+        self.time("created")
+    }
+
+    /// When the signature expires, if sent.
+    pub fn expires(&self) -> Option<SystemTime> {
+        self.time("expires")
+    }
+
+    /// The identifier of the key that made the signature, if sent.
+    pub fn keyid(&self) -> Option<&str> {
+        self.params.get("keyid").and_then(BareItem::as_str)
+    }
+
+    /// The signature algorithm, if sent.
+    pub fn alg(&self) -> Option<&str> {
+        self.params.get("alg").and_then(BareItem::as_str)
+    }
+
+    /// The nonce, if sent.
+    pub fn nonce(&self) -> Option<&str> {
+        self.params.get("nonce").and_then(BareItem::as_str)
+    }
+
+    /// The application-specific tag, if sent.
+    pub fn tag(&self) -> Option<&str> {
+        self.params.get("tag").and_then(BareItem::as_str)
+    }
+
+    fn time(&self, key: &str) -> Option<SystemTime> {
+        self.params
+            .get(key)
+            .and_then(BareItem::as_integer)
+            .and_then(unix_time)
+    }
+
+    fn from_member(member: &Member) -> Option<SignatureParams> {
+        let (items, params) = match *member {
+            Member::InnerList(ref items, ref params) => (items, params),
+            Member::Item(_) => return None,
+        };
+
+        // The defined parameters must have the right types, so that the
+        // accessors can't silently hide a malformed value.
+        for (key, value) in params.iter() {
+            let valid = match key {
+                "created" | "expires" => value.as_integer().and_then(unix_time).is_some(),
+                "keyid" | "alg" | "nonce" | "tag" => value.as_str().is_some(),
+                _ => true,
+            };
+            if !valid {
+                return None;
+            }
+        }
+
+        items
+            .iter()
+            .map(|item| item.bare.as_str().map(|_| Component(item.clone())))
+            .collect::<Option<Vec<_>>>()
+            .map(|components| SignatureParams {
+                components,
+                params: params.clone(),
+            })
+    }
+
+    fn to_member(&self) -> Member {
+        let items = self.components.iter().map(|c| c.0.clone()).collect();
+        Member::InnerList(items, self.params.clone())
+    }
+}
+
+fn unix_secs(time: SystemTime) -> i64 {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    secs.min(sf::MAX_INTEGER as u64) as i64
+}
+
+/// The time `secs` after the Unix epoch, if `SystemTime` can hold it.
+fn unix_time(secs: i64) -> Option<SystemTime> {
+    if secs < 0 {
+        return None;
+    }
+    UNIX_EPOCH.checked_add(Duration::from_secs(secs as u64))
+}
+
+impl fmt::Display for SignatureParams {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.to_member(), f)
+    }
+}
+
+// ===== impl Component =====
+
+impl Component {
+    fn new(name: &str) -> Result<Component, InvalidSignatureInput> {
+        if !sf::is_string(name) {
+            return Err(InvalidSignatureInput { _inner: () });
+        }
+        Ok(Component(Item::new(BareItem::String(name.to_owned()))))
+    }
+
+    /// The component identifier, such as `@method` or `content-type`.
+    pub fn name(&self) -> &str {
+        self.0.bare.as_str().expect("Component is always a string")
+    }
+
+    /// The value of a parameter of this component, such as the `name` of an
+    /// `@query-param`.
+    ///
+    /// Returns `None` if the parameter is missing, or isn't a string.
+    pub fn param(&self, key: &str) -> Option<&str> {
+        self.0.params.get(key).and_then(BareItem::as_str)
+    }
+
+    /// Check if a flag parameter of this component, such as `sf` or `req`,
+    /// is set.
+    pub fn has_flag(&self, key: &str) -> bool {
+        self.0.params.get(key).and_then(BareItem::as_bool) == Some(true)
+    }
+}
+
+impl fmt::Display for Component {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    // From RFC 9421, section 2.5.
+    const EXAMPLE: &str = "sig1=(\"@method\" \"@authority\" \"@path\" \"content-digest\" \
+                           \"content-length\" \"content-type\");created=1618884473;\
+                           keyid=\"test-key-rsa-pss\"";
+
+    #[test]
+    fn round_trip() {
+        let input = test_decode::<SignatureInput>(&[EXAMPLE]).unwrap();
+        let params = input.get("sig1").unwrap();
+        let names = params.components().map(Component::name).collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            [
+                "@method",
+                "@authority",
+                "@path",
+                "content-digest",
+                "content-length",
+                "content-type"
+            ]
+        );
+        assert_eq!(
+            params.created(),
+            Some(UNIX_EPOCH + Duration::from_secs(1_618_884_473))
+        );
+        assert_eq!(params.expires(), None);
+        assert_eq!(params.keyid(), Some("test-key-rsa-pss"));
+        assert_eq!(params.alg(), None);
+
+        let headers = test_encode(input);
+        assert_eq!(headers["signature-input"], EXAMPLE);
+    }
+
+    #[test]
+    fn component_params() {
+        let input = test_decode::<SignatureInput>(&[
+            "a=(\"@query-param\";name=\"id\" \"example-dict\";sf);tag=\"x\"",
+            "b=()",
+        ])
+        .unwrap();
+        let labels = input.iter().map(|(label, _)| label).collect::<Vec<_>>();
+        assert_eq!(labels, ["a", "b"]);
+
+        let params = input.get("a").unwrap();
+        let components = params.components().collect::<Vec<_>>();
+        assert_eq!(components[0].param("name"), Some("id"));
+        assert!(!components[0].has_flag("sf"));
+        assert!(components[1].has_flag("sf"));
+        assert_eq!(params.tag(), Some("x"));
+
+        assert_eq!(input.get("b").unwrap().components().count(), 0);
+    }
+
+    #[test]
+    fn builder() {
+        let params = SignatureParams::new(vec!["@method", "@target-uri"])
+            .unwrap()
+            .with_created(UNIX_EPOCH + Duration::from_secs(1_700_000_000))
+            .with_alg("ed25519")
+            .unwrap()
+            .with_nonce("n-1")
+            .unwrap();
+        assert_eq!(
+            params.to_string(),
+            "(\"@method\" \"@target-uri\");created=1700000000;alg=\"ed25519\";nonce=\"n-1\""
+        );
+
+        let input = SignatureInput::new("sig", params).unwrap();
+        let headers = test_encode(input);
+        assert!(headers["signature-input"]
+            .to_str()
+            .unwrap()
+            .starts_with("sig=(\"@method\""));
+
+        assert!(SignatureParams::new(vec!["caf\u{e9}"]).is_err());
+        assert!(SignatureParams::new(vec!["a"])
+            .unwrap()
+            .with_keyid("\n")
+            .is_err());
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<SignatureInput>(&[""]), None);
+        assert_eq!(test_decode::<SignatureInput>(&["sig1=\"@method\""]), None);
+        assert_eq!(test_decode::<SignatureInput>(&["sig1=(@method)"]), None);
+        assert_eq!(
+            test_decode::<SignatureInput>(&["sig1=();created=\"now\""]),
+            None
+        );
+        assert_eq!(test_decode::<SignatureInput>(&["sig1=();keyid=k"]), None);
+    }
+}
@@ -0,0 +1,166 @@
+use std::fmt;
+use std::str::FromStr;
+
+use http::Uri;
+
+use util::{self, HeaderValueString, IterExt};
+use {HeaderMap, HeaderName, HeaderValue};
+
+const SOURCEMAP: HeaderName = HeaderName::from_static("sourcemap");
+const X_SOURCEMAP: HeaderName = HeaderName::from_static("x-sourcemap");
+
+/// `SourceMap` header, defined in the
+/// [Source Map specification](https://tc39.es/source-map/#linking-through-http-headers)
+///
+/// The `SourceMap` response header field links a generated file, such as
+/// minified JavaScript, to the source map describing how it was generated,
+/// so developer tools can show the original source.
+///
+/// The value is a URI reference, and is usually relative to the URL of the
+/// generated file. It is kept as is, use `try_resolve` to resolve it.
+///
+/// # Example values
+///
+/// * `/path/to/file.js.map`
+/// * `https://example.com/app.min.js.map`
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// # extern crate http;
+/// use headers::SourceMap;
+///
+/// let map = SourceMap::new("app.min.js.map").unwrap();
+/// let base = "https://example.com/js/app.min.js".parse().unwrap();
+/// assert_eq!(
+///     map.try_resolve(&base).unwrap(),
+///     "https://example.com/js/app.min.js.map"
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SourceMap(HeaderValueString);
+
+error_type!(InvalidSourceMap);
+
+impl SourceMap {
+    /// Try to create a `SourceMap` from a URI reference.
+    pub fn new(uri: &str) -> Result<SourceMap, InvalidSourceMap> {
+        uri.parse()
+    }
+
+    /// Decode a `SourceMap` from a `HeaderMap`, falling back to the legacy
+    /// `X-SourceMap` header if there is no `SourceMap` header.
+    pub fn from_headers(headers: &HeaderMap) -> Option<SourceMap> {
+        let mut values = headers.get_all(&SOURCEMAP).iter().peekable();
+        if values.peek().is_some() {
+            return ::Header::decode(&mut values).ok();
+        }
+        ::Header::decode(&mut headers.get_all(&X_SOURCEMAP).iter()).ok()
+    }
+
+    /// View the URI reference as a `&str`.
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    /// Resolve the URI reference against the URI of the generated file.
+    ///
+    /// Returns `None` if `base` is not absolute, or the reference can't be
+    /// resolved into a `Uri`.
+    pub fn try_resolve(&self, base: &Uri) -> Option<Uri> {
+        util::uri::resolve(base, self.as_str())
+    }
+}
+
+impl ::Header for SourceMap {
+    fn name() -> &'static HeaderName {
+        static NAME: HeaderName = SOURCEMAP;
+        &NAME
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        values
+            .just_one()
+            .and_then(|val| HeaderValueString::from_val(val).ok())
+            .filter(|s| !s.as_str().is_empty())
+            .map(SourceMap)
+            .ok_or_else(::Error::invalid)
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once((&self.0).into()));
+    }
+}
+
+impl ::NamedHeader for SourceMap {
+    const NAME: ::HeaderName = SOURCEMAP;
+}
+
+impl FromStr for SourceMap {
+    type Err = InvalidSourceMap;
+
+    fn from_str(src: &str) -> Result<Self, Self::Err> {
+        if src.is_empty() {
+            return Err(InvalidSourceMap { _inner: () });
+        }
+        HeaderValueString::from_str(src)
+            .map(SourceMap)
+            .map_err(|_| InvalidSourceMap { _inner: () })
+    }
+}
+
+impl fmt::Display for SourceMap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let map = test_decode::<SourceMap>(&["../maps/app.js.map"]).unwrap();
+        assert_eq!(map.as_str(), "../maps/app.js.map");
+
+        let headers = test_encode(map);
+        assert_eq!(headers["sourcemap"], "../maps/app.js.map");
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<SourceMap>(&[""]), None);
+        assert_eq!(test_decode::<SourceMap>(&["a.map", "b.map"]), None);
+    }
+
+    #[test]
+    fn from_headers_legacy() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-sourcemap", "legacy.js.map".parse().unwrap());
+        assert_eq!(
+            SourceMap::from_headers(&headers).unwrap().as_str(),
+            "legacy.js.map"
+        );
+
+        headers.insert("sourcemap", "current.js.map".parse().unwrap());
+        assert_eq!(
+            SourceMap::from_headers(&headers).unwrap().as_str(),
+            "current.js.map"
+        );
+
+        assert_eq!(SourceMap::from_headers(&HeaderMap::new()), None);
+    }
+
+    #[test]
+    fn try_resolve() {
+        let map = SourceMap::new("/maps/app.js.map").unwrap();
+        let base = "https://cdn.example.com/js/app.js".parse().unwrap();
+        assert_eq!(
+            map.try_resolve(&base).unwrap(),
+            "https://cdn.example.com/maps/app.js.map"
+        );
+    }
+}
@@ -90,6 +90,7 @@ enum Directive {
     Unknown,
 }
 
+#[allow(clippy::manual_try_fold)]
 fn from_str(s: &str) -> Result<StrictTransportSecurity, ::Error> {
     s.split(';')
         .map(str::trim)
@@ -159,9 +160,13 @@ impl ::Header for StrictTransportSecurity {
     }
 }
 
+impl ::NamedHeader for StrictTransportSecurity {
+    const NAME: ::HeaderName = ::http::header::STRICT_TRANSPORT_SECURITY;
+}
+
 #[cfg(test)]
 mod tests {
-    use super::super::test_decode;
+    use super::super::{test_decode, test_encode};
     use super::StrictTransportSecurity;
     use std::time::Duration;
 
@@ -227,6 +232,48 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_builders_and_accessors() {
+        let year = Duration::from_secs(31536000);
+        let h = test_decode::<StrictTransportSecurity>(&["max-age=31536000; includeSubDomains"])
+            .unwrap();
+        assert_eq!(h.max_age(), year);
+        assert!(h.include_subdomains());
+        assert_eq!(h, StrictTransportSecurity::including_subdomains(year));
+
+        let h = StrictTransportSecurity::excluding_subdomains(year);
+        assert!(!h.include_subdomains());
+        let headers = test_encode(h);
+        assert_eq!(headers["strict-transport-security"], "max-age=31536000");
+
+        let headers = test_encode(StrictTransportSecurity::including_subdomains(year));
+        assert_eq!(
+            headers["strict-transport-security"],
+            "max-age=31536000; includeSubdomains"
+        );
+    }
+
+    #[test]
+    fn test_parse_reordered_with_whitespace() {
+        let h = test_decode::<StrictTransportSecurity>(&[
+            "  includeSubDomains ;preload;   max-age = 31536000  ",
+        ])
+        .unwrap();
+        assert_eq!(
+            h,
+            StrictTransportSecurity::including_subdomains(Duration::from_secs(31536000))
+        );
+    }
+
+    #[test]
+    fn test_parse_no_max_age_with_others() {
+        assert_eq!(
+            test_decode::<StrictTransportSecurity>(&["includeSubDomains; preload"]),
+            None,
+        );
+        assert_eq!(test_decode::<StrictTransportSecurity>(&[""]), None);
+    }
+
     #[test]
     fn test_parse_max_age_nan() {
         assert_eq!(
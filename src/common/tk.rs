@@ -0,0 +1,221 @@
+//! Tk header and types.
+
+use std::fmt;
+
+use util::{self, IterExt};
+use {HeaderName, HeaderValue};
+
+const TK: HeaderName = HeaderName::from_static("tk");
+
+/// `Tk` header, defined in
+/// [Tracking Preference Expression](https://www.w3.org/TR/tracking-dnt/#response-header-field)
+///
+/// The `Tk` response header field tells the user agent how the origin
+/// server is tracking it, in answer to a `DNT` request header. Tracking
+/// Preference Expression has been discontinued, but some compliance tools
+/// still read this header.
+///
+/// # ABNF
+///
+/// ```text
+/// Tk        = TSV [ ";" status-id ]
+/// TSV       = "!" / "?" / "G" / "N" / "T" / "C" / "P" / "D" / "U"
+/// status-id = 1*id-char
+/// id-char   = ALPHA / DIGIT / "_" / "-" / "+" / "=" / "/"
+/// ```
+///
+/// # Example values
+///
+/// * `N`
+/// * `T;fRx42`
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// use headers::Tk;
+/// use headers::tk::TrackingStatus;
+///
+/// let tk = Tk::new(TrackingStatus::Consent).with_id("fRx42").unwrap();
+/// assert_eq!(tk.status(), TrackingStatus::Consent);
+/// assert_eq!(tk.id(), Some("fRx42"));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Tk {
+    status: TrackingStatus,
+    id: Option<String>,
+}
+
+/// A tracking status value, sent in a `Tk` header.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TrackingStatus {
+    /// `!`, the site is under construction.
+    UnderConstruction,
+    /// `?`, the status depends on the request, and is in a status resource.
+    Dynamic,
+    /// `G`, a gateway to several parties with different statuses.
+    Gateway,
+    /// `N`, not tracking.
+    NotTracking,
+    /// `T`, tracking.
+    Tracking,
+    /// `C`, tracking with the user's consent.
+    Consent,
+    /// `P`, tracking until the user's consent can be checked.
+    PotentialConsent,
+    /// `D`, disregarding the user's tracking preference.
+    Disregarding,
+    /// `U`, the tracking status has changed, and is in a status resource.
+    Updated,
+}
+
+error_type!(InvalidTk);
+
+impl Tk {
+    /// Create a `Tk` with a status, and no status-id.
+    pub fn new(status: TrackingStatus) -> Tk {
+        Tk { status, id: None }
+    }
+
+    /// Set the status-id, which names a status resource.
+    ///
+    /// Fails if `id` is empty, or has a character other than a letter,
+    /// digit, or one of `_-+=/`.
+    pub fn with_id(mut self, id: &str) -> Result<Self, InvalidTk> {
+        if !is_status_id(id) {
+            return Err(InvalidTk { _inner: () });
+        }
+        self.id = Some(id.to_owned());
+        Ok(self)
+    }
+
+    /// The tracking status.
+    pub fn status(&self) -> TrackingStatus {
+        self.status
+    }
+
+    /// The status-id, if there is one.
+    pub fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+}
+
+impl TrackingStatus {
+    fn from_byte(b: u8) -> Option<TrackingStatus> {
+        Some(match b {
+            b'!' => TrackingStatus::UnderConstruction,
+            b'?' => TrackingStatus::Dynamic,
+            b'G' => TrackingStatus::Gateway,
+            b'N' => TrackingStatus::NotTracking,
+            b'T' => TrackingStatus::Tracking,
+            b'C' => TrackingStatus::Consent,
+            b'P' => TrackingStatus::PotentialConsent,
+            b'D' => TrackingStatus::Disregarding,
+            b'U' => TrackingStatus::Updated,
+            _ => return None,
+        })
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            TrackingStatus::UnderConstruction => "!",
+            TrackingStatus::Dynamic => "?",
+            TrackingStatus::Gateway => "G",
+            TrackingStatus::NotTracking => "N",
+            TrackingStatus::Tracking => "T",
+            TrackingStatus::Consent => "C",
+            TrackingStatus::PotentialConsent => "P",
+            TrackingStatus::Disregarding => "D",
+            TrackingStatus::Updated => "U",
+        }
+    }
+}
+
+fn is_status_id(id: &str) -> bool {
+    !id.is_empty()
+        && id
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b"_-+=/".contains(&b))
+}
+
+impl ::Header for Tk {
+    fn name() -> &'static HeaderName {
+        static NAME: HeaderName = TK;
+        &NAME
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        let s = values
+            .just_one()
+            .and_then(|val| val.to_str().ok())
+            .ok_or_else(::Error::invalid)?;
+        let (status, id) = match s.find(';') {
+            Some(i) => (&s[..i], Some(&s[i + 1..])),
+            None => (s, None),
+        };
+
+        let status = match status.as_bytes() {
+            &[b] => TrackingStatus::from_byte(b).ok_or_else(::Error::invalid)?,
+            _ => return Err(::Error::invalid()),
+        };
+        let tk = Tk::new(status);
+        match id {
+            Some(id) => tk.with_id(id).map_err(|_| ::Error::invalid()),
+            None => Ok(tk),
+        }
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once(util::fmt(self)));
+    }
+}
+
+impl ::NamedHeader for Tk {
+    const NAME: ::HeaderName = TK;
+}
+
+impl fmt::Display for Tk {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.status.as_str())?;
+        if let Some(ref id) = self.id {
+            write!(f, ";{}", id)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let tk = test_decode::<Tk>(&["N"]).unwrap();
+        assert_eq!(tk.status(), TrackingStatus::NotTracking);
+        assert_eq!(tk.id(), None);
+
+        let tk = test_decode::<Tk>(&["T;fRx42"]).unwrap();
+        assert_eq!(tk.status(), TrackingStatus::Tracking);
+        assert_eq!(tk.id(), Some("fRx42"));
+
+        let headers = test_encode(tk);
+        assert_eq!(headers["tk"], "T;fRx42");
+
+        for &s in &["!", "?", "G", "N", "T", "C", "P", "D", "U"] {
+            let tk = test_decode::<Tk>(&[s]).unwrap();
+            assert_eq!(tk.to_string(), s);
+        }
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<Tk>(&[""]), None);
+        assert_eq!(test_decode::<Tk>(&["X"]), None);
+        assert_eq!(test_decode::<Tk>(&["n"]), None);
+        assert_eq!(test_decode::<Tk>(&["NT"]), None);
+        assert_eq!(test_decode::<Tk>(&["N;"]), None);
+        assert_eq!(test_decode::<Tk>(&["N;a b"]), None);
+        assert_eq!(test_decode::<Tk>(&["N", "T"]), None);
+    }
+}
@@ -66,6 +66,14 @@ impl TransferEncoding {
             })
             .unwrap_or(false)
     }
+
+    /// The names of the codings, in the order they were applied.
+    pub(crate) fn codings(&self) -> impl Iterator<Item = &str> {
+        self.0
+            .iter()
+            .map(|coding| coding.split(';').next().unwrap_or("").trim())
+            .filter(|coding| !coding.is_empty())
+    }
 }
 
 #[cfg(test)]
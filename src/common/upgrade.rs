@@ -1,3 +1,9 @@
+//! Upgrade header and types.
+
+use std::fmt;
+use std::str::FromStr;
+
+use util::{self, csv};
 use HeaderValue;
 
 /// `Upgrade` header, defined in [RFC7230](http://tools.ietf.org/html/rfc7230#section-6.7)
@@ -38,18 +44,157 @@ use HeaderValue;
 /// use headers::Upgrade;
 ///
 /// let ws = Upgrade::websocket();
+/// assert!(ws.contains("WebSocket"));
 /// ```
 #[derive(Clone, Debug, PartialEq)]
-pub struct Upgrade(HeaderValue);
+pub struct Upgrade(Vec<Protocol>);
 
-derive_header! {
-    Upgrade(_),
-    name: UPGRADE
+/// A single protocol in an `Upgrade` header, such as `HTTP/2.0`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Protocol {
+    name: String,
+    version: Option<String>,
 }
 
 impl Upgrade {
     /// Constructs an `Upgrade: websocket` header.
     pub fn websocket() -> Upgrade {
-        Upgrade(HeaderValue::from_static("websocket"))
+        Upgrade(vec![Protocol {
+            name: "websocket".to_owned(),
+            version: None,
+        }])
+    }
+
+    /// Check if this header contains a protocol with the given name.
+    ///
+    /// The comparison is case-insensitive, and ignores any protocol version.
+    pub fn contains(&self, name: &str) -> bool {
+        self.0
+            .iter()
+            .any(|protocol| protocol.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Iterate the protocols of this `Upgrade`, in order of preference.
+    pub fn iter(&self) -> impl Iterator<Item = &Protocol> {
+        self.0.iter()
+    }
+}
+
+impl ::Header for Upgrade {
+    fn name() -> &'static ::HeaderName {
+        &::http::header::UPGRADE
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        let protocols: Vec<Protocol> = csv::from_comma_delimited(values)?;
+        if protocols.is_empty() {
+            Err(::Error::invalid())
+        } else {
+            Ok(Upgrade(protocols))
+        }
+    }
+
+    fn encode<E: Extend<::HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once(util::fmt(Fmt(self))));
+    }
+}
+
+impl ::NamedHeader for Upgrade {
+    const NAME: ::HeaderName = ::http::header::UPGRADE;
+}
+
+struct Fmt<'a>(&'a Upgrade);
+
+impl<'a> fmt::Display for Fmt<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        csv::fmt_comma_delimited(f, self.0 .0.iter())
+    }
+}
+
+impl Protocol {
+    /// Get the protocol name, such as `websocket` or `HTTP`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the optional protocol version, such as `2.0`.
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+}
+
+impl FromStr for Protocol {
+    type Err = ::Error;
+
+    fn from_str(s: &str) -> Result<Protocol, ::Error> {
+        let mut parts = s.splitn(2, '/');
+        let name = parts.next().unwrap_or("");
+        let version = parts.next();
+
+        if !util::is_token(name) {
+            return Err(::Error::invalid());
+        }
+        if let Some(version) = version {
+            if !util::is_token(version) {
+                return Err(::Error::invalid());
+            }
+        }
+
+        Ok(Protocol {
+            name: name.to_owned(),
+            version: version.map(str::to_owned),
+        })
+    }
+}
+
+impl fmt::Display for Protocol {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.name)?;
+        if let Some(ref version) = self.version {
+            write!(f, "/{}", version)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn websocket() {
+        let upgrade = test_decode::<Upgrade>(&["websocket"]).unwrap();
+        assert_eq!(upgrade, Upgrade::websocket());
+        assert!(upgrade.contains("websocket"));
+        assert!(upgrade.contains("WebSocket"));
+        assert!(!upgrade.contains("h2c"));
+
+        let headers = test_encode(upgrade);
+        assert_eq!(headers["upgrade"], "websocket");
+    }
+
+    #[test]
+    fn protocol_versions() {
+        let upgrade = test_decode::<Upgrade>(&["websocket, HTTP/2.0", "h2c"]).unwrap();
+
+        let protocols = upgrade.iter().collect::<Vec<_>>();
+        assert_eq!(protocols.len(), 3);
+        assert_eq!(protocols[0].name(), "websocket");
+        assert_eq!(protocols[0].version(), None);
+        assert_eq!(protocols[1].name(), "HTTP");
+        assert_eq!(protocols[1].version(), Some("2.0"));
+        assert_eq!(protocols[2].name(), "h2c");
+
+        let headers = test_encode(upgrade);
+        assert_eq!(headers["upgrade"], "websocket, HTTP/2.0, h2c");
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(test_decode::<Upgrade>(&[""]), None);
+        assert_eq!(test_decode::<Upgrade>(&["HTTP/"]), None);
+        assert_eq!(test_decode::<Upgrade>(&["/2.0"]), None);
+        assert_eq!(test_decode::<Upgrade>(&["foo bar"]), None);
     }
 }
@@ -0,0 +1,81 @@
+use util::IterExt;
+use HeaderValue;
+
+/// `Upgrade-Insecure-Requests` header, defined in
+/// [Upgrade Insecure Requests](https://w3c.github.io/webappsec-upgrade-insecure-requests/#preference)
+///
+/// The `Upgrade-Insecure-Requests` request header field tells the server
+/// that the client prefers secure responses, and would upgrade insecure
+/// subresource requests itself. A server may then redirect to HTTPS, or
+/// send `Content-Security-Policy: upgrade-insecure-requests`.
+///
+/// # ABNF
+///
+/// ```text
+/// Upgrade-Insecure-Requests = "1"
+/// ```
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// use headers::UpgradeInsecureRequests;
+///
+/// let uir = UpgradeInsecureRequests::new();
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct UpgradeInsecureRequests;
+
+impl UpgradeInsecureRequests {
+    /// Create an `Upgrade-Insecure-Requests: 1` header.
+    pub fn new() -> UpgradeInsecureRequests {
+        UpgradeInsecureRequests
+    }
+}
+
+impl ::Header for UpgradeInsecureRequests {
+    fn name() -> &'static ::HeaderName {
+        &::http::header::UPGRADE_INSECURE_REQUESTS
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        values
+            .just_one()
+            .filter(|value| *value == "1")
+            .map(|_| UpgradeInsecureRequests)
+            .ok_or_else(::Error::invalid)
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once(HeaderValue::from_static("1")));
+    }
+}
+
+impl ::NamedHeader for UpgradeInsecureRequests {
+    const NAME: ::HeaderName = ::http::header::UPGRADE_INSECURE_REQUESTS;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::UpgradeInsecureRequests;
+
+    #[test]
+    fn one() {
+        assert_eq!(
+            test_decode::<UpgradeInsecureRequests>(&["1"]),
+            Some(UpgradeInsecureRequests::new())
+        );
+
+        let headers = test_encode(UpgradeInsecureRequests::new());
+        assert_eq!(headers["upgrade-insecure-requests"], "1");
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<UpgradeInsecureRequests>(&["0"]), None);
+        assert_eq!(test_decode::<UpgradeInsecureRequests>(&["true"]), None);
+        assert_eq!(test_decode::<UpgradeInsecureRequests>(&[" 1 "]), None);
+        assert_eq!(test_decode::<UpgradeInsecureRequests>(&["1", "1"]), None);
+    }
+}
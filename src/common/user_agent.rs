@@ -2,6 +2,7 @@ use std::fmt;
 use std::str::FromStr;
 
 use util::HeaderValueString;
+use Product;
 
 /// `User-Agent` header, defined in
 /// [RFC7231](http://tools.ietf.org/html/rfc7231#section-5.5.3)
@@ -27,10 +28,6 @@ use util::HeaderValueString;
 /// * `CERN-LineMode/2.15 libwww/2.17b3`
 /// * `Bunnies`
 ///
-/// # Notes
-///
-/// * The parser does not split the value
-///
 /// # Example
 ///
 /// ```
@@ -57,10 +54,29 @@ impl UserAgent {
         UserAgent(HeaderValueString::from_static(src))
     }
 
+    /// Decode the user agent as text, reading any obs-text as ISO-8859-1.
+    ///
+    /// `decode` rejects a value that isn't visible ASCII, while this
+    /// accepts any bytes, as browsers do. It returns a `String` because a
+    /// `UserAgent` can only hold visible ASCII.
+    pub fn decode_lossy<'i, I>(values: &mut I) -> Result<String, ::Error>
+    where
+        I: Iterator<Item = &'i ::HeaderValue>,
+    {
+        HeaderValueString::decode_lossy(values)
+    }
+
     /// View this `UserAgent` as a `&str`.
     pub fn as_str(&self) -> &str {
         self.0.as_str()
     }
+
+    /// Iterate the products, most significant first.
+    ///
+    /// See `Product` for how the value is split.
+    pub fn products(&self) -> impl Iterator<Item = Product<'_>> {
+        super::product::products(self.as_str())
+    }
 }
 
 error_type!(InvalidUserAgent);
@@ -1,6 +1,8 @@
-use util::FlatCsv;
+use std::iter::FromIterator;
 
-use HeaderValue;
+use util::ListHeader;
+
+use {HeaderName, HeaderValue};
 
 /// `Vary` header, defined in [RFC7231](https://tools.ietf.org/html/rfc7231#section-7.1.4)
 ///
@@ -11,6 +13,9 @@ use HeaderValue;
 /// either a single asterisk ("*") or a list of header field names
 /// (case-insensitive).
 ///
+/// Several `Vary` lines are merged into one list, and a `*` in any of them
+/// makes the whole header `*`.
+///
 /// # ABNF
 ///
 /// ```text
@@ -29,28 +34,66 @@ use HeaderValue;
 ///
 /// let vary = Vary::any();
 /// ```
+// Names aren't checked when decoding, and a value that isn't text is read
+// as ISO-8859-1, so that an invalid `Vary` still decodes, and
+// `cache::vary_key` can refuse it rather than the response looking like it
+// has no `Vary` at all.
 #[derive(Debug, Clone, PartialEq)]
-pub struct Vary(FlatCsv);
+pub struct Vary(ListHeader<String>);
+
+impl ::Header for Vary {
+    fn name() -> &'static HeaderName {
+        &::http::header::VARY
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        Ok(Vary(ListHeader::from_values_latin1(values)))
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once((&self.0).into()));
+    }
+}
 
-derive_header! {
-    Vary(_),
-    name: VARY
+impl ::NamedHeader for Vary {
+    const NAME: ::HeaderName = ::http::header::VARY;
 }
 
 impl Vary {
     /// Create a new `Very: *` header.
     pub fn any() -> Vary {
-        Vary(HeaderValue::from_static("*").into())
+        Vary(ListHeader::from(vec!["*".to_owned()]))
     }
 
     /// Check if this includes `*`.
     pub fn is_any(&self) -> bool {
-        self.0.iter().any(|val| val == "*")
+        self.iter_strs().any(|val| val == "*")
+    }
+
+    /// Check if a cache may reuse the response for later requests.
+    ///
+    /// This is `false` for `*`, since the response then varies on things
+    /// other than request headers, so no stored copy matches another
+    /// request. Otherwise, a cache can match requests on the listed
+    /// headers, as with [`cache::vary_key`](::cache::vary_key).
+    pub fn is_cacheable(&self) -> bool {
+        !self.is_any()
     }
 
     /// Iterate the header names of this `Vary`.
     pub fn iter_strs(&self) -> impl Iterator<Item = &str> {
-        self.0.iter()
+        self.0.iter().map(String::as_str)
+    }
+}
+
+impl FromIterator<HeaderName> for Vary {
+    /// Collect header names into a `Vary`, skipping any repeated name.
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = HeaderName>,
+    {
+        let names = iter.into_iter().map(|name| name.as_str().to_owned());
+        Vary(names.collect())
     }
 }
 
@@ -75,10 +118,66 @@ test_vary {
 
 #[cfg(test)]
 mod tests {
+    use super::super::{test_decode, test_encode};
     use super::*;
 
     #[test]
     fn any_is_any() {
         assert!(Vary::any().is_any());
     }
+
+    #[test]
+    fn is_cacheable() {
+        assert!(!Vary::any().is_cacheable());
+        assert!(!test_decode::<Vary>(&["Accept, *"]).unwrap().is_cacheable());
+
+        let vary = test_decode::<Vary>(&["Accept-Encoding, Accept-Language"]).unwrap();
+        assert!(vary.is_cacheable());
+    }
+
+    #[test]
+    fn from_iter() {
+        let vary = Vary::from_iter([
+            ::http::header::ACCEPT_ENCODING,
+            ::http::header::COOKIE,
+            ::http::header::ACCEPT_ENCODING,
+        ]);
+        let headers = test_encode(vary);
+        assert_eq!(headers["vary"], "accept-encoding, cookie");
+
+        let vary = vec![::http::header::ACCEPT, ::http::header::AUTHORIZATION]
+            .into_iter()
+            .filter(|name| name != ::http::header::AUTHORIZATION)
+            .collect::<Vary>();
+        assert_eq!(vary.iter_strs().collect::<Vec<_>>(), ["accept"]);
+    }
+
+    #[test]
+    fn merge_lines() {
+        let vary = test_decode::<Vary>(&["Accept-Encoding", "Cookie"]).unwrap();
+        assert!(!vary.is_any());
+        assert_eq!(
+            vary.iter_strs().collect::<Vec<_>>(),
+            ["Accept-Encoding", "Cookie"]
+        );
+
+        let headers = test_encode(vary);
+        assert_eq!(headers["vary"], "Accept-Encoding, Cookie");
+    }
+
+    #[test]
+    fn any_in_any_line() {
+        let vary = test_decode::<Vary>(&["Accept-Encoding", "*"]).unwrap();
+        assert!(vary.is_any());
+
+        let vary = test_decode::<Vary>(&["*", "Cookie, Accept"]).unwrap();
+        assert!(vary.is_any());
+    }
+
+    #[test]
+    fn decode_non_text() {
+        let values = [HeaderValue::from_bytes(b"accept, x-\xe9").unwrap()];
+        let vary = <Vary as ::Header>::decode(&mut values.iter()).unwrap();
+        assert_eq!(vary.iter_strs().collect::<Vec<_>>(), ["accept", "x-\u{e9}"]);
+    }
 }
@@ -0,0 +1,326 @@
+//! Via header and types.
+
+use std::fmt;
+
+use util::{self, is_token};
+use HeaderValue;
+
+/// `Via` header, defined in
+/// [RFC9110](https://www.rfc-editor.org/rfc/rfc9110.html#section-7.6.3)
+///
+/// The `Via` header field indicates the presence of intermediate protocols
+/// and recipients between the user agent and the server, on requests, and
+/// between the origin server and the client, on responses. Each proxy
+/// appends an entry, so the first is the one closest to the sender.
+///
+/// # ABNF
+///
+/// ```text
+/// Via = #( received-protocol RWS received-by [ RWS comment ] )
+///
+/// received-protocol = [ protocol-name "/" ] protocol-version
+/// received-by       = pseudonym [ ":" port ]
+/// pseudonym         = token
+/// ```
+///
+/// # Example values
+///
+/// * `1.1 vegur`
+/// * `HTTP/1.1 GWA`
+/// * `1.0 fred, 1.1 p.example.net (Apache/1.1)`
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// use headers::Via;
+///
+/// let via = Via::new("1.1", "proxy.example").unwrap();
+/// assert!(via.contains_received_by("proxy.example"));
+///
+/// let entry = via.entries().next().unwrap();
+/// assert_eq!(entry.protocol_name(), "HTTP");
+/// assert_eq!(entry.protocol_version(), "1.1");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Via(Vec<Received>);
+
+/// One entry of a `Via` header, added by one recipient.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Received {
+    protocol: String,
+    received_by: String,
+    comment: Option<String>,
+}
+
+error_type!(InvalidVia);
+
+impl Via {
+    /// Create a `Via` with a single entry.
+    ///
+    /// `protocol` is the received protocol, such as `1.1` or `HTTP/2`, and
+    /// `received_by` is the host, optionally with a port, or a pseudonym of
+    /// the recipient.
+    pub fn new(protocol: &str, received_by: &str) -> Result<Via, InvalidVia> {
+        Via(Vec::new()).with_received(protocol, received_by)
+    }
+
+    /// Append another entry, as a proxy does when it forwards a message.
+    pub fn with_received(mut self, protocol: &str, received_by: &str) -> Result<Self, InvalidVia> {
+        if !is_protocol(protocol) || !is_received_by(received_by) {
+            return Err(InvalidVia { _inner: () });
+        }
+        self.0.push(Received {
+            protocol: protocol.to_owned(),
+            received_by: received_by.to_owned(),
+            comment: None,
+        });
+        Ok(self)
+    }
+
+    /// Iterate the entries, in the order they were added.
+    pub fn entries(&self) -> impl Iterator<Item = &Received> {
+        self.0.iter()
+    }
+
+    /// Iterate the `received-by` of each entry, in the order they were
+    /// added.
+    pub fn received_by(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(|entry| entry.received_by.as_str())
+    }
+
+    /// Check if `pseudonym` already appears as a `received-by` in the chain.
+    ///
+    /// A proxy that finds its own name has received the message before, so
+    /// is in a forwarding loop, and should refuse to forward it again. The
+    /// names are compared ignoring ASCII case, including any port.
+    pub fn contains_received_by(&self, pseudonym: &str) -> bool {
+        self.received_by()
+            .any(|received_by| received_by.eq_ignore_ascii_case(pseudonym))
+    }
+}
+
+impl Received {
+    /// The name of the received protocol, such as `HTTP`.
+    ///
+    /// The name may be left out when it is HTTP, as in `1.1 proxy`, so
+    /// this is `HTTP` then. An explicit name is returned as sent.
+    pub fn protocol_name(&self) -> &str {
+        match self.protocol.find('/') {
+            Some(i) => &self.protocol[..i],
+            None => "HTTP",
+        }
+    }
+
+    /// The version of the received protocol, such as `1.1`.
+    pub fn protocol_version(&self) -> &str {
+        match self.protocol.find('/') {
+            Some(i) => &self.protocol[i + 1..],
+            None => &self.protocol,
+        }
+    }
+
+    /// The host, optionally with a port, or pseudonym of the recipient.
+    pub fn received_by(&self) -> &str {
+        &self.received_by
+    }
+
+    /// The comment after the entry, with its parentheses, if there is one.
+    pub fn comment(&self) -> Option<&str> {
+        self.comment.as_deref()
+    }
+}
+
+fn is_protocol(s: &str) -> bool {
+    let mut parts = s.splitn(2, '/');
+    let first = parts.next().unwrap_or("");
+    match parts.next() {
+        Some(version) => is_token(first) && is_token(version),
+        None => is_token(first),
+    }
+}
+
+fn is_received_by(s: &str) -> bool {
+    match s.rfind(':') {
+        Some(i) if s[i + 1..].bytes().all(|b| b.is_ascii_digit()) => {
+            is_token(&s[..i]) || is_host(&s[..i])
+        }
+        _ => is_token(s) || is_host(s),
+    }
+}
+
+// An IPv6 literal, like `[::1]`, isn't a token (`[` and `:` are not
+// tchars), but is a valid host.
+fn is_host(s: &str) -> bool {
+    s.len() > 2
+        && s.starts_with('[')
+        && s.ends_with(']')
+        && s[1..s.len() - 1]
+            .bytes()
+            .all(|b| b.is_ascii_hexdigit() || b == b':' || b == b'.')
+}
+
+/// Split on commas that aren't inside a comment.
+fn split_entries(s: &str) -> Option<Vec<&str>> {
+    let mut entries = Vec::new();
+    let mut depth = 0usize;
+    let mut escaped = false;
+    let mut start = 0;
+    for (i, b) in s.bytes().enumerate() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match b {
+            b'\\' if depth > 0 => escaped = true,
+            b'(' => depth += 1,
+            b')' => depth = depth.checked_sub(1)?,
+            b',' if depth == 0 => {
+                entries.push(&s[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if depth != 0 {
+        return None;
+    }
+    entries.push(&s[start..]);
+    Some(entries)
+}
+
+fn parse_entry(entry: &str) -> Option<Received> {
+    let entry = entry.trim_matches(|c| c == ' ' || c == '\t');
+    let (protocol, rest) = entry.split_at(entry.find(|c| c == ' ' || c == '\t')?);
+    let rest = rest.trim_start_matches(|c| c == ' ' || c == '\t');
+    let (received_by, comment) = match rest.find(|c| c == ' ' || c == '\t') {
+        Some(i) => (&rest[..i], Some(rest[i..].trim_start())),
+        None => (rest, None),
+    };
+
+    if !is_protocol(protocol) || !is_received_by(received_by) {
+        return None;
+    }
+    let comment = match comment {
+        Some(c) if c.len() >= 2 && c.starts_with('(') && c.ends_with(')') => Some(c.to_owned()),
+        Some(_) => return None,
+        None => None,
+    };
+    Some(Received {
+        protocol: protocol.to_owned(),
+        received_by: received_by.to_owned(),
+        comment,
+    })
+}
+
+impl ::Header for Via {
+    fn name() -> &'static ::HeaderName {
+        &::http::header::VIA
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        let mut entries = Vec::new();
+        for value in values {
+            let s = value.to_str().map_err(|_| ::Error::invalid())?;
+            for entry in split_entries(s).ok_or_else(::Error::invalid)? {
+                if entry.trim().is_empty() {
+                    continue;
+                }
+                entries.push(parse_entry(entry).ok_or_else(::Error::invalid)?);
+            }
+        }
+
+        if entries.is_empty() {
+            Err(::Error::invalid())
+        } else {
+            Ok(Via(entries))
+        }
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once(util::fmt(self)));
+    }
+}
+
+impl ::NamedHeader for Via {
+    const NAME: ::HeaderName = ::http::header::VIA;
+}
+
+impl fmt::Display for Via {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, entry) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{} {}", entry.protocol, entry.received_by)?;
+            if let Some(ref comment) = entry.comment {
+                write!(f, " {}", comment)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::Via;
+
+    #[test]
+    fn round_trip() {
+        let via = test_decode::<Via>(&[
+            "1.0 fred, 1.1 p.example.net (Apache/1.1, with (nested) comment)",
+            "HTTP/2 [::1]:8080",
+        ])
+        .unwrap();
+        let names = via.received_by().collect::<Vec<_>>();
+        assert_eq!(names, ["fred", "p.example.net", "[::1]:8080"]);
+
+        let headers = test_encode(via);
+        assert_eq!(
+            headers["via"],
+            "1.0 fred, 1.1 p.example.net (Apache/1.1, with (nested) comment), HTTP/2 [::1]:8080"
+        );
+    }
+
+    #[test]
+    fn contains_received_by() {
+        let via = test_decode::<Via>(&["1.1 edge.example, 1.1 proxy.example:3128"]).unwrap();
+        assert!(via.contains_received_by("edge.example"));
+        assert!(via.contains_received_by("EDGE.example"));
+        assert!(via.contains_received_by("proxy.example:3128"));
+        assert!(!via.contains_received_by("proxy.example"));
+        assert!(!via.contains_received_by("origin.example"));
+
+        let via = via.with_received("1.1", "origin.example").unwrap();
+        assert!(via.contains_received_by("origin.example"));
+    }
+
+    #[test]
+    fn protocol() {
+        let via = test_decode::<Via>(&["1.1 proxy, FOO/1.0 proxy (x), HTTP/2 edge"]).unwrap();
+        let protocols = via
+            .entries()
+            .map(|entry| (entry.protocol_name(), entry.protocol_version()))
+            .collect::<Vec<_>>();
+        assert_eq!(protocols, [("HTTP", "1.1"), ("FOO", "1.0"), ("HTTP", "2")]);
+
+        let entry = via.entries().nth(1).unwrap();
+        assert_eq!(entry.received_by(), "proxy");
+        assert_eq!(entry.comment(), Some("(x)"));
+
+        // The protocol is sent as it was received.
+        let headers = test_encode(via);
+        assert_eq!(headers["via"], "1.1 proxy, FOO/1.0 proxy (x), HTTP/2 edge");
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<Via>(&[""]), None);
+        assert_eq!(test_decode::<Via>(&["1.1"]), None);
+        assert_eq!(test_decode::<Via>(&["1.1 fred junk"]), None);
+        assert_eq!(test_decode::<Via>(&["1.1 fred (unclosed"]), None);
+        assert_eq!(test_decode::<Via>(&["1.1 fr@d"]), None);
+        assert!(Via::new("1.1", "bad name").is_err());
+    }
+}
@@ -0,0 +1,99 @@
+use util::{sf, IterExt};
+use {HeaderName, HeaderValue};
+
+const VIEWPORT_WIDTH: HeaderName = HeaderName::from_static("viewport-width");
+
+/// `Viewport-Width` header, defined in
+/// [Client Hints](https://wicg.github.io/responsive-image-client-hints/#sec-ch-viewport-width)
+///
+/// The `Viewport-Width` client hint indicates the width of the user agent's
+/// layout viewport, in CSS pixels.
+///
+/// # ABNF
+///
+/// ```text
+/// Viewport-Width = sf-integer
+/// ```
+///
+/// # Example values
+///
+/// * `320`
+/// * `1920`
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// use headers::ViewportWidth;
+///
+/// let width = ViewportWidth::new(1920);
+/// assert_eq!(width.width(), 1920);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ViewportWidth(u32);
+
+impl ViewportWidth {
+    /// Create a `Viewport-Width` header, in CSS pixels.
+    pub fn new(width: u32) -> ViewportWidth {
+        ViewportWidth(width)
+    }
+
+    /// Get the viewport width, in CSS pixels.
+    pub fn width(&self) -> u32 {
+        self.0
+    }
+}
+
+impl ::Header for ViewportWidth {
+    fn name() -> &'static HeaderName {
+        static NAME: HeaderName = VIEWPORT_WIDTH;
+        &NAME
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        values
+            .just_one()
+            .and_then(|val| val.to_str().ok())
+            .and_then(|s| sf::parse_item(s).ok())
+            .and_then(|item| item.bare.as_integer())
+            .and_then(|width| ::std::convert::TryFrom::try_from(width).ok())
+            .map(ViewportWidth)
+            .ok_or_else(::Error::invalid)
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once(self.0.into()));
+    }
+}
+
+impl ::NamedHeader for ViewportWidth {
+    const NAME: ::HeaderName = VIEWPORT_WIDTH;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::ViewportWidth;
+
+    #[test]
+    fn decode() {
+        assert_eq!(
+            test_decode::<ViewportWidth>(&["1920"]),
+            Some(ViewportWidth(1920))
+        );
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<ViewportWidth>(&["-1"]), None);
+        assert_eq!(test_decode::<ViewportWidth>(&["19.5"]), None);
+        assert_eq!(test_decode::<ViewportWidth>(&["99999999999"]), None);
+        assert_eq!(test_decode::<ViewportWidth>(&["wide"]), None);
+    }
+
+    #[test]
+    fn encode() {
+        let headers = test_encode(ViewportWidth::new(320));
+        assert_eq!(headers["viewport-width"], "320");
+    }
+}
@@ -0,0 +1,268 @@
+//! Want-Content-Digest header and types.
+
+use util::sf::{self, BareItem, Item, Member};
+use util::{self, TryFromValues};
+use {HeaderName, HeaderValue};
+
+use super::DigestAlgorithm;
+
+const WANT_CONTENT_DIGEST: HeaderName = HeaderName::from_static("want-content-digest");
+
+/// The highest preference a digest algorithm can be given.
+const MAX_PREFERENCE: u8 = 10;
+
+/// `Want-Content-Digest` header, defined in
+/// [RFC9530](https://www.rfc-editor.org/rfc/rfc9530.html#section-4)
+///
+/// The `Want-Content-Digest` header field asks the recipient to send a
+/// `Content-Digest` in later messages, and says which hash algorithms it
+/// would like. Each algorithm is given a preference from 1, the least
+/// preferred, to 10, the most. A preference of 0 means the algorithm is not
+/// acceptable.
+///
+/// Algorithms this crate doesn't know, or that RFC 9530 deprecates, are
+/// ignored. A received preference outside 0 to 10 is clamped into it.
+///
+/// # ABNF
+///
+/// ```text
+/// Want-Content-Digest = sf-dictionary   ; of sf-integer preferences
+/// ```
+///
+/// # Example values
+///
+/// * `sha-256=3, sha-512=10`
+/// * `sha-512=3, sha-256=10, unixsum=0`
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// use headers::{DigestAlgorithm, WantContentDigest};
+///
+/// let want = WantContentDigest::new(DigestAlgorithm::Sha256, 3)
+///     .and_then(|want| want.with_preference(DigestAlgorithm::Sha512, 10))
+///     .unwrap();
+/// assert_eq!(want.preferred(), Some(DigestAlgorithm::Sha512));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WantContentDigest(Preferences);
+
+error_type!(InvalidWantContentDigest);
+
+impl WantContentDigest {
+    /// Create a `Want-Content-Digest` with a single algorithm.
+    ///
+    /// Fails if the algorithm has no registered name, or the preference is
+    /// over 10.
+    pub fn new(alg: DigestAlgorithm, preference: u8) -> Result<Self, InvalidWantContentDigest> {
+        WantContentDigest(Preferences::default()).with_preference(alg, preference)
+    }
+
+    /// Add another algorithm, replacing the preference of an algorithm
+    /// already listed.
+    ///
+    /// Fails if the algorithm has no registered name, or the preference is
+    /// over 10.
+    pub fn with_preference(
+        mut self,
+        alg: DigestAlgorithm,
+        preference: u8,
+    ) -> Result<Self, InvalidWantContentDigest> {
+        if self.0.insert(alg, preference) {
+            Ok(self)
+        } else {
+            Err(InvalidWantContentDigest { _inner: () })
+        }
+    }
+
+    /// Iterate the algorithms, with the preference of each.
+    pub fn iter(&self) -> impl Iterator<Item = (DigestAlgorithm, u8)> + '_ {
+        self.0.iter()
+    }
+
+    /// The algorithms and their preferences, in the order they were sent.
+    ///
+    /// This is for callers that pick an algorithm their own way, rather
+    /// than with [`preferred`](WantContentDigest::preferred).
+    pub fn preferences(&self) -> &[(DigestAlgorithm, u8)] {
+        self.0.as_slice()
+    }
+
+    /// Get the preference of `alg`, if it is listed.
+    pub fn preference(&self, alg: DigestAlgorithm) -> Option<u8> {
+        self.0.get(alg)
+    }
+
+    /// The most preferred algorithm.
+    ///
+    /// Between algorithms of the same preference, the strongest is picked,
+    /// whatever order they were sent in, so `sha-256=5, sha-512=5` prefers
+    /// SHA-512. Returns `None` if every algorithm is listed as not
+    /// acceptable.
+    pub fn preferred(&self) -> Option<DigestAlgorithm> {
+        self.0.preferred()
+    }
+}
+
+impl ::Header for WantContentDigest {
+    fn name() -> &'static HeaderName {
+        static NAME: HeaderName = WANT_CONTENT_DIGEST;
+        &NAME
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        Preferences::try_from_values(values).map(WantContentDigest)
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once(self.0.to_value()));
+    }
+}
+
+impl ::NamedHeader for WantContentDigest {
+    const NAME: ::HeaderName = WANT_CONTENT_DIGEST;
+}
+
+/// Digest algorithm preferences, shared by the `Want-*-Digest` headers.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(super) struct Preferences(Vec<(DigestAlgorithm, u8)>);
+
+impl Preferences {
+    /// Set the preference of `alg`, returning whether it was valid.
+    pub(super) fn insert(&mut self, alg: DigestAlgorithm, preference: u8) -> bool {
+        if alg.http_name().is_none() || preference > MAX_PREFERENCE {
+            return false;
+        }
+        match self.0.iter_mut().find(|entry| entry.0 == alg) {
+            Some(entry) => entry.1 = preference,
+            None => self.0.push((alg, preference)),
+        }
+        true
+    }
+
+    pub(super) fn iter(&self) -> impl Iterator<Item = (DigestAlgorithm, u8)> + '_ {
+        self.0.iter().cloned()
+    }
+
+    pub(super) fn as_slice(&self) -> &[(DigestAlgorithm, u8)] {
+        &self.0
+    }
+
+    pub(super) fn get(&self, alg: DigestAlgorithm) -> Option<u8> {
+        self.iter().find(|&(a, _)| a == alg).map(|(_, pref)| pref)
+    }
+
+    pub(super) fn preferred(&self) -> Option<DigestAlgorithm> {
+        // `DigestAlgorithm` is ordered from weakest to strongest, so ties go
+        // to the stronger algorithm.
+        self.iter()
+            .filter(|&(_, pref)| pref > 0)
+            .max_by_key(|&(alg, pref)| (pref, alg))
+            .map(|(alg, _)| alg)
+    }
+
+    pub(super) fn to_value(&self) -> HeaderValue {
+        let mut dict = sf::Dictionary::default();
+        for &(alg, pref) in &self.0 {
+            let name = alg.http_name().expect("checked in insert");
+            let item = Item::new(BareItem::Integer(i64::from(pref)));
+            dict.insert(name.to_owned(), Member::Item(item));
+        }
+        util::fmt(dict)
+    }
+}
+
+impl TryFromValues for Preferences {
+    fn try_from_values<'i, I>(values: &mut I) -> Result<Self, ::Error>
+    where
+        I: Iterator<Item = &'i HeaderValue>,
+    {
+        let dict = sf::parse_dictionary(values)?;
+        let mut prefs = Preferences::default();
+        for (key, member) in dict.iter() {
+            let pref = member
+                .as_item()
+                .and_then(|item| item.bare.as_integer())
+                .ok_or_else(::Error::invalid)?;
+            let pref = pref.max(0).min(i64::from(MAX_PREFERENCE)) as u8;
+            if let Some(alg) = DigestAlgorithm::from_http_name(key) {
+                prefs.insert(alg, pref);
+            }
+        }
+
+        if prefs.0.is_empty() {
+            Err(::Error::invalid())
+        } else {
+            Ok(prefs)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let want = test_decode::<WantContentDigest>(&["sha-256=3, sha-512=10"]).unwrap();
+        assert_eq!(
+            want.iter().collect::<Vec<_>>(),
+            [(DigestAlgorithm::Sha256, 3), (DigestAlgorithm::Sha512, 10)]
+        );
+        assert_eq!(want.preference(DigestAlgorithm::Sha256), Some(3));
+        assert_eq!(want.preference(DigestAlgorithm::Sha384), None);
+        assert_eq!(want.preferred(), Some(DigestAlgorithm::Sha512));
+
+        let headers = test_encode(want);
+        assert_eq!(headers["want-content-digest"], "sha-256=3, sha-512=10");
+    }
+
+    #[test]
+    fn preferred() {
+        let want = test_decode::<WantContentDigest>(&["sha-512=3, sha-256=10, unixsum=0"]).unwrap();
+        assert_eq!(want.preferred(), Some(DigestAlgorithm::Sha256));
+
+        let want = test_decode::<WantContentDigest>(&["sha-512=0, sha-256=0"]).unwrap();
+        assert_eq!(want.preferred(), None);
+    }
+
+    #[test]
+    fn preferred_tie() {
+        let want = test_decode::<WantContentDigest>(&["sha-256=5, sha-512=5"]).unwrap();
+        assert_eq!(want.preferred(), Some(DigestAlgorithm::Sha512));
+
+        let want = test_decode::<WantContentDigest>(&["sha-512=5, sha-256=5"]).unwrap();
+        assert_eq!(want.preferred(), Some(DigestAlgorithm::Sha512));
+        assert_eq!(
+            want.preferences(),
+            [(DigestAlgorithm::Sha512, 5), (DigestAlgorithm::Sha256, 5)]
+        );
+    }
+
+    #[test]
+    fn clamped() {
+        let want = test_decode::<WantContentDigest>(&["sha-256=11, sha-512=-1"]).unwrap();
+        assert_eq!(
+            want.preferences(),
+            [(DigestAlgorithm::Sha256, 10), (DigestAlgorithm::Sha512, 0)]
+        );
+        assert_eq!(want.preferred(), Some(DigestAlgorithm::Sha256));
+    }
+
+    #[test]
+    fn decode_invalid() {
+        assert_eq!(test_decode::<WantContentDigest>(&[""]), None);
+        assert_eq!(test_decode::<WantContentDigest>(&["md5=5"]), None);
+        assert_eq!(test_decode::<WantContentDigest>(&["sha-256=1.5"]), None);
+        assert_eq!(test_decode::<WantContentDigest>(&["sha-256"]), None);
+    }
+
+    #[test]
+    fn new_checks_preference() {
+        assert!(WantContentDigest::new(DigestAlgorithm::Sha256, 10).is_ok());
+        assert!(WantContentDigest::new(DigestAlgorithm::Sha256, 11).is_err());
+        assert!(WantContentDigest::new(DigestAlgorithm::Sha384, 1).is_err());
+    }
+}
@@ -0,0 +1,142 @@
+use util::TryFromValues;
+use {HeaderName, HeaderValue};
+
+use super::want_content_digest::Preferences;
+use super::DigestAlgorithm;
+
+const WANT_REPR_DIGEST: HeaderName = HeaderName::from_static("want-repr-digest");
+
+/// `Want-Repr-Digest` header, defined in
+/// [RFC9530](https://www.rfc-editor.org/rfc/rfc9530.html#section-4)
+///
+/// The `Want-Repr-Digest` header field asks the recipient to send a
+/// `Repr-Digest`, the hash of the selected representation, in later
+/// messages. It has the same syntax as `Want-Content-Digest`: each hash
+/// algorithm is given a preference from 1 to 10, or 0 if it is not
+/// acceptable. A received preference outside 0 to 10 is clamped into it.
+///
+/// # ABNF
+///
+/// ```text
+/// Want-Repr-Digest = sf-dictionary   ; of sf-integer preferences
+/// ```
+///
+/// # Example values
+///
+/// * `sha-256=1`
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// use headers::{DigestAlgorithm, WantReprDigest};
+///
+/// let want = WantReprDigest::new(DigestAlgorithm::Sha256, 1).unwrap();
+/// assert_eq!(want.preferred(), Some(DigestAlgorithm::Sha256));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WantReprDigest(Preferences);
+
+error_type!(InvalidWantReprDigest);
+
+impl WantReprDigest {
+    /// Create a `Want-Repr-Digest` with a single algorithm.
+    ///
+    /// Fails if the algorithm has no registered name, or the preference is
+    /// over 10.
+    pub fn new(alg: DigestAlgorithm, preference: u8) -> Result<Self, InvalidWantReprDigest> {
+        WantReprDigest(Preferences::default()).with_preference(alg, preference)
+    }
+
+    /// Add another algorithm, replacing the preference of an algorithm
+    /// already listed.
+    ///
+    /// Fails if the algorithm has no registered name, or the preference is
+    /// over 10.
+    pub fn with_preference(
+        mut self,
+        alg: DigestAlgorithm,
+        preference: u8,
+    ) -> Result<Self, InvalidWantReprDigest> {
+        if self.0.insert(alg, preference) {
+            Ok(self)
+        } else {
+            Err(InvalidWantReprDigest { _inner: () })
+        }
+    }
+
+    /// Iterate the algorithms, with the preference of each.
+    pub fn iter(&self) -> impl Iterator<Item = (DigestAlgorithm, u8)> + '_ {
+        self.0.iter()
+    }
+
+    /// The algorithms and their preferences, in the order they were sent.
+    ///
+    /// This is for callers that pick an algorithm their own way, rather
+    /// than with [`preferred`](WantReprDigest::preferred).
+    pub fn preferences(&self) -> &[(DigestAlgorithm, u8)] {
+        self.0.as_slice()
+    }
+
+    /// Get the preference of `alg`, if it is listed.
+    pub fn preference(&self, alg: DigestAlgorithm) -> Option<u8> {
+        self.0.get(alg)
+    }
+
+    /// The most preferred algorithm.
+    ///
+    /// Between algorithms of the same preference, the strongest is picked,
+    /// whatever order they were sent in, so `sha-256=5, sha-512=5` prefers
+    /// SHA-512. Returns `None` if every algorithm is listed as not
+    /// acceptable.
+    pub fn preferred(&self) -> Option<DigestAlgorithm> {
+        self.0.preferred()
+    }
+}
+
+impl ::Header for WantReprDigest {
+    fn name() -> &'static HeaderName {
+        static NAME: HeaderName = WANT_REPR_DIGEST;
+        &NAME
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        Preferences::try_from_values(values).map(WantReprDigest)
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(::std::iter::once(self.0.to_value()));
+    }
+}
+
+impl ::NamedHeader for WantReprDigest {
+    const NAME: ::HeaderName = WANT_REPR_DIGEST;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let want = test_decode::<WantReprDigest>(&["sha-512=3, sha-256=10"]).unwrap();
+        assert_eq!(want.preferred(), Some(DigestAlgorithm::Sha256));
+
+        let headers = test_encode(want);
+        assert_eq!(headers["want-repr-digest"], "sha-512=3, sha-256=10");
+    }
+
+    #[test]
+    fn preferred_tie() {
+        let want = test_decode::<WantReprDigest>(&["sha-256=1, sha-512=1"]).unwrap();
+        assert_eq!(want.preferred(), Some(DigestAlgorithm::Sha512));
+        assert_eq!(
+            want.preferences(),
+            [(DigestAlgorithm::Sha256, 1), (DigestAlgorithm::Sha512, 1)]
+        );
+
+        let want = test_decode::<WantReprDigest>(&["sha-512=1, sha-256=1"]).unwrap();
+        assert_eq!(want.preferred(), Some(DigestAlgorithm::Sha512));
+    }
+}
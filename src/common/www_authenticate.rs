@@ -0,0 +1,651 @@
+//! WWW-Authenticate header and types.
+
+use std::fmt;
+
+use util::{self, is_tchar, is_token, is_token68, is_token68_char};
+use HeaderValue;
+
+/// `WWW-Authenticate` header, defined in
+/// [RFC7235](https://tools.ietf.org/html/rfc7235#section-4.1)
+///
+/// The `WWW-Authenticate` header field indicates the authentication
+/// scheme(s) and parameters applicable to the target resource. It is sent
+/// with 401 (Unauthorized) responses, and may contain several challenges.
+///
+/// # ABNF
+///
+/// ```text
+/// WWW-Authenticate = 1#challenge
+///
+/// challenge   = auth-scheme [ 1*SP ( token68 / #auth-param ) ]
+/// auth-param  = token BWS "=" BWS ( token / quoted-string )
+/// ```
+///
+/// # Example values
+///
+/// * `Basic realm="simple"`
+/// * `Newauth realm="apps", type=1, title="Login to \"apps\"", Basic realm="simple"`
+/// * `Bearer realm="example", error="invalid_token"`
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// use headers::www_authenticate::Challenge;
+/// use headers::WwwAuthenticate;
+///
+/// let challenge = Challenge::bearer(Some("example"))
+///     .and_then(|c| c.with_error("invalid_token"))
+///     .unwrap();
+/// let www_auth = WwwAuthenticate::new(challenge);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WwwAuthenticate(Vec<Challenge>);
+
+/// A single challenge in a `WWW-Authenticate` header.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Challenge {
+    scheme: String,
+    token68: Option<String>,
+    params: Vec<Param>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Param {
+    name: String,
+    value: String,
+    quoted: bool,
+}
+
+error_type!(InvalidChallenge);
+
+impl WwwAuthenticate {
+    /// Create a `WWW-Authenticate` header with a single challenge.
+    pub fn new(challenge: Challenge) -> Self {
+        WwwAuthenticate(vec![challenge])
+    }
+
+    /// Iterate the challenges, in the order they were sent.
+    pub fn iter(&self) -> impl Iterator<Item = &Challenge> {
+        self.0.iter()
+    }
+}
+
+impl From<Challenge> for WwwAuthenticate {
+    fn from(challenge: Challenge) -> Self {
+        WwwAuthenticate::new(challenge)
+    }
+}
+
+impl ::Header for WwwAuthenticate {
+    fn name() -> &'static ::HeaderName {
+        &::http::header::WWW_AUTHENTICATE
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        let mut challenges = Vec::new();
+        for value in values {
+            let s = value.to_str().map_err(|_| ::Error::invalid())?;
+            Parser::new(s).parse_into(&mut challenges)?;
+        }
+
+        if challenges.is_empty() {
+            Err(::Error::invalid())
+        } else {
+            Ok(WwwAuthenticate(challenges))
+        }
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(self.0.iter().map(util::fmt));
+    }
+}
+
+impl ::NamedHeader for WwwAuthenticate {
+    const NAME: ::HeaderName = ::http::header::WWW_AUTHENTICATE;
+}
+
+impl Challenge {
+    /// Create a challenge for an authentication scheme, with no parameters.
+    pub fn new(scheme: &str) -> Result<Self, InvalidChallenge> {
+        if !is_token(scheme) {
+            return Err(InvalidChallenge { _inner: () });
+        }
+        Ok(Challenge {
+            scheme: scheme.to_owned(),
+            token68: None,
+            params: Vec::new(),
+        })
+    }
+
+    /// Create a `Bearer` challenge, as defined in
+    /// [RFC6750](https://tools.ietf.org/html/rfc6750#section-3).
+    pub fn bearer(realm: Option<&str>) -> Result<Self, InvalidChallenge> {
+        let challenge = Challenge::new("Bearer")?;
+        match realm {
+            Some(realm) => challenge.with_param("realm", realm),
+            None => Ok(challenge),
+        }
+    }
+
+    /// Set the `error` parameter, such as `invalid_token`.
+    pub fn with_error(self, error: &str) -> Result<Self, InvalidChallenge> {
+        self.with_param("error", error)
+    }
+
+    /// Set the human-readable `error_description` parameter.
+    pub fn with_error_description(self, description: &str) -> Result<Self, InvalidChallenge> {
+        self.with_param("error_description", description)
+    }
+
+    /// Set the `scope` parameter, a space-delimited list of scopes.
+    pub fn with_scope(self, scope: &str) -> Result<Self, InvalidChallenge> {
+        self.with_param("scope", scope)
+    }
+
+    /// Set a parameter, replacing any existing one with the same name.
+    ///
+    /// The value is always sent as a quoted-string, escaping as needed.
+    pub fn with_param(mut self, name: &str, value: &str) -> Result<Self, InvalidChallenge> {
+        if self.token68.is_some() || !is_token(name) || !value.bytes().all(is_qdtext_or_pair) {
+            return Err(InvalidChallenge { _inner: () });
+        }
+
+        let param = Param {
+            name: name.to_owned(),
+            value: value.to_owned(),
+            quoted: true,
+        };
+        match self
+            .params
+            .iter_mut()
+            .find(|p| p.name.eq_ignore_ascii_case(name))
+        {
+            Some(existing) => *existing = param,
+            None => self.params.push(param),
+        }
+        Ok(self)
+    }
+
+    /// Set the `token68` form of the challenge data, as used by schemes such
+    /// as `Negotiate` and `NTLM`, replacing any existing one.
+    ///
+    /// A challenge has either a `token68` or parameters, never both.
+    pub fn with_token68(mut self, token68: &str) -> Result<Self, InvalidChallenge> {
+        if !self.params.is_empty() || !is_token68(token68) {
+            return Err(InvalidChallenge { _inner: () });
+        }
+        self.token68 = Some(token68.to_owned());
+        Ok(self)
+    }
+
+    /// The authentication scheme, such as `Basic` or `Bearer`.
+    pub fn scheme(&self) -> &str {
+        &self.scheme
+    }
+
+    /// Get the value of a parameter, matching its name case-insensitively.
+    ///
+    /// Quoted values are returned unescaped.
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|p| p.name.eq_ignore_ascii_case(name))
+            .map(|p| &*p.value)
+    }
+
+    /// Iterate the parameters as `(name, value)` pairs.
+    pub fn params(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.params.iter().map(|p| (&*p.name, &*p.value))
+    }
+
+    /// The `realm` parameter.
+    pub fn realm(&self) -> Option<&str> {
+        self.param("realm")
+    }
+
+    /// The `token68` form of the challenge data, if it was sent instead of
+    /// parameters.
+    pub fn token68(&self) -> Option<&str> {
+        self.token68.as_deref()
+    }
+
+    /// The `algorithm` parameter of a `Digest` challenge, such as `SHA-256`
+    /// or `MD5-sess`, as defined in
+    /// [RFC7616](https://tools.ietf.org/html/rfc7616#section-3.3).
+    ///
+    /// If it is missing, `MD5` is implied.
+    pub fn algorithm(&self) -> Option<&str> {
+        self.param("algorithm")
+    }
+
+    /// The options listed in the `qop` parameter of a `Digest` challenge,
+    /// such as `auth` and `auth-int`, in the order they were sent.
+    ///
+    /// Returns an empty list if there is no `qop`.
+    pub fn qop_options(&self) -> Vec<&str> {
+        self.param("qop")
+            .map(|qop| {
+                qop.split(',')
+                    .map(str::trim)
+                    .filter(|option| !option.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl fmt::Display for Challenge {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.scheme)?;
+        if let Some(ref token68) = self.token68 {
+            return write!(f, " {}", token68);
+        }
+
+        for (i, param) in self.params.iter().enumerate() {
+            f.write_str(if i == 0 { " " } else { ", " })?;
+            f.write_str(&param.name)?;
+            f.write_str("=")?;
+            if param.quoted {
+                write_quoted(&param.value, f)?;
+            } else {
+                f.write_str(&param.value)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn write_quoted(value: &str, f: &mut fmt::Formatter) -> fmt::Result {
+    f.write_str("\"")?;
+    for c in value.chars() {
+        if c == '"' || c == '\\' {
+            f.write_str("\\")?;
+        }
+        write!(f, "{}", c)?;
+    }
+    f.write_str("\"")
+}
+
+/// Whether `b` can appear in a quoted-string, possibly after escaping.
+fn is_qdtext_or_pair(b: u8) -> bool {
+    b == b'\t' || (b' '..=b'~').contains(&b)
+}
+
+struct Parser<'a> {
+    s: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(s: &'a str) -> Self {
+        Parser {
+            s: s.as_bytes(),
+            pos: 0,
+        }
+    }
+
+    fn parse_into(mut self, challenges: &mut Vec<Challenge>) -> Result<(), ::Error> {
+        loop {
+            self.skip_list_separators();
+            if self.pos == self.s.len() {
+                return Ok(());
+            }
+            let challenge = self.challenge()?;
+            challenges.push(challenge);
+        }
+    }
+
+    fn challenge(&mut self) -> Result<Challenge, ::Error> {
+        let scheme = self.token().ok_or_else(|| self.error())?;
+        let mut challenge = Challenge {
+            scheme: scheme.to_owned(),
+            token68: None,
+            params: Vec::new(),
+        };
+
+        if !self.skip_spaces() {
+            return self.end_of_element().map(|()| challenge);
+        }
+
+        if !self.at_param() {
+            if let Some(token68) = self.token68() {
+                challenge.token68 = Some(token68.to_owned());
+                return Ok(challenge);
+            }
+        }
+
+        loop {
+            let param = self.param()?;
+            challenge.params.push(param);
+
+            // Parameters continue until the next element is not `name=`.
+            self.end_of_element()?;
+            let next = self.pos;
+            self.skip_list_separators();
+            if self.pos == self.s.len() || !self.at_param() {
+                self.pos = next;
+                return Ok(challenge);
+            }
+        }
+    }
+
+    fn param(&mut self) -> Result<Param, ::Error> {
+        let name = self.token().ok_or_else(|| self.error())?.to_owned();
+        self.skip_ows();
+        self.expect(b'=')?;
+        self.skip_ows();
+
+        if self.peek() == Some(b'"') {
+            let value = self.quoted_string()?;
+            Ok(Param {
+                name,
+                value,
+                quoted: true,
+            })
+        } else {
+            let value = self.token().ok_or_else(|| self.error())?.to_owned();
+            Ok(Param {
+                name,
+                value,
+                quoted: false,
+            })
+        }
+    }
+
+    /// Whether the next element is `name=value`, rather than a token68 or
+    /// the start of another challenge.
+    fn at_param(&self) -> bool {
+        let mut probe = Parser {
+            s: self.s,
+            pos: self.pos,
+        };
+        if probe.token().is_none() {
+            return false;
+        }
+        probe.skip_ows();
+        if probe.expect(b'=').is_err() {
+            return false;
+        }
+        probe.skip_ows();
+        match probe.peek() {
+            None | Some(b'=') | Some(b',') => false,
+            Some(_) => true,
+        }
+    }
+
+    fn token68(&mut self) -> Option<&'a str> {
+        let start = self.pos;
+        let mut end = start;
+        while end < self.s.len() && is_token68_char(self.s[end]) {
+            end += 1;
+        }
+        if end == start {
+            return None;
+        }
+        while end < self.s.len() && self.s[end] == b'=' {
+            end += 1;
+        }
+
+        // It's only a token68 if nothing else follows in this element.
+        let mut probe = Parser {
+            s: self.s,
+            pos: end,
+        };
+        if probe.end_of_element().is_err() {
+            return None;
+        }
+        self.pos = end;
+        Some(::std::str::from_utf8(&self.s[start..end]).expect("token68 is ASCII"))
+    }
+
+    fn token(&mut self) -> Option<&'a str> {
+        let start = self.pos;
+        while self.pos < self.s.len() && is_tchar(self.s[self.pos]) {
+            self.pos += 1;
+        }
+        if self.pos == start {
+            None
+        } else {
+            Some(::std::str::from_utf8(&self.s[start..self.pos]).expect("tokens are ASCII"))
+        }
+    }
+
+    fn quoted_string(&mut self) -> Result<String, ::Error> {
+        let start = self.pos;
+        self.expect(b'"')?;
+        let mut value = Vec::new();
+        loop {
+            match self.peek() {
+                Some(b'"') => {
+                    self.pos += 1;
+                    return String::from_utf8(value).map_err(|_| ::Error::invalid_at(start));
+                }
+                Some(b'\\') => {
+                    self.pos += 1;
+                    let b = self.peek().ok_or_else(|| self.error())?;
+                    value.push(b);
+                    self.pos += 1;
+                }
+                Some(b) => {
+                    value.push(b);
+                    self.pos += 1;
+                }
+                None => return Err(self.error()),
+            }
+        }
+    }
+
+    fn end_of_element(&mut self) -> Result<(), ::Error> {
+        self.skip_ows();
+        match self.peek() {
+            None | Some(b',') => Ok(()),
+            Some(_) => Err(self.error()),
+        }
+    }
+
+    fn expect(&mut self, b: u8) -> Result<(), ::Error> {
+        if self.peek() == Some(b) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.error())
+        }
+    }
+
+    /// A syntax error at the current position.
+    fn error(&self) -> ::Error {
+        ::Error::invalid_at(self.pos)
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.s.get(self.pos).cloned()
+    }
+
+    /// Skip spaces, returning whether there were any.
+    fn skip_spaces(&mut self) -> bool {
+        let start = self.pos;
+        while self.peek() == Some(b' ') {
+            self.pos += 1;
+        }
+        self.pos > start
+    }
+
+    fn skip_ows(&mut self) {
+        while let Some(b' ') | Some(b'\t') = self.peek() {
+            self.pos += 1;
+        }
+    }
+
+    fn skip_list_separators(&mut self) {
+        while let Some(b' ') | Some(b'\t') | Some(b',') = self.peek() {
+            self.pos += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn bearer_rfc6750() {
+        let challenge = Challenge::bearer(Some("example"))
+            .and_then(|c| c.with_error("invalid_token"))
+            .and_then(|c| c.with_error_description("The access token expired"))
+            .unwrap();
+
+        let headers = test_encode(WwwAuthenticate::new(challenge));
+        assert_eq!(
+            headers["www-authenticate"],
+            "Bearer realm=\"example\", error=\"invalid_token\", \
+             error_description=\"The access token expired\""
+        );
+    }
+
+    #[test]
+    fn bearer_quoting() {
+        let challenge = Challenge::bearer(None)
+            .and_then(|c| c.with_scope("read \"all\" \\ write"))
+            .unwrap();
+        assert_eq!(
+            challenge.to_string(),
+            "Bearer scope=\"read \\\"all\\\" \\\\ write\""
+        );
+
+        assert!(Challenge::bearer(Some("bad\nrealm")).is_err());
+        assert!(Challenge::new("not a token").is_err());
+    }
+
+    #[test]
+    fn decode_multiple_challenges() {
+        let www_auth = test_decode::<WwwAuthenticate>(&[
+            "Newauth realm=\"apps\", type=1, title=\"Login to \\\"apps\\\"\", Basic realm=\"simple\"",
+        ])
+        .unwrap();
+
+        let challenges = www_auth.iter().collect::<Vec<_>>();
+        assert_eq!(challenges.len(), 2);
+        assert_eq!(challenges[0].scheme(), "Newauth");
+        assert_eq!(challenges[0].realm(), Some("apps"));
+        assert_eq!(challenges[0].param("TYPE"), Some("1"));
+        assert_eq!(challenges[0].param("title"), Some("Login to \"apps\""));
+        assert_eq!(challenges[1].scheme(), "Basic");
+        assert_eq!(challenges[1].realm(), Some("simple"));
+    }
+
+    #[test]
+    fn digest() {
+        let www_auth = test_decode::<WwwAuthenticate>(&[
+            "Digest realm=\"x\", qop=\"auth,auth-int\", algorithm=SHA-256, \
+             nonce=\"7ypf/xlj9XXwfDPEoM4URrv/xwf94BcCAzFZH4GiTo0v\", \
+             Digest realm=\"x\", qop=\"auth\", algorithm=MD5-sess, nonce=\"abc\", \
+             Basic realm=\"x\"",
+        ])
+        .unwrap();
+
+        let challenges = www_auth.iter().collect::<Vec<_>>();
+        assert_eq!(challenges.len(), 3);
+        assert_eq!(challenges[0].qop_options(), ["auth", "auth-int"]);
+        assert_eq!(challenges[0].algorithm(), Some("SHA-256"));
+        assert_eq!(
+            challenges[0].param("nonce"),
+            Some("7ypf/xlj9XXwfDPEoM4URrv/xwf94BcCAzFZH4GiTo0v")
+        );
+        assert_eq!(challenges[1].qop_options(), ["auth"]);
+        assert_eq!(challenges[1].algorithm(), Some("MD5-sess"));
+        assert_eq!(challenges[2].scheme(), "Basic");
+        assert!(challenges[2].qop_options().is_empty());
+        assert_eq!(challenges[2].algorithm(), None);
+    }
+
+    #[test]
+    fn decode_token68_and_bare_schemes() {
+        let www_auth = test_decode::<WwwAuthenticate>(&[
+            "Negotiate",
+            "NTLM TlRMTVNTUAACAAAA==, Basic realm=x",
+        ])
+        .unwrap();
+
+        let challenges = www_auth.iter().collect::<Vec<_>>();
+        assert_eq!(challenges.len(), 3);
+        assert_eq!(challenges[0].scheme(), "Negotiate");
+        assert_eq!(challenges[0].token68(), None);
+        assert_eq!(challenges[1].token68(), Some("TlRMTVNTUAACAAAA=="));
+        assert_eq!(challenges[2].realm(), Some("x"));
+    }
+
+    #[test]
+    fn negotiate_round_trip() {
+        let value = "Negotiate YIIZxwYGKwYBBQUCoIIZuzCCGbegMDAuBgkqhkiC9xIBAgIGCSqGSIb3EgECAgYKKwYBBAGCNwICHgYKKwYBBAGCNwICCg==";
+        let www_auth = test_decode::<WwwAuthenticate>(&[value]).unwrap();
+
+        let challenge = www_auth.iter().next().unwrap();
+        assert_eq!(challenge.scheme(), "Negotiate");
+        assert_eq!(challenge.token68(), Some(&value["Negotiate ".len()..]));
+        assert_eq!(challenge.params().count(), 0);
+
+        let headers = test_encode(www_auth);
+        assert_eq!(headers["www-authenticate"], value);
+    }
+
+    #[test]
+    fn with_token68() {
+        let challenge = Challenge::new("Negotiate")
+            .and_then(|c| c.with_token68("oYG2MIGzoAMKAQ=="))
+            .unwrap();
+        assert_eq!(challenge.to_string(), "Negotiate oYG2MIGzoAMKAQ==");
+
+        assert!(challenge.clone().with_param("realm", "x").is_err());
+        assert!(Challenge::new("NTLM")
+            .and_then(|c| c.with_token68("not token68"))
+            .is_err());
+        assert!(Challenge::bearer(Some("x"))
+            .and_then(|c| c.with_token68("abc"))
+            .is_err());
+    }
+
+    #[test]
+    fn round_trip() {
+        let www_auth =
+            test_decode::<WwwAuthenticate>(&["Basic realm=\"simple\", charset=UTF-8, Bearer"])
+                .unwrap();
+
+        let headers = test_encode(www_auth);
+        let values = headers
+            .get_all("www-authenticate")
+            .iter()
+            .collect::<Vec<_>>();
+        assert_eq!(values, ["Basic realm=\"simple\", charset=UTF-8", "Bearer"]);
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(test_decode::<WwwAuthenticate>(&[""]), None);
+        assert_eq!(
+            test_decode::<WwwAuthenticate>(&["Basic realm=\"unterminated"]),
+            None
+        );
+        assert_eq!(
+            test_decode::<WwwAuthenticate>(&["Basic realm=\"x\" junk"]),
+            None
+        );
+        assert_eq!(test_decode::<WwwAuthenticate>(&["=realm"]), None);
+    }
+
+    #[test]
+    fn invalid_offset() {
+        let offset = |s: &'static str| {
+            let value = HeaderValue::from_static(s);
+            <WwwAuthenticate as ::Header>::decode(&mut ::std::iter::once(&value))
+                .unwrap_err()
+                .offset()
+        };
+        assert_eq!(offset("=realm"), Some(0));
+        assert_eq!(offset("Basic realm=\"x\" junk"), Some(16));
+        assert_eq!(offset("Basic realm=\"unterminated"), Some(25));
+        assert_eq!(offset("Basic, Bearer realm=?"), Some(20));
+        assert_eq!(offset(""), None);
+    }
+}
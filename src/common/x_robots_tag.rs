@@ -0,0 +1,482 @@
+//! X-Robots-Tag header and types.
+
+use std::fmt;
+use std::time::SystemTime;
+
+use util::{self, is_token, HttpDate};
+use {HeaderName, HeaderValue};
+
+const X_ROBOTS_TAG: HeaderName = HeaderName::from_static("x-robots-tag");
+
+/// `X-Robots-Tag` header, as documented by
+/// [Google](https://developers.google.com/search/docs/crawling-indexing/robots-meta-tag#xrobotstag)
+///
+/// The `X-Robots-Tag` response header field tells crawlers how a response
+/// may be indexed, just like a `robots` meta tag, but also works for
+/// responses that aren't HTML, such as PDFs and images.
+///
+/// Directives can be prefixed with the name of a crawler, in which case
+/// they, and any directives after them in the same line, only apply to
+/// that crawler. Directives that aren't recognized are skipped.
+///
+/// # Example values
+///
+/// * `noindex, nofollow`
+/// * `googlebot: nofollow`
+/// * `otherbot: noindex, max-snippet:20`
+/// * `unavailable_after: Fri, 25 Jun 2010 15:00:00 GMT`
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// # extern crate http;
+/// use headers::x_robots_tag::Directive;
+/// use headers::{HeaderMapExt, XRobotsTag};
+///
+/// let mut map = http::HeaderMap::new();
+/// map.append("x-robots-tag", "noarchive".parse().unwrap());
+/// map.append("x-robots-tag", "googlebot: noindex, nofollow".parse().unwrap());
+///
+/// let robots = map.typed_get::<XRobotsTag>().unwrap();
+/// assert!(robots.contains(Directive::NoArchive));
+/// assert!(!robots.contains(Directive::NoIndex));
+/// assert!(robots.applies_to("Googlebot").any(|&d| d == Directive::NoIndex));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct XRobotsTag(Vec<Rule>);
+
+#[derive(Clone, Debug, PartialEq)]
+struct Rule {
+    bot: Option<String>,
+    directives: Vec<Directive>,
+}
+
+/// A directive in an `X-Robots-Tag` header.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Directive {
+    /// `all`, no restrictions.
+    All,
+    /// `noindex`, don't show the response in search results.
+    NoIndex,
+    /// `nofollow`, don't follow the links in the response.
+    NoFollow,
+    /// `none`, the same as `noindex, nofollow`.
+    None,
+    /// `noarchive`, don't show a cached copy.
+    NoArchive,
+    /// `nosnippet`, don't show a text snippet or video preview.
+    NoSnippet,
+    /// `notranslate`, don't offer a translation.
+    NoTranslate,
+    /// `noimageindex`, don't index images on the page.
+    NoImageIndex,
+    /// `indexifembedded`, allow indexing when embedded, despite `noindex`.
+    IndexIfEmbedded,
+    /// `max-snippet`, the longest text snippet in characters, or -1 for no
+    /// limit.
+    MaxSnippet(i32),
+    /// `max-image-preview`, the largest image preview.
+    MaxImagePreview(ImagePreview),
+    /// `max-video-preview`, the longest video preview in seconds, or -1 for
+    /// no limit.
+    MaxVideoPreview(i32),
+    /// `unavailable_after`, don't show the response in search results after
+    /// this date.
+    ///
+    /// A time outside of the years 1970 to 9999 is sent as the nearest
+    /// date that can be.
+    UnavailableAfter(SystemTime),
+}
+
+/// The size of an image preview, for `Directive::MaxImagePreview`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImagePreview {
+    /// `none`, no image preview.
+    None,
+    /// `standard`, a default image preview.
+    Standard,
+    /// `large`, a preview up to the width of the viewport.
+    Large,
+}
+
+error_type!(InvalidBotName);
+
+impl XRobotsTag {
+    /// Create an `X-Robots-Tag` with directives for every crawler.
+    pub fn new<I: IntoIterator<Item = Directive>>(directives: I) -> Self {
+        XRobotsTag(vec![Rule {
+            bot: None,
+            directives: directives.into_iter().collect(),
+        }])
+    }
+
+    /// Add directives that only apply to the crawler named `bot`.
+    pub fn with_bot<I>(mut self, bot: &str, directives: I) -> Result<Self, InvalidBotName>
+    where
+        I: IntoIterator<Item = Directive>,
+    {
+        if !is_token(bot) || takes_value(bot) {
+            return Err(InvalidBotName { _inner: () });
+        }
+        self.0.push(Rule {
+            bot: Some(bot.to_owned()),
+            directives: directives.into_iter().collect(),
+        });
+        Ok(self)
+    }
+
+    /// Iterate the directives, with the crawler they apply to, or `None`
+    /// if they apply to every crawler.
+    pub fn iter(&self) -> impl Iterator<Item = (Option<&str>, &[Directive])> {
+        self.0
+            .iter()
+            .map(|rule| (rule.bot.as_deref(), &rule.directives[..]))
+    }
+
+    /// Iterate the directives that apply to the crawler named `bot`,
+    /// including those for every crawler.
+    ///
+    /// Crawler names are compared case-insensitively.
+    pub fn applies_to<'a>(&'a self, bot: &'a str) -> impl Iterator<Item = &'a Directive> + 'a {
+        self.0
+            .iter()
+            .filter(move |rule| {
+                rule.bot
+                    .as_ref()
+                    .map_or(true, |name| name.eq_ignore_ascii_case(bot))
+            })
+            .flat_map(|rule| rule.directives.iter())
+    }
+
+    /// Check if a directive applies to every crawler.
+    ///
+    /// Directives for a specific crawler are not checked, use `applies_to`
+    /// for those.
+    pub fn contains(&self, directive: Directive) -> bool {
+        self.0
+            .iter()
+            .filter(|rule| rule.bot.is_none())
+            .any(|rule| rule.directives.contains(&directive))
+    }
+
+    fn parse_line(s: &str, rules: &mut Vec<Rule>) -> Option<()> {
+        let mut current = Rule {
+            bot: None,
+            directives: Vec::new(),
+        };
+        let mut parts = s.split(',').map(str::trim);
+        while let Some(part) = parts.next() {
+            if part.is_empty() {
+                continue;
+            }
+
+            let (mut name, mut value) = split_directive(part);
+            if let Some(rest) = value {
+                if !takes_value(name) {
+                    // A crawler name, starting a new rule.
+                    if !is_token(name) {
+                        return None;
+                    }
+                    let rule = ::std::mem::replace(
+                        &mut current,
+                        Rule {
+                            bot: Some(name.to_owned()),
+                            directives: Vec::new(),
+                        },
+                    );
+                    if rule.bot.is_some() || !rule.directives.is_empty() {
+                        rules.push(rule);
+                    }
+                    if rest.is_empty() {
+                        continue;
+                    }
+                    let directive = split_directive(rest);
+                    name = directive.0;
+                    value = directive.1;
+                }
+            }
+
+            // A date such as `Fri, 25 Jun 2010 15:00:00 GMT` contains a
+            // comma, so join the weekday with the rest of the date.
+            let joined;
+            if let Some(v) = value {
+                if name.eq_ignore_ascii_case("unavailable_after")
+                    && !v.bytes().any(|b| b.is_ascii_digit())
+                {
+                    joined = format!("{}, {}", v, parts.next().unwrap_or(""));
+                    value = Some(&joined);
+                }
+            }
+
+            current.directives.extend(Directive::parse(name, value));
+        }
+
+        if current.bot.is_some() || !current.directives.is_empty() {
+            rules.push(current);
+        }
+        Some(())
+    }
+}
+
+fn split_directive(s: &str) -> (&str, Option<&str>) {
+    match s.find(':') {
+        Some(idx) => (s[..idx].trim(), Some(s[idx + 1..].trim())),
+        None => (s, None),
+    }
+}
+
+/// Whether `name` is a directive that takes a value.
+fn takes_value(name: &str) -> bool {
+    [
+        "max-snippet",
+        "max-image-preview",
+        "max-video-preview",
+        "unavailable_after",
+    ]
+    .iter()
+    .any(|known| name.eq_ignore_ascii_case(known))
+}
+
+impl Directive {
+    fn parse(name: &str, value: Option<&str>) -> Option<Directive> {
+        let name = name.to_ascii_lowercase();
+        let directive = match (&*name, value) {
+            ("all", None) => Directive::All,
+            ("noindex", None) => Directive::NoIndex,
+            ("nofollow", None) => Directive::NoFollow,
+            ("none", None) => Directive::None,
+            ("noarchive", None) => Directive::NoArchive,
+            ("nosnippet", None) => Directive::NoSnippet,
+            ("notranslate", None) => Directive::NoTranslate,
+            ("noimageindex", None) => Directive::NoImageIndex,
+            ("indexifembedded", None) => Directive::IndexIfEmbedded,
+            ("max-snippet", Some(v)) => Directive::MaxSnippet(parse_limit(v)?),
+            ("max-video-preview", Some(v)) => Directive::MaxVideoPreview(parse_limit(v)?),
+            ("max-image-preview", Some(v)) => {
+                let size = match &*v.to_ascii_lowercase() {
+                    "none" => ImagePreview::None,
+                    "standard" => ImagePreview::Standard,
+                    "large" => ImagePreview::Large,
+                    _ => return None,
+                };
+                Directive::MaxImagePreview(size)
+            }
+            ("unavailable_after", Some(v)) => {
+                Directive::UnavailableAfter(v.parse::<HttpDate>().ok()?.into())
+            }
+            _ => return None,
+        };
+        Some(directive)
+    }
+}
+
+fn parse_limit(s: &str) -> Option<i32> {
+    s.parse().ok().filter(|&n| n >= -1)
+}
+
+impl ::Header for XRobotsTag {
+    fn name() -> &'static HeaderName {
+        static NAME: HeaderName = X_ROBOTS_TAG;
+        &NAME
+    }
+
+    fn decode<'i, I: Iterator<Item = &'i HeaderValue>>(values: &mut I) -> Result<Self, ::Error> {
+        let mut rules = Vec::new();
+        for value in values {
+            let s = value.to_str().map_err(|_| ::Error::invalid())?;
+            XRobotsTag::parse_line(s, &mut rules).ok_or_else(::Error::invalid)?;
+        }
+
+        if rules.is_empty() {
+            Err(::Error::invalid())
+        } else {
+            Ok(XRobotsTag(rules))
+        }
+    }
+
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        values.extend(self.0.iter().map(util::fmt));
+    }
+}
+
+impl ::NamedHeader for XRobotsTag {
+    const NAME: ::HeaderName = X_ROBOTS_TAG;
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(ref bot) = self.bot {
+            write!(f, "{}: ", bot)?;
+        }
+        util::csv::fmt_comma_delimited(f, self.directives.iter())
+    }
+}
+
+impl fmt::Display for Directive {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Directive::All => f.write_str("all"),
+            Directive::NoIndex => f.write_str("noindex"),
+            Directive::NoFollow => f.write_str("nofollow"),
+            Directive::None => f.write_str("none"),
+            Directive::NoArchive => f.write_str("noarchive"),
+            Directive::NoSnippet => f.write_str("nosnippet"),
+            Directive::NoTranslate => f.write_str("notranslate"),
+            Directive::NoImageIndex => f.write_str("noimageindex"),
+            Directive::IndexIfEmbedded => f.write_str("indexifembedded"),
+            Directive::MaxSnippet(n) => write!(f, "max-snippet:{}", n),
+            Directive::MaxImagePreview(size) => {
+                let size = match size {
+                    ImagePreview::None => "none",
+                    ImagePreview::Standard => "standard",
+                    ImagePreview::Large => "large",
+                };
+                write!(f, "max-image-preview:{}", size)
+            }
+            Directive::MaxVideoPreview(n) => write!(f, "max-video-preview:{}", n),
+            Directive::UnavailableAfter(time) => {
+                write!(
+                    f,
+                    "unavailable_after: {}",
+                    HttpDate::from_system_time_clamped(time)
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{test_decode, test_encode};
+    use super::*;
+
+    #[test]
+    fn decode_directives() {
+        let robots = test_decode::<XRobotsTag>(&["noindex, NoFollow", "max-snippet: 20"]).unwrap();
+        assert!(robots.contains(Directive::NoIndex));
+        assert!(robots.contains(Directive::NoFollow));
+        assert!(robots.contains(Directive::MaxSnippet(20)));
+        assert!(!robots.contains(Directive::NoArchive));
+    }
+
+    #[test]
+    fn decode_bots() {
+        let robots = test_decode::<XRobotsTag>(&[
+            "nosnippet",
+            "googlebot: noindex, nofollow, otherbot: max-image-preview:large",
+            "BingBot: noarchive",
+        ])
+        .unwrap();
+
+        let rules = robots.iter().collect::<Vec<_>>();
+        assert_eq!(
+            rules,
+            [
+                (None, &[Directive::NoSnippet][..]),
+                (
+                    Some("googlebot"),
+                    &[Directive::NoIndex, Directive::NoFollow][..]
+                ),
+                (
+                    Some("otherbot"),
+                    &[Directive::MaxImagePreview(ImagePreview::Large)][..]
+                ),
+                (Some("BingBot"), &[Directive::NoArchive][..]),
+            ]
+        );
+
+        let google = robots.applies_to("GoogleBot").collect::<Vec<_>>();
+        assert_eq!(
+            google,
+            [
+                &Directive::NoSnippet,
+                &Directive::NoIndex,
+                &Directive::NoFollow
+            ]
+        );
+        assert!(!robots.contains(Directive::NoIndex));
+    }
+
+    #[test]
+    fn decode_unavailable_after() {
+        let robots = test_decode::<XRobotsTag>(&[
+            "googlebot: unavailable_after: Fri, 25 Jun 2010 15:00:00 GMT, noarchive",
+        ])
+        .unwrap();
+        let date = "Fri, 25 Jun 2010 15:00:00 GMT".parse::<HttpDate>().unwrap();
+        assert_eq!(
+            robots.applies_to("googlebot").collect::<Vec<_>>(),
+            [
+                &Directive::UnavailableAfter(date.into()),
+                &Directive::NoArchive
+            ]
+        );
+    }
+
+    #[test]
+    fn unavailable_after_out_of_range() {
+        use std::time::{Duration, UNIX_EPOCH};
+
+        let before = Directive::UnavailableAfter(UNIX_EPOCH - Duration::from_secs(1));
+        assert_eq!(
+            before.to_string(),
+            "unavailable_after: Thu, 01 Jan 1970 00:00:00 GMT"
+        );
+
+        let after = Directive::UnavailableAfter(UNIX_EPOCH + Duration::from_secs(300_000_000_000));
+        assert_eq!(
+            after.to_string(),
+            "unavailable_after: Fri, 31 Dec 9999 23:59:59 GMT"
+        );
+    }
+
+    #[test]
+    fn decode_skips_unknown() {
+        let robots = test_decode::<XRobotsTag>(&["noindex, nocache, max-snippet:x"]).unwrap();
+        assert_eq!(
+            robots.iter().collect::<Vec<_>>(),
+            [(None, &[Directive::NoIndex][..])]
+        );
+
+        assert_eq!(test_decode::<XRobotsTag>(&[""]), None);
+        assert_eq!(test_decode::<XRobotsTag>(&["bad bot: noindex"]), None);
+    }
+
+    #[test]
+    fn encode() {
+        let date = "Fri, 25 Jun 2010 15:00:00 GMT".parse::<HttpDate>().unwrap();
+        let robots = XRobotsTag::new(vec![Directive::NoIndex, Directive::MaxSnippet(-1)])
+            .with_bot(
+                "googlebot",
+                vec![
+                    Directive::UnavailableAfter(date.into()),
+                    Directive::NoFollow,
+                ],
+            )
+            .unwrap();
+
+        let headers = test_encode(robots.clone());
+        let values = headers.get_all("x-robots-tag").iter().collect::<Vec<_>>();
+        assert_eq!(
+            values,
+            [
+                "noindex, max-snippet:-1",
+                "googlebot: unavailable_after: Fri, 25 Jun 2010 15:00:00 GMT, nofollow",
+            ]
+        );
+
+        let values = values
+            .into_iter()
+            .map(|v| v.to_str().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(test_decode::<XRobotsTag>(&values), Some(robots));
+    }
+
+    #[test]
+    fn invalid_bot_name() {
+        let robots = XRobotsTag::new(vec![Directive::NoIndex]);
+        assert!(robots.clone().with_bot("bad bot", vec![]).is_err());
+        assert!(robots.with_bot("max-snippet", vec![]).is_err());
+    }
+}
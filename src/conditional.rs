@@ -0,0 +1,172 @@
+//! Evaluating conditional requests.
+//!
+//! A client revalidating a cached response sends the validators it has,
+//! in `If-None-Match` and `If-Modified-Since`, and the server answers
+//! `304 Not Modified` if they are still current.
+
+use http::HeaderMap;
+
+use {ETag, HeaderMapExt, IfModifiedSince, IfNoneMatch, LastModified};
+
+/// Check if a `GET` or `HEAD` `request` should get a `304 Not Modified`.
+///
+/// `etag` and `last_modified` are the validators of the selected
+/// representation, if the server has them. This follows the precedence of
+/// [RFC7232](https://tools.ietf.org/html/rfc7232#section-6):
+///
+/// 1. If the request has `If-None-Match`, a 304 is warranted if it is `*`,
+///    or lists a tag matching `etag` by the weak comparison.
+///    `If-Modified-Since` is then ignored, even if it disagrees.
+/// 2. Otherwise, if the request has `If-Modified-Since`, a 304 is
+///    warranted if `last_modified` is no later than it.
+/// 3. Otherwise, it isn't.
+///
+/// A conditional header that fails to decode is treated as not matching,
+/// so the full response is sent. Other methods should be evaluated with
+/// `If-Match` and `If-Unmodified-Since` instead.
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// # extern crate http;
+/// use headers::conditional::strong_validator_matches;
+/// use headers::ETag;
+///
+/// let etag = "\"xyzzy\"".parse::<ETag>().unwrap();
+///
+/// let mut request = http::HeaderMap::new();
+/// request.insert("if-none-match", "W/\"xyzzy\"".parse().unwrap());
+/// assert!(strong_validator_matches(&request, Some(&etag), None));
+/// ```
+pub fn strong_validator_matches(
+    request: &HeaderMap,
+    etag: Option<&ETag>,
+    last_modified: Option<&LastModified>,
+) -> bool {
+    match request.typed_try_get::<IfNoneMatch>() {
+        Ok(Some(if_none_match)) => {
+            return if if_none_match == IfNoneMatch::any() {
+                true
+            } else {
+                etag.map_or(false, |etag| !if_none_match.precondition_passes(etag))
+            };
+        }
+        Err(_) => return false,
+        Ok(None) => {}
+    }
+
+    match (request.typed_get::<IfModifiedSince>(), last_modified) {
+        (Some(since), Some(&last_modified)) => !since.is_modified(last_modified.into()),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+
+    use http::HeaderMap;
+
+    use super::strong_validator_matches;
+    use {ETag, HeaderMapExt, IfModifiedSince, LastModified};
+
+    fn request(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for &(name, value) in pairs {
+            map.insert(name, value.parse().unwrap());
+        }
+        map
+    }
+
+    fn etag() -> ETag {
+        "\"xyzzy\"".parse().unwrap()
+    }
+
+    fn times() -> (LastModified, HeaderMap, HeaderMap) {
+        let modified = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+        let mut before = HeaderMap::new();
+        before.typed_insert(IfModifiedSince::from(modified - Duration::from_secs(60)));
+        let mut at = HeaderMap::new();
+        at.typed_insert(IfModifiedSince::from(modified));
+        (LastModified::from(modified), before, at)
+    }
+
+    #[test]
+    fn unconditional() {
+        let (last_modified, _, _) = times();
+        let req = HeaderMap::new();
+        assert!(!strong_validator_matches(
+            &req,
+            Some(&etag()),
+            Some(&last_modified)
+        ));
+        assert!(!strong_validator_matches(&req, None, None));
+    }
+
+    #[test]
+    fn if_none_match() {
+        let etag = etag();
+        let req = request(&[("if-none-match", "\"a\", \"xyzzy\"")]);
+        assert!(strong_validator_matches(&req, Some(&etag), None));
+
+        let req = request(&[("if-none-match", "W/\"xyzzy\"")]);
+        assert!(strong_validator_matches(&req, Some(&etag), None));
+
+        let req = request(&[("if-none-match", "\"other\"")]);
+        assert!(!strong_validator_matches(&req, Some(&etag), None));
+        assert!(!strong_validator_matches(&req, None, None));
+
+        let req = request(&[("if-none-match", "*")]);
+        assert!(strong_validator_matches(&req, Some(&etag), None));
+        assert!(strong_validator_matches(&req, None, None));
+
+        let req = request(&[("if-none-match", "xyzzy")]);
+        assert!(!strong_validator_matches(&req, Some(&etag), None));
+    }
+
+    #[test]
+    fn if_modified_since() {
+        let (last_modified, before, at) = times();
+        assert!(strong_validator_matches(&at, None, Some(&last_modified)));
+        assert!(strong_validator_matches(
+            &at,
+            Some(&etag()),
+            Some(&last_modified)
+        ));
+        assert!(!strong_validator_matches(
+            &before,
+            None,
+            Some(&last_modified)
+        ));
+        assert!(!strong_validator_matches(&at, None, None));
+
+        let req = request(&[("if-modified-since", "yesterday")]);
+        assert!(!strong_validator_matches(&req, None, Some(&last_modified)));
+    }
+
+    #[test]
+    fn if_none_match_wins() {
+        let etag = etag();
+        let (last_modified, mut before, mut at) = times();
+
+        // Matching tag, though the date says modified.
+        before.insert("if-none-match", "\"xyzzy\"".parse().unwrap());
+        assert!(strong_validator_matches(
+            &before,
+            Some(&etag),
+            Some(&last_modified)
+        ));
+
+        // Unmatched tag, though the date says not modified.
+        at.insert("if-none-match", "\"other\"".parse().unwrap());
+        assert!(!strong_validator_matches(
+            &at,
+            Some(&etag),
+            Some(&last_modified)
+        ));
+
+        // No tag to compare, so the date is still ignored.
+        assert!(!strong_validator_matches(&at, None, Some(&last_modified)));
+    }
+}
@@ -0,0 +1,215 @@
+//! Helpers for CORS.
+
+use std::collections::HashSet;
+use std::str::FromStr;
+
+use Origin;
+
+/// A set of origins allowed to make cross-origin requests.
+///
+/// Origins can be allowed exactly, or by an `OriginPattern` matching any
+/// subdomain. Matching compares the scheme, host and port of an `Origin`,
+/// so `https://example.com` does not allow `https://example.com:8443`.
+///
+/// The `null` origin, and allowing any origin, must be enabled explicitly.
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// use headers::cors::OriginAllowList;
+/// use headers::Origin;
+///
+/// let allowed = OriginAllowList::new()
+///     .with_origin(Origin::try_from_parts("https", "example.com", None).unwrap())
+///     .with_pattern("https://*.example.com".parse().unwrap());
+///
+/// let api = Origin::try_from_parts("https", "api.example.com", None).unwrap();
+/// assert!(allowed.is_allowed(&api));
+/// assert!(!allowed.is_allowed(&Origin::NULL));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct OriginAllowList {
+    exact: HashSet<Origin>,
+    patterns: Vec<OriginPattern>,
+    null: bool,
+    any: bool,
+}
+
+/// A pattern matching every subdomain of an origin, such as
+/// `https://*.example.com`.
+///
+/// The pattern does not match the parent domain itself, and like an exact
+/// origin, the scheme and port must match too.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OriginPattern {
+    // The origin with the `*.` prefix of the host removed.
+    base: Origin,
+}
+
+error_type!(InvalidOriginPattern);
+
+impl OriginAllowList {
+    /// Create an empty `OriginAllowList`, which allows no origins.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allow an exact origin.
+    pub fn with_origin(mut self, origin: Origin) -> Self {
+        self.exact.insert(origin);
+        self
+    }
+
+    /// Allow every origin matching a pattern.
+    pub fn with_pattern(mut self, pattern: OriginPattern) -> Self {
+        self.patterns.push(pattern);
+        self
+    }
+
+    /// Allow the `null` origin, as sent by sandboxed documents and `file:`
+    /// URLs.
+    pub fn with_null(mut self) -> Self {
+        self.null = true;
+        self
+    }
+
+    /// Allow any origin, except `null` unless `with_null` is also set.
+    pub fn with_any(mut self) -> Self {
+        self.any = true;
+        self
+    }
+
+    /// Check if an `Origin` is allowed.
+    pub fn is_allowed(&self, origin: &Origin) -> bool {
+        if origin.is_null() {
+            return self.null;
+        }
+
+        self.any
+            || self.exact.contains(origin)
+            || self.patterns.iter().any(|pattern| pattern.matches(origin))
+    }
+}
+
+impl OriginPattern {
+    /// Check if an `Origin` matches this pattern.
+    pub fn matches(&self, origin: &Origin) -> bool {
+        if origin.is_null()
+            || !origin.scheme().eq_ignore_ascii_case(self.base.scheme())
+            || origin.port() != self.base.port()
+        {
+            return false;
+        }
+
+        let host = origin.hostname().as_bytes();
+        let suffix = self.base.hostname().as_bytes();
+        // At least one label, and its dot, must come before the suffix.
+        host.len() > suffix.len() + 1
+            && host[host.len() - suffix.len() - 1] == b'.'
+            && host[host.len() - suffix.len()..].eq_ignore_ascii_case(suffix)
+    }
+}
+
+impl FromStr for OriginPattern {
+    type Err = InvalidOriginPattern;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.splitn(2, "://");
+        let scheme = parts.next().ok_or(InvalidOriginPattern { _inner: () })?;
+        let rest = parts.next().ok_or(InvalidOriginPattern { _inner: () })?;
+        if !rest.starts_with("*.") {
+            return Err(InvalidOriginPattern { _inner: () });
+        }
+
+        let (host, port) = match rest[2..].rfind(':') {
+            Some(idx) => {
+                let port = rest[2 + idx + 1..]
+                    .parse::<u16>()
+                    .map_err(|_| InvalidOriginPattern { _inner: () })?;
+                (&rest[2..2 + idx], Some(port))
+            }
+            None => (&rest[2..], None),
+        };
+        if host.is_empty() || host.contains('*') {
+            return Err(InvalidOriginPattern { _inner: () });
+        }
+
+        Origin::try_from_parts(scheme, host, port)
+            .map(|base| OriginPattern { base })
+            .map_err(|_| InvalidOriginPattern { _inner: () })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn origin(s: &str) -> Origin {
+        let mut parts = s.splitn(2, "://");
+        let scheme = parts.next().unwrap();
+        let auth = parts
+            .next()
+            .unwrap()
+            .parse::<::http::uri::Authority>()
+            .unwrap();
+        Origin::try_from_parts(scheme, auth.host(), auth.port_u16()).unwrap()
+    }
+
+    #[test]
+    fn exact() {
+        let allowed = OriginAllowList::new().with_origin(origin("https://example.com"));
+        assert!(allowed.is_allowed(&origin("https://example.com")));
+        assert!(allowed.is_allowed(&origin("https://EXAMPLE.com")));
+        assert!(!allowed.is_allowed(&origin("http://example.com")));
+        assert!(!allowed.is_allowed(&origin("https://api.example.com")));
+    }
+
+    #[test]
+    fn subdomain_wildcard() {
+        let allowed = OriginAllowList::new().with_pattern("https://*.example.com".parse().unwrap());
+        assert!(allowed.is_allowed(&origin("https://api.example.com")));
+        assert!(allowed.is_allowed(&origin("https://a.b.Example.com")));
+        assert!(!allowed.is_allowed(&origin("https://example.com")));
+        assert!(!allowed.is_allowed(&origin("https://badexample.com")));
+        assert!(!allowed.is_allowed(&origin("http://api.example.com")));
+    }
+
+    #[test]
+    fn port_sensitive() {
+        let allowed = OriginAllowList::new()
+            .with_origin(origin("http://localhost:3000"))
+            .with_pattern("https://*.example.com:8443".parse().unwrap());
+        assert!(allowed.is_allowed(&origin("http://localhost:3000")));
+        assert!(!allowed.is_allowed(&origin("http://localhost")));
+        assert!(!allowed.is_allowed(&origin("http://localhost:3001")));
+        assert!(allowed.is_allowed(&origin("https://api.example.com:8443")));
+        assert!(!allowed.is_allowed(&origin("https://api.example.com")));
+    }
+
+    #[test]
+    fn null_and_any() {
+        let allowed = OriginAllowList::new();
+        assert!(!allowed.is_allowed(&Origin::NULL));
+        assert!(!allowed.is_allowed(&origin("https://example.com")));
+
+        let allowed = OriginAllowList::new().with_any();
+        assert!(allowed.is_allowed(&origin("https://example.com")));
+        assert!(!allowed.is_allowed(&Origin::NULL));
+
+        let allowed = OriginAllowList::new().with_null();
+        assert!(allowed.is_allowed(&Origin::NULL));
+        assert!(!allowed.is_allowed(&origin("https://example.com")));
+    }
+
+    #[test]
+    fn invalid_patterns() {
+        assert!("https://example.com".parse::<OriginPattern>().is_err());
+        assert!("*.example.com".parse::<OriginPattern>().is_err());
+        assert!("https://*.".parse::<OriginPattern>().is_err());
+        assert!("https://*.*.example.com".parse::<OriginPattern>().is_err());
+        assert!("https://*.example.com:port"
+            .parse::<OriginPattern>()
+            .is_err());
+    }
+}
@@ -0,0 +1,189 @@
+//! Message framing checks.
+//!
+//! The length of an HTTP/1.1 message body is determined by the
+//! `Transfer-Encoding` and `Content-Length` headers. When those headers
+//! disagree, different implementations can frame the same message
+//! differently, which is the root of request smuggling attacks. Gateways
+//! should reject such messages before reading any body.
+
+use std::error;
+use std::fmt;
+
+use http::HeaderMap;
+
+use {ContentLength, HeaderMapExt, TransferEncoding};
+
+/// A problem with how a message's body length is framed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FramingError {
+    /// Both `Transfer-Encoding` and `Content-Length` were sent.
+    ///
+    /// [RFC7230](https://tools.ietf.org/html/rfc7230#section-3.3.3) says
+    /// `Transfer-Encoding` overrides `Content-Length`, but such a message
+    /// "ought to be handled as an error".
+    TransferEncodingWithContentLength,
+    /// The `Transfer-Encoding` applied another coding after `chunked`, or
+    /// didn't decode.
+    ConflictingTransferEncoding,
+    /// The `Content-Length` was invalid, or multiple differing values were
+    /// sent.
+    InvalidContentLength,
+}
+
+/// Check the framing headers of a message for dangerous combinations.
+///
+/// Returns `None` if the message can be safely framed.
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// # extern crate http;
+/// use headers::framing::{detect_conflict, FramingError};
+/// use headers::{ContentLength, HeaderMapExt, TransferEncoding};
+///
+/// let mut headers = http::HeaderMap::new();
+/// headers.typed_insert(TransferEncoding::chunked());
+/// assert_eq!(detect_conflict(&headers), None);
+///
+/// headers.typed_insert(ContentLength(5));
+/// assert_eq!(
+///     detect_conflict(&headers),
+///     Some(FramingError::TransferEncodingWithContentLength),
+/// );
+/// ```
+pub fn detect_conflict(headers: &HeaderMap) -> Option<FramingError> {
+    let content_length = match headers.typed_try_get::<ContentLength>() {
+        Ok(len) => len,
+        Err(_) => return Some(FramingError::InvalidContentLength),
+    };
+
+    let transfer_encoding = match headers.typed_try_get::<TransferEncoding>() {
+        Ok(te) => te,
+        Err(_) => return Some(FramingError::ConflictingTransferEncoding),
+    };
+    if let Some(ref te) = transfer_encoding {
+        // Every line adds to the list of codings, so `gzip` and `chunked`
+        // on separate lines are the same as `gzip, chunked`. Only a coding
+        // applied after `chunked` makes the body length unclear.
+        let mut codings = te
+            .codings()
+            .skip_while(|c| !c.eq_ignore_ascii_case("chunked"));
+        if codings.any(|c| !c.eq_ignore_ascii_case("chunked")) {
+            return Some(FramingError::ConflictingTransferEncoding);
+        }
+    }
+
+    if transfer_encoding.is_some() && content_length.is_some() {
+        return Some(FramingError::TransferEncodingWithContentLength);
+    }
+
+    None
+}
+
+impl fmt::Display for FramingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            FramingError::TransferEncodingWithContentLength => {
+                "both Transfer-Encoding and Content-Length are present"
+            }
+            FramingError::ConflictingTransferEncoding => "conflicting Transfer-Encoding values",
+            FramingError::InvalidContentLength => "invalid Content-Length",
+        })
+    }
+}
+
+impl error::Error for FramingError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use HeaderValue;
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for &(name, value) in pairs {
+            map.append(name, HeaderValue::from_static(value));
+        }
+        map
+    }
+
+    #[test]
+    fn no_conflict() {
+        assert_eq!(detect_conflict(&headers(&[])), None);
+        assert_eq!(detect_conflict(&headers(&[("content-length", "5")])), None);
+        assert_eq!(
+            detect_conflict(&headers(&[("transfer-encoding", "gzip, chunked")])),
+            None
+        );
+        assert_eq!(
+            detect_conflict(&headers(&[
+                ("transfer-encoding", "chunked"),
+                ("transfer-encoding", "chunked"),
+            ])),
+            None
+        );
+    }
+
+    #[test]
+    fn chunked_with_content_length() {
+        let map = headers(&[("transfer-encoding", "chunked"), ("content-length", "5")]);
+        assert_eq!(
+            detect_conflict(&map),
+            Some(FramingError::TransferEncodingWithContentLength)
+        );
+    }
+
+    #[test]
+    fn conflicting_transfer_encoding() {
+        let map = headers(&[
+            ("transfer-encoding", "chunked"),
+            ("transfer-encoding", "identity"),
+        ]);
+        assert_eq!(
+            detect_conflict(&map),
+            Some(FramingError::ConflictingTransferEncoding)
+        );
+        assert_eq!(
+            detect_conflict(&headers(&[("transfer-encoding", "chunked, gzip")])),
+            Some(FramingError::ConflictingTransferEncoding)
+        );
+    }
+
+    #[test]
+    fn transfer_encoding_split_across_lines() {
+        let map = headers(&[
+            ("transfer-encoding", "gzip"),
+            ("transfer-encoding", "chunked"),
+        ]);
+        assert_eq!(detect_conflict(&map), None);
+    }
+
+    #[test]
+    fn transfer_encoding_mixed_case() {
+        let map = headers(&[
+            ("transfer-encoding", "chunked"),
+            ("transfer-encoding", "Chunked"),
+        ]);
+        assert_eq!(detect_conflict(&map), None);
+        assert_eq!(
+            detect_conflict(&headers(&[("transfer-encoding", "GZIP, CHUNKED")])),
+            None
+        );
+    }
+
+    #[test]
+    fn invalid_content_length() {
+        let map = headers(&[("content-length", "5"), ("content-length", "6")]);
+        assert_eq!(
+            detect_conflict(&map),
+            Some(FramingError::InvalidContentLength)
+        );
+
+        let map = headers(&[("content-length", "+5")]);
+        assert_eq!(
+            detect_conflict(&map),
+            Some(FramingError::InvalidContentLength)
+        );
+    }
+}
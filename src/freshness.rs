@@ -0,0 +1,113 @@
+//! How long a cached response stays fresh.
+
+use std::time::{Duration, SystemTime};
+
+use {CacheControl, Date, Expires};
+
+/// Compute how long a response stays fresh after it was generated, per
+/// [RFC7234](https://tools.ietf.org/html/rfc7234#section-4.2.1).
+///
+/// A `max-age` directive wins over `Expires`. Otherwise the lifetime is
+/// the time from the `Date` to the `Expires`, or from `now` if there is no
+/// `Date`, and an `Expires` before then gives a lifetime of zero. Returns
+/// `None` if there is neither, leaving it to the cache to pick a heuristic
+/// lifetime, if any.
+///
+/// This is the lifetime for a private cache. A shared cache should check
+/// `CacheControl::s_max_age` first.
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// use std::time::{Duration, SystemTime};
+///
+/// use headers::freshness::freshness_lifetime;
+/// use headers::{CacheControl, Date, Expires};
+///
+/// let now = SystemTime::now();
+/// let date = Date::from(now);
+/// let expires = Expires::from(now + Duration::from_secs(600));
+/// assert_eq!(
+///     freshness_lifetime(None, Some(&expires), Some(&date), now),
+///     Some(Duration::from_secs(600)),
+/// );
+///
+/// let cc = CacheControl::new().with_max_age(Duration::from_secs(60));
+/// assert_eq!(
+///     freshness_lifetime(Some(&cc), Some(&expires), Some(&date), now),
+///     Some(Duration::from_secs(60)),
+/// );
+/// ```
+pub fn freshness_lifetime(
+    cache_control: Option<&CacheControl>,
+    expires: Option<&Expires>,
+    date: Option<&Date>,
+    now: SystemTime,
+) -> Option<Duration> {
+    if let Some(max_age) = cache_control.and_then(CacheControl::max_age) {
+        return Some(max_age);
+    }
+
+    let expires = SystemTime::from(*expires?);
+    let date = date.map(|&date| SystemTime::from(date)).unwrap_or(now);
+    Some(expires.duration_since(date).unwrap_or(Duration::ZERO))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(secs: u64) -> SystemTime {
+        SystemTime::UNIX_EPOCH + Duration::from_secs(secs)
+    }
+
+    #[test]
+    fn freshness_max_age() {
+        let cc = CacheControl::new().with_max_age(Duration::from_secs(60));
+        let expires = Expires::from(at(1_000_600));
+        let date = Date::from(at(1_000_000));
+        assert_eq!(
+            freshness_lifetime(Some(&cc), Some(&expires), Some(&date), at(1_000_000)),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(
+            freshness_lifetime(Some(&cc), None, None, at(0)),
+            Some(Duration::from_secs(60))
+        );
+    }
+
+    #[test]
+    fn freshness_expires() {
+        let expires = Expires::from(at(1_000_600));
+        let date = Date::from(at(1_000_000));
+        let no_max_age = CacheControl::new().with_no_cache();
+        assert_eq!(
+            freshness_lifetime(Some(&no_max_age), Some(&expires), Some(&date), at(0)),
+            Some(Duration::from_secs(600))
+        );
+
+        // Without a `Date`, the lifetime starts now.
+        assert_eq!(
+            freshness_lifetime(None, Some(&expires), None, at(1_000_500)),
+            Some(Duration::from_secs(100))
+        );
+
+        // Already expired.
+        let date = Date::from(at(2_000_000));
+        assert_eq!(
+            freshness_lifetime(None, Some(&expires), Some(&date), at(0)),
+            Some(Duration::ZERO)
+        );
+    }
+
+    #[test]
+    fn freshness_none() {
+        let date = Date::from(at(1_000_000));
+        assert_eq!(freshness_lifetime(None, None, Some(&date), at(0)), None);
+        assert_eq!(
+            freshness_lifetime(Some(&CacheControl::new()), None, None, at(0)),
+            None
+        );
+    }
+}
@@ -71,19 +71,46 @@
 //!     }
 //! }
 //! ```
+//!
+//! # `no_std`
+//!
+//! This crate needs `std`, because every header is decoded from and encoded
+//! to `http::HeaderValue`s. The parsing of the simplest headers is in
+//! [`headers_core::parse`] instead, which builds without `std`: turn off the
+//! default features of `headers-core`, and turn on `alloc` if there is a heap.
+//!
+//! Without `std`, it has the parsing of:
+//!
+//! - `Content-Length`, which [`ContentLength`](crate::ContentLength)
+//!   decodes with.
+//! - The media type of `Content-Type`, its type and subtype.
+//! - Lists of tokens, such as `Connection` and `Transfer-Encoding`.
+//! - Quoted-strings, with `alloc`.
+//!
+//! Date headers, and the rest, stay behind `std`.
+//!
 
 extern crate base64;
 extern crate bytes;
+#[cfg(feature = "chrono")]
+extern crate chrono;
+#[cfg(any(feature = "jitter", feature = "multipart"))]
+extern crate fastrand;
 extern crate headers_core;
 extern crate http;
+#[cfg(test)]
 extern crate httpdate;
 extern crate itertools;
 extern crate mime;
 extern crate sha1;
+#[cfg(feature = "sha2")]
+extern crate sha2;
 #[cfg(all(test, feature = "nightly"))]
 extern crate test;
+#[cfg(feature = "time")]
+extern crate time;
 
-pub use headers_core::{Error, Header};
+pub use headers_core::{DecodeOptions, Error, Header, NamedHeader};
 
 #[doc(hidden)]
 pub use http::HeaderMap;
@@ -93,8 +120,21 @@ pub use http::header::{HeaderName, HeaderValue};
 
 #[macro_use]
 mod util;
+pub mod auth;
+pub mod backoff;
+pub mod body;
+pub mod cache;
 mod common;
+pub mod conditional;
+pub mod cors;
+pub mod framing;
+pub mod freshness;
 mod map_ext;
+pub mod origin_ref;
+mod single_value;
 
 pub use self::common::*;
-pub use self::map_ext::HeaderMapExt;
+pub use self::map_ext::{
+    HeaderMapExt, KnownHeaders, TypedHeaderBuilder, TypedHeaders, ValueTooLong,
+};
+pub use self::single_value::SingleValueHeader;
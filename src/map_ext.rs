@@ -1,6 +1,12 @@
-use super::{Error, Header, HeaderValue};
+use std::error;
+use std::fmt;
+
+use super::{DecodeOptions, Error, Header, HeaderName, HeaderValue};
 use http;
 
+use common::authorization::Credentials;
+use common::*;
+
 /// An extension trait adding "typed" methods to `http::HeaderMap`.
 pub trait HeaderMapExt: self::sealed::Sealed {
     /// Inserts the typed `Header` into this `HeaderMap`.
@@ -8,15 +14,86 @@ pub trait HeaderMapExt: self::sealed::Sealed {
     where
         H: Header;
 
+    /// Inserts the typed `Header`, if every encoded value fits in `max_len`
+    /// bytes.
+    ///
+    /// `HeaderValue` itself has no length limit, but servers and proxies
+    /// commonly reject large headers, such as with a `431 Request Header
+    /// Fields Too Large`. If any value is too long, the map is left
+    /// unchanged.
+    fn typed_try_insert<H>(&mut self, header: H, max_len: usize) -> Result<(), ValueTooLong>
+    where
+        H: Header;
+
+    /// Appends the typed `Header` to this `HeaderMap`.
+    ///
+    /// Unlike `typed_insert`, any values already in the map for this header
+    /// are kept. The encoded values are written straight into the map, so
+    /// no intermediate `Vec` is allocated.
+    fn typed_append<H>(&mut self, header: H)
+    where
+        H: Header;
+
     /// Tries to find the header by name, and then decode it into `H`.
+    ///
+    /// This returns `None` both when the header is missing and when it
+    /// fails to decode. Use `typed_try_get` to tell the two apart.
     fn typed_get<H>(&self) -> Option<H>
     where
         H: Header;
 
     /// Tries to find the header by name, and then decode it into `H`.
+    ///
+    /// Returns `Ok(None)` if the header is missing, and `Err` if it is
+    /// present but invalid, such as for a server to respond with a
+    /// `400 Bad Request`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate headers;
+    /// # extern crate http;
+    /// use headers::{ContentLength, HeaderMapExt};
+    ///
+    /// let mut map = http::HeaderMap::new();
+    /// assert!(map.typed_try_get::<ContentLength>().unwrap().is_none());
+    ///
+    /// map.insert("content-length", "nope".parse().unwrap());
+    /// assert!(map.typed_try_get::<ContentLength>().is_err());
+    /// ```
     fn typed_try_get<H>(&self) -> Result<Option<H>, Error>
     where
         H: Header;
+
+    /// Like `typed_try_get`, but decodes with `options`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate headers;
+    /// # extern crate http;
+    /// use headers::{DecodeOptions, Date, HeaderMapExt};
+    ///
+    /// let mut map = http::HeaderMap::new();
+    /// map.insert("date", "Sunday, 06-Nov-94 08:49:37 GMT".parse().unwrap());
+    /// assert!(map.typed_try_get::<Date>().unwrap().is_some());
+    ///
+    /// let strict = DecodeOptions::strict();
+    /// assert!(map.typed_try_get_with::<Date>(&strict).is_err());
+    /// ```
+    fn typed_try_get_with<H>(&self, options: &DecodeOptions) -> Result<Option<H>, Error>
+    where
+        H: Header;
+
+    /// Get a `TypedHeaders` view over this `HeaderMap`.
+    fn typed(&self) -> TypedHeaders<'_>;
+
+    /// Decode all the common headers in one pass over this `HeaderMap`.
+    ///
+    /// This only looks up the headers that are present, where calling
+    /// `typed_get` for each header looks up every one. See `KnownHeaders`
+    /// for which headers are decoded.
+    fn decode_known(&self) -> KnownHeaders;
 }
 
 impl HeaderMapExt for http::HeaderMap {
@@ -31,6 +108,33 @@ impl HeaderMapExt for http::HeaderMap {
         header.encode(&mut values);
     }
 
+    fn typed_try_insert<H>(&mut self, header: H, max_len: usize) -> Result<(), ValueTooLong>
+    where
+        H: Header,
+    {
+        let mut encoded = Vec::new();
+        header.encode(&mut encoded);
+        check_len(&encoded, max_len)?;
+
+        let mut values = ToValues {
+            state: State::First(self.entry(H::name())),
+        };
+        values.extend(encoded);
+        Ok(())
+    }
+
+    fn typed_append<H>(&mut self, header: H)
+    where
+        H: Header,
+    {
+        let state = match self.entry(H::name()) {
+            http::header::Entry::Occupied(e) => State::Latter(e),
+            vacant => State::First(vacant),
+        };
+        let mut values = ToValues { state };
+        header.encode(&mut values);
+    }
+
     fn typed_get<H>(&self) -> Option<H>
     where
         H: Header,
@@ -49,8 +153,390 @@ impl HeaderMapExt for http::HeaderMap {
             H::decode(&mut values).map(Some)
         }
     }
+
+    fn typed_try_get_with<H>(&self, options: &DecodeOptions) -> Result<Option<H>, Error>
+    where
+        H: Header,
+    {
+        let mut values = self.get_all(H::name()).iter();
+        if values.size_hint() == (0, Some(0)) {
+            Ok(None)
+        } else {
+            H::decode_with(&mut values, options).map(Some)
+        }
+    }
+
+    fn typed(&self) -> TypedHeaders<'_> {
+        TypedHeaders(self)
+    }
+
+    fn decode_known(&self) -> KnownHeaders {
+        KnownHeaders::decode(self)
+    }
 }
 
+/// A typed view over a borrowed `HeaderMap`.
+///
+/// Each method decodes its header on every call, the same as
+/// `HeaderMapExt::typed_get`, returning `None` if the header is missing or
+/// invalid. Nothing is cached. Headers without a method here can be read
+/// with `TypedHeaders::get`.
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// # extern crate http;
+/// use headers::{ContentLength, HeaderMapExt};
+///
+/// let mut map = http::HeaderMap::new();
+/// map.typed_insert(ContentLength(1024));
+///
+/// assert_eq!(map.typed().content_length(), Some(ContentLength(1024)));
+/// assert_eq!(map.typed().content_type(), None);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct TypedHeaders<'a>(&'a http::HeaderMap);
+
+macro_rules! typed_getters {
+    ($($(#[$attr:meta])* $method:ident: $ty:ident,)+) => {
+        $(
+            $(#[$attr])*
+            pub fn $method(&self) -> Option<$ty> {
+                self.get()
+            }
+        )+
+    };
+}
+
+impl<'a> TypedHeaders<'a> {
+    /// Decode any header `H` from the underlying map.
+    pub fn get<H: Header>(&self) -> Option<H> {
+        self.0.typed_get()
+    }
+
+    /// Decode the `Authorization` header with credentials `C`.
+    pub fn authorization<C: Credentials>(&self) -> Option<Authorization<C>> {
+        self.get()
+    }
+
+    typed_getters! {
+        /// Decode the `Cache-Control` header.
+        cache_control: CacheControl,
+        /// Decode the `Connection` header.
+        connection: Connection,
+        /// Decode the `Content-Length` header.
+        content_length: ContentLength,
+        /// Decode the `Content-Type` header.
+        content_type: ContentType,
+        /// Decode the `Cookie` header.
+        cookie: Cookie,
+        /// Decode the `Date` header.
+        date: Date,
+        /// Decode the `ETag` header.
+        etag: ETag,
+        /// Decode the `Host` header.
+        host: Host,
+        /// Decode the `If-Modified-Since` header.
+        if_modified_since: IfModifiedSince,
+        /// Decode the `If-None-Match` header.
+        if_none_match: IfNoneMatch,
+        /// Decode the `Last-Modified` header.
+        last_modified: LastModified,
+        /// Decode the `Location` header.
+        location: Location,
+        /// Decode the `Origin` header.
+        origin: Origin,
+        /// Decode the `Referer` header.
+        referer: Referer,
+        /// Decode the `Transfer-Encoding` header.
+        transfer_encoding: TransferEncoding,
+        /// Decode the `User-Agent` header.
+        user_agent: UserAgent,
+    }
+}
+
+macro_rules! known_headers {
+    ($($(#[$attr:meta])* $field:ident: $ty:ident = $name:literal,)+) => {
+        /// The common headers of a `HeaderMap`, decoded at once.
+        ///
+        /// Returned by `HeaderMapExt::decode_known`. A field is `None` if the
+        /// header is missing or failed to decode, and each header that failed
+        /// is also listed in `warnings`. Headers not listed here are ignored,
+        /// and more may be added, so this can't be built directly.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// # extern crate headers;
+        /// # extern crate http;
+        /// use headers::{ContentLength, HeaderMapExt};
+        ///
+        /// let mut map = http::HeaderMap::new();
+        /// map.typed_insert(ContentLength(1024));
+        /// map.insert("host", "not a host".parse().unwrap());
+        ///
+        /// let known = map.decode_known();
+        /// assert_eq!(known.content_length, Some(ContentLength(1024)));
+        /// assert_eq!(known.host, None);
+        /// assert_eq!(known.warnings[0].0, "host");
+        /// ```
+        #[derive(Debug, Default)]
+        #[non_exhaustive]
+        pub struct KnownHeaders {
+            $(
+                $(#[$attr])*
+                pub $field: Option<$ty>,
+            )+
+            /// The headers that were present, but failed to decode.
+            pub warnings: Vec<(HeaderName, Error)>,
+        }
+
+        impl KnownHeaders {
+            fn decode(map: &http::HeaderMap) -> KnownHeaders {
+                let mut known = KnownHeaders::default();
+                for name in map.keys() {
+                    match name.as_str() {
+                        $(
+                            $name => decode_into(map, name, &mut known.$field, &mut known.warnings),
+                        )+
+                        _ => {}
+                    }
+                }
+                known
+            }
+        }
+
+        #[cfg(test)]
+        #[test]
+        fn known_header_names() {
+            $(
+                assert_eq!(<$ty as ::NamedHeader>::NAME, $name);
+            )+
+        }
+    };
+}
+
+known_headers! {
+    /// The `Cache-Control` header.
+    cache_control: CacheControl = "cache-control",
+    /// The `Connection` header.
+    connection: Connection = "connection",
+    /// The `Content-Length` header.
+    content_length: ContentLength = "content-length",
+    /// The `Content-Type` header.
+    content_type: ContentType = "content-type",
+    /// The `Cookie` header.
+    cookie: Cookie = "cookie",
+    /// The `Date` header.
+    date: Date = "date",
+    /// The `ETag` header.
+    etag: ETag = "etag",
+    /// The `Host` header.
+    host: Host = "host",
+    /// The `If-Modified-Since` header.
+    if_modified_since: IfModifiedSince = "if-modified-since",
+    /// The `If-None-Match` header.
+    if_none_match: IfNoneMatch = "if-none-match",
+    /// The `Last-Modified` header.
+    last_modified: LastModified = "last-modified",
+    /// The `Location` header.
+    location: Location = "location",
+    /// The `Origin` header.
+    origin: Origin = "origin",
+    /// The `Referer` header.
+    referer: Referer = "referer",
+    /// The `Transfer-Encoding` header.
+    transfer_encoding: TransferEncoding = "transfer-encoding",
+    /// The `User-Agent` header.
+    user_agent: UserAgent = "user-agent",
+}
+
+fn decode_into<H: Header>(
+    map: &http::HeaderMap,
+    name: &HeaderName,
+    field: &mut Option<H>,
+    warnings: &mut Vec<(HeaderName, Error)>,
+) {
+    match H::decode(&mut map.get_all(name).iter()) {
+        Ok(header) => *field = Some(header),
+        Err(err) => warnings.push((name.clone(), err)),
+    }
+}
+
+/// Build a `HeaderMap` from typed headers.
+///
+/// Each header replaces any earlier one of the same name, except through
+/// `append` and `set_cookie`, which add to it, as a response can have many
+/// `Set-Cookie` fields.
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// # extern crate http;
+/// use headers::{CacheControl, ContentLength, ContentType, SetCookie, TypedHeaderBuilder};
+///
+/// let map = TypedHeaderBuilder::new()
+///     .content_type(ContentType::json())
+///     .content_length(ContentLength(2))
+///     .cache_control(CacheControl::new().with_no_store())
+///     .set_cookie(SetCookie::new("a", "1").unwrap())
+///     .set_cookie(SetCookie::new("b", "2").unwrap())
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(map["content-type"], "application/json");
+/// assert_eq!(map.get_all("set-cookie").iter().count(), 2);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct TypedHeaderBuilder {
+    map: http::HeaderMap,
+    max_len: Option<usize>,
+    error: Option<ValueTooLong>,
+}
+
+macro_rules! typed_setters {
+    ($($(#[$attr:meta])* $method:ident: $ty:ident,)+) => {
+        $(
+            $(#[$attr])*
+            pub fn $method(self, header: $ty) -> Self {
+                self.header(header)
+            }
+        )+
+    };
+}
+
+impl TypedHeaderBuilder {
+    /// Create a builder with no headers.
+    pub fn new() -> TypedHeaderBuilder {
+        TypedHeaderBuilder::default()
+    }
+
+    /// Fail the build if any value encodes to more than `max_len` bytes,
+    /// as `HeaderMapExt::typed_try_insert` does.
+    ///
+    /// This applies to headers added after it is set.
+    pub fn max_value_len(mut self, max_len: usize) -> Self {
+        self.max_len = Some(max_len);
+        self
+    }
+
+    /// Add any header `H`, replacing an earlier one of the same name.
+    pub fn header<H: Header>(self, header: H) -> Self {
+        self.add(header, false)
+    }
+
+    /// Add any header `H`, keeping an earlier one of the same name.
+    pub fn append<H: Header>(self, header: H) -> Self {
+        self.add(header, true)
+    }
+
+    /// Add a `Set-Cookie` header, keeping any earlier ones.
+    pub fn set_cookie(self, header: SetCookie) -> Self {
+        self.append(header)
+    }
+
+    typed_setters! {
+        /// Add the `Cache-Control` header.
+        cache_control: CacheControl,
+        /// Add the `Connection` header.
+        connection: Connection,
+        /// Add the `Content-Length` header.
+        content_length: ContentLength,
+        /// Add the `Content-Type` header.
+        content_type: ContentType,
+        /// Add the `Date` header.
+        date: Date,
+        /// Add the `ETag` header.
+        etag: ETag,
+        /// Add the `Last-Modified` header.
+        last_modified: LastModified,
+        /// Add the `Location` header.
+        location: Location,
+        /// Add the `Transfer-Encoding` header.
+        transfer_encoding: TransferEncoding,
+        /// Add the `Vary` header.
+        vary: Vary,
+    }
+
+    /// Get the `HeaderMap`.
+    ///
+    /// Fails with the first value that was longer than `max_value_len`.
+    pub fn build(self) -> Result<http::HeaderMap, ValueTooLong> {
+        match self.error {
+            Some(err) => Err(err),
+            None => Ok(self.map),
+        }
+    }
+
+    fn add<H: Header>(mut self, header: H, append: bool) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+
+        let mut encoded = Vec::new();
+        header.encode(&mut encoded);
+        if let Some(max_len) = self.max_len {
+            if let Err(err) = check_len(&encoded, max_len) {
+                self.error = Some(err);
+                return self;
+            }
+        }
+
+        let state = match self.map.entry(H::name()) {
+            http::header::Entry::Occupied(e) if append => State::Latter(e),
+            entry => State::First(entry),
+        };
+        ToValues { state }.extend(encoded);
+        self
+    }
+}
+
+fn check_len(encoded: &[HeaderValue], max_len: usize) -> Result<(), ValueTooLong> {
+    match encoded.iter().find(|value| value.len() > max_len) {
+        Some(value) => Err(ValueTooLong {
+            len: value.len(),
+            max_len,
+        }),
+        None => Ok(()),
+    }
+}
+
+/// An encoded header value was longer than allowed.
+///
+/// Returned by `HeaderMapExt::typed_try_insert` and
+/// `TypedHeaderBuilder::build`.
+#[derive(Clone, Debug)]
+pub struct ValueTooLong {
+    len: usize,
+    max_len: usize,
+}
+
+impl ValueTooLong {
+    /// The length of the offending value, in bytes.
+    pub fn value_len(&self) -> usize {
+        self.len
+    }
+
+    /// The limit that was exceeded.
+    pub fn max_len(&self) -> usize {
+        self.max_len
+    }
+}
+
+impl fmt::Display for ValueTooLong {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "header value of {} bytes exceeds the limit of {} bytes",
+            self.len, self.max_len
+        )
+    }
+}
+
+impl error::Error for ValueTooLong {}
+
 struct ToValues<'a> {
     state: State<'a>,
 }
@@ -65,6 +551,11 @@ enum State<'a> {
 impl<'a> Extend<HeaderValue> for ToValues<'a> {
     fn extend<T: IntoIterator<Item = HeaderValue>>(&mut self, iter: T) {
         for value in iter {
+            debug_assert!(
+                !value.as_bytes().iter().any(|&b| b == b'\r' || b == b'\n'),
+                "Header::encode produced a value with a CR or LF: {:?}",
+                value
+            );
             let entry = match ::std::mem::replace(&mut self.state, State::Tmp) {
                 State::First(http::header::Entry::Occupied(mut e)) => {
                     e.insert(value);
@@ -86,3 +577,215 @@ mod sealed {
     pub trait Sealed {}
     impl Sealed for ::http::HeaderMap {}
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{HeaderMapExt, TypedHeaderBuilder};
+    use http::HeaderMap;
+    use {CacheControl, ContentLength, ContentType, Cookie, DecodeOptions, ETag, SetCookie, Vary};
+
+    #[test]
+    fn builder() {
+        let map = TypedHeaderBuilder::new()
+            .content_type(ContentType::text())
+            .content_length(ContentLength(1))
+            .cache_control(CacheControl::new().with_no_cache())
+            .content_length(ContentLength(5))
+            .etag("\"xyzzy\"".parse::<ETag>().unwrap())
+            .vary(Vary::any())
+            .set_cookie(SetCookie::new("a", "1").unwrap())
+            .set_cookie(SetCookie::new("b", "2").unwrap())
+            .build()
+            .unwrap();
+
+        assert_eq!(map.len(), 7);
+        assert_eq!(map["content-type"], "text/plain");
+        assert_eq!(map["content-length"], "5");
+        assert_eq!(map["cache-control"], "no-cache");
+        assert_eq!(map["etag"], "\"xyzzy\"");
+        assert_eq!(map["vary"], "*");
+        let cookies = map.get_all("set-cookie").iter().collect::<Vec<_>>();
+        assert_eq!(cookies, ["a=1", "b=2"]);
+    }
+
+    #[test]
+    fn builder_limit() {
+        let err = TypedHeaderBuilder::new()
+            .content_length(ContentLength(5))
+            .max_value_len(4)
+            .etag("\"xyzzy\"".parse::<ETag>().unwrap())
+            .content_type(ContentType::text())
+            .build()
+            .unwrap_err();
+        assert_eq!(err.value_len(), 7);
+        assert_eq!(err.max_len(), 4);
+    }
+
+    #[test]
+    fn typed_append_keeps_existing() {
+        let mut map = HeaderMap::new();
+        map.insert("vary", "accept".parse().unwrap());
+        map.typed_append(Vary::any());
+
+        let values = map.get_all("vary").iter().collect::<Vec<_>>();
+        assert_eq!(values, ["accept", "*"]);
+    }
+
+    #[test]
+    fn typed_append_vacant() {
+        let mut map = HeaderMap::new();
+        map.typed_append(ContentLength(5));
+        assert_eq!(map["content-length"], "5");
+    }
+
+    #[test]
+    fn typed_try_get() {
+        let mut map = HeaderMap::new();
+        assert_eq!(map.typed_try_get::<ContentLength>().unwrap(), None);
+        assert_eq!(map.typed_get::<ContentLength>(), None);
+
+        map.insert("content-length", "5".parse().unwrap());
+        assert_eq!(
+            map.typed_try_get::<ContentLength>().unwrap(),
+            Some(ContentLength(5))
+        );
+        assert_eq!(map.typed_get::<ContentLength>(), Some(ContentLength(5)));
+
+        map.insert("content-length", "nope".parse().unwrap());
+        assert!(map.typed_try_get::<ContentLength>().is_err());
+        assert_eq!(map.typed_get::<ContentLength>(), None);
+    }
+
+    #[test]
+    fn typed_try_get_with() {
+        let strict = DecodeOptions::strict();
+        let mut map = HeaderMap::new();
+        assert_eq!(
+            map.typed_try_get_with::<ContentLength>(&strict).unwrap(),
+            None
+        );
+
+        map.append("content-length", "5".parse().unwrap());
+        map.append("content-length", "5".parse().unwrap());
+        assert_eq!(
+            map.typed_try_get_with::<ContentLength>(&DecodeOptions::default())
+                .unwrap(),
+            Some(ContentLength(5))
+        );
+        assert!(map.typed_try_get_with::<ContentLength>(&strict).is_err());
+    }
+
+    #[test]
+    fn typed_try_insert_limit() {
+        let pairs = (0..100)
+            .map(|i| format!("key{}=value{}", i, i))
+            .collect::<Vec<_>>()
+            .join("; ");
+        let mut map = HeaderMap::new();
+        map.insert("cookie", pairs.parse().unwrap());
+        let cookie = map.typed_get::<Cookie>().unwrap();
+        map.insert("cookie", "old=1".parse().unwrap());
+
+        let err = map
+            .typed_try_insert(cookie.clone(), pairs.len() - 1)
+            .unwrap_err();
+        assert_eq!(err.value_len(), pairs.len());
+        assert_eq!(err.max_len(), pairs.len() - 1);
+        assert_eq!(map["cookie"], "old=1");
+
+        map.typed_try_insert(cookie, pairs.len()).unwrap();
+        assert_eq!(map["cookie"], &pairs[..]);
+    }
+
+    #[test]
+    fn typed_view() {
+        let mut map = HeaderMap::new();
+        map.insert("host", "example.com".parse().unwrap());
+        map.insert("content-length", "nope".parse().unwrap());
+
+        let typed = map.typed();
+        assert_eq!(typed.host().unwrap().hostname(), "example.com");
+        assert_eq!(typed.content_length(), None);
+        assert_eq!(typed.get::<Vary>(), None);
+    }
+
+    #[test]
+    fn decode_known() {
+        let mut map = HeaderMap::new();
+        map.insert("content-length", "5".parse().unwrap());
+        map.insert("host", "example.com".parse().unwrap());
+        map.insert("date", "yesterday".parse().unwrap());
+        map.insert("vary", "accept".parse().unwrap());
+        map.append("user-agent", "a".parse().unwrap());
+        map.append("user-agent", "b".parse().unwrap());
+
+        let known = map.decode_known();
+        assert_eq!(known.content_length, Some(ContentLength(5)));
+        assert_eq!(known.host.unwrap().hostname(), "example.com");
+        assert_eq!(known.date, None);
+        assert_eq!(known.user_agent, None);
+        assert_eq!(known.content_type, None);
+
+        let mut warned = known
+            .warnings
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>();
+        warned.sort_unstable();
+        assert_eq!(warned, ["date", "user-agent"]);
+    }
+
+    #[cfg(feature = "nightly")]
+    mod bench {
+        use http::HeaderMap;
+        use {
+            CacheControl, ContentLength, ContentType, Date, ETag, Header, HeaderMapExt, HeaderValue,
+        };
+
+        fn headers() -> (ContentType, ContentLength, CacheControl, ETag, Date) {
+            (
+                ContentType::json(),
+                ContentLength(1024),
+                CacheControl::new().with_no_cache(),
+                "\"xyzzy\"".parse().unwrap(),
+                Date::from(::std::time::UNIX_EPOCH),
+            )
+        }
+
+        fn append_via_vec<H: Header>(map: &mut HeaderMap, header: H) {
+            let mut values = Vec::<HeaderValue>::new();
+            header.encode(&mut values);
+            for value in values {
+                map.append(H::name(), value);
+            }
+        }
+
+        #[bench]
+        fn bench_five_headers_via_vec(b: &mut ::test::Bencher) {
+            let (ct, cl, cc, etag, date) = headers();
+            let mut map = HeaderMap::with_capacity(8);
+            b.iter(|| {
+                append_via_vec(&mut map, ct.clone());
+                append_via_vec(&mut map, cl);
+                append_via_vec(&mut map, cc.clone());
+                append_via_vec(&mut map, etag.clone());
+                append_via_vec(&mut map, date);
+                map.clear();
+            });
+        }
+
+        #[bench]
+        fn bench_five_headers_typed_append(b: &mut ::test::Bencher) {
+            let (ct, cl, cc, etag, date) = headers();
+            let mut map = HeaderMap::with_capacity(8);
+            b.iter(|| {
+                map.typed_append(ct.clone());
+                map.typed_append(cl);
+                map.typed_append(cc.clone());
+                map.typed_append(etag.clone());
+                map.typed_append(date);
+                map.clear();
+            });
+        }
+    }
+}
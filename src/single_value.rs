@@ -0,0 +1,183 @@
+use super::{Header, HeaderValue};
+
+use common::authorization::Credentials;
+use common::*;
+
+/// A `Header` that always encodes to exactly one `HeaderValue`.
+///
+/// Most headers are a single field line. The few that can't be combined
+/// into one line, such as `Set-Cookie`, don't implement this.
+///
+/// # Example
+///
+/// ```
+/// # extern crate headers;
+/// use headers::{ContentLength, SingleValueHeader};
+///
+/// let value = ContentLength(1024).to_header_value();
+/// assert_eq!(value, "1024");
+/// ```
+pub trait SingleValueHeader: Header {
+    /// Encode this header into its one `HeaderValue`.
+    ///
+    /// This is exactly the value that `Header::encode` produces, for use
+    /// without a `HeaderMap`, such as when building some other container
+    /// of headers.
+    fn to_header_value(&self) -> HeaderValue {
+        let mut one = One(None);
+        self.encode(&mut one);
+        one.0.expect("SingleValueHeader encodes one value")
+    }
+}
+
+struct One(Option<HeaderValue>);
+
+impl Extend<HeaderValue> for One {
+    fn extend<T: IntoIterator<Item = HeaderValue>>(&mut self, iter: T) {
+        for value in iter {
+            debug_assert!(self.0.is_none(), "SingleValueHeader encoded many values");
+            debug_assert!(
+                !value.as_bytes().iter().any(|&b| b == b'\r' || b == b'\n'),
+                "Header::encode produced a value with a CR or LF: {:?}",
+                value
+            );
+            self.0 = Some(value);
+        }
+    }
+}
+
+impl<C: Credentials> SingleValueHeader for Authorization<C> {}
+impl<C: Credentials> SingleValueHeader for ProxyAuthorization<C> {}
+#[allow(deprecated)]
+impl SingleValueHeader for PublicKeyPins {}
+
+macro_rules! single_value {
+    (impl above: [$($other:ty,)+] $($ty:ident,)+) => {
+        $(
+            impl SingleValueHeader for $ty {}
+        )+
+
+        /// The names of the headers above, including those with their own
+        /// impl, so the round-trip tests can check that each of them is
+        /// covered.
+        #[cfg(test)]
+        #[allow(deprecated)]
+        pub(crate) const NAMES: &[fn() -> &'static ::HeaderName] = &[
+            $(<$other as Header>::name,)+
+            $(<$ty as Header>::name,)+
+        ];
+    };
+}
+
+single_value! {
+    impl above: [
+        Authorization<::common::authorization::Basic>,
+        ProxyAuthorization<::common::authorization::Basic>,
+        PublicKeyPins,
+    ]
+    Accept,
+    AcceptCH,
+    AcceptEncoding,
+    AcceptPost,
+    AcceptRanges,
+    AccessControlAllowCredentials,
+    AccessControlAllowHeaders,
+    AccessControlAllowMethods,
+    AccessControlAllowOrigin,
+    AccessControlExposeHeaders,
+    AccessControlMaxAge,
+    AccessControlRequestHeaders,
+    AccessControlRequestMethod,
+    Age,
+    Allow,
+    AltUsed,
+    CacheControl,
+    CacheStatus,
+    Connection,
+    ContentDigest,
+    ContentDisposition,
+    ContentEncoding,
+    ContentLength,
+    ContentLocation,
+    ContentRange,
+    ContentSecurityPolicy,
+    ContentSecurityPolicyReportOnly,
+    ContentType,
+    Cookie,
+    Date,
+    DeviceMemory,
+    Dnt,
+    ETag,
+    Expect,
+    ExpectCT,
+    Expires,
+    FeaturePolicy,
+    Forwarded,
+    Host,
+    IdempotencyKey,
+    IfMatch,
+    IfModifiedSince,
+    IfNoneMatch,
+    IfRange,
+    IfUnmodifiedSince,
+    LastModified,
+    Link,
+    Location,
+    Nel,
+    Origin,
+    Pragma,
+    Priority,
+    ProxyConnection,
+    Range,
+    Referer,
+    ReferrerPolicy,
+    Refresh,
+    RetryAfter,
+    SecChUa,
+    SecPurpose,
+    SecWebsocketAccept,
+    SecWebsocketKey,
+    SecWebsocketVersion,
+    Server,
+    Signature,
+    SignatureInput,
+    SourceMap,
+    StrictTransportSecurity,
+    Te,
+    Tk,
+    TransferEncoding,
+    Upgrade,
+    UpgradeInsecureRequests,
+    UserAgent,
+    Vary,
+    Via,
+    ViewportWidth,
+    WantContentDigest,
+    WantReprDigest,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SingleValueHeader;
+    use common::authorization::Bearer;
+    use {Authorization, CacheControl, ContentType, Date, ETag, HeaderValue, Vary};
+
+    fn assert_matches_encode<H: SingleValueHeader>(header: H) {
+        let mut values = Vec::<HeaderValue>::new();
+        header.encode(&mut values);
+        assert_eq!(values, [header.to_header_value()]);
+    }
+
+    #[test]
+    fn matches_encode() {
+        assert_matches_encode(ContentType::json());
+        assert_matches_encode(CacheControl::new().with_no_cache().with_private());
+        assert_matches_encode(Date::from(::std::time::UNIX_EPOCH));
+        assert_matches_encode(ETag::new("xyzzy").unwrap());
+        assert_matches_encode(Vary::any());
+
+        let auth = Authorization::<Bearer>::bearer("abc").unwrap();
+        assert!(auth.to_header_value().is_sensitive());
+        assert_matches_encode(auth);
+    }
+}
@@ -36,28 +36,32 @@ impl Separator for SemiColon {
 
 impl<Sep: Separator> FlatCsv<Sep> {
     pub(crate) fn iter(&self) -> impl Iterator<Item = &str> {
-        self.value.to_str().ok().into_iter().flat_map(|value_str| {
-            let mut in_quotes = false;
-            value_str
-                .split(move |c| {
-                    if in_quotes {
+        self.value.to_str().ok().into_iter().flat_map(Self::split)
+    }
+
+    /// Split `value_str` at each separator outside of quotes, trimming the
+    /// elements.
+    pub(crate) fn split(value_str: &str) -> impl Iterator<Item = &str> {
+        let mut in_quotes = false;
+        value_str
+            .split(move |c| {
+                if in_quotes {
+                    if c == '"' {
+                        in_quotes = false;
+                    }
+                    false // dont split
+                } else {
+                    if c == Sep::CHAR {
+                        true // split
+                    } else {
                         if c == '"' {
-                            in_quotes = false;
+                            in_quotes = true;
                         }
                         false // dont split
-                    } else {
-                        if c == Sep::CHAR {
-                            true // split
-                        } else {
-                            if c == '"' {
-                                in_quotes = true;
-                            }
-                            false // dont split
-                        }
                     }
-                })
-                .map(|item| item.trim())
-        })
+                }
+            })
+            .map(|item| item.trim())
     }
 }
 
@@ -113,7 +117,7 @@ impl<'a, Sep: Separator> FromIterator<&'a HeaderValue> for FlatCsv<Sep> {
             .next()
             .cloned()
             .map(|val| BytesMut::from(val.as_bytes()))
-            .unwrap_or_else(|| BytesMut::new());
+            .unwrap_or_default();
 
         for val in values {
             buf.extend_from_slice(&[Sep::BYTE, b' ']);
@@ -144,7 +148,7 @@ impl<Sep: Separator> FromIterator<HeaderValue> for FlatCsv<Sep> {
         let mut buf = values
             .next()
             .map(|val| BytesMut::from(val.as_bytes()))
-            .unwrap_or_else(|| BytesMut::new());
+            .unwrap_or_default();
 
         for val in values {
             buf.extend_from_slice(&[Sep::BYTE, b' ']);
@@ -1,12 +1,12 @@
 use std::fmt;
 use std::str::FromStr;
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use bytes::Bytes;
 use http::header::HeaderValue;
-use httpdate;
+use DecodeOptions;
 
-use super::IterExt;
+use super::{IterExt, TryFromValues};
 
 /// A timestamp with HTTP formatting and parsing
 //   Prior to 1995, there were three different formats commonly used by
@@ -31,20 +31,226 @@ use super::IterExt;
 //   header field that contains one or more timestamps defined as
 //   HTTP-date, the sender MUST generate those timestamps in the
 //   IMF-fixdate format.
+//
+// This keeps the seconds since the epoch, and reads and writes the
+// IMF-fixdate format itself, straight from and to bytes. Nearly every date
+// sent today is in that format, and doing it here skips the UTF-8 check,
+// the attempts at the other formats, and the conversions through a
+// calendar date that `httpdate` makes. The obsolete formats are parsed
+// here too, so that every format is checked the same way: a date that
+// doesn't exist, such as 31 April or 25:00, is refused, while a leap
+// second, `:60`, is read as `:59`.
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub(crate) struct HttpDate(httpdate::HttpDate);
+pub(crate) struct HttpDate(u64);
+
+// 10000-01-01, which no longer fits in four digits.
+const MAX_SECS: u64 = 253_402_300_800;
 
 impl HttpDate {
     pub(crate) fn from_val(val: &HeaderValue) -> Option<Self> {
-        val.to_str().ok()?.parse().ok()
+        parse_imf_fixdate(val.as_bytes()).or_else(|| val.to_str().ok()?.parse().ok())
+    }
+
+    /// Like `from_val`, but a strict date must be an IMF-fixdate.
+    pub(crate) fn from_val_with(val: &HeaderValue, options: &DecodeOptions) -> Option<Self> {
+        if options.strict {
+            parse_imf_fixdate(val.as_bytes())
+        } else {
+            HttpDate::from_val(val)
+        }
+    }
+
+    /// Like `From<SystemTime>`, but a time before 1970 becomes the Unix
+    /// epoch, and one after 9999 becomes its last second.
+    pub(crate) fn from_system_time_clamped(time: SystemTime) -> Self {
+        match time.duration_since(UNIX_EPOCH) {
+            Ok(since) => HttpDate(since.as_secs().min(MAX_SECS - 1)),
+            Err(_) => HttpDate(0),
+        }
+    }
+
+    /// Formats as an IMF-fixdate, such as `Sun, 06 Nov 1994 08:49:37 GMT`.
+    fn to_imf_fixdate(self) -> [u8; 29] {
+        let days = self.0 / 86_400;
+        let secs = self.0 % 86_400;
+        let (year, month, day) = civil_from_days(days);
+
+        let mut buf = *b"   , 00     0000 00:00:00 GMT";
+        buf[..3].copy_from_slice(WEEKDAYS[((days + 3) % 7) as usize]);
+        buf[8..11].copy_from_slice(MONTHS[(month - 1) as usize]);
+        put_digits(&mut buf[5..7], day);
+        put_digits(&mut buf[12..16], year);
+        put_digits(&mut buf[17..19], secs / 3_600);
+        put_digits(&mut buf[20..22], secs / 60 % 60);
+        put_digits(&mut buf[23..25], secs % 60);
+        buf
+    }
+}
+
+/// Parses an IMF-fixdate straight from the bytes.
+///
+/// This accepts exactly what `httpdate` would for this format, including
+/// checking the weekday, except that it also accepts a leap second.
+fn parse_imf_fixdate(s: &[u8]) -> Option<HttpDate> {
+    // Example: `Sun, 06 Nov 1994 08:49:37 GMT`
+    if s.len() != 29 || s[3..5] != *b", " || s[7] != b' ' || s[11] != b' ' || s[16] != b' ' {
+        return None;
+    }
+    if s[25..] != *b" GMT" {
+        return None;
+    }
+
+    let weekday = WEEKDAYS.iter().position(|&d| s[..3] == *d)?;
+    let month = MONTHS.iter().position(|&m| s[8..11] == *m)? as u64 + 1;
+    let day = digits(&s[5..7])?;
+    let year = digits(&s[12..16])?;
+    from_parts(weekday, year, month, day, &s[17..25])
+}
+
+/// Parses the obsolete RFC 850 format, such as
+/// `Sunday, 06-Nov-94 08:49:37 GMT`.
+///
+/// Two-digit years are windowed as in
+/// [RFC6265](https://www.rfc-editor.org/rfc/rfc6265#section-5.1.1): `70`
+/// to `99` are in the 1900s, and `00` to `69` are in the 2000s.
+fn parse_rfc850_date(s: &[u8]) -> Option<HttpDate> {
+    let comma = s.iter().position(|&b| b == b',')?;
+    let weekday = WEEKDAY_NAMES.iter().position(|&d| s[..comma] == *d)?;
+    let s = &s[comma..];
+    if s.len() != 24 || s[1] != b' ' || s[4] != b'-' || s[8] != b'-' || s[11] != b' ' {
+        return None;
+    }
+    if s[20..] != *b" GMT" {
+        return None;
+    }
+
+    let month = MONTHS.iter().position(|&m| s[5..8] == *m)? as u64 + 1;
+    let day = digits(&s[2..4])?;
+    let year = match digits(&s[9..11])? {
+        year @ 70..=99 => year + 1900,
+        year => year + 2000,
+    };
+    from_parts(weekday, year, month, day, &s[12..20])
+}
+
+/// Parses the obsolete asctime format, such as `Sun Nov  6 08:49:37 1994`,
+/// where a day before the 10th may be padded with a space or a zero.
+fn parse_asctime(s: &[u8]) -> Option<HttpDate> {
+    if s.len() != 24 || s[3] != b' ' || s[7] != b' ' || s[10] != b' ' || s[19] != b' ' {
+        return None;
     }
+
+    let weekday = WEEKDAYS.iter().position(|&d| s[..3] == *d)?;
+    let month = MONTHS.iter().position(|&m| s[4..7] == *m)? as u64 + 1;
+    let day = match s[8] {
+        b' ' => digits(&s[9..10])?,
+        _ => digits(&s[8..10])?,
+    };
+    let year = digits(&s[20..24])?;
+    from_parts(weekday, year, month, day, &s[11..19])
+}
+
+/// Checks and combines the parts of a date, with the time of day in the
+/// form `08:49:37`.
+fn from_parts(weekday: usize, year: u64, month: u64, day: u64, time: &[u8]) -> Option<HttpDate> {
+    if time.len() != 8 || time[2] != b':' || time[5] != b':' {
+        return None;
+    }
+    let hour = digits(&time[..2])?;
+    let min = digits(&time[3..5])?;
+    // A leap second is read as the second before it, since the seconds
+    // since the epoch skip it.
+    let sec = match digits(&time[6..])? {
+        60 => 59,
+        sec => sec,
+    };
+
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let month_len = match month {
+        2 if leap => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    };
+    if year < 1970 || day == 0 || day > month_len || hour > 23 || min > 59 || sec > 59 {
+        return None;
+    }
+
+    let days = days_from_civil(year, month, day);
+    if (days + 3) % 7 != weekday as u64 {
+        return None;
+    }
+    Some(HttpDate(days * 86_400 + hour * 3_600 + min * 60 + sec))
+}
+
+const MONTHS: [&[u8; 3]; 12] = [
+    b"Jan", b"Feb", b"Mar", b"Apr", b"May", b"Jun", b"Jul", b"Aug", b"Sep", b"Oct", b"Nov", b"Dec",
+];
+
+// Starting from Monday, so that 1970-01-01, a Thursday, is at 3.
+const WEEKDAYS: [&[u8; 3]; 7] = [b"Mon", b"Tue", b"Wed", b"Thu", b"Fri", b"Sat", b"Sun"];
+
+const WEEKDAY_NAMES: [&[u8]; 7] = [
+    b"Monday",
+    b"Tuesday",
+    b"Wednesday",
+    b"Thursday",
+    b"Friday",
+    b"Saturday",
+    b"Sunday",
+];
+
+fn digits(s: &[u8]) -> Option<u64> {
+    s.iter().try_fold(0, |acc, &b| {
+        let digit = b.wrapping_sub(b'0');
+        if digit < 10 {
+            Some(acc * 10 + u64::from(digit))
+        } else {
+            None
+        }
+    })
+}
+
+fn put_digits(buf: &mut [u8], mut n: u64) {
+    for b in buf.iter_mut().rev() {
+        *b = b'0' + (n % 10) as u8;
+        n /= 10;
+    }
+}
+
+// The conversions between days since the epoch and calendar dates count
+// years from March, so that the leap day comes last. See
+// http://howardhinnant.github.io/date_algorithms.html
+
+fn days_from_civil(year: u64, month: u64, day: u64) -> u64 {
+    let (y, m) = if month <= 2 {
+        (year - 1, month + 9)
+    } else {
+        (year, month - 3)
+    };
+    let day_of_year = (153 * m + 2) / 5 + day - 1;
+    y * 365 + y / 4 - y / 100 + y / 400 + day_of_year - 719_468
+}
+
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let day_of_era = z % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+    (year, month, day)
 }
 
 // TODO: remove this and FromStr?
 #[derive(Debug)]
 pub struct Error(());
 
-impl super::TryFromValues for HttpDate {
+impl TryFromValues for HttpDate {
     fn try_from_values<'i, I>(values: &mut I) -> Result<Self, ::Error>
     where
         I: Iterator<Item = &'i HeaderValue>,
@@ -54,6 +260,17 @@ impl super::TryFromValues for HttpDate {
             .and_then(HttpDate::from_val)
             .ok_or_else(::Error::invalid)
     }
+
+    /// A strict date must be an IMF-fixdate, without the obsolete formats.
+    fn try_from_values_with<'i, I>(values: &mut I, options: &DecodeOptions) -> Result<Self, ::Error>
+    where
+        I: Iterator<Item = &'i HeaderValue>,
+    {
+        values
+            .just_one()
+            .and_then(|val| HttpDate::from_val_with(val, options))
+            .ok_or_else(::Error::invalid)
+    }
 }
 
 impl From<HttpDate> for HeaderValue {
@@ -64,9 +281,7 @@ impl From<HttpDate> for HeaderValue {
 
 impl<'a> From<&'a HttpDate> for HeaderValue {
     fn from(date: &'a HttpDate) -> HeaderValue {
-        // TODO: could be just BytesMut instead of String
-        let s = date.to_string();
-        let bytes = Bytes::from(s);
+        let bytes = Bytes::copy_from_slice(&date.to_imf_fixdate());
         HeaderValue::from_maybe_shared(bytes).expect("HttpDate always is a valid value")
     }
 }
@@ -74,44 +289,130 @@ impl<'a> From<&'a HttpDate> for HeaderValue {
 impl FromStr for HttpDate {
     type Err = Error;
     fn from_str(s: &str) -> Result<HttpDate, Error> {
-        Ok(HttpDate(s.parse().map_err(|_| Error(()))?))
+        let bytes = s.trim().as_bytes();
+        parse_imf_fixdate(bytes)
+            .or_else(|| parse_rfc850_date(bytes))
+            .or_else(|| parse_asctime(bytes))
+            .ok_or(Error(()))
     }
 }
 
 impl fmt::Debug for HttpDate {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        fmt::Display::fmt(&self.0, f)
+        fmt::Display::fmt(self, f)
     }
 }
 
 impl fmt::Display for HttpDate {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        fmt::Display::fmt(&self.0, f)
+        let buf = self.to_imf_fixdate();
+        f.write_str(::std::str::from_utf8(&buf).expect("IMF-fixdate is ASCII"))
     }
 }
 
 impl From<SystemTime> for HttpDate {
     fn from(sys: SystemTime) -> HttpDate {
-        HttpDate(sys.into())
+        let secs = sys
+            .duration_since(UNIX_EPOCH)
+            .expect("all times should be after the epoch")
+            .as_secs();
+        assert!(secs < MAX_SECS, "date must be before year 9999");
+        HttpDate(secs)
     }
 }
 
 impl From<HttpDate> for SystemTime {
     fn from(date: HttpDate) -> SystemTime {
-        SystemTime::from(date.0)
+        UNIX_EPOCH + Duration::from_secs(date.0)
+    }
+}
+
+/// An HTTP date can't be before 1970 or after 9999, so times outside that
+/// range are clamped, rather than panicking like `From<SystemTime>`.
+#[cfg(any(feature = "chrono", feature = "time"))]
+fn clamped(unix_secs: i64) -> HttpDate {
+    HttpDate(unix_secs.max(0).min(MAX_SECS as i64 - 1) as u64)
+}
+
+/// Dates before 1970 become the Unix epoch, and dates after 9999 become
+/// its last second. Fractions of a second are dropped.
+#[cfg(feature = "chrono")]
+impl From<::chrono::DateTime<::chrono::Utc>> for HttpDate {
+    fn from(time: ::chrono::DateTime<::chrono::Utc>) -> HttpDate {
+        clamped(time.timestamp())
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<HttpDate> for ::chrono::DateTime<::chrono::Utc> {
+    fn from(date: HttpDate) -> ::chrono::DateTime<::chrono::Utc> {
+        SystemTime::from(date).into()
+    }
+}
+
+/// Dates before 1970 become the Unix epoch, and dates after 9999 become
+/// its last second. Fractions of a second are dropped.
+#[cfg(feature = "time")]
+impl From<::time::OffsetDateTime> for HttpDate {
+    fn from(time: ::time::OffsetDateTime) -> HttpDate {
+        clamped(time.unix_timestamp())
+    }
+}
+
+#[cfg(feature = "time")]
+impl From<HttpDate> for ::time::OffsetDateTime {
+    fn from(date: HttpDate) -> ::time::OffsetDateTime {
+        SystemTime::from(date).into()
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::HttpDate;
+    use super::{HttpDate, TryFromValues};
+    use {DecodeOptions, HeaderValue};
 
-    use std::time::{Duration, UNIX_EPOCH};
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
     // The old tests had Sunday, but 1994-11-07 is a Monday.
     // See https://github.com/pyfisch/httpdate/pull/6#issuecomment-846881001
     fn nov_07() -> HttpDate {
-        HttpDate((UNIX_EPOCH + Duration::new(784198117, 0)).into())
+        HttpDate::from(UNIX_EPOCH + Duration::new(784198117, 0))
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_chrono_conversion() {
+        use chrono::{DateTime, TimeZone, Utc};
+
+        let time = Utc.timestamp_opt(784198117, 250_000_000).unwrap();
+        let date = HttpDate::from(time);
+        assert_eq!(date, nov_07());
+
+        let back = DateTime::<Utc>::from(date);
+        assert_eq!(back, Utc.timestamp_opt(784198117, 0).unwrap());
+
+        let before_epoch = Utc.with_ymd_and_hms(1969, 7, 20, 20, 17, 40).unwrap();
+        assert_eq!(HttpDate::from(before_epoch), HttpDate::from(UNIX_EPOCH));
+
+        let far_future = Utc.with_ymd_and_hms(12000, 1, 1, 0, 0, 0).unwrap();
+        let date = HttpDate::from(far_future);
+        assert_eq!(date.to_string(), "Fri, 31 Dec 9999 23:59:59 GMT");
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn test_time_conversion() {
+        let time = ::time::OffsetDateTime::from_unix_timestamp(784198117).unwrap()
+            + ::time::Duration::milliseconds(250);
+        let date = HttpDate::from(time);
+        assert_eq!(date, nov_07());
+
+        let back = ::time::OffsetDateTime::from(date);
+        assert_eq!(back.unix_timestamp(), 784198117);
+        assert_eq!(back.offset(), ::time::UtcOffset::UTC);
+
+        let before_epoch = ::time::OffsetDateTime::from_unix_timestamp(-14_182_940).unwrap();
+        assert_eq!(HttpDate::from(before_epoch), HttpDate::from(UNIX_EPOCH));
     }
 
     #[test]
@@ -135,9 +436,118 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_strict_only_imf_fixdate() {
+        let strict = DecodeOptions::strict();
+        let decode = |s: &'static str, options: &DecodeOptions| {
+            let value = HeaderValue::from_static(s);
+            HttpDate::try_from_values_with(&mut ::std::iter::once(&value), options).ok()
+        };
+
+        let imf = decode("Mon, 07 Nov 1994 08:48:37 GMT", &strict);
+        assert_eq!(imf, Some(nov_07()));
+        for &obsolete in &["Monday, 07-Nov-94 08:48:37 GMT", "Mon Nov  7 08:48:37 1994"] {
+            assert_eq!(decode(obsolete, &DecodeOptions::default()), imf);
+            assert_eq!(decode(obsolete, &strict), None);
+        }
+    }
+
+    #[test]
+    fn test_rfc_850_two_digit_years() {
+        let parse = |s: &str| s.parse::<HttpDate>().unwrap();
+        assert_eq!(
+            parse("Tuesday, 31-Dec-69 23:59:59 GMT"),
+            parse("Tue, 31 Dec 2069 23:59:59 GMT")
+        );
+        assert_eq!(
+            parse("Thursday, 01-Jan-70 00:00:00 GMT"),
+            HttpDate::from(UNIX_EPOCH)
+        );
+        assert_eq!(
+            parse("Friday, 31-Dec-99 23:59:59 GMT"),
+            parse("Fri, 31 Dec 1999 23:59:59 GMT")
+        );
+        assert_eq!(
+            parse("Saturday, 01-Jan-00 00:00:00 GMT"),
+            parse("Sat, 01 Jan 2000 00:00:00 GMT")
+        );
+
+        // The weekday must match the windowed year.
+        assert!("Wednesday, 31-Dec-69 23:59:59 GMT"
+            .parse::<HttpDate>()
+            .is_err());
+        assert!("Mon, 07-Nov-94 08:48:37 GMT".parse::<HttpDate>().is_err());
+    }
+
     #[test]
     fn test_asctime() {
-        assert_eq!("Mon Nov  7 08:48:37 1994".parse::<HttpDate>().unwrap(), nov_07());
+        assert_eq!(
+            "Mon Nov  7 08:48:37 1994".parse::<HttpDate>().unwrap(),
+            nov_07()
+        );
+        assert_eq!(
+            "Mon Nov 07 08:48:37 1994".parse::<HttpDate>().unwrap(),
+            nov_07()
+        );
+        assert!("Tue Nov  7 08:48:37 1994".parse::<HttpDate>().is_err());
+        assert!("Mon Nov  7 08:48:37 94".parse::<HttpDate>().is_err());
+    }
+
+    #[test]
+    fn test_out_of_range() {
+        for s in &[
+            "Thu, 32 Dec 1994 08:48:37 GMT",
+            "Thu, 00 Dec 1994 08:48:37 GMT",
+            "Thursday, 32-Dec-94 08:48:37 GMT",
+            "Thu Dec 32 08:48:37 1994",
+            "Mon, 07 Nov 1994 25:48:37 GMT",
+            "Mon, 07 Nov 1994 08:60:37 GMT",
+            "Mon, 07 Nov 1994 08:48:61 GMT",
+            "Mon Nov  7 24:00:00 1994",
+        ] {
+            assert!(s.parse::<HttpDate>().is_err(), "{}", s);
+        }
+        // Month 13 can't be spelled, but a number isn't a month either.
+        assert!("Mon, 07 013 1994 08:48:37 GMT".parse::<HttpDate>().is_err());
+    }
+
+    #[test]
+    fn test_leap_second() {
+        let parse = |s: &str| s.parse::<HttpDate>().unwrap();
+        let before = parse("Sat, 31 Dec 2016 23:59:59 GMT");
+        assert_eq!(parse("Sat, 31 Dec 2016 23:59:60 GMT"), before);
+        assert_eq!(parse("Saturday, 31-Dec-16 23:59:60 GMT"), before);
+        assert_eq!(parse("Sat Dec 31 23:59:60 2016"), before);
+        assert_eq!(before.to_string(), "Sat, 31 Dec 2016 23:59:59 GMT");
+    }
+
+    #[test]
+    fn test_imf_fixdate_fast_path_matches_httpdate() {
+        // Every few days, at varying times, across leap years and centuries.
+        let mut secs = 0;
+        while secs < 8_000_000_000 {
+            let s = httpdate::fmt_http_date(UNIX_EPOCH + Duration::from_secs(secs));
+            let date = HttpDate::from(UNIX_EPOCH + Duration::from_secs(secs));
+            assert_eq!(date.to_string(), s);
+            let fast = super::parse_imf_fixdate(s.as_bytes());
+            let slow = httpdate::parse_http_date(&s).unwrap();
+            assert_eq!(fast.map(SystemTime::from), Some(slow), "{}", s);
+            secs += 260_017;
+        }
+
+        for s in &[
+            "Tue, 07 Nov 1994 08:48:37 GMT",
+            "Mon, 07 Nov 1994 24:48:37 GMT",
+            "Thu, 29 Feb 1900 00:00:00 GMT",
+            "Mon, 31 Apr 2000 00:00:00 GMT",
+            "Wed, 31 Dec 1969 23:59:59 GMT",
+            "Mon, 07 nov 1994 08:48:37 GMT",
+            "Mon, 07 Nov 1994 08:48:37 UTC",
+            "Mon,  7 Nov 1994 08:48:37 GMT",
+        ] {
+            assert_eq!(super::parse_imf_fixdate(s.as_bytes()), None, "{}", s);
+            assert!(s.parse::<HttpDate>().is_err(), "{}", s);
+        }
     }
 
     #[test]
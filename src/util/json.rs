@@ -0,0 +1,352 @@
+//! A minimal JSON reader and writer, for the few headers whose values are
+//! JSON, such as `NEL` and `Report-To`.
+
+use std::fmt::{self, Write};
+
+use HeaderValue;
+
+// Deeper nesting than this is rejected, instead of risking the stack.
+const MAX_DEPTH: usize = 32;
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    /// Get a member of an object by key.
+    pub(crate) fn get(&self, key: &str) -> Option<&Value> {
+        match *self {
+            Value::Object(ref members) => members.iter().find(|m| m.0 == key).map(|m| &m.1),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_bool(&self) -> Option<bool> {
+        match *self {
+            Value::Bool(b) => Some(b),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_f64(&self) -> Option<f64> {
+        match *self {
+            Value::Number(n) => Some(n),
+            _ => None,
+        }
+    }
+
+    /// Get a number that is a whole number in `u32` range.
+    pub(crate) fn as_u32(&self) -> Option<u32> {
+        match *self {
+            Value::Number(n) if n >= 0.0 && n <= f64::from(u32::MAX) && n.fract() == 0.0 => {
+                Some(n as u32)
+            }
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_str(&self) -> Option<&str> {
+        match *self {
+            Value::String(ref s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_array(&self) -> Option<&[Value]> {
+        match *self {
+            Value::Array(ref items) => Some(items),
+            _ => None,
+        }
+    }
+}
+
+/// Parse a comma-separated list of JSON values.
+pub(crate) fn parse_list(s: &str) -> Option<Vec<Value>> {
+    let mut parser = Parser {
+        s: s.as_bytes(),
+        pos: 0,
+    };
+    let mut values = Vec::new();
+    loop {
+        parser.skip_ws();
+        values.push(parser.value(0)?);
+        parser.skip_ws();
+        match parser.next() {
+            None => return Some(values),
+            Some(b',') => (),
+            Some(_) => return None,
+        }
+    }
+}
+
+/// Parse the values of a JSON header, sent as one or more field lines.
+pub(crate) fn parse_values<'i, I>(values: I) -> Option<Vec<Value>>
+where
+    I: Iterator<Item = &'i HeaderValue>,
+{
+    let mut joined = String::new();
+    for value in values {
+        if !joined.is_empty() {
+            joined.push(',');
+        }
+        joined.push_str(value.to_str().ok()?);
+    }
+    parse_list(&joined)
+}
+
+/// Write `s` as a JSON string, escaping everything outside of printable
+/// ASCII, so the output is always a valid `HeaderValue`.
+pub(crate) fn write_str(s: &str, f: &mut fmt::Formatter) -> fmt::Result {
+    f.write_char('"')?;
+    for c in s.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            ' '..='~' => f.write_char(c)?,
+            _ => {
+                let mut buf = [0; 2];
+                for unit in c.encode_utf16(&mut buf) {
+                    write!(f, "\\u{:04x}", unit)?;
+                }
+            }
+        }
+    }
+    f.write_char('"')
+}
+
+struct Parser<'a> {
+    s: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn value(&mut self, depth: usize) -> Option<Value> {
+        if depth > MAX_DEPTH {
+            return None;
+        }
+
+        match self.peek()? {
+            b'{' => self.object(depth),
+            b'[' => self.array(depth),
+            b'"' => self.string().map(Value::String),
+            b't' => self.literal("true", Value::Bool(true)),
+            b'f' => self.literal("false", Value::Bool(false)),
+            b'n' => self.literal("null", Value::Null),
+            _ => self.number(),
+        }
+    }
+
+    fn object(&mut self, depth: usize) -> Option<Value> {
+        self.expect(b'{')?;
+        let mut members = Vec::new();
+        self.skip_ws();
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+            return Some(Value::Object(members));
+        }
+        loop {
+            self.skip_ws();
+            let key = self.string()?;
+            self.skip_ws();
+            self.expect(b':')?;
+            self.skip_ws();
+            let value = self.value(depth + 1)?;
+            members.push((key, value));
+            self.skip_ws();
+            match self.next()? {
+                b',' => (),
+                b'}' => return Some(Value::Object(members)),
+                _ => return None,
+            }
+        }
+    }
+
+    fn array(&mut self, depth: usize) -> Option<Value> {
+        self.expect(b'[')?;
+        let mut items = Vec::new();
+        self.skip_ws();
+        if self.peek() == Some(b']') {
+            self.pos += 1;
+            return Some(Value::Array(items));
+        }
+        loop {
+            self.skip_ws();
+            items.push(self.value(depth + 1)?);
+            self.skip_ws();
+            match self.next()? {
+                b',' => (),
+                b']' => return Some(Value::Array(items)),
+                _ => return None,
+            }
+        }
+    }
+
+    fn string(&mut self) -> Option<String> {
+        self.expect(b'"')?;
+        let mut out = String::new();
+        loop {
+            match self.next()? {
+                b'"' => return Some(out),
+                b'\\' => {
+                    let c = match self.next()? {
+                        b'"' => '"',
+                        b'\\' => '\\',
+                        b'/' => '/',
+                        b'b' => '\u{8}',
+                        b'f' => '\u{c}',
+                        b'n' => '\n',
+                        b'r' => '\r',
+                        b't' => '\t',
+                        b'u' => self.unicode_escape()?,
+                        _ => return None,
+                    };
+                    out.push(c);
+                }
+                b if b < 0x20 => return None,
+                _ => {
+                    // Copy the rest of a multi-byte UTF-8 sequence as is.
+                    let start = self.pos - 1;
+                    while self.peek().map_or(false, |b| b & 0xC0 == 0x80) {
+                        self.pos += 1;
+                    }
+                    out.push_str(::std::str::from_utf8(&self.s[start..self.pos]).ok()?);
+                }
+            }
+        }
+    }
+
+    fn unicode_escape(&mut self) -> Option<char> {
+        let first = self.hex4()?;
+        if (0xD800..0xDC00).contains(&first) {
+            self.expect(b'\\')?;
+            self.expect(b'u')?;
+            let second = self.hex4()?;
+            if !(0xDC00..0xE000).contains(&second) {
+                return None;
+            }
+            let c = 0x10000 + ((first - 0xD800) << 10) + (second - 0xDC00);
+            ::std::char::from_u32(c)
+        } else {
+            ::std::char::from_u32(first)
+        }
+    }
+
+    fn hex4(&mut self) -> Option<u32> {
+        let digits = self.s.get(self.pos..self.pos + 4)?;
+        let digits = ::std::str::from_utf8(digits).ok()?;
+        let n = u32::from_str_radix(digits, 16).ok()?;
+        self.pos += 4;
+        Some(n)
+    }
+
+    fn number(&mut self) -> Option<Value> {
+        let start = self.pos;
+        while let Some(b'0'..=b'9') | Some(b'-') | Some(b'+') | Some(b'.') | Some(b'e')
+        | Some(b'E') = self.peek()
+        {
+            self.pos += 1;
+        }
+        let s = ::std::str::from_utf8(&self.s[start..self.pos]).ok()?;
+        let digits = s.trim_start_matches('-');
+        if !digits.starts_with(|c: char| c.is_ascii_digit())
+            || (digits.starts_with('0') && digits[1..].starts_with(|c: char| c.is_ascii_digit()))
+        {
+            return None;
+        }
+        s.parse()
+            .ok()
+            .filter(|n: &f64| n.is_finite())
+            .map(Value::Number)
+    }
+
+    fn literal(&mut self, word: &str, value: Value) -> Option<Value> {
+        if self.s[self.pos..].starts_with(word.as_bytes()) {
+            self.pos += word.len();
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    fn expect(&mut self, b: u8) -> Option<()> {
+        if self.next()? == b {
+            Some(())
+        } else {
+            None
+        }
+    }
+
+    fn next(&mut self) -> Option<u8> {
+        let b = self.peek()?;
+        self.pos += 1;
+        Some(b)
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.s.get(self.pos).cloned()
+    }
+
+    fn skip_ws(&mut self) {
+        while let Some(b' ') | Some(b'\t') | Some(b'\n') | Some(b'\r') = self.peek() {
+            self.pos += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Str<'a>(&'a str);
+
+    impl<'a> fmt::Display for Str<'a> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write_str(self.0, f)
+        }
+    }
+
+    #[test]
+    fn parse_values() {
+        let values =
+            parse_list(r#"{"a": [1, -2.5e1, true, null], "b": "x\"é😀"}, "second""#).unwrap();
+        assert_eq!(values.len(), 2);
+
+        let a = values[0].get("a").unwrap().as_array().unwrap();
+        assert_eq!(a[0].as_u32(), Some(1));
+        assert_eq!(a[1].as_f64(), Some(-25.0));
+        assert_eq!(a[2].as_bool(), Some(true));
+        assert_eq!(a[3], Value::Null);
+        assert_eq!(
+            values[0].get("b").unwrap().as_str(),
+            Some("x\"\u{e9}\u{1f600}")
+        );
+        assert_eq!(values[1].as_str(), Some("second"));
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert_eq!(parse_list(""), None);
+        assert_eq!(parse_list("{"), None);
+        assert_eq!(parse_list(r#"{"a" 1}"#), None);
+        assert_eq!(parse_list("[1,]"), None);
+        assert_eq!(parse_list("01"), None);
+        assert_eq!(parse_list("tru"), None);
+        assert_eq!(parse_list("{} {}"), None);
+        assert_eq!(parse_list(&"[".repeat(100)), None);
+    }
+
+    #[test]
+    fn write_escapes() {
+        assert_eq!(Str("a\"b\\c").to_string(), r#""a\"b\\c""#);
+        assert_eq!(
+            Str("\n\u{e9}\u{1f600}").to_string(),
+            r#""\u000a\u00e9\ud83d\ude00""#
+        );
+    }
+}
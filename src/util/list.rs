@@ -0,0 +1,162 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::iter::FromIterator;
+use std::str::FromStr;
+
+use util::{csv, FlatCsv, TryFromValues};
+use HeaderValue;
+
+/// A comma-separated list of items, for headers defined as `#item`.
+///
+/// Decoding merges every line of the header, splits at commas outside of
+/// quoted strings, trims whitespace, and skips empty elements, as
+/// [RFC7230](https://tools.ietf.org/html/rfc7230#section-7) requires. Each
+/// element is parsed with `FromStr`, and encoding joins the `Display` of
+/// each item.
+///
+/// A list header wraps this, so it can be derived with `derive_header!`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct ListHeader<T>(Vec<T>);
+
+impl<T> ListHeader<T> {
+    /// Iterate the items, in the order they were sent.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &T> {
+        self.0.iter()
+    }
+}
+
+impl<T> From<Vec<T>> for ListHeader<T> {
+    fn from(items: Vec<T>) -> Self {
+        ListHeader(items)
+    }
+}
+
+impl<T: PartialEq> FromIterator<T> for ListHeader<T> {
+    /// Collect items into a list, skipping any repeated item.
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        ListHeader(dedup(iter))
+    }
+}
+
+/// Collect the items of `iter`, skipping any repeated item.
+///
+/// This is for list headers that collect typed items, but don't keep them
+/// in a `ListHeader`.
+pub(crate) fn dedup<T: PartialEq>(iter: impl IntoIterator<Item = T>) -> Vec<T> {
+    let mut items = Vec::new();
+    for item in iter {
+        if !items.contains(&item) {
+            items.push(item);
+        }
+    }
+    items
+}
+
+impl<T: FromStr> TryFromValues for ListHeader<T> {
+    fn try_from_values<'i, I>(values: &mut I) -> Result<Self, ::Error>
+    where
+        I: Iterator<Item = &'i HeaderValue>,
+    {
+        let mut items = Vec::new();
+        for value in values {
+            // `FlatCsv` silently yields nothing for a value that isn't text.
+            let value_str = value.to_str().map_err(|_| ::Error::invalid())?;
+            push_elements(&mut items, value_str)?;
+        }
+        Ok(ListHeader(items))
+    }
+}
+
+impl ListHeader<String> {
+    /// Decode the items like `try_from_values`, but read a value that
+    /// isn't text as ISO-8859-1 rather than failing.
+    ///
+    /// This is for headers whose elements get checked later, so that a bad
+    /// value still shows up as a bad element.
+    pub(crate) fn from_values_latin1<'i, I>(values: &mut I) -> Self
+    where
+        I: Iterator<Item = &'i HeaderValue>,
+    {
+        let mut items = Vec::new();
+        for value in values {
+            let value_str = match value.to_str() {
+                Ok(value_str) => Cow::Borrowed(value_str),
+                Err(_) => Cow::Owned(super::latin1(value.as_bytes())),
+            };
+            push_elements(&mut items, &value_str).expect("a String always parses");
+        }
+        ListHeader(items)
+    }
+}
+
+fn push_elements<T: FromStr>(items: &mut Vec<T>, value_str: &str) -> Result<(), ::Error> {
+    for element in <FlatCsv>::split(value_str) {
+        if !element.is_empty() {
+            items.push(element.parse().map_err(|_| ::Error::invalid())?);
+        }
+    }
+    Ok(())
+}
+
+impl<T: fmt::Display> fmt::Display for ListHeader<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        csv::fmt_comma_delimited(f, self.0.iter())
+    }
+}
+
+impl<'a, T: fmt::Display> From<&'a ListHeader<T>> for HeaderValue {
+    fn from(list: &'a ListHeader<T>) -> HeaderValue {
+        ::util::fmt(list)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ListHeader;
+    use util::TryFromValues;
+    use HeaderValue;
+
+    fn decode(values: &[&'static str]) -> Option<ListHeader<String>> {
+        let values = values
+            .iter()
+            .map(|&s| HeaderValue::from_static(s))
+            .collect::<Vec<_>>();
+        ListHeader::try_from_values(&mut values.iter()).ok()
+    }
+
+    #[test]
+    fn folds_lines() {
+        let list = decode(&["a,b ", " ,, c", ""]).unwrap();
+        assert_eq!(list.iter().collect::<Vec<_>>(), ["a", "b", "c"]);
+        assert_eq!(HeaderValue::from(&list), "a, b, c");
+
+        assert_eq!(decode(&[]).unwrap().iter().count(), 0);
+    }
+
+    #[test]
+    fn quoted_commas() {
+        let list = decode(&["\"a, b\", c, \"d,\""]).unwrap();
+        assert_eq!(list.iter().collect::<Vec<_>>(), ["\"a, b\"", "c", "\"d,\""]);
+    }
+
+    #[test]
+    fn parse_failure() {
+        let values = [HeaderValue::from_static("1, two")];
+        assert!(ListHeader::<u8>::try_from_values(&mut values.iter()).is_err());
+
+        let values = [HeaderValue::from_bytes(b"\xfe").unwrap()];
+        assert!(ListHeader::<String>::try_from_values(&mut values.iter()).is_err());
+
+        let list = ListHeader::from_values_latin1(&mut values.iter());
+        assert_eq!(list.iter().collect::<Vec<_>>(), ["\u{fe}"]);
+    }
+
+    #[test]
+    fn from_iter_dedups() {
+        let list = vec![1u8, 2, 1, 3].into_iter().collect::<ListHeader<_>>();
+        assert_eq!(HeaderValue::from(&list), "1, 2, 3");
+    }
+}
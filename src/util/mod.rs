@@ -1,4 +1,4 @@
-use HeaderValue;
+use {DecodeOptions, HeaderValue};
 
 //pub use self::charset::Charset;
 //pub use self::encoding::Encoding;
@@ -7,9 +7,11 @@ pub(crate) use self::flat_csv::{FlatCsv, SemiColon};
 pub(crate) use self::fmt::fmt;
 pub(crate) use self::http_date::HttpDate;
 pub(crate) use self::iter::IterExt;
+pub(crate) use self::list::{dedup, ListHeader};
 //pub use language_tags::LanguageTag;
-pub(crate) use self::quality_value::QualityValue;
+pub(crate) use self::quality_list::QualityList;
 pub(crate) use self::seconds::Seconds;
+pub(crate) use self::token::{is_tchar, is_token, is_token68, is_token68_char};
 pub(crate) use self::value_string::HeaderValueString;
 
 //mod charset;
@@ -20,8 +22,15 @@ mod flat_csv;
 mod fmt;
 mod http_date;
 mod iter;
-mod quality_value;
+pub(crate) mod json;
+mod list;
+mod quality_list;
 mod seconds;
+// Not every header built on structured fields uses every part of them.
+#[allow(dead_code)]
+pub(crate) mod sf;
+mod token;
+pub(crate) mod uri;
 mod value_string;
 
 macro_rules! error_type {
@@ -61,10 +70,58 @@ macro_rules! derive_header {
                 ::util::TryFromValues::try_from_values(values).map($type)
             }
 
+            fn decode_with<'i, I>(
+                values: &mut I,
+                options: &::DecodeOptions,
+            ) -> Result<Self, ::Error>
+            where
+                I: Iterator<Item = &'i ::http::header::HeaderValue>,
+            {
+                ::util::TryFromValues::try_from_values_with(values, options).map($type)
+            }
+
             fn encode<E: Extend<::HeaderValue>>(&self, values: &mut E) {
                 values.extend(::std::iter::once((&self.0).into()));
             }
         }
+
+        impl crate::NamedHeader for $type {
+            const NAME: ::http::header::HeaderName = ::http::header::$name;
+        }
+    };
+}
+
+/// Conversions between a date header wrapping an `HttpDate` and the date
+/// types of optional time crates.
+macro_rules! date_conversions {
+    ($type:ident) => {
+        #[cfg(feature = "chrono")]
+        impl From<::chrono::DateTime<::chrono::Utc>> for $type {
+            fn from(time: ::chrono::DateTime<::chrono::Utc>) -> $type {
+                $type(time.into())
+            }
+        }
+
+        #[cfg(feature = "chrono")]
+        impl From<$type> for ::chrono::DateTime<::chrono::Utc> {
+            fn from(date: $type) -> ::chrono::DateTime<::chrono::Utc> {
+                date.0.into()
+            }
+        }
+
+        #[cfg(feature = "time")]
+        impl From<::time::OffsetDateTime> for $type {
+            fn from(time: ::time::OffsetDateTime) -> $type {
+                $type(time.into())
+            }
+        }
+
+        #[cfg(feature = "time")]
+        impl From<$type> for ::time::OffsetDateTime {
+            fn from(date: $type) -> ::time::OffsetDateTime {
+                date.0.into()
+            }
+        }
     };
 }
 
@@ -75,6 +132,18 @@ pub(crate) trait TryFromValues: Sized {
     where
         Self: Sized,
         I: Iterator<Item = &'i HeaderValue>;
+
+    /// Try to convert from the values, following `options`.
+    ///
+    /// By default this ignores `options`, for types with only one form.
+    fn try_from_values_with<'i, I>(values: &mut I, options: &DecodeOptions) -> Result<Self, ::Error>
+    where
+        Self: Sized,
+        I: Iterator<Item = &'i HeaderValue>,
+    {
+        let _ = options;
+        Self::try_from_values(values)
+    }
 }
 
 impl TryFromValues for HeaderValue {
@@ -82,6 +151,16 @@ impl TryFromValues for HeaderValue {
     where
         I: Iterator<Item = &'i HeaderValue>,
     {
-        values.next().cloned().ok_or_else(|| ::Error::invalid())
+        values.next().cloned().ok_or_else(::Error::invalid)
     }
 }
+
+pub(crate) use headers_core::parse::{trim_ows, unquote};
+
+/// Decode `bytes` as ISO-8859-1, the way browsers treat header values.
+///
+/// Every byte maps to the code point of the same number, so this can't
+/// fail. It's for obs-text, which `HeaderValue::to_str` rejects.
+pub(crate) fn latin1(bytes: &[u8]) -> String {
+    bytes.iter().map(|&b| char::from(b)).collect()
+}
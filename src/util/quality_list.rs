@@ -14,13 +14,13 @@ use util::FlatCsv;
 /// representations that can be selected for a resource.
 ///
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
-pub struct QualityValue<QualSep = SemiQ> {
+pub struct QualityList<QualSep = SemiQ> {
     csv: FlatCsv,
     _marker: PhantomData<QualSep>,
 }
 
 mod sealed {
-    use super::QualityValue;
+    use super::QualityList;
     use std::cmp::Ordering;
     use std::convert::{From, TryFrom};
     use std::marker::PhantomData;
@@ -42,6 +42,7 @@ mod sealed {
     }
 
     /// enum that represents the ';level=' delimiter (extremely rare)
+    #[allow(dead_code)]
     #[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
     pub enum SemiLevel {}
 
@@ -96,68 +97,94 @@ mod sealed {
         }
     }
 
-    impl<Delm: QualityDelimiter + Ord> QualityValue<Delm> {
+    impl<Delm: QualityDelimiter + Ord> QualityList<Delm> {
         pub(crate) fn iter(&self) -> impl Iterator<Item = &str> {
             self.csv
                 .iter()
                 .map(|v| QualityMeta::<Delm>::try_from(v).unwrap())
-                .into_iter()
                 .sorted()
                 .map(|pair| pair.data)
-                .into_iter()
         }
     }
 
-    impl<Delm: QualityDelimiter> From<FlatCsv> for QualityValue<Delm> {
+    impl<Delm: QualityDelimiter> QualityList<Delm> {
+        /// Iterate the values with their quality, out of 1000, in the order
+        /// they were sent.
+        ///
+        /// Parameters are read leniently, with any spacing and the name in
+        /// any case. A value without a quality has 1000, and a value whose
+        /// quality doesn't parse is skipped.
+        pub(crate) fn iter_with_quality(&self) -> impl Iterator<Item = (&str, u16)> {
+            let name = Delm::STR.trim_start_matches(';').trim_end_matches('=');
+            self.csv.iter().filter_map(move |v| {
+                let mut parts = v.split(';');
+                let data = parts.next()?.trim();
+                let mut quality = 1000;
+                for param in parts {
+                    let mut kv = param.splitn(2, '=');
+                    if kv.next()?.trim().eq_ignore_ascii_case(name) {
+                        let q: f32 = kv.next()?.trim().parse().ok()?;
+                        if !(0.0..=1.0).contains(&q) {
+                            return None;
+                        }
+                        quality = (q * 1000_f32).round() as u16;
+                    }
+                }
+                Some((data, quality))
+            })
+        }
+    }
+
+    impl<Delm: QualityDelimiter> From<FlatCsv> for QualityList<Delm> {
         fn from(csv: FlatCsv) -> Self {
-            QualityValue {
+            QualityList {
                 csv,
                 _marker: PhantomData,
             }
         }
     }
 
-    impl<Delm: QualityDelimiter, F: Into<f32>> TryFrom<(&str, F)> for QualityValue<Delm> {
+    impl<Delm: QualityDelimiter, F: Into<f32>> TryFrom<(&str, F)> for QualityList<Delm> {
         type Error = ::Error;
 
         fn try_from(pair: (&str, F)) -> Result<Self, ::Error> {
             let value = HeaderValue::try_from(format!("{}{}{}", pair.0, Delm::STR, pair.1.into()))
                 .map_err(|_e| ::Error::invalid())?;
-            Ok(QualityValue {
+            Ok(QualityList {
                 csv: value.into(),
                 _marker: PhantomData,
             })
         }
     }
 
-    impl<Delm> From<HeaderValue> for QualityValue<Delm> {
+    impl<Delm> From<HeaderValue> for QualityList<Delm> {
         fn from(value: HeaderValue) -> Self {
-            QualityValue {
+            QualityList {
                 csv: value.into(),
                 _marker: PhantomData,
             }
         }
     }
 
-    impl<'a, Delm> From<&'a QualityValue<Delm>> for HeaderValue {
-        fn from(qual: &'a QualityValue<Delm>) -> HeaderValue {
+    impl<'a, Delm> From<&'a QualityList<Delm>> for HeaderValue {
+        fn from(qual: &'a QualityList<Delm>) -> HeaderValue {
             qual.csv.value.clone()
         }
     }
 
-    impl<Delm> From<QualityValue<Delm>> for HeaderValue {
-        fn from(qual: QualityValue<Delm>) -> HeaderValue {
+    impl<Delm> From<QualityList<Delm>> for HeaderValue {
+        fn from(qual: QualityList<Delm>) -> HeaderValue {
             qual.csv.value
         }
     }
 
-    impl<Delm: QualityDelimiter> TryFromValues for QualityValue<Delm> {
+    impl<Delm: QualityDelimiter> TryFromValues for QualityList<Delm> {
         fn try_from_values<'i, I>(values: &mut I) -> Result<Self, ::Error>
         where
             I: Iterator<Item = &'i HeaderValue>,
         {
             let flat: FlatCsv = values.collect();
-            Ok(QualityValue::from(flat))
+            Ok(QualityList::from(flat))
         }
     }
 }
@@ -166,14 +193,14 @@ mod sealed {
 mod tests {
     use super::{
         sealed::{SemiLevel, SemiQ},
-        QualityValue,
+        QualityList,
     };
     use HeaderValue;
 
     #[test]
     fn multiple_qualities() {
         let val = HeaderValue::from_static("gzip;q=1, br;q=0.8");
-        let qual = QualityValue::<SemiQ>::from(val);
+        let qual = QualityList::<SemiQ>::from(val);
 
         let mut values = qual.iter();
         assert_eq!(values.next(), Some("gzip"));
@@ -184,7 +211,7 @@ mod tests {
     #[test]
     fn multiple_qualities_wrong_order() {
         let val = HeaderValue::from_static("br;q=0.8, gzip;q=1.0");
-        let qual = QualityValue::<SemiQ>::from(val);
+        let qual = QualityList::<SemiQ>::from(val);
 
         let mut values = qual.iter();
         assert_eq!(values.next(), Some("gzip"));
@@ -195,7 +222,7 @@ mod tests {
     #[test]
     fn multiple_values() {
         let val = HeaderValue::from_static("deflate, gzip;q=1, br;q=0.8");
-        let qual = QualityValue::<SemiQ>::from(val);
+        let qual = QualityList::<SemiQ>::from(val);
 
         let mut values = qual.iter();
         assert_eq!(values.next(), Some("deflate"));
@@ -207,7 +234,7 @@ mod tests {
     #[test]
     fn multiple_values_wrong_order() {
         let val = HeaderValue::from_static("deflate, br;q=0.8, gzip;q=1, *;q=0.1");
-        let qual = QualityValue::<SemiQ>::from(val);
+        let qual = QualityList::<SemiQ>::from(val);
 
         let mut values = qual.iter();
         assert_eq!(values.next(), Some("deflate"));
@@ -217,10 +244,19 @@ mod tests {
         assert_eq!(values.next(), None);
     }
 
+    #[test]
+    fn with_quality() {
+        let val = HeaderValue::from_static("br ; Q=0.8, gzip, *;q=0, x;q=2, y;q=nope");
+        let qual = QualityList::<SemiQ>::from(val);
+
+        let values = qual.iter_with_quality().collect::<Vec<_>>();
+        assert_eq!(values, [("br", 800), ("gzip", 1000), ("*", 0)]);
+    }
+
     #[test]
     fn alternate_delimiter() {
         let val = HeaderValue::from_static("deflate, br;level=0.8, gzip;level=1");
-        let qual = QualityValue::<SemiLevel>::from(val);
+        let qual = QualityList::<SemiLevel>::from(val);
 
         let mut values = qual.iter();
         assert_eq!(values.next(), Some("deflate"));
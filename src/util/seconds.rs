@@ -9,7 +9,13 @@ pub(crate) struct Seconds(Duration);
 
 impl Seconds {
     pub(crate) fn from_val(val: &HeaderValue) -> Option<Self> {
-        let secs = val.to_str().ok()?.parse().ok()?;
+        // delta-seconds is only digits, while `u64::from_str` also takes a
+        // leading `+`.
+        let s = val.to_str().ok()?;
+        if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        let secs = s.parse().ok()?;
 
         Some(Self::from_secs(secs))
     }
@@ -0,0 +1,719 @@
+//! Structured Field Values, defined in
+//! [RFC8941](https://www.rfc-editor.org/rfc/rfc8941.html).
+//!
+//! Newer headers are defined in terms of these, so this provides the data
+//! model, a parser for the three top-level types (`Item`, `List`, and
+//! `Dictionary`), and serialization through their `Display` impls.
+
+use std::fmt::{self, Write};
+
+use base64::engine::general_purpose::STANDARD as ENGINE;
+use base64::Engine;
+
+use HeaderValue;
+
+pub(crate) const MAX_INTEGER: i64 = 999_999_999_999_999;
+
+/// A bare item, without any parameters.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum BareItem {
+    Integer(i64),
+    Decimal(f64),
+    String(String),
+    Token(String),
+    ByteSeq(Vec<u8>),
+    Boolean(bool),
+}
+
+/// An ordered map of parameters, attached to an `Item` or inner list.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct Parameters(Vec<(String, BareItem)>);
+
+/// A bare item with its parameters.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Item {
+    pub(crate) bare: BareItem,
+    pub(crate) params: Parameters,
+}
+
+/// A member of a `List` or `Dictionary`.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Member {
+    Item(Item),
+    InnerList(Vec<Item>, Parameters),
+}
+
+/// An ordered list of members.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct List(pub(crate) Vec<Member>);
+
+/// An ordered map of keys to members.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct Dictionary(Vec<(String, Member)>);
+
+// ===== impl BareItem =====
+
+impl BareItem {
+    pub(crate) fn as_integer(&self) -> Option<i64> {
+        match *self {
+            BareItem::Integer(n) => Some(n),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_decimal(&self) -> Option<f64> {
+        match *self {
+            BareItem::Decimal(n) => Some(n),
+            BareItem::Integer(n) => Some(n as f64),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_str(&self) -> Option<&str> {
+        match *self {
+            BareItem::String(ref s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_token(&self) -> Option<&str> {
+        match *self {
+            BareItem::Token(ref s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_bool(&self) -> Option<bool> {
+        match *self {
+            BareItem::Boolean(b) => Some(b),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for BareItem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            BareItem::Integer(n) => {
+                debug_assert!(n.abs() <= MAX_INTEGER, "sf-integer out of range: {}", n);
+                fmt::Display::fmt(&n, f)
+            }
+            BareItem::Decimal(n) => {
+                let s = format!("{:.3}", n);
+                // Drop trailing zeros, but keep at least one fractional digit.
+                let s = s.trim_end_matches('0');
+                f.write_str(s)?;
+                if s.ends_with('.') {
+                    f.write_char('0')?;
+                }
+                Ok(())
+            }
+            BareItem::String(ref s) => {
+                f.write_char('"')?;
+                for c in s.chars() {
+                    if c == '"' || c == '\\' {
+                        f.write_char('\\')?;
+                    }
+                    f.write_char(c)?;
+                }
+                f.write_char('"')
+            }
+            BareItem::Token(ref s) => f.write_str(s),
+            BareItem::ByteSeq(ref bytes) => write!(f, ":{}:", ENGINE.encode(bytes)),
+            BareItem::Boolean(b) => f.write_str(if b { "?1" } else { "?0" }),
+        }
+    }
+}
+
+// ===== impl Parameters =====
+
+impl Parameters {
+    pub(crate) fn get(&self, key: &str) -> Option<&BareItem> {
+        self.0.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+
+    /// Insert a parameter, replacing the value of an existing key in place.
+    pub(crate) fn insert(&mut self, key: String, value: BareItem) {
+        if let Some(entry) = self.0.iter_mut().find(|entry| entry.0 == key) {
+            entry.1 = value;
+            return;
+        }
+        self.0.push((key, value));
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&str, &BareItem)> {
+        self.0.iter().map(|(k, v)| (k.as_str(), v))
+    }
+}
+
+impl fmt::Display for Parameters {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (key, value) in &self.0 {
+            write!(f, ";{}", key)?;
+            if *value != BareItem::Boolean(true) {
+                write!(f, "={}", value)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// ===== impl Item =====
+
+impl Item {
+    pub(crate) fn new(bare: BareItem) -> Item {
+        Item {
+            bare,
+            params: Parameters::default(),
+        }
+    }
+}
+
+impl fmt::Display for Item {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", self.bare, self.params)
+    }
+}
+
+// ===== impl Member =====
+
+impl Member {
+    pub(crate) fn as_item(&self) -> Option<&Item> {
+        match *self {
+            Member::Item(ref item) => Some(item),
+            Member::InnerList(..) => None,
+        }
+    }
+}
+
+impl fmt::Display for Member {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Member::Item(ref item) => fmt::Display::fmt(item, f),
+            Member::InnerList(ref items, ref params) => {
+                f.write_char('(')?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        f.write_char(' ')?;
+                    }
+                    fmt::Display::fmt(item, f)?;
+                }
+                write!(f, "){}", params)
+            }
+        }
+    }
+}
+
+// ===== impl List =====
+
+impl fmt::Display for List {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        super::csv::fmt_comma_delimited(f, self.0.iter())
+    }
+}
+
+// ===== impl Dictionary =====
+
+impl Dictionary {
+    pub(crate) fn get(&self, key: &str) -> Option<&Member> {
+        self.0.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+
+    /// Insert a member, replacing the value of an existing key in place.
+    pub(crate) fn insert(&mut self, key: String, value: Member) {
+        if let Some(entry) = self.0.iter_mut().find(|entry| entry.0 == key) {
+            entry.1 = value;
+            return;
+        }
+        self.0.push((key, value));
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&str, &Member)> {
+        self.0.iter().map(|(k, v)| (k.as_str(), v))
+    }
+}
+
+impl fmt::Display for Dictionary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, (key, member)) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            f.write_str(key)?;
+            match *member {
+                Member::Item(Item {
+                    bare: BareItem::Boolean(true),
+                    ref params,
+                }) => fmt::Display::fmt(params, f)?,
+                _ => write!(f, "={}", member)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+// ===== parsing =====
+
+/// Parse an `Item` from a single field value.
+pub(crate) fn parse_item(input: &str) -> Result<Item, ::Error> {
+    Parser::new(input).top_level(Parser::item)
+}
+
+/// Parse a `List`, combining multiple field lines.
+pub(crate) fn parse_list<'i, I>(values: I) -> Result<List, ::Error>
+where
+    I: Iterator<Item = &'i HeaderValue>,
+{
+    Parser::new(&join(values)?).top_level(Parser::list)
+}
+
+/// Parse a `Dictionary`, combining multiple field lines.
+pub(crate) fn parse_dictionary<'i, I>(values: I) -> Result<Dictionary, ::Error>
+where
+    I: Iterator<Item = &'i HeaderValue>,
+{
+    Parser::new(&join(values)?).top_level(Parser::dictionary)
+}
+
+/// Check if `s` can be used as a dictionary or parameter key.
+pub(crate) fn is_key(s: &str) -> bool {
+    let mut parser = Parser::new(s);
+    parser.key().is_some() && parser.is_empty()
+}
+
+/// Check if `s` can be serialized as a `String` item.
+pub(crate) fn is_string(s: &str) -> bool {
+    s.bytes().all(|b| (0x20..=0x7e).contains(&b))
+}
+
+fn join<'i, I>(values: I) -> Result<String, ::Error>
+where
+    I: Iterator<Item = &'i HeaderValue>,
+{
+    let mut joined = String::new();
+    for value in values {
+        if !joined.is_empty() {
+            joined.push_str(", ");
+        }
+        joined.push_str(value.to_str().map_err(|_| ::Error::invalid())?);
+    }
+    Ok(joined)
+}
+
+struct Parser<'a> {
+    input: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str) -> Parser<'a> {
+        Parser {
+            input: input.as_bytes(),
+            pos: 0,
+        }
+    }
+
+    fn top_level<T>(mut self, parse: fn(&mut Self) -> Option<T>) -> Result<T, ::Error> {
+        self.skip_sp();
+        let value = parse(&mut self).ok_or_else(::Error::invalid)?;
+        self.skip_sp();
+        if self.is_empty() {
+            Ok(value)
+        } else {
+            Err(::Error::invalid())
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.get(self.pos).cloned()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.pos >= self.input.len()
+    }
+
+    fn eat(&mut self, b: u8) -> bool {
+        if self.peek() == Some(b) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn skip_sp(&mut self) {
+        while self.eat(b' ') {}
+    }
+
+    fn skip_ows(&mut self) {
+        while self.eat(b' ') || self.eat(b'\t') {}
+    }
+
+    fn take_while(&mut self, pred: impl Fn(u8) -> bool) -> &'a str {
+        let start = self.pos;
+        while self.peek().map_or(false, &pred) {
+            self.pos += 1;
+        }
+        // Only ever called with predicates that match ASCII.
+        ::std::str::from_utf8(&self.input[start..self.pos]).expect("ascii")
+    }
+
+    /// Parse the comma-separated tail shared by lists and dictionaries.
+    ///
+    /// Returns `false` when the input is exhausted.
+    fn next_member(&mut self) -> Option<bool> {
+        self.skip_ows();
+        if self.is_empty() {
+            return Some(false);
+        }
+        if !self.eat(b',') {
+            return None;
+        }
+        self.skip_ows();
+        if self.is_empty() {
+            // trailing comma
+            return None;
+        }
+        Some(true)
+    }
+
+    fn list(&mut self) -> Option<List> {
+        let mut members = Vec::new();
+        if self.is_empty() {
+            return Some(List(members));
+        }
+        loop {
+            members.push(self.member()?);
+            if !self.next_member()? {
+                return Some(List(members));
+            }
+        }
+    }
+
+    fn dictionary(&mut self) -> Option<Dictionary> {
+        let mut dict = Dictionary::default();
+        if self.is_empty() {
+            return Some(dict);
+        }
+        loop {
+            let key = self.key()?;
+            let member = if self.eat(b'=') {
+                self.member()?
+            } else {
+                Member::Item(Item {
+                    bare: BareItem::Boolean(true),
+                    params: self.parameters()?,
+                })
+            };
+            dict.insert(key, member);
+            if !self.next_member()? {
+                return Some(dict);
+            }
+        }
+    }
+
+    fn member(&mut self) -> Option<Member> {
+        if self.peek() == Some(b'(') {
+            self.inner_list()
+        } else {
+            self.item().map(Member::Item)
+        }
+    }
+
+    fn inner_list(&mut self) -> Option<Member> {
+        if !self.eat(b'(') {
+            return None;
+        }
+        let mut items = Vec::new();
+        loop {
+            self.skip_sp();
+            if self.eat(b')') {
+                return Some(Member::InnerList(items, self.parameters()?));
+            }
+            items.push(self.item()?);
+            match self.peek() {
+                Some(b' ') | Some(b')') => (),
+                _ => return None,
+            }
+        }
+    }
+
+    fn item(&mut self) -> Option<Item> {
+        let bare = self.bare_item()?;
+        let params = self.parameters()?;
+        Some(Item { bare, params })
+    }
+
+    fn parameters(&mut self) -> Option<Parameters> {
+        let mut params = Parameters::default();
+        while self.eat(b';') {
+            self.skip_sp();
+            let key = self.key()?;
+            let value = if self.eat(b'=') {
+                self.bare_item()?
+            } else {
+                BareItem::Boolean(true)
+            };
+            params.insert(key, value);
+        }
+        Some(params)
+    }
+
+    fn key(&mut self) -> Option<String> {
+        match self.peek() {
+            Some(b'*') | Some(b'a'..=b'z') => (),
+            _ => return None,
+        }
+        let key =
+            self.take_while(|b| matches!(b, b'a'..=b'z' | b'0'..=b'9' | b'_' | b'-' | b'.' | b'*'));
+        Some(key.to_owned())
+    }
+
+    fn bare_item(&mut self) -> Option<BareItem> {
+        match self.peek()? {
+            b'-' | b'0'..=b'9' => self.number(),
+            b'"' => self.string(),
+            b':' => self.byte_seq(),
+            b'?' => self.boolean(),
+            b'*' | b'a'..=b'z' | b'A'..=b'Z' => self.token(),
+            _ => None,
+        }
+    }
+
+    fn number(&mut self) -> Option<BareItem> {
+        let negative = self.eat(b'-');
+        if !self.peek().map_or(false, |b| b.is_ascii_digit()) {
+            return None;
+        }
+
+        let num = self.take_while(|b| b.is_ascii_digit() || b == b'.');
+        let sign = if negative { -1 } else { 1 };
+
+        match num.find('.') {
+            None => {
+                if num.len() > 15 {
+                    return None;
+                }
+                num.parse::<i64>().ok().map(|n| BareItem::Integer(sign * n))
+            }
+            Some(dot) => {
+                let frac = &num[dot + 1..];
+                if dot > 12 || frac.is_empty() || frac.len() > 3 || frac.contains('.') {
+                    return None;
+                }
+                num.parse::<f64>()
+                    .ok()
+                    .map(|n| BareItem::Decimal(sign as f64 * n))
+            }
+        }
+    }
+
+    fn string(&mut self) -> Option<BareItem> {
+        if !self.eat(b'"') {
+            return None;
+        }
+        let mut s = String::new();
+        loop {
+            match self.peek()? {
+                b'"' => {
+                    self.pos += 1;
+                    return Some(BareItem::String(s));
+                }
+                b'\\' => {
+                    self.pos += 1;
+                    match self.peek()? {
+                        c @ b'"' | c @ b'\\' => s.push(c as char),
+                        _ => return None,
+                    }
+                }
+                c @ 0x20..=0x7e => s.push(c as char),
+                _ => return None,
+            }
+            self.pos += 1;
+        }
+    }
+
+    fn token(&mut self) -> Option<BareItem> {
+        let token = self.take_while(|b| super::token::is_tchar(b) || b == b':' || b == b'/');
+        Some(BareItem::Token(token.to_owned()))
+    }
+
+    fn byte_seq(&mut self) -> Option<BareItem> {
+        if !self.eat(b':') {
+            return None;
+        }
+        let b64 =
+            self.take_while(|b| b.is_ascii_alphanumeric() || b == b'+' || b == b'/' || b == b'=');
+        if !self.eat(b':') {
+            return None;
+        }
+        ENGINE.decode(b64).ok().map(BareItem::ByteSeq)
+    }
+
+    fn boolean(&mut self) -> Option<BareItem> {
+        if !self.eat(b'?') {
+            return None;
+        }
+        if self.eat(b'1') {
+            Some(BareItem::Boolean(true))
+        } else if self.eat(b'0') {
+            Some(BareItem::Boolean(false))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list(s: &'static str) -> Result<List, ::Error> {
+        parse_list(::std::iter::once(&HeaderValue::from_static(s)))
+    }
+
+    fn dictionary(s: &'static str) -> Result<Dictionary, ::Error> {
+        parse_dictionary(::std::iter::once(&HeaderValue::from_static(s)))
+    }
+
+    #[test]
+    fn bare_items() {
+        let cases = &[
+            ("42", BareItem::Integer(42)),
+            ("-42", BareItem::Integer(-42)),
+            ("4.5", BareItem::Decimal(4.5)),
+            ("-0.25", BareItem::Decimal(-0.25)),
+            (
+                "\"hello \\\"world\\\"\"",
+                BareItem::String("hello \"world\"".to_owned()),
+            ),
+            ("foo123/456", BareItem::Token("foo123/456".to_owned())),
+            (
+                ":cHJldGVuZCB0aGlzIGlzIGJpbmFyeSBjb250ZW50Lg==:",
+                BareItem::ByteSeq(b"pretend this is binary content.".to_vec()),
+            ),
+            ("?1", BareItem::Boolean(true)),
+            ("?0", BareItem::Boolean(false)),
+        ];
+
+        for &(s, ref expected) in cases {
+            let item = parse_item(s).unwrap();
+            assert_eq!(item.bare, *expected, "parsing {:?}", s);
+            assert_eq!(item.to_string(), s);
+        }
+    }
+
+    #[test]
+    fn decimal_serialization() {
+        assert_eq!(BareItem::Decimal(1.0).to_string(), "1.0");
+        assert_eq!(BareItem::Decimal(1.5).to_string(), "1.5");
+        assert_eq!(BareItem::Decimal(0.1234).to_string(), "0.123");
+    }
+
+    #[test]
+    fn invalid_items() {
+        let cases = &[
+            "",
+            "1234567890123456",
+            "1234567890123.0",
+            "1.1234",
+            "1.",
+            "\"unterminated",
+            "\"bad \\escape\"",
+            ":not base64!:",
+            "?2",
+            "Key=1",
+            "a, b",
+        ];
+        for s in cases {
+            assert!(parse_item(s).is_err(), "parsing {:?}", s);
+        }
+    }
+
+    #[test]
+    fn item_parameters() {
+        let item = parse_item("5; foo=bar;baz").unwrap();
+        assert_eq!(item.bare, BareItem::Integer(5));
+        assert_eq!(
+            item.params.get("foo"),
+            Some(&BareItem::Token("bar".to_owned()))
+        );
+        assert_eq!(item.params.get("baz"), Some(&BareItem::Boolean(true)));
+        assert_eq!(item.to_string(), "5;foo=bar;baz");
+    }
+
+    #[test]
+    fn lists() {
+        let parsed = list("sugar, tea, rum").unwrap();
+        assert_eq!(parsed.0.len(), 3);
+        assert_eq!(parsed.to_string(), "sugar, tea, rum");
+
+        let parsed = list("(\"foo\" \"bar\"), (\"baz\"), (\"bat\" \"one\"), ()").unwrap();
+        assert_eq!(parsed.0.len(), 4);
+        assert_eq!(
+            parsed.to_string(),
+            "(\"foo\" \"bar\"), (\"baz\"), (\"bat\" \"one\"), ()"
+        );
+
+        let parsed = list("abc;a=1;b=2; cde_456, (ghi;jk=4 l);q=\"9\";r=w").unwrap();
+        assert_eq!(
+            parsed.to_string(),
+            "abc;a=1;b=2;cde_456, (ghi;jk=4 l);q=\"9\";r=w"
+        );
+
+        assert_eq!(list("").unwrap(), List::default());
+    }
+
+    #[test]
+    fn lists_across_field_lines() {
+        let values = [
+            HeaderValue::from_static("sugar, tea"),
+            HeaderValue::from_static("rum"),
+        ];
+        let parsed = parse_list(values.iter()).unwrap();
+        assert_eq!(parsed.to_string(), "sugar, tea, rum");
+    }
+
+    #[test]
+    fn invalid_lists() {
+        assert!(list("sugar,").is_err());
+        assert!(list("sugar,,tea").is_err());
+        assert!(list("(a b").is_err());
+        assert!(list("(a\tb)").is_err());
+    }
+
+    #[test]
+    fn dictionaries() {
+        let parsed = dictionary("en=\"Applepie\", da=:w4ZibGV0w6ZydGUK:").unwrap();
+        assert_eq!(
+            parsed.get("en").and_then(Member::as_item).map(|i| &i.bare),
+            Some(&BareItem::String("Applepie".to_owned()))
+        );
+        assert_eq!(parsed.to_string(), "en=\"Applepie\", da=:w4ZibGV0w6ZydGUK:");
+
+        let parsed = dictionary("a=?0, b, c; foo=bar").unwrap();
+        assert_eq!(parsed.to_string(), "a=?0, b, c;foo=bar");
+
+        let parsed = dictionary("rating=1.5, feelings=(joy sadness)").unwrap();
+        assert_eq!(parsed.to_string(), "rating=1.5, feelings=(joy sadness)");
+
+        let parsed = dictionary("a=(1 2), b=3, c=4;aa=bb, d=(5 6);valid").unwrap();
+        assert_eq!(parsed.to_string(), "a=(1 2), b=3, c=4;aa=bb, d=(5 6);valid");
+    }
+
+    #[test]
+    fn dictionary_duplicate_keys() {
+        let parsed = dictionary("a=1, b=2, a=3").unwrap();
+        assert_eq!(parsed.to_string(), "a=3, b=2");
+    }
+
+    #[test]
+    fn keys() {
+        assert!(is_key("sig1"));
+        assert!(is_key("*a.b-c_d"));
+        assert!(!is_key(""));
+        assert!(!is_key("Sig"));
+        assert!(!is_key("1a"));
+        assert!(!is_key("a b"));
+    }
+}
@@ -0,0 +1,53 @@
+use headers_core::parse;
+
+/// Whether `s` is a valid `token`, as defined in
+/// [RFC7230](https://tools.ietf.org/html/rfc7230#section-3.2.6).
+///
+/// ```text
+/// token = 1*tchar
+/// ```
+pub(crate) fn is_token(s: &str) -> bool {
+    parse::is_token(s.as_bytes())
+}
+
+pub(crate) use headers_core::parse::is_tchar;
+
+/// Whether `s` is a valid `token68`, as defined in
+/// [RFC7235](https://tools.ietf.org/html/rfc7235#section-2.1).
+///
+/// ```text
+/// token68 = 1*( ALPHA / DIGIT / "-" / "." / "_" / "~" / "+" / "/" ) *"="
+/// ```
+pub(crate) fn is_token68(s: &str) -> bool {
+    let body = s.trim_end_matches('=');
+    !body.is_empty() && body.bytes().all(is_token68_char)
+}
+
+pub(crate) fn is_token68_char(b: u8) -> bool {
+    matches!(b, b'-' | b'.' | b'_' | b'~' | b'+' | b'/') || b.is_ascii_alphanumeric()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_token, is_token68};
+
+    #[test]
+    fn tokens() {
+        assert!(is_token("websocket"));
+        assert!(is_token("x-custom_token.1"));
+        assert!(!is_token(""));
+        assert!(!is_token("two words"));
+        assert!(!is_token("quoted\"value"));
+        assert!(!is_token("a/b"));
+    }
+
+    #[test]
+    fn token68s() {
+        assert!(is_token68("YIIZ+/w=="));
+        assert!(is_token68("abc-._~"));
+        assert!(!is_token68(""));
+        assert!(!is_token68("=="));
+        assert!(!is_token68("a=b"));
+        assert!(!is_token68("a b"));
+    }
+}
@@ -0,0 +1,141 @@
+use http::Uri;
+
+/// Resolve a URI reference against an absolute `base`, as defined in
+/// [RFC3986](https://tools.ietf.org/html/rfc3986#section-5.2).
+///
+/// Any fragment is dropped, since a `Uri` can't hold one. Returns `None` if
+/// `base` has no scheme or authority, or if the result isn't a valid `Uri`.
+pub(crate) fn resolve(base: &Uri, reference: &str) -> Option<Uri> {
+    let base_authority = base.authority()?;
+    let mut scheme = base.scheme_str()?;
+
+    let reference = reference.split('#').next().unwrap_or("");
+    let mut rest = reference;
+    if let Some(colon) = reference.find(':') {
+        if is_scheme(&reference[..colon]) {
+            scheme = &reference[..colon];
+            rest = &reference[colon + 1..];
+            // Only hierarchical URIs can be represented by a `Uri`.
+            if !rest.starts_with("//") {
+                return None;
+            }
+        }
+    }
+
+    let (rest, query) = match rest.find('?') {
+        Some(idx) => (&rest[..idx], Some(&rest[idx + 1..])),
+        None => (rest, None),
+    };
+
+    let base_path = base.path();
+    let (authority, path, query) = if let Some(net) = rest.strip_prefix("//") {
+        let end = net.find('/').unwrap_or(net.len());
+        (&net[..end], remove_dot_segments(&net[end..]), query)
+    } else if rest.starts_with('/') {
+        (base_authority.as_str(), remove_dot_segments(rest), query)
+    } else if rest.is_empty() {
+        let query = query.or_else(|| base.query());
+        (base_authority.as_str(), base_path.to_owned(), query)
+    } else {
+        let dir = &base_path[..base_path.rfind('/').map_or(0, |idx| idx + 1)];
+        let merged = if dir.is_empty() {
+            format!("/{}", rest)
+        } else {
+            format!("{}{}", dir, rest)
+        };
+        (base_authority.as_str(), remove_dot_segments(&merged), query)
+    };
+
+    let mut target = format!("{}://{}{}", scheme, authority, path);
+    if let Some(query) = query {
+        target.push('?');
+        target.push_str(query);
+    }
+    target.parse().ok()
+}
+
+/// ```text
+/// scheme = ALPHA *( ALPHA / DIGIT / "+" / "-" / "." )
+/// ```
+fn is_scheme(s: &str) -> bool {
+    let mut bytes = s.bytes();
+    bytes.next().map_or(false, |b| b.is_ascii_alphabetic())
+        && bytes.all(|b| b.is_ascii_alphanumeric() || b == b'+' || b == b'-' || b == b'.')
+}
+
+fn remove_dot_segments(path: &str) -> String {
+    let absolute = path.starts_with('/');
+    let segments = if absolute { &path[1..] } else { path }
+        .split('/')
+        .collect::<Vec<_>>();
+
+    let mut out = Vec::new();
+    for (i, segment) in segments.iter().enumerate() {
+        let last = i + 1 == segments.len();
+        match *segment {
+            "." => {
+                if last {
+                    out.push("");
+                }
+            }
+            ".." => {
+                out.pop();
+                if last {
+                    out.push("");
+                }
+            }
+            segment => out.push(segment),
+        }
+    }
+
+    let joined = out.join("/");
+    if absolute {
+        format!("/{}", joined)
+    } else {
+        joined
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rfc3986_examples() {
+        let base = "http://a/b/c/d;p?q".parse::<Uri>().unwrap();
+        let cases = [
+            ("g", "http://a/b/c/g"),
+            ("./g", "http://a/b/c/g"),
+            ("g/", "http://a/b/c/g/"),
+            ("/g", "http://a/g"),
+            ("//g", "http://g/"),
+            ("?y", "http://a/b/c/d;p?y"),
+            ("g?y", "http://a/b/c/g?y"),
+            ("#s", "http://a/b/c/d;p?q"),
+            ("g#s", "http://a/b/c/g"),
+            (";x", "http://a/b/c/;x"),
+            ("", "http://a/b/c/d;p?q"),
+            (".", "http://a/b/c/"),
+            ("./", "http://a/b/c/"),
+            ("..", "http://a/b/"),
+            ("../g", "http://a/b/g"),
+            ("../..", "http://a/"),
+            ("../../g", "http://a/g"),
+            ("../../../g", "http://a/g"),
+            ("/./g", "http://a/g"),
+            ("g/../h", "http://a/b/c/h"),
+            ("https://example.com/x/../y", "https://example.com/y"),
+        ];
+        for &(reference, expected) in cases.iter() {
+            let resolved = resolve(&base, reference).map(|uri| uri.to_string());
+            assert_eq!(resolved.as_deref(), Some(expected), "{:?}", reference);
+        }
+    }
+
+    #[test]
+    fn unresolvable() {
+        let base = "http://a/b".parse::<Uri>().unwrap();
+        assert_eq!(resolve(&base, "mailto:someone@example.com"), None);
+        assert_eq!(resolve(&"/b".parse().unwrap(), "g"), None);
+    }
+}
@@ -60,6 +60,20 @@ impl fmt::Display for HeaderValueString {
     }
 }
 
+impl HeaderValueString {
+    /// Decode the one value as text, reading obs-text as ISO-8859-1
+    /// instead of rejecting it.
+    pub(crate) fn decode_lossy<'i, I>(values: &mut I) -> Result<String, ::Error>
+    where
+        I: Iterator<Item = &'i HeaderValue>,
+    {
+        values
+            .just_one()
+            .map(|val| super::latin1(val.as_bytes()))
+            .ok_or_else(::Error::invalid)
+    }
+}
+
 impl super::TryFromValues for HeaderValueString {
     fn try_from_values<'i, I>(values: &mut I) -> Result<Self, ::Error>
     where